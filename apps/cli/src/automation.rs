@@ -115,11 +115,7 @@ impl AutomationHost for CliAutomationHost {
         let output_path = match destination {
             ExportDestination::ProjectFolder => None,
             ExportDestination::CustomPath { dir } => {
-                let ext = match profile.format {
-                    ExportFormat::Mp4 => "mp4",
-                    ExportFormat::Gif => "gif",
-                    ExportFormat::Mov => "mov",
-                };
+                let ext = profile.format.extension();
                 let name = project_path
                     .file_stem()
                     .map(|s| s.to_string_lossy().to_string())
@@ -128,7 +124,8 @@ impl AutomationHost for CliAutomationHost {
             }
         };
 
-        let mut builder = cap_export::ExporterBase::builder(project_path.clone());
+        let mut builder = cap_export::ExporterBase::builder(project_path.clone())
+            .with_range(profile.range.clone());
         if let Some(ref out) = output_path {
             builder = builder.with_output_path(out.clone());
         }
@@ -143,6 +140,8 @@ impl AutomationHost for CliAutomationHost {
                     custom_bpp: None,
                     force_ffmpeg_decoder: false,
                     optimize_filesize: false,
+                    loudness: None,
+                    range: profile.range.clone(),
                 }
                 .export(base, |_| true)
                 .await
@@ -152,6 +151,7 @@ impl AutomationHost for CliAutomationHost {
                     fps: profile.fps,
                     resolution_base: profile.resolution_base,
                     quality: None,
+                    range: profile.range.clone(),
                 }
                 .export(base, |_| true)
                 .await
@@ -161,10 +161,43 @@ impl AutomationHost for CliAutomationHost {
                     fps: profile.fps,
                     resolution_base: profile.resolution_base,
                     cursor_only: false,
+                    range: profile.range.clone(),
                 }
                 .export(base, |_| true)
                 .await
             }
+            ExportFormat::Webm | ExportFormat::WebmAv1 => {
+                cap_export::webm::WebmExportSettings {
+                    fps: profile.fps,
+                    resolution_base: profile.resolution_base,
+                    codec: if profile.format == ExportFormat::WebmAv1 {
+                        cap_export::webm::WebmCodec::Av1
+                    } else {
+                        cap_export::webm::WebmCodec::Vp9
+                    },
+                    compression: map_compression(profile.compression),
+                    force_ffmpeg_decoder: false,
+                    loudness: None,
+                    range: profile.range.clone(),
+                }
+                .export(base, |_| true)
+                .await
+            }
+            ExportFormat::Webp | ExportFormat::Apng => {
+                let format = if profile.format == ExportFormat::Webp {
+                    cap_export::animated::AnimatedImageFormat::WebP
+                } else {
+                    cap_export::animated::AnimatedImageFormat::Apng
+                };
+                cap_export::animated::AnimatedImageExportSettings {
+                    fps: profile.fps,
+                    resolution_base: profile.resolution_base,
+                    quality: None,
+                    range: profile.range.clone(),
+                }
+                .export(format, base, |_| true)
+                .await
+            }
         }
         .map_err(|e| format!("Export failed: {e}"))?;
 
@@ -516,6 +549,15 @@ pub async fn run_recording_finished(project_path: &Path, mode: AutomationRecordi
     run_trigger(trigger, ctx).await;
 }
 
+pub async fn run_video_imported(project_path: &Path) {
+    if load_store().is_none() {
+        return;
+    }
+
+    let ctx = TriggerContext::new().with_project_path(project_path.to_path_buf());
+    run_trigger(Trigger::VideoImported, ctx).await;
+}
+
 pub async fn run_upload_completed(project_path: &Path, link: &str, id: &str) {
     if load_store().is_none() {
         return;
@@ -0,0 +1,440 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use cap_project::{
+    CaptionTrackSegment, CaptionsData, RecordingMeta, SubtitleCue, SubtitleFormat,
+    TimelineConfiguration,
+};
+use cap_rendering::ProjectRecordingsMeta;
+use cap_transcription::{ParakeetEngine, TranscriptionEngine, TranscriptionOptions, WhisperEngine};
+use clap::{Args, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use crate::{OutputFormat, finish_json, resolve_format, write_json};
+
+#[derive(Args)]
+pub struct CaptionsArgs {
+    #[command(subcommand)]
+    command: CaptionsCommands,
+}
+
+#[derive(Subcommand)]
+enum CaptionsCommands {
+    /// Replace a project's captions with an SRT, WebVTT or ASS file
+    Import(CaptionsImport),
+    /// Write a project's captions as SRT, WebVTT or ASS
+    Export(CaptionsExport),
+    /// Transcribe a project's microphone and system audio into captions
+    Generate(CaptionsGenerate),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum SubtitleFormatArg {
+    Srt,
+    Vtt,
+    Ass,
+}
+
+impl From<SubtitleFormatArg> for SubtitleFormat {
+    fn from(value: SubtitleFormatArg) -> Self {
+        match value {
+            SubtitleFormatArg::Srt => Self::Srt,
+            SubtitleFormatArg::Vtt => Self::WebVtt,
+            SubtitleFormatArg::Ass => Self::Ass,
+        }
+    }
+}
+
+#[derive(Args)]
+#[command(
+    long_about = "Replace a '.cap' project's captions with a subtitle file.
+
+Cue times are read as positions in the edited (exported) video, so subtitles made from a `cap export` \
+line up with what was rendered. They are stored against the original recording, so the captions keep \
+following their speech as the timeline is edited further. Pass --source-timed when the file was timed \
+against the raw recording instead. Captions are enabled, so the next `cap export` burns them in.
+
+NOTE: --format selects the SUBTITLE FORMAT (srt/vtt/ass, detected from the file extension by default), \
+not the output mode. Use --json for machine-readable output."
+)]
+struct CaptionsImport {
+    /// Path to a '.cap' project directory
+    project_path: PathBuf,
+    /// Subtitle file to import
+    file: PathBuf,
+    /// Subtitle format of the file (detected from its extension by default)
+    #[arg(long, value_enum)]
+    format: Option<SubtitleFormatArg>,
+    /// Cue times are positions in the raw recording rather than the edited video
+    #[arg(long)]
+    source_timed: bool,
+}
+
+#[derive(Args)]
+#[command(
+    long_about = "Write a '.cap' project's captions as a subtitle file, timed against the \
+edited (exported) video.
+
+NOTE: --format selects the SUBTITLE FORMAT (srt/vtt/ass, detected from --output by default, else srt), \
+not the output mode. Without --output the subtitles are printed to stdout; with --json they are \
+returned in the \"content\" field."
+)]
+struct CaptionsExport {
+    /// Path to a '.cap' project directory
+    project_path: PathBuf,
+    /// File to write the subtitles to
+    #[arg(long, short = 'o')]
+    output: Option<PathBuf>,
+    /// Subtitle format to write (detected from --output by default, else srt)
+    #[arg(long, value_enum)]
+    format: Option<SubtitleFormatArg>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum TranscriptionEngineArg {
+    Whisper,
+    Parakeet,
+}
+
+#[derive(Args)]
+#[command(
+    long_about = "Transcribe a '.cap' project's microphone and system audio into captions with a local \
+speech-to-text model, replacing any existing captions.
+
+--model is a Whisper GGML model file (e.g. ggml-base.en.bin) or a Parakeet TDT model directory; the \
+engine is picked from it unless --engine is given. Models are the ones the desktop app downloads into \
+its models folder. Captions are timed against the recording and enabled, so the next `cap export` \
+burns them in."
+)]
+struct CaptionsGenerate {
+    /// Path to a '.cap' project directory
+    project_path: PathBuf,
+    /// Whisper model file or Parakeet model directory
+    #[arg(long)]
+    model: PathBuf,
+    /// Transcription engine (detected from --model by default)
+    #[arg(long, value_enum)]
+    engine: Option<TranscriptionEngineArg>,
+    /// Spoken language as an ISO 639-1 code, or "auto" to detect it (Whisper only)
+    #[arg(long, default_value = "auto")]
+    language: String,
+    /// Name or term to prefer when the audio is ambiguous (repeatable, Whisper only)
+    #[arg(long = "hint")]
+    hints: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptionsImportResult<'a> {
+    path: &'a Path,
+    format: SubtitleFormat,
+    cues: usize,
+    track_segments: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptionsGenerateResult<'a> {
+    path: &'a Path,
+    engine: &'static str,
+    segments: usize,
+    words: usize,
+    track_segments: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptionsExportResult<'a> {
+    format: SubtitleFormat,
+    cues: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a Path>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<&'a str>,
+}
+
+impl CaptionsArgs {
+    pub async fn run(self, json: bool) -> Result<(), String> {
+        let format = resolve_format(json, OutputFormat::Text);
+        let result = match self.command {
+            CaptionsCommands::Import(args) => args.run(format),
+            CaptionsCommands::Export(args) => args.run(format),
+            CaptionsCommands::Generate(args) => args.run(format).await,
+        };
+        finish_json(format, result)
+    }
+}
+
+/// Recording durations in the two forms the caption projection needs: the
+/// display durations that define source time, and the full segment durations
+/// a default timeline spans.
+struct RecordingDurations {
+    display: Vec<f64>,
+    segments: Vec<f64>,
+}
+
+fn recording_durations(meta: &RecordingMeta) -> Result<RecordingDurations, String> {
+    let studio_meta = meta
+        .studio_meta()
+        .ok_or("Captions are only supported for studio recordings")?;
+    let recordings = ProjectRecordingsMeta::new(&meta.project_path, studio_meta)?;
+
+    Ok(RecordingDurations {
+        display: recordings
+            .segments
+            .iter()
+            .map(|segment| segment.display.duration)
+            .collect(),
+        segments: recordings
+            .segments
+            .iter()
+            .map(|segment| segment.duration())
+            .collect(),
+    })
+}
+
+fn default_timeline(durations: &RecordingDurations) -> Result<TimelineConfiguration, String> {
+    TimelineConfiguration::from_recording_durations(&durations.segments)
+        .ok_or_else(|| "Recording has no media to caption".to_string())
+}
+
+impl CaptionsImport {
+    fn run(self, format: OutputFormat) -> Result<(), String> {
+        let subtitle_format = self
+            .format
+            .map(SubtitleFormat::from)
+            .or_else(|| SubtitleFormat::from_path(&self.file))
+            .ok_or_else(|| {
+                format!(
+                    "Cannot tell the subtitle format of {}; pass --format srt|vtt|ass",
+                    self.file.display()
+                )
+            })?;
+        let input = std::fs::read_to_string(&self.file)
+            .map_err(|e| format!("Failed to read {}: {e}", self.file.display()))?;
+        let cues = subtitle_format
+            .parse(&input)
+            .map_err(|e| format!("Failed to parse {}: {e}", self.file.display()))?;
+
+        let track_segments =
+            replace_captions(&self.project_path, |captions, timeline, durations| {
+                if self.source_timed {
+                    captions.segments = cap_project::subtitle_cues_to_segments(&cues);
+                    captions.source_timed = true;
+                } else {
+                    captions.set_output_timed_cues(&cues, timeline, &durations.display);
+                }
+            })?;
+
+        match format {
+            OutputFormat::Text => {
+                println!(
+                    "Imported {} caption(s) into {}",
+                    cues.len(),
+                    self.project_path.display()
+                );
+                Ok(())
+            }
+            OutputFormat::Json => write_json(&CaptionsImportResult {
+                path: &self.project_path,
+                format: subtitle_format,
+                cues: cues.len(),
+                track_segments,
+            }),
+        }
+    }
+}
+
+/// Replaces a project's captions via `fill`, enables them and rebuilds the
+/// timeline's caption track, returning its length.
+fn replace_captions(
+    project_path: &Path,
+    fill: impl FnOnce(&mut CaptionsData, &TimelineConfiguration, &RecordingDurations),
+) -> Result<usize, String> {
+    let meta = RecordingMeta::load_for_project(project_path)
+        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+    let durations = recording_durations(&meta)?;
+    let mut config = crate::project::load_config(project_path)?;
+    let mut timeline = match config.timeline.take() {
+        Some(timeline) => timeline,
+        None => default_timeline(&durations)?,
+    };
+
+    let mut captions = config.captions.take().unwrap_or_default();
+    captions.settings.enabled = true;
+    fill(&mut captions, &timeline, &durations);
+    // Style overrides belonged to the captions being replaced.
+    timeline.caption_segments.clear();
+    timeline.caption_segments = captions.track_segments(&timeline, &durations.display);
+    let track_segments = timeline.caption_segments.len();
+
+    write_captions_json(project_path, &captions)?;
+    config.captions = Some(captions);
+    config.timeline = Some(timeline);
+    config
+        .write(project_path)
+        .map_err(|e| format!("Failed to write project config: {e}"))?;
+
+    Ok(track_segments)
+}
+
+/// Older desktop builds keep a `captions.json` alongside the project config and
+/// prefer it when loading, so keep it in step when present.
+fn write_captions_json(project_path: &Path, captions: &CaptionsData) -> Result<(), String> {
+    let captions_path = project_path.join("captions.json");
+    if !captions_path.exists() {
+        return Ok(());
+    }
+
+    let json = serde_json::to_string_pretty(captions)
+        .map_err(|e| format!("Failed to serialize captions: {e}"))?;
+    std::fs::write(&captions_path, json)
+        .map_err(|e| format!("Failed to write {}: {e}", captions_path.display()))
+}
+
+impl CaptionsGenerate {
+    async fn run(self, format: OutputFormat) -> Result<(), String> {
+        if !self.model.exists() {
+            return Err(format!("Model not found: {}", self.model.display()));
+        }
+        // Fail on a non-studio project before spending minutes transcribing it.
+        let meta = RecordingMeta::load_for_project(&self.project_path)
+            .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+        recording_durations(&meta)?;
+
+        let engine: Arc<dyn TranscriptionEngine> = match self.engine {
+            Some(TranscriptionEngineArg::Whisper) => Arc::new(WhisperEngine::new(&self.model)),
+            Some(TranscriptionEngineArg::Parakeet) => Arc::new(ParakeetEngine::new(&self.model)),
+            None => cap_transcription::engine_for_model(&self.model),
+        };
+        let engine_name = engine.name();
+        if format == OutputFormat::Text {
+            eprintln!("Transcribing with {engine_name}...");
+        }
+
+        let segments = cap_transcription::transcribe(
+            &self.project_path,
+            engine,
+            TranscriptionOptions {
+                language: self.language,
+                hints: self.hints,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+        let segment_count = segments.len();
+        let words = segments.iter().map(|segment| segment.words.len()).sum();
+
+        // The engines time words against the recording's audio, which is
+        // source time.
+        let track_segments = replace_captions(&self.project_path, |captions, _, _| {
+            captions.segments = segments;
+            captions.source_timed = true;
+        })?;
+
+        match format {
+            OutputFormat::Text => {
+                println!(
+                    "Generated {segment_count} caption(s) for {}",
+                    self.project_path.display()
+                );
+                Ok(())
+            }
+            OutputFormat::Json => write_json(&CaptionsGenerateResult {
+                path: &self.project_path,
+                engine: engine_name,
+                segments: segment_count,
+                words,
+                track_segments,
+            }),
+        }
+    }
+}
+
+impl CaptionsExport {
+    fn run(self, format: OutputFormat) -> Result<(), String> {
+        let subtitle_format = self
+            .format
+            .map(SubtitleFormat::from)
+            .or_else(|| self.output.as_deref().and_then(SubtitleFormat::from_path))
+            .unwrap_or(SubtitleFormat::Srt);
+
+        let meta = RecordingMeta::load_for_project(&self.project_path)
+            .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+        let config = meta.project_config();
+        let captions = config
+            .captions
+            .as_ref()
+            .filter(|captions| !captions.segments.is_empty())
+            .ok_or("Project has no captions")?;
+        let track = output_timed_track(&meta, config.timeline.as_ref(), captions)?;
+
+        let cues = track.iter().map(SubtitleCue::from).collect::<Vec<_>>();
+        let content = subtitle_format.write(&cues, &captions.settings);
+
+        if let Some(output) = &self.output {
+            std::fs::write(output, &content)
+                .map_err(|e| format!("Failed to write {}: {e}", output.display()))?;
+        }
+
+        match (format, &self.output) {
+            (OutputFormat::Text, Some(output)) => {
+                println!("Wrote {} caption(s) to {}", cues.len(), output.display());
+                Ok(())
+            }
+            (OutputFormat::Text, None) => {
+                print!("{content}");
+                Ok(())
+            }
+            (OutputFormat::Json, output) => write_json(&CaptionsExportResult {
+                format: subtitle_format,
+                cues: cues.len(),
+                path: output.as_deref(),
+                content: output.is_none().then_some(content.as_str()),
+            }),
+        }
+    }
+}
+
+/// The captions as they are rendered: the timeline's caption track when the
+/// editor has derived one, otherwise a projection of the stored captions.
+fn output_timed_track(
+    meta: &RecordingMeta,
+    timeline: Option<&TimelineConfiguration>,
+    captions: &CaptionsData,
+) -> Result<Vec<CaptionTrackSegment>, String> {
+    if let Some(timeline) = timeline
+        && !timeline.caption_segments.is_empty()
+    {
+        return Ok(timeline.caption_segments.clone());
+    }
+
+    let durations = recording_durations(meta)?;
+    let timeline = match timeline {
+        Some(timeline) => timeline.clone(),
+        None => default_timeline(&durations)?,
+    };
+    Ok(captions.track_segments(&timeline, &durations.display))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_result_inlines_content_only_without_output() {
+        let result = serde_json::to_value(CaptionsExportResult {
+            format: SubtitleFormat::WebVtt,
+            cues: 1,
+            path: None,
+            content: Some("WEBVTT\n\n"),
+        })
+        .unwrap();
+
+        assert_eq!(result["format"], "vtt");
+        assert_eq!(result["content"], "WEBVTT\n\n");
+        assert!(result.get("path").is_none());
+    }
+}
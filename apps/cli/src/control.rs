@@ -0,0 +1,419 @@
+//! Local control endpoint for a detached recording, so it can be driven without polling files.
+//!
+//! The worker listens on a Unix domain socket (`~/.cap/sessions/<id>.sock`) on Linux and macOS, and
+//! on a named pipe (`\\.\pipe\cap-recording-<id>`) on Windows, and advertises it as `control` in the
+//! session file. Clients write one JSON-RPC 2.0 request per line and read one response per line:
+//!
+//! - `stop`: stop and finalize the recording. Replies once the stop has been requested; the session
+//!   file's status turns `stopped` when the `.cap` is ready.
+//! - `pause` / `resume`: reply with `{"paused": bool}` once the recording has changed state.
+//! - `addMarker` `{"name"?}`: reply with `{"name"}`, the marker's name (studio recordings only).
+//! - `setMicrophone` `{"name": string | null}` / `setCamera` `{"deviceId": string | null}`: swap or
+//!   remove an input while a studio recording is paused.
+//! - `stats`: reply with the recording's current [`SessionStats`](crate::session::SessionStats).
+//!
+//! The `.stop`, `.pause` and `.markers` files in [`crate::session`] keep working alongside it, and
+//! `cap record` falls back to them when a worker has no endpoint.
+
+use std::future::Future;
+
+use futures::{StreamExt, stream::FuturesUnordered};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tracing::debug;
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// The request was understood but the recording could not carry it out.
+const REQUEST_FAILED: i64 = -32000;
+
+/// A request to a recording's control endpoint.
+#[derive(Debug, PartialEq)]
+pub enum ControlRequest {
+    Stop,
+    Pause,
+    Resume,
+    AddMarker { name: Option<String> },
+    SetMicrophone { name: Option<String> },
+    SetCamera { device_id: Option<String> },
+    Stats,
+}
+
+impl ControlRequest {
+    fn parse(method: &str, params: Value) -> Result<Self, RpcError> {
+        #[derive(Deserialize, Default)]
+        #[serde(rename_all = "camelCase", default)]
+        struct Params {
+            name: Option<String>,
+            device_id: Option<String>,
+        }
+
+        let params: Params = match params {
+            Value::Null => Params::default(),
+            params => serde_json::from_value(params)
+                .map_err(|e| RpcError::new(INVALID_PARAMS, format!("Invalid params: {e}")))?,
+        };
+        Ok(match method {
+            "stop" => Self::Stop,
+            "pause" => Self::Pause,
+            "resume" => Self::Resume,
+            "addMarker" => Self::AddMarker { name: params.name },
+            "setMicrophone" => Self::SetMicrophone { name: params.name },
+            "setCamera" => Self::SetCamera {
+                device_id: params.device_id,
+            },
+            "stats" => Self::Stats,
+            method => {
+                return Err(RpcError::new(
+                    METHOD_NOT_FOUND,
+                    format!("Unknown method '{method}'"),
+                ));
+            }
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RpcResponse {
+    jsonrpc: String,
+    #[serde(default)]
+    id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+/// The endpoint the recording `id` listens on.
+pub fn endpoint(id: &str) -> Result<String, String> {
+    #[cfg(unix)]
+    {
+        crate::session::socket_file(id).map(|path| path.display().to_string())
+    }
+    #[cfg(windows)]
+    {
+        Ok(format!(r"\\.\pipe\cap-recording-{id}"))
+    }
+}
+
+/// Answer `handle`'s result for every request on `listener`, from any number of clients at once.
+/// Runs until the caller drops it.
+pub async fn serve<F, Fut>(mut listener: Listener, handle: F)
+where
+    F: Fn(ControlRequest) -> Fut,
+    Fut: Future<Output = Result<Value, String>>,
+{
+    let mut connections = FuturesUnordered::new();
+    loop {
+        tokio::select! {
+            stream = listener.accept() => match stream {
+                Ok(stream) => connections.push(serve_connection(stream, &handle)),
+                Err(error) => debug!("Control endpoint accept failed: {error}"),
+            },
+            Some(()) = connections.next(), if !connections.is_empty() => {}
+        }
+    }
+}
+
+async fn serve_connection<S, F, Fut>(stream: S, handle: &F)
+where
+    S: AsyncRead + AsyncWrite,
+    F: Fn(ControlRequest) -> Fut,
+    Fut: Future<Output = Result<Value, String>>,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = respond(&line, handle).await;
+        let Ok(mut body) = serde_json::to_vec(&response) else {
+            continue;
+        };
+        body.push(b'\n');
+        if writer.write_all(&body).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn respond<F, Fut>(line: &str, handle: &F) -> RpcResponse
+where
+    F: Fn(ControlRequest) -> Fut,
+    Fut: Future<Output = Result<Value, String>>,
+{
+    let (id, outcome) = match serde_json::from_str::<RpcRequest>(line) {
+        Ok(request) => {
+            let outcome = match ControlRequest::parse(&request.method, request.params) {
+                Ok(request) => handle(request)
+                    .await
+                    .map_err(|message| RpcError::new(REQUEST_FAILED, message)),
+                Err(error) => Err(error),
+            };
+            (request.id, outcome)
+        }
+        Err(e) => (
+            Value::Null,
+            Err(RpcError::new(PARSE_ERROR, format!("Invalid request: {e}"))),
+        ),
+    };
+    let (result, error) = match outcome {
+        Ok(result) => (Some(result), None),
+        Err(error) => (None, Some(error)),
+    };
+    RpcResponse {
+        jsonrpc: "2.0".to_string(),
+        id,
+        result,
+        error,
+    }
+}
+
+/// Send `method` to the recording listening on `endpoint` and wait for its result. `None` when
+/// nothing is listening there, so the caller can fall back to the session files.
+pub async fn call(endpoint: &str, method: &str, params: Value) -> Option<Result<Value, String>> {
+    let stream = match connect(endpoint).await {
+        Ok(stream) => stream,
+        Err(error) => {
+            debug!("Control endpoint {endpoint} unavailable: {error}");
+            return None;
+        }
+    };
+    Some(exchange(stream, method, params).await)
+}
+
+async fn exchange<S>(stream: S, method: &str, params: Value) -> Result<Value, String>
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut request = serde_json::to_vec(&serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    }))
+    .map_err(|e| e.to_string())?;
+    request.push(b'\n');
+    writer
+        .write_all(&request)
+        .await
+        .map_err(|e| format!("Could not send '{method}' to the recording: {e}"))?;
+
+    let line = BufReader::new(reader)
+        .lines()
+        .next_line()
+        .await
+        .map_err(|e| format!("Could not read the recording's reply to '{method}': {e}"))?
+        .ok_or_else(|| format!("The recording closed its control endpoint during '{method}'"))?;
+    let response: RpcResponse = serde_json::from_str(&line)
+        .map_err(|e| format!("Invalid reply to '{method}' from the recording: {e}"))?;
+    match response.error {
+        Some(error) => Err(error.message),
+        None => Ok(response.result.unwrap_or(Value::Null)),
+    }
+}
+
+#[cfg(unix)]
+use unix::connect;
+#[cfg(unix)]
+pub use unix::{Listener, bind};
+
+#[cfg(unix)]
+mod unix {
+    use tokio::net::{UnixListener, UnixStream};
+
+    pub struct Listener {
+        listener: UnixListener,
+        path: std::path::PathBuf,
+    }
+
+    /// Listen on `endpoint`, replacing a socket a crashed worker may have left there.
+    pub fn bind(endpoint: &str) -> std::io::Result<Listener> {
+        let path = std::path::PathBuf::from(endpoint);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let _ = std::fs::remove_file(&path);
+        Ok(Listener {
+            listener: UnixListener::bind(&path)?,
+            path,
+        })
+    }
+
+    impl Listener {
+        pub(super) async fn accept(&mut self) -> std::io::Result<UnixStream> {
+            self.listener.accept().await.map(|(stream, _)| stream)
+        }
+    }
+
+    impl Drop for Listener {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    pub(super) async fn connect(endpoint: &str) -> std::io::Result<UnixStream> {
+        UnixStream::connect(endpoint).await
+    }
+}
+
+#[cfg(windows)]
+use windows::connect;
+#[cfg(windows)]
+pub use windows::{Listener, bind};
+
+#[cfg(windows)]
+mod windows {
+    use std::time::Duration;
+
+    use tokio::net::windows::named_pipe::{
+        ClientOptions, NamedPipeClient, NamedPipeServer, ServerOptions,
+    };
+
+    const ERROR_PIPE_BUSY: i32 = 231;
+
+    /// A named pipe serves one client per instance, so a fresh instance is created as each client
+    /// connects.
+    pub struct Listener {
+        endpoint: String,
+        next: NamedPipeServer,
+    }
+
+    pub fn bind(endpoint: &str) -> std::io::Result<Listener> {
+        Ok(Listener {
+            endpoint: endpoint.to_string(),
+            next: ServerOptions::new()
+                .first_pipe_instance(true)
+                .create(endpoint)?,
+        })
+    }
+
+    impl Listener {
+        pub(super) async fn accept(&mut self) -> std::io::Result<NamedPipeServer> {
+            self.next.connect().await?;
+            let next = ServerOptions::new().create(&self.endpoint)?;
+            Ok(std::mem::replace(&mut self.next, next))
+        }
+    }
+
+    pub(super) async fn connect(endpoint: &str) -> std::io::Result<NamedPipeClient> {
+        let mut attempts = 0;
+        loop {
+            match ClientOptions::new().open(endpoint) {
+                Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) && attempts < 20 => {
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_parsed_from_method_and_params() {
+        assert_eq!(
+            ControlRequest::parse("stop", Value::Null).unwrap(),
+            ControlRequest::Stop
+        );
+        assert_eq!(
+            ControlRequest::parse("addMarker", serde_json::json!({ "name": "Demo" })).unwrap(),
+            ControlRequest::AddMarker {
+                name: Some("Demo".to_string())
+            }
+        );
+        assert_eq!(
+            ControlRequest::parse("setCamera", serde_json::json!({ "deviceId": null })).unwrap(),
+            ControlRequest::SetCamera { device_id: None }
+        );
+        assert_eq!(
+            ControlRequest::parse("rewind", Value::Null)
+                .unwrap_err()
+                .code,
+            METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            ControlRequest::parse("addMarker", serde_json::json!({ "name": 3 }))
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+    }
+
+    #[tokio::test]
+    async fn replies_carry_the_handlers_result_or_error() {
+        let (client, server) = tokio::io::duplex(1024);
+        let handle = |request: ControlRequest| async move {
+            match request {
+                ControlRequest::Pause => Ok(serde_json::json!({ "paused": true })),
+                _ => Err("Recording no longer active".to_string()),
+            }
+        };
+        let server = serve_connection(server, &handle);
+        let client = async {
+            let (reader, mut writer) = tokio::io::split(client);
+            writer
+                .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":7,\"method\":\"pause\"}\nnot json\n")
+                .await
+                .unwrap();
+            writer
+                .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":8,\"method\":\"resume\"}\n")
+                .await
+                .unwrap();
+            writer.shutdown().await.unwrap();
+
+            let mut lines = BufReader::new(reader).lines();
+            let mut responses = Vec::new();
+            while let Some(line) = lines.next_line().await.unwrap() {
+                responses.push(serde_json::from_str::<Value>(&line).unwrap());
+            }
+            responses
+        };
+
+        let ((), responses) = tokio::join!(server, client);
+        assert_eq!(responses.len(), 3);
+        assert_eq!(
+            responses[0],
+            serde_json::json!({ "jsonrpc": "2.0", "id": 7, "result": { "paused": true } })
+        );
+        assert_eq!(responses[1]["id"], Value::Null);
+        assert_eq!(responses[1]["error"]["code"], PARSE_ERROR);
+        assert_eq!(
+            responses[2],
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 8,
+                "error": { "code": REQUEST_FAILED, "message": "Recording no longer active" }
+            })
+        );
+    }
+}
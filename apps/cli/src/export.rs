@@ -7,17 +7,44 @@ use std::{
     },
 };
 
-use cap_export::{ExporterBase, make_cursor_only_project};
+use cap_export::{
+    ExporterBase,
+    animated::AnimatedImageFormat,
+    loudness::LoudnessTarget,
+    make_cursor_only_project,
+    range::{ExportRange, SegmentSelector},
+};
 use cap_project::{RecordingMeta, RecordingMetaInner, XY};
-use clap::{Args, ValueEnum};
+use cap_rendering::SharedWgpuDevice;
+use clap::{Args, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
+use crate::export_batch::ExportBatch;
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
 pub enum ExportFormat {
     Mp4,
     Gif,
     Mov,
+    Webm,
+    Webp,
+    Apng,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum CodecArg {
+    Vp9,
+    Av1,
+}
+
+impl From<CodecArg> for cap_export::webm::WebmCodec {
+    fn from(value: CodecArg) -> Self {
+        match value {
+            CodecArg::Vp9 => Self::Vp9,
+            CodecArg::Av1 => Self::Av1,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
@@ -40,13 +67,40 @@ impl From<QualityArg> for cap_export::mp4::ExportCompression {
 }
 
 #[derive(Args)]
-#[command(long_about = "Render a '.cap' project to a video file.
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    command: Option<ExportCommands>,
+
+    #[command(flatten)]
+    args: Export,
+}
+
+#[derive(Subcommand)]
+enum ExportCommands {
+    /// Export many projects listed in a JSON manifest on one shared GPU context
+    Batch(ExportBatch),
+}
+
+impl ExportArgs {
+    pub async fn run(self, json: bool) -> Result<(), String> {
+        match self.command {
+            Some(ExportCommands::Batch(batch)) => batch.run(json).await,
+            None => self.args.run(json).await,
+        }
+    }
+}
 
-NOTE: here --format selects the CONTAINER (mp4/gif/mov), NOT the output mode. For machine-readable
+#[derive(Args)]
+#[command(
+    long_about = "Render a '.cap' project to a video file, or many with `cap export batch`.
+
+NOTE: here --format selects the CONTAINER (mp4/gif/mov/webm/webp/apng), NOT the output mode. For machine-readable
 output pass --json (the global flag), which streams NDJSON progress + completion events to stdout.
 The NDJSON uses PascalCase type tags and snake_case fields ({\"type\":\"Progress\",\"rendered_count\":N,
 \"total_frames\":N} then {\"type\":\"Completed\",\"path\":\"...\"}); on failure a final
-{\"type\":\"Error\",\"error\":\"...\"} is emitted.")]
+{\"type\":\"Error\",\"error\":\"...\"} is emitted."
+)]
 pub struct Export {
     /// Path to a '.cap' project directory (as produced by `cap record`)
     project_path: PathBuf,
@@ -55,24 +109,47 @@ pub struct Export {
     /// Output file to write the export to
     #[arg(long, short = 'o')]
     output: Option<PathBuf>,
-    /// Container to export: mp4 (default), gif, or mov. NOT the output mode — use --json for JSON
+    /// Container to export: mp4 (default), gif, mov, webm, webp, or apng. NOT the output mode — use --json for JSON
     #[arg(long, value_enum)]
     format: Option<ExportFormat>,
+    /// Video codec for webm: vp9 (default) or av1
+    #[arg(long, value_enum)]
+    codec: Option<CodecArg>,
     /// Frames per second to render
     #[arg(long)]
     fps: Option<u32>,
     /// Output resolution as WIDTHxHEIGHT, e.g. 1920x1080
     #[arg(long)]
     resolution: Option<String>,
-    /// Compression preset (mp4 only)
+    /// Compression preset (mp4 and webm only)
     #[arg(long, value_enum)]
     quality: Option<QualityArg>,
     /// Optimise for smaller files using CRF (mp4 only)
     #[arg(long)]
     optimize_filesize: bool,
+    /// Normalise the audio to this integrated loudness in LUFS, e.g. -16 (mp4 and webm only)
+    #[arg(long, value_name = "LUFS", allow_negative_numbers = true)]
+    loudness: Option<f64>,
+    /// True-peak ceiling for --loudness in dBTP [default: -1]
+    #[arg(
+        long,
+        value_name = "DBTP",
+        allow_negative_numbers = true,
+        requires = "loudness"
+    )]
+    true_peak: Option<f64>,
     /// Full export settings as JSON, e.g. {"format":"Mp4","fps":60,"resolution_base":{"x":1920,"y":1080},"compression":"Maximum","custom_bpp":null} (mutually exclusive with the flags above)
     #[arg(long)]
     settings_json: Option<String>,
+    /// Export from this point of the edited timeline: seconds or [HH:]MM:SS[.mmm]
+    #[arg(long, value_parser = parse_timestamp, conflicts_with = "segments")]
+    from: Option<f64>,
+    /// Export up to this point of the edited timeline: seconds or [HH:]MM:SS[.mmm]
+    #[arg(long, value_parser = parse_timestamp, conflicts_with = "segments")]
+    to: Option<f64>,
+    /// Export only this timeline segment, by name or 0-based index; repeat to join several
+    #[arg(long = "segment", value_name = "NAME|INDEX")]
+    segments: Vec<String>,
     /// Decode source video with FFmpeg instead of the platform hardware decoder
     #[arg(long)]
     force_ffmpeg_decoder: bool,
@@ -87,24 +164,31 @@ pub struct Export {
 #[derive(Default)]
 pub struct ExportFlags {
     pub format: Option<ExportFormat>,
+    pub codec: Option<CodecArg>,
     pub fps: Option<u32>,
     pub resolution: Option<String>,
     pub quality: Option<QualityArg>,
     pub optimize_filesize: bool,
+    pub loudness: Option<f64>,
+    pub true_peak: Option<f64>,
     pub force_ffmpeg_decoder: bool,
+    pub range: Option<ExportRange>,
 }
 
 impl ExportFlags {
     fn is_set(&self) -> bool {
         self.format.is_some()
+            || self.codec.is_some()
             || self.fps.is_some()
             || self.resolution.is_some()
             || self.quality.is_some()
             || self.optimize_filesize
+            || self.loudness.is_some()
+            || self.true_peak.is_some()
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Clone)]
 #[serde(tag = "format")]
 pub enum CliExportSettings {
     #[serde(alias = "mp4")]
@@ -113,6 +197,12 @@ pub enum CliExportSettings {
     Gif(cap_export::gif::GifExportSettings),
     #[serde(alias = "mov")]
     Mov(cap_export::mov::MovExportSettings),
+    #[serde(alias = "webm")]
+    Webm(cap_export::webm::WebmExportSettings),
+    #[serde(alias = "webp")]
+    Webp(cap_export::animated::AnimatedImageExportSettings),
+    #[serde(alias = "apng")]
+    Apng(cap_export::animated::AnimatedImageExportSettings),
 }
 
 impl CliExportSettings {
@@ -121,28 +211,87 @@ impl CliExportSettings {
             Self::Mp4(settings) => settings.fps,
             Self::Gif(settings) => settings.fps,
             Self::Mov(settings) => settings.fps,
+            Self::Webm(settings) => settings.fps,
+            Self::Webp(settings) | Self::Apng(settings) => settings.fps,
         }
     }
 
     fn force_ffmpeg_decoder(&self) -> bool {
         match self {
             Self::Mp4(settings) => settings.force_ffmpeg_decoder,
-            Self::Gif(_) | Self::Mov(_) => false,
+            Self::Webm(settings) => settings.force_ffmpeg_decoder,
+            Self::Gif(_) | Self::Mov(_) | Self::Webp(_) | Self::Apng(_) => false,
         }
     }
 
     fn cursor_only(&self) -> bool {
         match self {
             Self::Mov(settings) => settings.cursor_only,
-            Self::Mp4(_) | Self::Gif(_) => false,
+            Self::Mp4(_) | Self::Gif(_) | Self::Webm(_) | Self::Webp(_) | Self::Apng(_) => false,
+        }
+    }
+
+    pub fn range(&self) -> Option<&ExportRange> {
+        match self {
+            Self::Mp4(settings) => settings.range.as_ref(),
+            Self::Gif(settings) => settings.range.as_ref(),
+            Self::Mov(settings) => settings.range.as_ref(),
+            Self::Webm(settings) => settings.range.as_ref(),
+            Self::Webp(settings) | Self::Apng(settings) => settings.range.as_ref(),
+        }
+    }
+
+    /// These settings exporting `range`, which was given apart from them (by flags or a batch
+    /// job's "range"). `None` keeps their own range; naming one in both places is an error.
+    pub fn with_range(mut self, range: Option<ExportRange>) -> Result<Self, String> {
+        let Some(range) = range else {
+            return Ok(self);
+        };
+        let slot = match &mut self {
+            Self::Mp4(settings) => &mut settings.range,
+            Self::Gif(settings) => &mut settings.range,
+            Self::Mov(settings) => &mut settings.range,
+            Self::Webm(settings) => &mut settings.range,
+            Self::Webp(settings) | Self::Apng(settings) => &mut settings.range,
+        };
+        if slot.is_some() {
+            return Err(
+                "The export settings already have a \"range\"; give it there or separately, not both"
+                    .to_string(),
+            );
+        }
+        *slot = Some(range);
+        Ok(self)
+    }
+
+    /// The file an export with these settings writes: `output`, or the project's own output
+    /// path, with the extension the format's exporter forces onto it. MP4 exports keep the path
+    /// as given.
+    pub fn output_path(&self, project_path: &Path, output: Option<&Path>) -> PathBuf {
+        let mut path = output.map(Path::to_path_buf).unwrap_or_else(|| {
+            RecordingMeta::load_for_project(project_path)
+                .map(|meta| meta.output_path())
+                .unwrap_or_else(|_| project_path.join("output").join("result.mp4"))
+        });
+        let extension = match self {
+            Self::Mp4(_) => return path,
+            Self::Gif(_) => "gif",
+            Self::Mov(_) => "mov",
+            Self::Webm(_) => "webm",
+            Self::Webp(_) => AnimatedImageFormat::WebP.extension(),
+            Self::Apng(_) => AnimatedImageFormat::Apng.extension(),
+        };
+        if path.extension() != Some(std::ffi::OsStr::new(extension)) {
+            path.set_extension(extension);
         }
+        path
     }
 }
 
 fn default_fps(format: ExportFormat) -> u32 {
     match format {
-        ExportFormat::Mp4 | ExportFormat::Mov => 60,
-        ExportFormat::Gif => 30,
+        ExportFormat::Mp4 | ExportFormat::Mov | ExportFormat::Webm => 60,
+        ExportFormat::Gif | ExportFormat::Webp | ExportFormat::Apng => 30,
     }
 }
 
@@ -164,6 +313,32 @@ fn parse_resolution(value: &str) -> Result<XY<u32>, String> {
     Ok(XY::new(width, height))
 }
 
+fn loudness_from_flags(flags: &ExportFlags) -> Result<Option<LoudnessTarget>, String> {
+    let Some(integrated_lufs) = flags.loudness else {
+        if flags.true_peak.is_some() {
+            return Err("--true-peak requires --loudness".to_string());
+        }
+        return Ok(None);
+    };
+    if !integrated_lufs.is_finite() || integrated_lufs >= 0.0 {
+        return Err(format!(
+            "--loudness must be a negative LUFS value, got {integrated_lufs}"
+        ));
+    }
+    let true_peak_dbtp = flags
+        .true_peak
+        .unwrap_or(LoudnessTarget::default().true_peak_dbtp);
+    if !true_peak_dbtp.is_finite() || true_peak_dbtp > 0.0 {
+        return Err(format!(
+            "--true-peak must be 0 dBTP or lower, got {true_peak_dbtp}"
+        ));
+    }
+    Ok(Some(LoudnessTarget {
+        integrated_lufs,
+        true_peak_dbtp,
+    }))
+}
+
 pub fn settings_from_flags(flags: &ExportFlags) -> Result<CliExportSettings, String> {
     let format = flags.format.unwrap_or(ExportFormat::Mp4);
     let fps = flags.fps.unwrap_or_else(|| default_fps(format));
@@ -174,6 +349,13 @@ pub fn settings_from_flags(flags: &ExportFlags) -> Result<CliExportSettings, Str
         Some(value) => parse_resolution(value)?,
         None => XY::new(1920, 1080),
     };
+    if flags.codec.is_some() && format != ExportFormat::Webm {
+        return Err("--codec is only supported for --format webm".to_string());
+    }
+    let loudness = loudness_from_flags(flags)?;
+    if loudness.is_some() && !matches!(format, ExportFormat::Mp4 | ExportFormat::Webm) {
+        return Err("--loudness is only supported for --format mp4 or webm".to_string());
+    }
 
     match format {
         ExportFormat::Mp4 => Ok(CliExportSettings::Mp4(cap_export::mp4::Mp4ExportSettings {
@@ -186,11 +368,13 @@ pub fn settings_from_flags(flags: &ExportFlags) -> Result<CliExportSettings, Str
             custom_bpp: None,
             force_ffmpeg_decoder: flags.force_ffmpeg_decoder,
             optimize_filesize: flags.optimize_filesize,
+            loudness,
+            range: flags.range.clone(),
         })),
         ExportFormat::Gif => {
             if flags.quality.is_some() {
                 return Err(
-                    "--quality is only supported for --format mp4; use --settings-json for GIF quality"
+                    "--quality is only supported for --format mp4 or webm; use --settings-json for GIF quality"
                         .to_string(),
                 );
             }
@@ -201,11 +385,12 @@ pub fn settings_from_flags(flags: &ExportFlags) -> Result<CliExportSettings, Str
                 fps,
                 resolution_base,
                 quality: None,
+                range: flags.range.clone(),
             }))
         }
         ExportFormat::Mov => {
             if flags.quality.is_some() {
-                return Err("--quality is only supported for --format mp4".to_string());
+                return Err("--quality is only supported for --format mp4 or webm".to_string());
             }
             if flags.optimize_filesize {
                 return Err("--optimize-filesize is only supported for --format mp4".to_string());
@@ -214,8 +399,113 @@ pub fn settings_from_flags(flags: &ExportFlags) -> Result<CliExportSettings, Str
                 fps,
                 resolution_base,
                 cursor_only: false,
+                range: flags.range.clone(),
             }))
         }
+        ExportFormat::Webm => {
+            if flags.optimize_filesize {
+                return Err("--optimize-filesize is only supported for --format mp4".to_string());
+            }
+            Ok(CliExportSettings::Webm(
+                cap_export::webm::WebmExportSettings {
+                    fps,
+                    resolution_base,
+                    codec: flags.codec.map(Into::into).unwrap_or_default(),
+                    compression: flags
+                        .quality
+                        .map(Into::into)
+                        .unwrap_or(cap_export::mp4::ExportCompression::Maximum),
+                    force_ffmpeg_decoder: flags.force_ffmpeg_decoder,
+                    loudness,
+                    range: flags.range.clone(),
+                },
+            ))
+        }
+        ExportFormat::Webp | ExportFormat::Apng => {
+            if flags.quality.is_some() {
+                return Err(
+                    "--quality is only supported for --format mp4 or webm; use --settings-json for WebP quality"
+                        .to_string(),
+                );
+            }
+            if flags.optimize_filesize {
+                return Err("--optimize-filesize is only supported for --format mp4".to_string());
+            }
+            let settings = cap_export::animated::AnimatedImageExportSettings {
+                fps,
+                resolution_base,
+                quality: None,
+                range: flags.range.clone(),
+            };
+            Ok(if format == ExportFormat::Webp {
+                CliExportSettings::Webp(settings)
+            } else {
+                CliExportSettings::Apng(settings)
+            })
+        }
+    }
+}
+
+/// Parse seconds (`12.5`) or a clock time (`1:02:03.5`, `02:03`).
+fn parse_timestamp(value: &str) -> Result<f64, String> {
+    let invalid = || format!("Invalid time '{value}', expected seconds or [HH:]MM:SS[.mmm]");
+    let parts = value.trim().split(':').collect::<Vec<_>>();
+    if parts.len() > 3 {
+        return Err(invalid());
+    }
+
+    let mut seconds = 0.0;
+    for (i, part) in parts.iter().enumerate() {
+        let value: f64 = part.parse().map_err(|_| invalid())?;
+        let is_last = i == parts.len() - 1;
+        if !value.is_finite() || value < 0.0 || (!is_last && value.fract() != 0.0) {
+            return Err(invalid());
+        }
+        seconds = seconds * 60.0 + value;
+    }
+
+    Ok(seconds)
+}
+
+/// `--from/--to` or `--segment` as an export range; `None` exports the whole timeline.
+pub fn range_from_flags(
+    from: Option<f64>,
+    to: Option<f64>,
+    segments: &[String],
+) -> Option<ExportRange> {
+    if !segments.is_empty() {
+        return Some(ExportRange::Segments {
+            segments: segments
+                .iter()
+                .map(|segment| match segment.parse() {
+                    Ok(index) => SegmentSelector::Index(index),
+                    Err(_) => SegmentSelector::Name(segment.clone()),
+                })
+                .collect(),
+        });
+    }
+
+    (from.is_some() || to.is_some()).then_some(ExportRange::Time { from, to })
+}
+
+/// Full settings JSON wins when given; it is mutually exclusive with the individual flags.
+pub fn resolve_export_settings(
+    flags: &ExportFlags,
+    settings_json: Option<&str>,
+) -> Result<CliExportSettings, String> {
+    match settings_json {
+        Some(json) => {
+            if flags.is_set() {
+                return Err(
+                    "--settings-json cannot be combined with --format/--codec/--fps/--resolution/--quality/--optimize-filesize/--loudness/--true-peak"
+                        .to_string(),
+                );
+            }
+            let settings: CliExportSettings = serde_json::from_str(json)
+                .map_err(|e| format!("Invalid export settings JSON: {e}"))?;
+            settings.with_range(flags.range.clone())
+        }
+        None => settings_from_flags(flags),
     }
 }
 
@@ -252,25 +542,18 @@ impl Export {
     fn resolve_settings(&self) -> Result<CliExportSettings, String> {
         let flags = ExportFlags {
             format: self.format,
+            codec: self.codec,
             fps: self.fps,
             resolution: self.resolution.clone(),
             quality: self.quality,
             optimize_filesize: self.optimize_filesize,
+            loudness: self.loudness,
+            true_peak: self.true_peak,
             force_ffmpeg_decoder: self.force_ffmpeg_decoder,
+            range: range_from_flags(self.from, self.to, &self.segments),
         };
 
-        match &self.settings_json {
-            Some(json) => {
-                if flags.is_set() {
-                    return Err(
-                        "--settings-json cannot be combined with --format/--fps/--resolution/--quality/--optimize-filesize"
-                            .to_string(),
-                    );
-                }
-                serde_json::from_str(json).map_err(|e| format!("Invalid export settings JSON: {e}"))
-            }
-            None => settings_from_flags(&flags),
-        }
+        resolve_export_settings(&flags, self.settings_json.as_deref())
     }
 
     pub async fn run(self, json: bool) -> Result<(), String> {
@@ -309,57 +592,8 @@ impl Export {
         let output = self.resolve_output()?;
         let settings = self.resolve_settings()?;
 
-        ensure_remuxed(self.project_path.clone()).await?;
-        let meta = RecordingMeta::load_for_project(&self.project_path)
-            .map_err(|e| format!("Failed to load recording meta: {e}"))?;
-
-        if matches!(&meta.inner, RecordingMetaInner::Instant(_)) {
-            return export_instant_project(
-                self.project_path,
-                output,
-                &settings,
-                progress_json,
-                completion_json,
-                stdout,
-            )
-            .await;
-        }
-
-        let force_ffmpeg_decoder = self.force_ffmpeg_decoder || settings.force_ffmpeg_decoder();
-        let mut builder = ExporterBase::builder(self.project_path.clone())
-            .with_force_ffmpeg_decoder(force_ffmpeg_decoder);
-
-        if let Some(output_path) = output {
-            builder = builder.with_output_path(output_path);
-        }
-
-        if settings.cursor_only() {
-            builder = builder.with_config(make_cursor_only_project(meta.project_config()));
-        }
-
-        let exporter_base = builder
-            .build()
-            .await
-            .map_err(|v| format!("Exporter build error: {v}"))?;
-
-        let total_frames = exporter_base.total_frames(settings.fps());
-
-        if progress_json {
-            emit_export_message(
-                stdout,
-                &ExportProgressMessage::Progress {
-                    rendered_count: 0,
-                    total_frames,
-                },
-            )?;
-        }
-
-        let rendered = Arc::new(AtomicU32::new(0));
         let progress_stdout = Arc::clone(stdout);
-        let progress_rendered = Arc::clone(&rendered);
-        let on_progress = move |frame_index: u32| {
-            let count = (frame_index + 1).min(total_frames);
-            progress_rendered.store(count, Ordering::Relaxed);
+        let on_progress = move |rendered_count: u32, total_frames: u32| {
             if progress_json {
                 // Progress I/O must never cancel the render. Every exporter treats a `false`
                 // return as cancellation, so a transient stdout failure (closed pipe, poisoned
@@ -367,7 +601,7 @@ impl Export {
                 let _ = emit_export_message(
                     &progress_stdout,
                     &ExportProgressMessage::Progress {
-                        rendered_count: count,
+                        rendered_count,
                         total_frames,
                     },
                 );
@@ -375,22 +609,14 @@ impl Export {
             true
         };
 
-        let output_path = match settings {
-            CliExportSettings::Mp4(settings) => settings.export(exporter_base, on_progress).await,
-            CliExportSettings::Gif(settings) => settings.export(exporter_base, on_progress).await,
-            CliExportSettings::Mov(settings) => settings.export(exporter_base, on_progress).await,
-        }
-        .map_err(|v| format!("Exporter error: {v}"))?;
-
-        // Defense in depth: an export that renders no frames writes an empty (~few hundred byte) file
-        // but otherwise "succeeds". An agent must never silently get/upload that, so fail loudly and
-        // remove the empty artifact instead of reporting completion.
-        if total_frames > 0 && rendered.load(Ordering::Relaxed) == 0 {
-            let _ = std::fs::remove_file(&output_path);
-            return Err(format!(
-                "Export rendered 0 of {total_frames} frames; the recording may be unplayable. No output written."
-            ));
-        }
+        let output_path = export_project(
+            self.project_path,
+            output,
+            settings,
+            self.force_ffmpeg_decoder,
+            on_progress,
+        )
+        .await?;
 
         if progress_json || completion_json {
             emit_export_message(
@@ -409,6 +635,120 @@ impl Export {
     }
 }
 
+/// Render a project with `settings` and return the written file. `output` defaults to the
+/// project's own output path, and the settings' range to the whole timeline. `on_progress` receives
+/// `(rendered_count, total_frames)`, starting with a `0` report before the first frame; returning
+/// `false` cancels the export. This is the shared core behind `cap export`, `cap upload --export`
+/// and the MCP `export` tool.
+pub async fn export_project(
+    project_path: PathBuf,
+    output: Option<PathBuf>,
+    settings: CliExportSettings,
+    force_ffmpeg_decoder: bool,
+    on_progress: impl FnMut(u32, u32) -> bool + Send + 'static,
+) -> Result<PathBuf, String> {
+    export_project_on_device(
+        project_path,
+        output,
+        settings,
+        force_ffmpeg_decoder,
+        None,
+        on_progress,
+    )
+    .await
+}
+
+/// [`export_project`], rendering on `shared_device` when given instead of opening a GPU context
+/// for this export alone.
+pub async fn export_project_on_device(
+    project_path: PathBuf,
+    output: Option<PathBuf>,
+    settings: CliExportSettings,
+    force_ffmpeg_decoder: bool,
+    shared_device: Option<SharedWgpuDevice>,
+    mut on_progress: impl FnMut(u32, u32) -> bool + Send + 'static,
+) -> Result<PathBuf, String> {
+    ensure_remuxed(project_path.clone()).await?;
+    let meta = RecordingMeta::load_for_project(&project_path)
+        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+
+    if matches!(&meta.inner, RecordingMetaInner::Instant(_)) {
+        if settings.range().is_some() {
+            return Err(
+                "Instant recordings have no timeline; export them whole, without --from/--to/--segment or a \"range\""
+                    .to_string(),
+            );
+        }
+        return export_instant_project(project_path, output, &settings, on_progress).await;
+    }
+
+    let force_ffmpeg_decoder = force_ffmpeg_decoder || settings.force_ffmpeg_decoder();
+    let mut builder = ExporterBase::builder(project_path)
+        .with_force_ffmpeg_decoder(force_ffmpeg_decoder)
+        .with_range(settings.range().cloned());
+
+    if let Some(output_path) = output {
+        builder = builder.with_output_path(output_path);
+    }
+
+    if let Some(shared_device) = shared_device {
+        builder = builder.with_shared_device(shared_device);
+    }
+
+    if settings.cursor_only() {
+        builder = builder.with_config(make_cursor_only_project(meta.project_config()));
+    }
+
+    let exporter_base = builder
+        .build()
+        .await
+        .map_err(|v| format!("Exporter build error: {v}"))?;
+
+    let total_frames = exporter_base.total_frames(settings.fps());
+
+    if !on_progress(0, total_frames) {
+        return Err("Export cancelled".to_string());
+    }
+
+    let rendered = Arc::new(AtomicU32::new(0));
+    let progress_rendered = Arc::clone(&rendered);
+    let on_frame = move |frame_index: u32| {
+        let count = (frame_index + 1).min(total_frames);
+        progress_rendered.store(count, Ordering::Relaxed);
+        on_progress(count, total_frames)
+    };
+
+    let output_path = match settings {
+        CliExportSettings::Mp4(settings) => settings.export(exporter_base, on_frame).await,
+        CliExportSettings::Gif(settings) => settings.export(exporter_base, on_frame).await,
+        CliExportSettings::Mov(settings) => settings.export(exporter_base, on_frame).await,
+        CliExportSettings::Webm(settings) => settings.export(exporter_base, on_frame).await,
+        CliExportSettings::Webp(settings) => {
+            settings
+                .export(AnimatedImageFormat::WebP, exporter_base, on_frame)
+                .await
+        }
+        CliExportSettings::Apng(settings) => {
+            settings
+                .export(AnimatedImageFormat::Apng, exporter_base, on_frame)
+                .await
+        }
+    }
+    .map_err(|v| format!("Exporter error: {v}"))?;
+
+    // Defense in depth: an export that renders no frames writes an empty (~few hundred byte) file
+    // but otherwise "succeeds". An agent must never silently get/upload that, so fail loudly and
+    // remove the empty artifact instead of reporting completion.
+    if total_frames > 0 && rendered.load(Ordering::Relaxed) == 0 {
+        let _ = std::fs::remove_file(&output_path);
+        return Err(format!(
+            "Export rendered 0 of {total_frames} frames; the recording may be unplayable. No output written."
+        ));
+    }
+
+    Ok(output_path)
+}
+
 /// Remux a recording left as fragments (status `NeedsRemux`) into a progressive `display.mp4` before
 /// export, reusing the shared `RecoveryManager`. A graceful `cap record` stop already remuxes in
 /// `finalize`, so this only fires for recordings interrupted before that (e.g. a killed worker);
@@ -460,8 +800,13 @@ fn instant_export_settings_supported(settings: &CliExportSettings) -> bool {
                 )
                 && settings.custom_bpp.is_none()
                 && !settings.optimize_filesize
+                && settings.loudness.is_none()
         }
-        CliExportSettings::Gif(_) | CliExportSettings::Mov(_) => false,
+        CliExportSettings::Gif(_)
+        | CliExportSettings::Mov(_)
+        | CliExportSettings::Webm(_)
+        | CliExportSettings::Webp(_)
+        | CliExportSettings::Apng(_) => false,
     }
 }
 
@@ -515,10 +860,8 @@ async fn export_instant_project(
     project_path: PathBuf,
     output: Option<PathBuf>,
     settings: &CliExportSettings,
-    progress_json: bool,
-    completion_json: bool,
-    stdout: &Arc<Mutex<std::io::Stdout>>,
-) -> Result<(), String> {
+    mut on_progress: impl FnMut(u32, u32) -> bool,
+) -> Result<PathBuf, String> {
     if !instant_export_settings_supported(settings) {
         return Err(
             "Instant recordings are already finalized MP4 files; export supports copying them to an mp4 output path"
@@ -529,85 +872,23 @@ async fn export_instant_project(
     let source_path = prepare_instant_output(project_path).await?;
     let output_path = output.unwrap_or_else(|| source_path.clone());
 
-    if progress_json {
-        emit_export_message(
-            stdout,
-            &ExportProgressMessage::Progress {
-                rendered_count: 0,
-                total_frames: 1,
-            },
-        )?;
+    if !on_progress(0, 1) {
+        return Err("Export cancelled".to_string());
     }
 
     let output_path = copy_instant_output(&source_path, output_path)?;
-
-    if progress_json {
-        emit_export_message(
-            stdout,
-            &ExportProgressMessage::Progress {
-                rendered_count: 1,
-                total_frames: 1,
-            },
-        )?;
-    }
-
-    if progress_json || completion_json {
-        emit_export_message(
-            stdout,
-            &ExportProgressMessage::Completed { path: &output_path },
-        )?;
-    } else {
-        println!("Exported video to {}", output_path.display());
-    }
+    on_progress(1, 1);
 
     info!("Exported instant video to '{}'", output_path.display());
 
-    Ok(())
+    Ok(output_path)
 }
 
 /// Render a project to its default output path with default settings (mp4, 1080p60, Maximum). Used by
 /// `cap upload --export` to glue record -> export -> upload into one step.
 pub async fn export_project_default(project_path: PathBuf) -> Result<PathBuf, String> {
     let settings = settings_from_flags(&ExportFlags::default())?;
-    ensure_remuxed(project_path.clone()).await?;
-    let meta = RecordingMeta::load_for_project(&project_path)
-        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
-    if matches!(&meta.inner, RecordingMetaInner::Instant(_)) {
-        return prepare_instant_output(project_path).await;
-    }
-
-    let exporter_base = ExporterBase::builder(project_path)
-        .with_force_ffmpeg_decoder(settings.force_ffmpeg_decoder())
-        .build()
-        .await
-        .map_err(|v| format!("Exporter build error: {v}"))?;
-
-    let total_frames = exporter_base.total_frames(settings.fps());
-    let rendered = Arc::new(AtomicU32::new(0));
-    let progress_rendered = Arc::clone(&rendered);
-    let on_progress = move |frame_index: u32| {
-        progress_rendered.store((frame_index + 1).min(total_frames), Ordering::Relaxed);
-        true
-    };
-
-    let output_path = match settings {
-        CliExportSettings::Mp4(settings) => settings.export(exporter_base, on_progress).await,
-        CliExportSettings::Gif(settings) => settings.export(exporter_base, on_progress).await,
-        CliExportSettings::Mov(settings) => settings.export(exporter_base, on_progress).await,
-    }
-    .map_err(|v| format!("Exporter error: {v}"))?;
-
-    // Same 0-frame guard as Export::run_inner: a recording with missing media renders an empty,
-    // unplayable file that otherwise "succeeds", and `cap upload --export` would sign + upload it and
-    // hand back a valid-looking link. Fail loudly and remove the artifact instead.
-    if total_frames > 0 && rendered.load(Ordering::Relaxed) == 0 {
-        let _ = std::fs::remove_file(&output_path);
-        return Err(format!(
-            "Export rendered 0 of {total_frames} frames; the recording may be unplayable. No output written."
-        ));
-    }
-
-    Ok(output_path)
+    export_project(project_path, None, settings, false, |_, _| true).await
 }
 
 #[derive(Args)]
@@ -696,6 +977,24 @@ mod tests {
         assert!(matches!(settings, CliExportSettings::Gif(_)));
     }
 
+    #[test]
+    fn animated_image_formats_default_to_30fps_and_reject_quality() {
+        let settings = settings_from_flags(&ExportFlags {
+            format: Some(ExportFormat::Apng),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(settings.fps(), 30);
+        assert!(matches!(settings, CliExportSettings::Apng(_)));
+
+        let result = settings_from_flags(&ExportFlags {
+            format: Some(ExportFormat::Webp),
+            quality: Some(QualityArg::Web),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn resolution_and_fps_overrides_apply() {
         let settings = settings_from_flags(&ExportFlags {
@@ -775,6 +1074,49 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn webm_defaults_to_vp9_and_accepts_quality_and_codec() {
+        let settings = settings_from_flags(&ExportFlags {
+            format: Some(ExportFormat::Webm),
+            ..Default::default()
+        })
+        .unwrap();
+        match settings {
+            CliExportSettings::Webm(s) => {
+                assert_eq!(s.fps, 60);
+                assert_eq!(s.codec, cap_export::webm::WebmCodec::Vp9);
+            }
+            _ => panic!("expected webm settings"),
+        }
+
+        let settings = settings_from_flags(&ExportFlags {
+            format: Some(ExportFormat::Webm),
+            codec: Some(CodecArg::Av1),
+            quality: Some(QualityArg::Social),
+            ..Default::default()
+        })
+        .unwrap();
+        match settings {
+            CliExportSettings::Webm(s) => {
+                assert_eq!(s.codec, cap_export::webm::WebmCodec::Av1);
+                assert!(matches!(
+                    s.compression,
+                    cap_export::mp4::ExportCompression::Social
+                ));
+            }
+            _ => panic!("expected webm settings"),
+        }
+    }
+
+    #[test]
+    fn codec_without_webm_is_rejected() {
+        let result = settings_from_flags(&ExportFlags {
+            codec: Some(CodecArg::Av1),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn optimize_filesize_only_for_mp4() {
         assert!(
@@ -786,4 +1128,96 @@ mod tests {
             .is_err()
         );
     }
+
+    #[test]
+    fn loudness_targets_mp4_and_webm_only() {
+        let settings = settings_from_flags(&ExportFlags {
+            loudness: Some(-14.0),
+            ..Default::default()
+        })
+        .unwrap();
+        match settings {
+            CliExportSettings::Mp4(s) => assert_eq!(
+                s.loudness,
+                Some(LoudnessTarget {
+                    integrated_lufs: -14.0,
+                    true_peak_dbtp: -1.0,
+                })
+            ),
+            _ => panic!("expected mp4 settings"),
+        }
+
+        assert!(
+            settings_from_flags(&ExportFlags {
+                format: Some(ExportFormat::Gif),
+                loudness: Some(-16.0),
+                ..Default::default()
+            })
+            .is_err()
+        );
+        assert!(
+            settings_from_flags(&ExportFlags {
+                true_peak: Some(-2.0),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn parse_timestamp_accepts_seconds_and_clock_times() {
+        assert_eq!(parse_timestamp("12.5").unwrap(), 12.5);
+        assert_eq!(parse_timestamp("02:03").unwrap(), 123.0);
+        assert_eq!(parse_timestamp("1:02:03.5").unwrap(), 3723.5);
+        assert!(parse_timestamp("-1").is_err());
+        assert!(parse_timestamp("1.5:00").is_err());
+        assert!(parse_timestamp("1:2:3:4").is_err());
+    }
+
+    #[test]
+    fn segment_flags_accept_names_and_indices() {
+        assert_eq!(range_from_flags(None, None, &[]), None);
+        assert_eq!(
+            range_from_flags(Some(5.0), None, &[]),
+            Some(ExportRange::Time {
+                from: Some(5.0),
+                to: None
+            })
+        );
+        assert_eq!(
+            range_from_flags(None, None, &["2".to_string(), "intro".to_string()]),
+            Some(ExportRange::Segments {
+                segments: vec![
+                    SegmentSelector::Index(2),
+                    SegmentSelector::Name("intro".to_string())
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn range_flags_fill_in_the_settings_range() {
+        let flags = ExportFlags {
+            range: Some(ExportRange::Time {
+                from: Some(5.0),
+                to: None,
+            }),
+            ..Default::default()
+        };
+        let settings = settings_from_flags(&flags).unwrap();
+        assert_eq!(settings.range(), flags.range.as_ref());
+
+        let gif =
+            r#"{"format":"Gif","fps":15,"resolution_base":{"x":1280,"y":720},"quality":null}"#;
+        let settings = resolve_export_settings(&flags, Some(gif)).unwrap();
+        assert_eq!(settings.range(), flags.range.as_ref());
+
+        let gif_clip = r#"{"format":"Gif","fps":15,"resolution_base":{"x":1280,"y":720},"quality":null,"range":{"type":"Segments","segments":[0]}}"#;
+        let settings = resolve_export_settings(&ExportFlags::default(), Some(gif_clip)).unwrap();
+        assert!(matches!(
+            settings.range(),
+            Some(ExportRange::Segments { .. })
+        ));
+        assert!(resolve_export_settings(&flags, Some(gif_clip)).is_err());
+    }
 }
@@ -0,0 +1,712 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+    time::Instant,
+};
+
+use cap_project::ExportRange;
+use cap_rendering::SharedWgpuDevice;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::export::{
+    CliExportSettings, ExportFlags, export_project_on_device, settings_from_flags,
+};
+
+#[derive(Args)]
+#[command(long_about = "Export many '.cap' projects listed in a JSON manifest.
+
+All jobs render on one shared GPU context, --jobs at a time. The manifest looks like
+{\"concurrency\":2,\"settings\":{...},\"jobs\":[{\"project\":\"a.cap\",\"output\":\"a.mp4\"},
+{\"id\":\"b\",\"project\":\"b.cap\",\"settings\":{\"format\":\"Gif\",\"fps\":15,
+\"resolution_base\":{\"x\":1280,\"y\":720},\"quality\":null}}]}. Each job's \"settings\" uses the
+--settings-json shape; jobs without one use the manifest's \"settings\", else the mp4 defaults.
+A job's optional \"range\" exports part of its project: {\"type\":\"Time\",\"from\":10,\"to\":20} in
+seconds, or {\"type\":\"Segments\",\"segments\":[\"intro\",2]} by segment name or index; the
+same \"range\" can instead go inside its settings.
+Relative paths resolve against the manifest's directory.
+
+With --json, NDJSON events use PascalCase type tags and snake_case fields like `cap export`:
+JobStarted, Progress, JobCompleted and JobFailed carry the job's index and id, and a final Summary
+follows. A JSON report of every job is written when the batch ends; if any job failed the command
+exits non-zero after it.")]
+pub struct ExportBatch {
+    /// Path to the JSON manifest listing the projects to export
+    manifest: PathBuf,
+    /// How many exports to run at once (overrides the manifest's "concurrency"; default 1)
+    #[arg(long, short = 'j')]
+    jobs: Option<usize>,
+    /// Where to write the summary report (default: <manifest>.report.json beside the manifest)
+    #[arg(long)]
+    report: Option<PathBuf>,
+    /// Stop starting new jobs after the first failure; the rest are reported as skipped
+    #[arg(long)]
+    fail_fast: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchManifest {
+    #[serde(default)]
+    concurrency: Option<usize>,
+    #[serde(default)]
+    report: Option<PathBuf>,
+    /// Settings for jobs that do not carry their own.
+    #[serde(default)]
+    settings: Option<CliExportSettings>,
+    #[serde(default)]
+    force_ffmpeg_decoder: bool,
+    jobs: Vec<BatchJobSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BatchJobSpec {
+    #[serde(default)]
+    id: Option<String>,
+    project: PathBuf,
+    #[serde(default)]
+    output: Option<PathBuf>,
+    #[serde(default)]
+    settings: Option<CliExportSettings>,
+    #[serde(default)]
+    force_ffmpeg_decoder: bool,
+    /// Export only part of the project, e.g. {"type":"Time","from":10,"to":20}.
+    #[serde(default)]
+    range: Option<ExportRange>,
+}
+
+struct BatchJob {
+    index: usize,
+    id: String,
+    project: PathBuf,
+    output: Option<PathBuf>,
+    settings: CliExportSettings,
+    force_ffmpeg_decoder: bool,
+}
+
+struct BatchPlan {
+    concurrency: usize,
+    report: Option<PathBuf>,
+    jobs: Vec<BatchJob>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type")]
+enum BatchEvent<'a> {
+    JobStarted {
+        index: usize,
+        id: &'a str,
+        project: &'a Path,
+    },
+    Progress {
+        index: usize,
+        id: &'a str,
+        rendered_count: u32,
+        total_frames: u32,
+    },
+    JobCompleted {
+        index: usize,
+        id: &'a str,
+        path: &'a Path,
+        elapsed_seconds: f64,
+    },
+    // Named `reason` rather than `error`: one failed job is not a failed command, and the
+    // `"error" in obj` predicate is reserved for the terminal Error event.
+    JobFailed {
+        index: usize,
+        id: &'a str,
+        reason: &'a str,
+    },
+    Summary {
+        total: usize,
+        succeeded: usize,
+        failed: usize,
+        skipped: usize,
+        elapsed_seconds: f64,
+        report: &'a Path,
+    },
+    Error {
+        error: &'a str,
+    },
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum JobStatus {
+    Completed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Serialize)]
+struct JobReport {
+    index: usize,
+    id: String,
+    project: PathBuf,
+    status: JobStatus,
+    path: Option<PathBuf>,
+    error: Option<String>,
+    total_frames: Option<u32>,
+    elapsed_seconds: f64,
+}
+
+#[derive(Serialize)]
+struct BatchReport {
+    manifest: PathBuf,
+    started_at: String,
+    elapsed_seconds: f64,
+    concurrency: usize,
+    total: usize,
+    succeeded: usize,
+    failed: usize,
+    skipped: usize,
+    jobs: Vec<JobReport>,
+}
+
+impl BatchReport {
+    fn count(&self, status: JobStatus) -> usize {
+        self.jobs.iter().filter(|job| job.status == status).count()
+    }
+}
+
+fn resolve_against(base: &Path, path: PathBuf) -> PathBuf {
+    if path.is_absolute() {
+        path
+    } else {
+        base.join(path)
+    }
+}
+
+fn default_report_path(manifest: &Path) -> PathBuf {
+    let stem = manifest
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "batch".to_string());
+    manifest.with_file_name(format!("{stem}.report.json"))
+}
+
+fn plan_batch(manifest_path: &Path, contents: &str) -> Result<BatchPlan, String> {
+    let manifest: BatchManifest = serde_json::from_str(contents)
+        .map_err(|e| format!("Invalid batch manifest {}: {e}", manifest_path.display()))?;
+
+    if manifest.jobs.is_empty() {
+        return Err("Batch manifest has no jobs".to_string());
+    }
+
+    let base = manifest_path.parent().unwrap_or(Path::new("."));
+    let default_settings = match manifest.settings {
+        Some(settings) => settings,
+        None => settings_from_flags(&ExportFlags::default())?,
+    };
+
+    let mut ids = HashSet::new();
+    let mut destinations = HashSet::new();
+    let mut jobs = Vec::with_capacity(manifest.jobs.len());
+
+    for (index, spec) in manifest.jobs.into_iter().enumerate() {
+        let project = resolve_against(base, spec.project);
+        let output = spec.output.map(|output| resolve_against(base, output));
+        let id = spec.id.unwrap_or_else(|| {
+            project
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| format!("job-{index}"))
+        });
+
+        if !ids.insert(id.clone()) {
+            return Err(format!(
+                "Batch job {index} reuses id '{id}'; give each job a unique \"id\""
+            ));
+        }
+
+        let settings = spec
+            .settings
+            .unwrap_or_else(|| default_settings.clone())
+            .with_range(spec.range)
+            .map_err(|error| format!("Batch job '{id}': {error}"))?;
+
+        // Two jobs writing the same file would overwrite each other mid-render, whether the path
+        // was given or is the project's default for the format.
+        let destination = settings.output_path(&project, output.as_deref());
+        if !destinations.insert(destination.clone()) {
+            return Err(format!(
+                "Batch job '{id}' writes to {} like an earlier job; give each job a distinct \"output\"",
+                destination.display()
+            ));
+        }
+
+        jobs.push(BatchJob {
+            index,
+            id,
+            project,
+            output,
+            settings,
+            force_ffmpeg_decoder: manifest.force_ffmpeg_decoder || spec.force_ffmpeg_decoder,
+        });
+    }
+
+    Ok(BatchPlan {
+        concurrency: manifest.concurrency.unwrap_or(1),
+        report: manifest.report.map(|report| resolve_against(base, report)),
+        jobs,
+    })
+}
+
+struct BatchOutput {
+    json: bool,
+    total: usize,
+    stdout: Mutex<std::io::Stdout>,
+}
+
+impl BatchOutput {
+    fn event(&self, event: &BatchEvent<'_>) {
+        if !self.json {
+            return;
+        }
+
+        // Jobs report from several workers at once; serialise first so each event is written
+        // as one line under the lock.
+        let Ok(line) = serde_json::to_string(event) else {
+            return;
+        };
+        if let Ok(mut stdout) = self.stdout.lock() {
+            let _ = writeln!(stdout, "{line}");
+            let _ = stdout.flush();
+        }
+    }
+
+    fn text(&self, message: impl FnOnce() -> String) {
+        if !self.json {
+            eprintln!("{}", message());
+        }
+    }
+}
+
+impl ExportBatch {
+    pub async fn run(self, json: bool) -> Result<(), String> {
+        match self.run_inner(json).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                // Every worker has finished by now, so nothing else is writing to stdout.
+                if json {
+                    let _ = crate::write_json_line(&BatchEvent::Error { error: &error });
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn run_inner(self, json: bool) -> Result<(), String> {
+        let contents = std::fs::read_to_string(&self.manifest).map_err(|e| {
+            format!(
+                "Failed to read batch manifest {}: {e}",
+                self.manifest.display()
+            )
+        })?;
+        let plan = plan_batch(&self.manifest, &contents)?;
+
+        let concurrency = self.jobs.unwrap_or(plan.concurrency);
+        if concurrency == 0 {
+            return Err("--jobs must be greater than zero".to_string());
+        }
+        let concurrency = concurrency.min(plan.jobs.len());
+        let report_path = self
+            .report
+            .or(plan.report)
+            .unwrap_or_else(|| default_report_path(&self.manifest));
+
+        let output = Arc::new(BatchOutput {
+            json,
+            total: plan.jobs.len(),
+            stdout: Mutex::new(std::io::stdout()),
+        });
+
+        let shared_device = SharedWgpuDevice::create()
+            .await
+            .map_err(|e| format!("Failed to set up renderer: {e}"))?;
+
+        let started_at = chrono::Utc::now();
+        let started = Instant::now();
+        let queue = Arc::new(Mutex::new(plan.jobs.into_iter().collect::<VecDeque<_>>()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let workers = (0..concurrency)
+            .map(|_| {
+                tokio::spawn(run_worker(
+                    Arc::clone(&queue),
+                    shared_device.clone(),
+                    Arc::clone(&output),
+                    Arc::clone(&stop),
+                    self.fail_fast,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        let mut jobs = Vec::new();
+        for worker in workers {
+            jobs.extend(
+                worker
+                    .await
+                    .map_err(|e| format!("Batch export worker failed: {e}"))?,
+            );
+        }
+
+        // Anything still queued was never started because --fail-fast stopped the workers.
+        let remaining = std::mem::take(
+            &mut *queue
+                .lock()
+                .map_err(|_| "Batch queue poisoned".to_string())?,
+        );
+        jobs.extend(remaining.into_iter().map(|job| JobReport {
+            index: job.index,
+            id: job.id,
+            project: job.project,
+            status: JobStatus::Skipped,
+            path: None,
+            error: None,
+            total_frames: None,
+            elapsed_seconds: 0.0,
+        }));
+        jobs.sort_by_key(|job| job.index);
+
+        let mut report = BatchReport {
+            manifest: self.manifest.clone(),
+            started_at: started_at.to_rfc3339(),
+            elapsed_seconds: started.elapsed().as_secs_f64(),
+            concurrency,
+            total: jobs.len(),
+            succeeded: 0,
+            failed: 0,
+            skipped: 0,
+            jobs,
+        };
+        report.succeeded = report.count(JobStatus::Completed);
+        report.failed = report.count(JobStatus::Failed);
+        report.skipped = report.count(JobStatus::Skipped);
+
+        write_report(&report_path, &report)?;
+
+        output.event(&BatchEvent::Summary {
+            total: report.total,
+            succeeded: report.succeeded,
+            failed: report.failed,
+            skipped: report.skipped,
+            elapsed_seconds: report.elapsed_seconds,
+            report: &report_path,
+        });
+        if !json {
+            println!(
+                "Exported {} of {} projects in {:.1}s; report written to {}",
+                report.succeeded,
+                report.total,
+                report.elapsed_seconds,
+                report_path.display()
+            );
+        }
+
+        if report.failed > 0 || report.skipped > 0 {
+            return Err(format!(
+                "{} of {} export jobs failed{}; see {}",
+                report.failed,
+                report.total,
+                if report.skipped > 0 {
+                    format!(" and {} were skipped", report.skipped)
+                } else {
+                    String::new()
+                },
+                report_path.display()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+async fn run_worker(
+    queue: Arc<Mutex<VecDeque<BatchJob>>>,
+    shared_device: SharedWgpuDevice,
+    output: Arc<BatchOutput>,
+    stop: Arc<AtomicBool>,
+    fail_fast: bool,
+) -> Vec<JobReport> {
+    let mut reports = Vec::new();
+
+    loop {
+        if stop.load(Ordering::Relaxed) {
+            break;
+        }
+        let Some(job) = queue.lock().ok().and_then(|mut queue| queue.pop_front()) else {
+            break;
+        };
+
+        let report = run_job(job, shared_device.clone(), &output).await;
+        if fail_fast && report.status == JobStatus::Failed {
+            stop.store(true, Ordering::Relaxed);
+        }
+        reports.push(report);
+    }
+
+    reports
+}
+
+async fn run_job(
+    job: BatchJob,
+    shared_device: SharedWgpuDevice,
+    output: &Arc<BatchOutput>,
+) -> JobReport {
+    let started = Instant::now();
+    let BatchJob {
+        index,
+        id,
+        project,
+        output: output_path,
+        settings,
+        force_ffmpeg_decoder,
+    } = job;
+
+    output.event(&BatchEvent::JobStarted {
+        index,
+        id: &id,
+        project: &project,
+    });
+
+    let total_frames = Arc::new(AtomicU32::new(0));
+    let progress_total = Arc::clone(&total_frames);
+    let progress_output = Arc::clone(output);
+    let progress_id = id.clone();
+    let on_progress = move |rendered_count: u32, total_frames: u32| {
+        progress_total.store(total_frames, Ordering::Relaxed);
+        progress_output.event(&BatchEvent::Progress {
+            index,
+            id: &progress_id,
+            rendered_count,
+            total_frames,
+        });
+        true
+    };
+
+    let result = export_project_on_device(
+        project.clone(),
+        output_path,
+        settings,
+        force_ffmpeg_decoder,
+        Some(shared_device),
+        on_progress,
+    )
+    .await;
+    let elapsed_seconds = started.elapsed().as_secs_f64();
+    let total_frames = Some(total_frames.load(Ordering::Relaxed)).filter(|frames| *frames > 0);
+
+    match result {
+        Ok(path) => {
+            output.event(&BatchEvent::JobCompleted {
+                index,
+                id: &id,
+                path: &path,
+                elapsed_seconds,
+            });
+            output.text(|| {
+                format!(
+                    "[{}/{}] {id}: exported to {} ({elapsed_seconds:.1}s)",
+                    index + 1,
+                    output.total,
+                    path.display()
+                )
+            });
+            JobReport {
+                index,
+                id,
+                project,
+                status: JobStatus::Completed,
+                path: Some(path),
+                error: None,
+                total_frames,
+                elapsed_seconds,
+            }
+        }
+        Err(error) => {
+            output.event(&BatchEvent::JobFailed {
+                index,
+                id: &id,
+                reason: &error,
+            });
+            output.text(|| format!("[{}/{}] {id}: failed: {error}", index + 1, output.total));
+            JobReport {
+                index,
+                id,
+                project,
+                status: JobStatus::Failed,
+                path: None,
+                error: Some(error),
+                total_frames,
+                elapsed_seconds,
+            }
+        }
+    }
+}
+
+fn write_report(path: &Path, report: &BatchReport) -> Result<(), String> {
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Failed to create report directory {}: {e}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let json = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    std::fs::write(path, json)
+        .map_err(|e| format!("Failed to write batch report {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(contents: &str) -> Result<BatchPlan, String> {
+        plan_batch(Path::new("/work/release/manifest.json"), contents)
+    }
+
+    #[test]
+    fn jobs_resolve_against_the_manifest_and_inherit_default_settings() {
+        let plan = plan(
+            r#"{
+                "concurrency": 3,
+                "settings": {"format": "Gif", "fps": 12, "resolution_base": {"x": 640, "y": 360}, "quality": null},
+                "jobs": [
+                    {"project": "intro.cap", "output": "out/intro.gif"},
+                    {"id": "abs", "project": "/abs/demo.cap", "settings": {"format": "Mov", "fps": 30, "resolution_base": {"x": 1920, "y": 1080}}}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(plan.concurrency, 3);
+        assert_eq!(plan.jobs[0].id, "intro");
+        assert_eq!(
+            plan.jobs[0].project,
+            PathBuf::from("/work/release/intro.cap")
+        );
+        assert_eq!(
+            plan.jobs[0].output,
+            Some(PathBuf::from("/work/release/out/intro.gif"))
+        );
+        assert!(matches!(plan.jobs[0].settings, CliExportSettings::Gif(_)));
+        assert_eq!(plan.jobs[1].project, PathBuf::from("/abs/demo.cap"));
+        assert!(matches!(plan.jobs[1].settings, CliExportSettings::Mov(_)));
+    }
+
+    #[test]
+    fn jobs_without_settings_default_to_mp4() {
+        let plan = plan(r#"{"jobs": [{"project": "a.cap"}]}"#).unwrap();
+
+        assert_eq!(plan.concurrency, 1);
+        assert!(matches!(plan.jobs[0].settings, CliExportSettings::Mp4(_)));
+    }
+
+    #[test]
+    fn jobs_can_export_a_range_of_their_project() {
+        let plan = plan(
+            r#"{"jobs": [
+                {"id": "clip", "project": "a.cap", "output": "clip.mp4", "range": {"type": "Time", "from": 10, "to": 20}},
+                {"project": "a.cap", "range": {"type": "Segments", "segments": ["intro", 2]}}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            plan.jobs[0].settings.range(),
+            Some(&ExportRange::Time {
+                from: Some(10.0),
+                to: Some(20.0)
+            })
+        );
+        assert!(matches!(
+            plan.jobs[1].settings.range(),
+            Some(ExportRange::Segments { segments }) if segments.len() == 2
+        ));
+    }
+
+    #[test]
+    fn ranges_can_come_with_the_settings_but_not_twice() {
+        let settings = r#"{"format": "Mp4", "fps": 30, "resolution_base": {"x": 1280, "y": 720}, "compression": "Web", "custom_bpp": null, "range": {"type": "Time", "from": 5, "to": null}}"#;
+        let plan = plan(&format!(
+            r#"{{"settings": {settings}, "jobs": [{{"project": "a.cap"}}]}}"#
+        ))
+        .unwrap();
+        assert_eq!(
+            plan.jobs[0].settings.range(),
+            Some(&ExportRange::Time {
+                from: Some(5.0),
+                to: None
+            })
+        );
+
+        assert!(
+            plan(&format!(
+                r#"{{"settings": {settings}, "jobs": [{{"project": "a.cap", "range": {{"type": "Segments", "segments": [0]}}}}]}}"#
+            ))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn colliding_outputs_and_ids_are_rejected() {
+        assert!(
+            plan(r#"{"jobs": [{"project": "a.cap"}, {"id": "other", "project": "a.cap"}]}"#)
+                .is_err()
+        );
+        assert!(
+            plan(
+                r#"{"jobs": [{"project": "a.cap", "output": "x.mp4"}, {"project": "b.cap", "output": "x.mp4"}]}"#
+            )
+            .is_err()
+        );
+        assert!(
+            plan(r#"{"jobs": [{"id": "x", "project": "a.cap"}, {"id": "x", "project": "b.cap"}]}"#)
+                .is_err()
+        );
+        assert!(
+            plan(
+                r#"{"jobs": [{"project": "a.cap"}, {"project": "b.cap", "output": "a.cap/output/result.mp4"}]}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn default_outputs_on_one_project_differ_by_format() {
+        let plan = plan(
+            r#"{"jobs": [
+                {"project": "a.cap"},
+                {"id": "gif", "project": "a.cap", "settings": {"format": "Gif", "fps": 12, "resolution_base": {"x": 640, "y": 360}, "quality": null}}
+            ]}"#,
+        )
+        .unwrap();
+
+        assert_eq!(plan.jobs.len(), 2);
+    }
+
+    #[test]
+    fn empty_and_unknown_fields_are_rejected() {
+        assert!(plan(r#"{"jobs": []}"#).is_err());
+        assert!(plan(r#"{"jobs": [{"project": "a.cap", "ouptut": "a.mp4"}]}"#).is_err());
+    }
+
+    #[test]
+    fn default_report_sits_beside_the_manifest() {
+        assert_eq!(
+            default_report_path(Path::new("/work/release/manifest.json")),
+            PathBuf::from("/work/release/manifest.report.json")
+        );
+    }
+}
@@ -169,11 +169,11 @@ fn build() -> Guide {
             CommandDoc {
                 requires_duration: false,
                 notes: Some(
-                    "The `started` then `stopped` sequence applies to the foreground run. With `--detach` the stream emits only `started` (or `error`) and returns immediately with recordingId+pid; the `stopped` event is delivered by `cap record stop`.",
+                    "The `started` then `stopped` sequence applies to the foreground run. With `--detach` the stream emits only `started` (or `error`) and returns immediately with recordingId+pid; the `stopped` event is delivered by `cap record stop`. `--stream-url rtmp://…|srt://…` also broadcasts the recording live; a dropped connection is retried with backoff and never fails the local recording.",
                 ),
                 ..cmd(
                     "record start",
-                    "Start a recording. Foreground emits `started` then `stopped` (success requires recordingMetaExists:true); see notes for `--detach`.",
+                    "Start a recording of --screen, --window, or an --area x,y,width,height of a screen. Foreground emits `started` then `stopped` (success requires recordingMetaExists:true); see notes for `--detach`.",
                     OutputMode::Ndjson,
                     &["started", "stopped", "error"],
                 )
@@ -185,16 +185,41 @@ fn build() -> Guide {
                 &["stopped", "error"],
             ),
             cmd(
-                "record status",
-                "List active detached recording sessions.",
-                OutputMode::SingleJson,
-                &[],
+                "record marker",
+                "Drop a named chapter marker (--name, default \"Chapter N\") into a detached studio recording by recordingId (or --path). Exports write markers as MP4/MOV chapters plus a <output>.chapters.vtt sidecar. The marker is placed when it was asked for; if the control endpoint can't be reached it is queued through the session files and not confirmed.",
+                OutputMode::Ndjson,
+                &["marker", "error"],
+            ),
+            cmd(
+                "record pause",
+                "Pause a detached recording by recordingId (or --path); returns once the recording has paused.",
+                OutputMode::Ndjson,
+                &["paused", "error"],
+            ),
+            cmd(
+                "record resume",
+                "Resume a paused detached recording by recordingId (or --path).",
+                OutputMode::Ndjson,
+                &["resumed", "error"],
             ),
+            CommandDoc {
+                notes: Some(
+                    "With `--follow` (and --id/--path, or the only active session) the output is NDJSON instead: `health` lines carry a pipeline health `event` tagged by `kind` (e.g. frameDropRateHigh, framesDropped, stalled, muxerCrashed, diskSpaceLow, streamConnected, streamDisconnected), `stats` lines every 2s carry elapsedSecs, fps, videoFrames, droppedFrames, streamDroppedFrames (left out of the --stream-url broadcast only) and bytesWritten, and the stream ends with `stopped` or `error`.",
+                ),
+                ..cmd(
+                    "record status",
+                    "List active detached recording sessions with their latest stats, or follow one's health and stats until it stops.",
+                    OutputMode::SingleJson,
+                    &["health", "stats", "stopped", "error"],
+                )
+            },
             CommandDoc {
                 notes: Some(
                     "EXCEPTION: export NDJSON uses PascalCase `type` tags and snake_case fields \
                      (rendered_count, total_frames) for desktop compatibility. --format selects the \
-                     CONTAINER (mp4/gif/mov), NOT output mode; use --json for machine-readable output.",
+                     CONTAINER (mp4/gif/mov/webm/webp/apng), NOT output mode; use --json for machine-readable output. \
+                     --from/--to (seconds or [HH:]MM:SS) export part of the edited timeline; --segment NAME|INDEX \
+                     (repeatable) exports only those timeline segments.",
                 ),
                 ..cmd(
                     "export",
@@ -203,6 +228,31 @@ fn build() -> Guide {
                     &["Progress", "Completed", "Error"],
                 )
             },
+            cmd(
+                "export batch",
+                "Export every job in a JSON manifest ({jobs:[{project,output?,settings?,range?,id?}],settings?,concurrency?}) on one shared GPU context, --jobs at a time, then write a JSON report (--report, default <manifest>.report.json). Exits non-zero if any job failed.",
+                OutputMode::Ndjson,
+                &[
+                    "JobStarted",
+                    "Progress",
+                    "JobCompleted",
+                    "JobFailed",
+                    "Summary",
+                    "Error",
+                ],
+            ),
+            cmd(
+                "import",
+                "Turn a video or image into a .cap project, or append a video/.cap to an existing project with --append-to.",
+                OutputMode::Ndjson,
+                &["progress", "completed", "error"],
+            ),
+            cmd(
+                "captions generate|import|export",
+                "Transcribe a project's audio into captions with a local Whisper/Parakeet model (--model), replace them from an SRT/WebVTT/ASS file (timed against the edited video unless --source-timed), or write them out. --format selects the SUBTITLE FORMAT, not output mode.",
+                OutputMode::SingleJson,
+                &[],
+            ),
             cmd(
                 "screenshot",
                 "Capture a still of a screen/window. JSON emits {path,width,height}.",
@@ -343,7 +393,7 @@ fn build() -> Guide {
             ),
             cmd(
                 "mcp serve",
-                "Run the local stdio MCP server. Stdout is reserved exclusively for protocol traffic.",
+                "Run the local stdio MCP server, including local recording, project, export and screenshot tools. Stdout is reserved exclusively for protocol traffic.",
                 OutputMode::TextOnly,
                 &[],
             ),
@@ -377,6 +427,18 @@ fn build() -> Guide {
                 OutputMode::SingleJson,
                 &[],
             ),
+            cmd(
+                "project trim-silence",
+                "Cut silences longer than --min-silence (quieter than --threshold-db) out of a project's timeline, keeping --padding around speech and clips of at least --min-clip, with --transition cross-fades. --dry-run reports without writing.",
+                OutputMode::SingleJson,
+                &[],
+            ),
+            cmd(
+                "project loudness",
+                "EBU R128 integrated loudness (LUFS), loudness range and true peak of a project's audio mix, plus the gain `export --loudness` would apply to reach --target (default -16).",
+                OutputMode::SingleJson,
+                &[],
+            ),
             cmd(
                 "version",
                 "CLI version + execution context (distribution, bundled binaries).",
@@ -0,0 +1,204 @@
+use std::{
+    env::current_dir,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use cap_import::{ImportProgress, ImportStage, ProgressCallback};
+use clap::Args;
+use serde::Serialize;
+
+use crate::{OutputFormat, resolve_format, write_json_line};
+
+#[derive(Args)]
+#[command(
+    long_about = "Turn a video or image file into an editable '.cap' project, or append a video \
+(or another '.cap' project) to an existing one with --append-to.
+
+Videos are re-encoded to H.264 with their audio kept as a separate track, so any file FFmpeg can \
+decode works. With --json, NDJSON events are streamed to stdout: {\"type\":\"progress\",\"stage\":\"Converting\",\
+\"progress\":0.42,\"message\":\"...\"} then {\"type\":\"completed\",\"path\":\"...\"}; on failure a final \
+{\"type\":\"error\",\"error\":\"...\"} is emitted."
+)]
+pub struct Import {
+    /// Video or image file to import (or a '.cap' project when appending)
+    source: PathBuf,
+    /// Append to this '.cap' project as new recording segments instead of creating a project
+    #[arg(long)]
+    append_to: Option<PathBuf>,
+    /// Where to create the '.cap' project (defaults to "<file name> <date>.cap" in the working directory)
+    #[arg(long, conflicts_with = "append_to")]
+    path: Option<PathBuf>,
+    /// Output format for progress events
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+enum ImportEvent<'a> {
+    Progress {
+        stage: ImportStage,
+        progress: f64,
+        message: &'a str,
+    },
+    Completed {
+        path: &'a Path,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        imported_segments: Option<usize>,
+    },
+    Error {
+        error: &'a str,
+    },
+}
+
+fn emit_import_event(format: OutputFormat, event: &ImportEvent<'_>) -> Result<(), String> {
+    match format {
+        OutputFormat::Text => {
+            match event {
+                ImportEvent::Progress { .. } => {}
+                ImportEvent::Completed {
+                    path,
+                    imported_segments: Some(count),
+                } => println!("Imported {count} segment(s) into {}", path.display()),
+                ImportEvent::Completed { path, .. } => {
+                    println!("Imported project: {}", path.display())
+                }
+                ImportEvent::Error { error } => println!("Import error: {error}"),
+            }
+            Ok(())
+        }
+        OutputFormat::Json => write_json_line(event),
+    }
+}
+
+impl Import {
+    pub async fn run(self, json: bool) -> Result<(), String> {
+        let format = resolve_format(json, self.format);
+        match self.run_inner(format).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                if format == OutputFormat::Json {
+                    let _ = emit_import_event(format, &ImportEvent::Error { error: &error });
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn run_inner(self, format: OutputFormat) -> Result<(), String> {
+        if !self.source.exists() {
+            return Err(format!("{} does not exist", self.source.display()));
+        }
+
+        // Progress is a side channel: a closed stdout must not abort the import.
+        let on_progress: ProgressCallback = Arc::new(move |progress: ImportProgress| {
+            let _ = emit_import_event(
+                format,
+                &ImportEvent::Progress {
+                    stage: progress.stage,
+                    progress: progress.progress,
+                    message: &progress.message,
+                },
+            );
+        });
+
+        if let Some(target) = self.append_to {
+            if !cap_import::is_cap_project_path(&target) {
+                return Err(format!("{} is not a '.cap' project", target.display()));
+            }
+            if cap_import::is_supported_image_import_path(&self.source) {
+                return Err(
+                    "Only videos and '.cap' projects can be appended to a project".to_string(),
+                );
+            }
+
+            let count =
+                cap_import::append_to_project(target.clone(), self.source, on_progress).await?;
+            return emit_import_event(
+                format,
+                &ImportEvent::Completed {
+                    path: &target,
+                    imported_segments: Some(count),
+                },
+            );
+        }
+
+        let project_path = if cap_import::is_supported_video_import_path(&self.source) {
+            let project_name = cap_import::generate_project_name(&self.source);
+            let project_path = self.project_path(&project_name)?;
+            let import = cap_import::prepare_video_import(
+                self.source,
+                project_path.clone(),
+                project_name,
+                &on_progress,
+            )?;
+            import.run(on_progress).await?;
+            crate::automation::run_video_imported(&project_path).await;
+            project_path
+        } else if cap_import::is_supported_image_import_path(&self.source) {
+            let project_name = cap_import::generate_image_project_name(&self.source);
+            let project_path = self.project_path(&project_name)?;
+            cap_import::import_image(self.source, project_path.clone(), project_name).await?;
+            project_path
+        } else {
+            return Err(format!(
+                "Unsupported file type: {} (expected a video: {}; or an image: {})",
+                self.source.display(),
+                cap_import::VIDEO_IMPORT_EXTENSIONS.join(", "),
+                cap_import::IMAGE_IMPORT_EXTENSIONS.join(", ")
+            ));
+        };
+
+        emit_import_event(
+            format,
+            &ImportEvent::Completed {
+                path: &project_path,
+                imported_segments: None,
+            },
+        )
+    }
+
+    fn project_path(&self, project_name: &str) -> Result<PathBuf, String> {
+        match &self.path {
+            Some(path) if path.exists() => Err(format!("{} already exists", path.display())),
+            Some(path) => Ok(path.clone()),
+            None => {
+                let dir =
+                    current_dir().map_err(|e| format!("Could not read working directory: {e}"))?;
+                Ok(cap_import::unique_project_path(&dir, project_name))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_event_fields_are_camel_case() {
+        let event = serde_json::to_value(ImportEvent::Completed {
+            path: Path::new("clip.cap"),
+            imported_segments: Some(2),
+        })
+        .unwrap();
+
+        assert_eq!(event["type"], "completed");
+        assert_eq!(event["importedSegments"], 2);
+
+        let event = serde_json::to_value(ImportEvent::Progress {
+            stage: ImportStage::Converting,
+            progress: 0.5,
+            message: "Converting video... 50%",
+        })
+        .unwrap();
+
+        assert_eq!(event["type"], "progress");
+        assert_eq!(event["stage"], "Converting");
+    }
+}
@@ -6,12 +6,16 @@ mod analytics;
 mod atomic;
 mod automation;
 mod caps;
+mod captions;
 mod confirmation;
+mod control;
 mod credentials;
 mod developers;
 mod doctor;
 mod export;
+mod export_batch;
 mod guide;
+mod import;
 mod jobs;
 mod library;
 mod mcp;
@@ -32,8 +36,10 @@ use std::{
     path::PathBuf,
 };
 
+use cap_export::loudness::LoudnessTarget;
+use cap_project::SilenceCutOptions;
 use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
-use export::{Export, ExportPreview};
+use export::{ExportArgs, ExportPreview};
 use record::RecordStart;
 use serde::Serialize;
 use tracing_subscriber::{filter::LevelFilter, layer::SubscriberExt, util::SubscriberInitExt};
@@ -143,6 +149,10 @@ TYPICAL AGENT WORKFLOW
   cap record stop --id <recordingId> --json  # finalize the .cap recording
   cap project validate <path.cap> --json     # confirm the recording is complete
   cap export <path.cap> --output out.mp4 --json
+  cap export batch releases.json --jobs 2 --json  # many projects on one GPU context + report
+  cap import capture.mov --json              # or turn an existing video into a .cap project
+  cap captions generate <path.cap> --model ggml-base.en.bin --json  # transcribe captions locally
+  cap captions import <path.cap> subs.srt --json  # burn vendor subtitles into the next export
   cap upload out.mp4 --json                   # get a shareable link (needs CAP_API_KEY)";
 
 #[derive(Parser)]
@@ -189,10 +199,14 @@ impl CliLogLevel {
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Export a '.cap' project to a video file
-    Export(Export),
+    /// Export a '.cap' project to a video file, or many from a batch manifest
+    Export(ExportArgs),
     /// Render an export preview frame
     ExportPreview(ExportPreview),
+    /// Turn a video or image file into a '.cap' project, or append it to an existing one
+    Import(import::Import),
+    /// Generate a '.cap' project's captions, or import/export them as SRT, WebVTT or ASS subtitles
+    Captions(captions::CaptionsArgs),
     /// Inspect or validate a '.cap' project
     Project(ProjectArgs),
     /// Start a recording or list available capture targets and devices
@@ -280,8 +294,14 @@ enum RecordCommands {
     Start(RecordStart),
     /// Stop a detached recording started with `cap record start --detach`
     Stop(record::RecordStopArgs),
-    /// List active and recent detached recording sessions
-    Status(FormatArgs),
+    /// List active and recent detached recording sessions, or follow one's health while it records
+    Status(record::RecordStatusArgs),
+    /// Drop a named chapter marker into a detached studio recording
+    Marker(record::RecordMarkerArgs),
+    /// Pause a detached recording
+    Pause(record::RecordPauseArgs),
+    /// Resume a paused detached recording
+    Resume(record::RecordPauseArgs),
     /// Internal: background worker for detached recordings (do not call directly)
     #[command(name = "__session-run", hide = true)]
     SessionRun(record::SessionRunArgs),
@@ -342,6 +362,10 @@ enum ProjectCommands {
     Validate(ProjectTarget),
     /// Read or write a project's editor configuration (project-config.json)
     Config(ProjectConfigArgs),
+    /// Cut long silences out of a project's timeline
+    TrimSilence(ProjectTrimSilence),
+    /// Measure the loudness of a project's audio mix
+    Loudness(ProjectLoudness),
 }
 
 #[derive(Args)]
@@ -375,6 +399,65 @@ struct ProjectConfigSet {
     format: OutputFormat,
 }
 
+#[derive(Args)]
+#[command(long_about = "Cut long silences out of a '.cap' project's timeline.
+
+The microphone and system audio of every recording segment are scanned for stretches quieter than \
+--threshold-db. Each one longer than --min-silence is cut, leaving --padding either side so words \
+aren't clipped, and the clips either side are joined with a --transition cross-fade. A cut that \
+would leave a clip shorter than --min-clip is skipped. Zooms, captions and other timeline tracks \
+move with the footage. The new timeline is written to project-config.json; pass --dry-run to only \
+report what would be cut.")]
+struct ProjectTrimSilence {
+    project_path: PathBuf,
+    /// Report the cuts without writing the project
+    #[arg(long)]
+    dry_run: bool,
+    /// Level in dBFS below which audio counts as silence
+    #[arg(
+        long,
+        default_value_t = cap_audio::DEFAULT_SILENCE_THRESHOLD_DB,
+        allow_negative_numbers = true
+    )]
+    threshold_db: f32,
+    /// Shortest silence, in seconds, that is cut
+    #[arg(long, default_value_t = SilenceCutOptions::default().min_silence)]
+    min_silence: f64,
+    /// Seconds of silence kept either side of each cut
+    #[arg(long, default_value_t = SilenceCutOptions::default().padding)]
+    padding: f64,
+    /// Shortest clip, in seconds, a cut may leave
+    #[arg(long, default_value_t = SilenceCutOptions::default().min_clip)]
+    min_clip: f64,
+    /// Cross-fade length in seconds at each cut; 0 for hard cuts
+    #[arg(long, default_value_t = SilenceCutOptions::default().transition)]
+    transition: f64,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Args)]
+#[command(
+    long_about = "Measure the loudness of a '.cap' project's audio mix, as an export would render it.
+
+Reports EBU R128 integrated loudness (LUFS), loudness range (LU) and true peak (dBTP) of the \
+microphone, system audio and music mixed with the project's current edits, and the gain an export \
+with `--loudness` would apply to reach --target."
+)]
+struct ProjectLoudness {
+    project_path: PathBuf,
+    /// Integrated loudness in LUFS to report the normalisation gain for
+    #[arg(
+        long,
+        value_name = "LUFS",
+        default_value_t = LoudnessTarget::default().integrated_lufs,
+        allow_negative_numbers = true
+    )]
+    target: f64,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
 #[derive(Args)]
 struct RecordingsArgs {
     #[command(subcommand)]
@@ -556,14 +639,16 @@ async fn run(cli: Cli) -> Result<(), String> {
         Commands::Export(e) => e.run(json).await,
         Commands::ExportPreview(e) => e.run().await,
         Commands::Selftest(args) => args.run(json).await,
+        Commands::Import(args) => args.run(json).await,
+        Commands::Captions(args) => args.run(json).await,
         Commands::Project(args) => args.run(json),
         Commands::Record(RecordArgs { command, args }) => match command {
             Some(RecordCommands::Start(args)) => args.run(json).await,
             Some(RecordCommands::Stop(args)) => args.run(json).await,
-            Some(RecordCommands::Status(args)) => {
-                let format = resolve_format(json, args.format);
-                finish_json(format, record::status(format))
-            }
+            Some(RecordCommands::Marker(args)) => args.run(json).await,
+            Some(RecordCommands::Pause(args)) => args.run(json, true).await,
+            Some(RecordCommands::Resume(args)) => args.run(json, false).await,
+            Some(RecordCommands::Status(args)) => args.run(json).await,
             Some(RecordCommands::SessionRun(args)) => args.run().await,
             Some(RecordCommands::Screens(args)) => {
                 let format = resolve_format(json, args.format);
@@ -719,6 +804,32 @@ impl ProjectArgs {
                     )
                 }
             },
+            ProjectCommands::TrimSilence(args) => {
+                let format = resolve_format(json, args.format);
+                let options = SilenceCutOptions {
+                    min_silence: args.min_silence,
+                    padding: args.padding,
+                    min_clip: args.min_clip,
+                    transition: args.transition,
+                };
+                finish_json(
+                    format,
+                    project::trim_silence(
+                        &args.project_path,
+                        args.threshold_db,
+                        options,
+                        args.dry_run,
+                        format,
+                    ),
+                )
+            }
+            ProjectCommands::Loudness(args) => {
+                let format = resolve_format(json, args.format);
+                finish_json(
+                    format,
+                    project::loudness(&args.project_path, args.target, format),
+                )
+            }
         }
     }
 }
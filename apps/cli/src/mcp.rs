@@ -7,8 +7,9 @@ use rmcp::{
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
     model::{
         CallToolResult, ContentBlock, Implementation, ListResourceTemplatesResult,
-        PaginatedRequestParams, ReadResourceRequestParams, ReadResourceResult, Resource,
-        ResourceContents, ResourceTemplate, ServerCapabilities, ServerInfo,
+        PaginatedRequestParams, ProgressNotificationParam, ReadResourceRequestParams,
+        ReadResourceResult, Resource, ResourceContents, ResourceTemplate, ServerCapabilities,
+        ServerInfo,
     },
     schemars, tool, tool_handler, tool_router,
 };
@@ -648,6 +649,114 @@ struct SpaceMemberMutationInput {
     confirmed: bool,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct LocalProjectInput {
+    /// Path to a '.cap' project directory on this machine
+    project_path: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct LocalProjectConfigSetInput {
+    project_path: String,
+    /// Full ProjectConfiguration (camelCase keys); omitted fields reset to defaults
+    config: Value,
+    confirmed: bool,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct LocalRecordStartInput {
+    /// Screen id from targets_list (mutually exclusive with window)
+    #[serde(default)]
+    screen: Option<String>,
+    /// Window id from targets_list (mutually exclusive with screen)
+    #[serde(default)]
+    window: Option<String>,
+    /// studio (default) or instant
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(default)]
+    camera: Option<String>,
+    #[serde(default)]
+    mic: Option<String>,
+    #[serde(default)]
+    system_audio: bool,
+    /// Where to write the '.cap' project (defaults to <recordingId>.cap in the working directory)
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    fps: Option<u32>,
+    /// Stop automatically after this many seconds
+    #[serde(default)]
+    duration_seconds: Option<f64>,
+    confirmed: bool,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct LocalRecordStopInput {
+    #[serde(default)]
+    recording_id: Option<String>,
+    /// The '.cap' project path of the recording to stop (alternative to recording_id)
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    timeout_seconds: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct LocalExportInput {
+    project_path: String,
+    /// Output file (defaults to the project's output path)
+    #[serde(default)]
+    output_path: Option<String>,
+    /// mp4 (default), gif, mov, webm, webp, or apng
+    #[serde(default)]
+    format: Option<String>,
+    /// vp9 (default) or av1 (webm only)
+    #[serde(default)]
+    codec: Option<String>,
+    #[serde(default)]
+    fps: Option<u32>,
+    /// WIDTHxHEIGHT, e.g. 1920x1080
+    #[serde(default)]
+    resolution: Option<String>,
+    /// maximum, social, web, or potato (mp4 and webm only)
+    #[serde(default)]
+    quality: Option<String>,
+    #[serde(default)]
+    optimize_filesize: bool,
+    /// Normalise the audio to this integrated loudness in LUFS, e.g. -16 (mp4 and webm only)
+    #[serde(default)]
+    loudness_lufs: Option<f64>,
+    /// True-peak ceiling in dBTP for loudness_lufs (default -1)
+    #[serde(default)]
+    true_peak_dbtp: Option<f64>,
+    /// Full export settings, as accepted by `cap export --settings-json` (exclusive with the fields above)
+    #[serde(default)]
+    settings: Option<Value>,
+    #[serde(default)]
+    force_ffmpeg_decoder: bool,
+    /// Export from this many seconds into the edited timeline
+    #[serde(default)]
+    from_seconds: Option<f64>,
+    /// Export up to this many seconds into the edited timeline
+    #[serde(default)]
+    to_seconds: Option<f64>,
+    /// Export only these timeline segments, by name or 0-based index (exclusive with from/to)
+    #[serde(default)]
+    segments: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+struct LocalScreenshotInput {
+    #[serde(default)]
+    screen: Option<String>,
+    #[serde(default)]
+    window: Option<String>,
+    /// Output image path (format inferred from extension, e.g. .png)
+    path: String,
+    confirmed: bool,
+}
+
 #[derive(Clone)]
 struct CapMcpServer {
     client: AgentClient,
@@ -658,7 +767,7 @@ impl CapMcpServer {
     fn new(client: AgentClient) -> Self {
         Self {
             client,
-            tool_router: Self::tool_router(),
+            tool_router: Self::tool_router() + Self::local_tool_router(),
         }
     }
 
@@ -697,6 +806,21 @@ impl CapMcpServer {
         })
     }
 
+    /// Local tools report plain-string failures from the CLI command implementations they share.
+    fn local_result<T: Serialize>(result: Result<T, String>) -> CallToolResult {
+        Self::result(
+            result
+                .and_then(|value| serde_json::to_value(value).map_err(|e| e.to_string()))
+                .map_err(|message| AgentApiError {
+                    code: "LOCAL_OPERATION_FAILED".to_string(),
+                    message,
+                    retryable: false,
+                    retry_after_ms: None,
+                    request_id: None,
+                }),
+        )
+    }
+
     fn invalid(message: impl Into<String>) -> AgentApiError {
         AgentApiError {
             code: "INVALID_REQUEST".to_string(),
@@ -3466,6 +3590,391 @@ impl CapMcpServer {
     }
 }
 
+/// Tools that work on this machine's capture targets and '.cap' projects. They share their
+/// implementation with the corresponding CLI commands and never contact the Cap server.
+#[tool_router(router = local_tool_router)]
+impl CapMcpServer {
+    #[tool(
+        name = "targets_list",
+        description = "List the screens, windows, cameras and microphones available for recording on this machine",
+        annotations(
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn targets_list(&self) -> CallToolResult {
+        Self::local_result(Ok(crate::targets::all()))
+    }
+
+    #[tool(
+        name = "record_start",
+        description = "Start a detached local recording after explicit user confirmation. Returns recordingId, pid and the .cap path; finish it with record_stop",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn record_start(
+        &self,
+        Parameters(input): Parameters<LocalRecordStartInput>,
+    ) -> CallToolResult {
+        if let Some(result) = Self::require_confirmation(input.confirmed, "record the screen") {
+            return result;
+        }
+        let params = match local_record_params(input) {
+            Ok(params) => params,
+            Err(error) => return Self::result(Err(Self::invalid(error))),
+        };
+        Self::local_result(
+            crate::record::spawn_detached(params)
+                .await
+                .map(|recording| {
+                    json!({
+                        "recordingId": recording.recording_id,
+                        "pid": recording.pid,
+                        "path": recording.path,
+                    })
+                }),
+        )
+    }
+
+    #[tool(
+        name = "record_stop",
+        description = "Stop and finalize a detached local recording by recording_id or path. Waits for the .cap to be written",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn record_stop(
+        &self,
+        Parameters(input): Parameters<LocalRecordStopInput>,
+    ) -> CallToolResult {
+        let path = input.path.map(std::path::PathBuf::from);
+        let result = crate::record::stop_session(
+            input.recording_id.as_deref(),
+            path.as_deref(),
+            input.timeout_seconds.unwrap_or(30.0),
+        )
+        .await
+        .map(|stopped| {
+            crate::record::cleanup_session(&stopped.recording_id, &stopped.path);
+            json!({
+                "path": stopped.path,
+                "recordingMetaExists": stopped.recording_meta_exists,
+            })
+        });
+        Self::local_result(result)
+    }
+
+    #[tool(
+        name = "record_status",
+        description = "List active and recent detached local recording sessions",
+        annotations(
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn record_status(&self) -> CallToolResult {
+        Self::local_result(
+            crate::record::status_rows().map(|sessions| json!({ "sessions": sessions })),
+        )
+    }
+
+    #[tool(
+        name = "project_inspect",
+        description = "Read a local .cap project's metadata and editor configuration",
+        annotations(
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn project_inspect(
+        &self,
+        Parameters(input): Parameters<LocalProjectInput>,
+    ) -> CallToolResult {
+        Self::local_result(crate::project::inspection(input.project_path.into()))
+    }
+
+    #[tool(
+        name = "project_validate",
+        description = "Check that a local .cap project's metadata and media files exist. Branch on `valid`",
+        annotations(
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn project_validate(
+        &self,
+        Parameters(input): Parameters<LocalProjectInput>,
+    ) -> CallToolResult {
+        Self::local_result(Ok(crate::project::validation_report(std::path::Path::new(
+            &input.project_path,
+        ))))
+    }
+
+    #[tool(
+        name = "project_config_get",
+        description = "Read a local .cap project's editor configuration (project-config.json), or the effective default",
+        annotations(
+            read_only_hint = true,
+            destructive_hint = false,
+            idempotent_hint = true,
+            open_world_hint = false
+        )
+    )]
+    async fn project_config_get(
+        &self,
+        Parameters(input): Parameters<LocalProjectInput>,
+    ) -> CallToolResult {
+        Self::local_result(crate::project::load_config(std::path::Path::new(
+            &input.project_path,
+        )))
+    }
+
+    #[tool(
+        name = "project_config_set",
+        description = "Replace a local .cap project's editor configuration after explicit user confirmation. Read it with project_config_get first; omitted fields reset to defaults",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = true,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn project_config_set(
+        &self,
+        Parameters(input): Parameters<LocalProjectConfigSetInput>,
+    ) -> CallToolResult {
+        if let Some(result) =
+            Self::require_confirmation(input.confirmed, "replace this project's configuration")
+        {
+            return result;
+        }
+        Self::local_result(
+            crate::project::write_config(
+                std::path::Path::new(&input.project_path),
+                &input.config.to_string(),
+            )
+            .map(|()| json!({ "ok": true })),
+        )
+    }
+
+    #[tool(
+        name = "project_export",
+        description = "Render a local .cap project, or a time range or some segments of its timeline, to an mp4, gif, mov, webm, webp or apng file. Sends progress notifications when the request carries a progress token; cancelling the request cancels the export",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn project_export(
+        &self,
+        Parameters(input): Parameters<LocalExportInput>,
+        context: rmcp::service::RequestContext<RoleServer>,
+    ) -> CallToolResult {
+        let settings = match local_export_settings(&input) {
+            Ok(settings) => settings,
+            Err(error) => return Self::result(Err(Self::invalid(error))),
+        };
+
+        // Exporters report progress from a blocking encoder thread, so hop through a channel to
+        // send notifications from the runtime. Only whole-percent changes are forwarded.
+        let (progress_tx, mut progress_rx) = tokio::sync::mpsc::unbounded_channel::<(u32, u32)>();
+        let progress_token = context.meta.get_progress_token();
+        let peer = context.peer.clone();
+        let forward = tokio::spawn(async move {
+            while let Some((rendered, total)) = progress_rx.recv().await {
+                if let Some(token) = &progress_token {
+                    let _ = peer
+                        .notify_progress(
+                            ProgressNotificationParam::new(token.clone(), f64::from(rendered))
+                                .with_total(f64::from(total)),
+                        )
+                        .await;
+                }
+            }
+        });
+
+        let cancelled = context.ct.clone();
+        let mut last_percent = None;
+        let on_progress = move |rendered: u32, total: u32| {
+            let percent = (u64::from(rendered) * 100)
+                .checked_div(u64::from(total))
+                .unwrap_or(100);
+            if last_percent != Some(percent) {
+                last_percent = Some(percent);
+                let _ = progress_tx.send((rendered, total));
+            }
+            !cancelled.is_cancelled()
+        };
+
+        let result = crate::export::export_project(
+            input.project_path.into(),
+            input.output_path.map(Into::into),
+            settings,
+            input.force_ffmpeg_decoder,
+            on_progress,
+        )
+        .await;
+        let _ = forward.await;
+
+        Self::local_result(result.map(|path| json!({ "path": path })))
+    }
+
+    #[tool(
+        name = "screenshot",
+        description = "Capture a still image of a local screen or window after explicit user confirmation",
+        annotations(
+            read_only_hint = false,
+            destructive_hint = false,
+            idempotent_hint = false,
+            open_world_hint = false
+        )
+    )]
+    async fn screenshot(
+        &self,
+        Parameters(input): Parameters<LocalScreenshotInput>,
+    ) -> CallToolResult {
+        if let Some(result) = Self::require_confirmation(input.confirmed, "capture the screen") {
+            return result;
+        }
+        let target = match local_target_ids(input.screen.as_deref(), input.window.as_deref())
+            .and_then(|(screen, window)| {
+                crate::screenshot::resolve_target(screen.as_ref(), window.as_ref())
+            }) {
+            Ok(target) => target,
+            Err(error) => return Self::result(Err(Self::invalid(error))),
+        };
+        Self::local_result(crate::screenshot::capture(target, input.path.into()).await)
+    }
+}
+
+type LocalTargetIds = (
+    Option<scap_targets::DisplayId>,
+    Option<scap_targets::WindowId>,
+);
+
+fn local_target_ids(screen: Option<&str>, window: Option<&str>) -> Result<LocalTargetIds, String> {
+    if screen.is_some() && window.is_some() {
+        return Err("Pass either screen or window, not both".to_string());
+    }
+    let screen = screen
+        .map(|id| {
+            id.parse()
+                .map_err(|e| format!("Invalid screen id '{id}': {e}"))
+        })
+        .transpose()?;
+    let window = window
+        .map(|id| {
+            id.parse()
+                .map_err(|e| format!("Invalid window id '{id}': {e}"))
+        })
+        .transpose()?;
+    Ok((screen, window))
+}
+
+fn local_record_params(
+    input: LocalRecordStartInput,
+) -> Result<crate::record::RecordParams, String> {
+    let (screen, window) = local_target_ids(input.screen.as_deref(), input.window.as_deref())?;
+    let mode = match input.mode.as_deref() {
+        Some(mode) => clap::ValueEnum::from_str(mode, true)
+            .map_err(|_| "mode must be studio or instant".to_string())?,
+        None => crate::record::RecordMode::Studio,
+    };
+    let params = crate::record::RecordParams {
+        target: crate::record::RecordTargets {
+            screen,
+            window,
+            area: None,
+        },
+        mode,
+        camera: input.camera,
+        mic: input.mic.into_iter().collect(),
+        system_audio: input.system_audio,
+        path: input.path.map(Into::into),
+        fps: input.fps,
+        duration: input.duration_seconds,
+        keyboard: None,
+        custom_cursor: None,
+        quality: None,
+        stream_url: None,
+    };
+    params.validate()?;
+    Ok(params)
+}
+
+fn local_export_settings(
+    input: &LocalExportInput,
+) -> Result<crate::export::CliExportSettings, String> {
+    let format = input
+        .format
+        .as_deref()
+        .map(|format| {
+            clap::ValueEnum::from_str(format, true)
+                .map_err(|_| "format must be mp4, gif, mov, webm, webp, or apng".to_string())
+        })
+        .transpose()?;
+    let codec = input
+        .codec
+        .as_deref()
+        .map(|codec| {
+            clap::ValueEnum::from_str(codec, true)
+                .map_err(|_| "codec must be vp9 or av1".to_string())
+        })
+        .transpose()?;
+    let quality = input
+        .quality
+        .as_deref()
+        .map(|quality| {
+            clap::ValueEnum::from_str(quality, true)
+                .map_err(|_| "quality must be maximum, social, web, or potato".to_string())
+        })
+        .transpose()?;
+    let flags = crate::export::ExportFlags {
+        format,
+        codec,
+        fps: input.fps,
+        resolution: input.resolution.clone(),
+        quality,
+        optimize_filesize: input.optimize_filesize,
+        loudness: input.loudness_lufs,
+        true_peak: input.true_peak_dbtp,
+        force_ffmpeg_decoder: input.force_ffmpeg_decoder,
+        range: local_export_range(input)?,
+    };
+    let settings_json = input.settings.as_ref().map(Value::to_string);
+    crate::export::resolve_export_settings(&flags, settings_json.as_deref())
+}
+
+fn local_export_range(
+    input: &LocalExportInput,
+) -> Result<Option<cap_project::ExportRange>, String> {
+    if !input.segments.is_empty() && (input.from_seconds.is_some() || input.to_seconds.is_some()) {
+        return Err("segments cannot be combined with from_seconds/to_seconds".to_string());
+    }
+    Ok(crate::export::range_from_flags(
+        input.from_seconds,
+        input.to_seconds,
+        &input.segments,
+    ))
+}
+
 #[tool_handler(router = self.tool_router)]
 impl ServerHandler for CapMcpServer {
     fn get_info(&self) -> ServerInfo {
@@ -3477,7 +3986,7 @@ impl ServerHandler for CapMcpServer {
         )
         .with_server_info(Implementation::new("cap", env!("CARGO_PKG_VERSION")))
         .with_instructions(
-            "Use Cap resources for large transcript and activity data. Passwords, S3 credentials, image files, and newly issued developer credentials are never accepted or returned by MCP; use the corresponding `cap caps`, `cap organizations`, `cap account`, or `cap developers` command in a secure terminal.",
+            "Use Cap resources for large transcript and activity data. The targets_list, record_*, project_*, and screenshot tools work on this machine's screens and .cap projects without contacting Cap. Passwords, S3 credentials, image files, and newly issued developer credentials are never accepted or returned by MCP; use the corresponding `cap caps`, `cap organizations`, `cap account`, or `cap developers` command in a secure terminal.",
         )
     }
 
@@ -1,9 +1,12 @@
 use std::path::{Path, PathBuf};
 
+use cap_editor::AudioRenderer;
+use cap_export::loudness::LoudnessTarget;
 use cap_project::{
-    InstantRecordingMeta, RecordingMeta, RecordingMetaInner, StudioRecordingMeta,
-    StudioRecordingStatus,
+    InstantRecordingMeta, RecordingMeta, RecordingMetaInner, SilenceCutOptions,
+    StudioRecordingMeta, StudioRecordingStatus, TimelineConfiguration, TimelineSegment,
 };
+use cap_rendering::ProjectRecordingsMeta;
 use serde::Serialize;
 
 use crate::{OutputFormat, write_json};
@@ -21,15 +24,19 @@ pub struct ProjectInspection {
 }
 
 pub fn config_get(project_path: PathBuf) -> Result<(), String> {
-    let config = match cap_project::ProjectConfiguration::load(&project_path) {
-        Ok(config) => config,
+    crate::write_json(&load_config(&project_path)?)
+}
+
+/// The project's editor configuration, or the effective default when none has been written yet.
+pub fn load_config(project_path: &Path) -> Result<cap_project::ProjectConfiguration, String> {
+    match cap_project::ProjectConfiguration::load(project_path) {
+        Ok(config) => Ok(config),
         // Instant and un-edited studio recordings have no project-config.json; return the
         // effective default the editor/exporter would use rather than erroring.
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::from_str("{}")
-            .map_err(|e| format!("Failed to build default project config: {e}"))?,
-        Err(e) => return Err(format!("Failed to load project config: {e}")),
-    };
-    crate::write_json(&config)
+            .map_err(|e| format!("Failed to build default project config: {e}")),
+        Err(e) => Err(format!("Failed to load project config: {e}")),
+    }
 }
 
 pub fn config_set(
@@ -37,43 +44,51 @@ pub fn config_set(
     settings_json: &str,
     format: OutputFormat,
 ) -> Result<(), String> {
-    let config: cap_project::ProjectConfiguration = serde_json::from_str(settings_json)
-        .map_err(|e| format!("Invalid project config JSON: {e}"))?;
-    // write() validates internally before its atomic temp-file-then-rename.
-    config
-        .write(&project_path)
-        .map_err(|e| format!("Failed to write project config: {e}"))?;
+    write_config(&project_path, settings_json)?;
     if let OutputFormat::Json = format {
         crate::write_json(&serde_json::json!({ "ok": true }))?;
     }
     Ok(())
 }
 
+pub fn write_config(project_path: &Path, settings_json: &str) -> Result<(), String> {
+    let config: cap_project::ProjectConfiguration = serde_json::from_str(settings_json)
+        .map_err(|e| format!("Invalid project config JSON: {e}"))?;
+    // write() validates internally before its atomic temp-file-then-rename.
+    config
+        .write(project_path)
+        .map_err(|e| format!("Failed to write project config: {e}"))
+}
+
 pub fn inspect(project_path: PathBuf, format: OutputFormat) -> Result<(), String> {
-    let meta = RecordingMeta::load_for_project(&project_path)
-        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
-    let output_path = meta.output_path();
-    let config = meta.project_config();
+    let inspection = inspection(project_path)?;
 
     match format {
         OutputFormat::Text => {
-            println!("project: {}", project_path.display());
-            println!("name: {}", meta.pretty_name);
-            println!("type: {}", recording_type(&meta));
-            println!("output: {}", output_path.display());
+            println!("project: {}", inspection.project_path.display());
+            println!("name: {}", inspection.name);
+            println!("type: {}", inspection.recording_type);
+            println!("output: {}", inspection.output_path.display());
             Ok(())
         }
-        OutputFormat::Json => write_json(&ProjectInspection {
-            project_path,
-            output_path,
-            name: meta.pretty_name.clone(),
-            recording_type: recording_type(&meta),
-            meta,
-            config,
-        }),
+        OutputFormat::Json => write_json(&inspection),
     }
 }
 
+pub fn inspection(project_path: PathBuf) -> Result<ProjectInspection, String> {
+    let meta = RecordingMeta::load_for_project(&project_path)
+        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+
+    Ok(ProjectInspection {
+        output_path: meta.output_path(),
+        config: meta.project_config(),
+        name: meta.pretty_name.clone(),
+        recording_type: recording_type(&meta),
+        project_path,
+        meta,
+    })
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct FileCheck {
@@ -85,7 +100,7 @@ struct FileCheck {
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ValidationReport {
+pub struct ValidationReport {
     project_path: PathBuf,
     valid: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -154,6 +169,9 @@ fn studio_checks(meta: &RecordingMeta, studio: &StudioRecordingMeta) -> Vec<File
                 if let Some(mic) = &segment.mic {
                     checks.push(required_check("mic", meta.path(&mic.path)));
                 }
+                for mic in segment.additional_mics.iter().flatten() {
+                    checks.push(required_check("mic", meta.path(&mic.path)));
+                }
                 if let Some(system_audio) = &segment.system_audio {
                     checks.push(required_check("systemAudio", meta.path(&system_audio.path)));
                 }
@@ -258,6 +276,26 @@ fn build_report(project_path: &Path, meta: &RecordingMeta) -> ValidationReport {
     }
 }
 
+/// Like [`validate`], but returns the report instead of printing it. A project whose metadata
+/// cannot be loaded yields an invalid report rather than an error.
+pub fn validation_report(project_path: &Path) -> ValidationReport {
+    match RecordingMeta::load_for_project(project_path) {
+        Ok(meta) => build_report(project_path, &meta),
+        Err(e) => ValidationReport {
+            checks: vec![required_check(
+                "recordingMeta",
+                project_path.join("recording-meta.json"),
+            )],
+            missing: vec![project_path.join("recording-meta.json")],
+            project_path: project_path.to_path_buf(),
+            valid: false,
+            recording_type: None,
+            error: Some(format!("Failed to load recording meta: {e}")),
+            problems: Vec::new(),
+        },
+    }
+}
+
 pub(crate) fn validate_project(project_path: &Path) -> Result<(), String> {
     let meta = RecordingMeta::load_for_project(project_path)
         .map_err(|e| format!("Failed to load recording meta: {e}"))?;
@@ -273,22 +311,7 @@ pub(crate) fn validate_project(project_path: &Path) -> Result<(), String> {
 }
 
 pub fn validate(project_path: PathBuf, format: OutputFormat) -> Result<(), String> {
-    let report = match RecordingMeta::load_for_project(&project_path) {
-        Ok(meta) => build_report(&project_path, &meta),
-        Err(e) => ValidationReport {
-            checks: vec![required_check(
-                "recordingMeta",
-                project_path.join("recording-meta.json"),
-            )],
-            missing: vec![project_path.join("recording-meta.json")],
-            project_path: project_path.clone(),
-            valid: false,
-            recording_type: None,
-            error: Some(format!("Failed to load recording meta: {e}")),
-            problems: Vec::new(),
-        },
-    };
-
+    let report = validation_report(&project_path);
     let valid = report.valid;
 
     match format {
@@ -324,3 +347,184 @@ pub fn validate(project_path: PathBuf, format: OutputFormat) -> Result<(), Strin
         Err("project validation failed".to_string())
     }
 }
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrimSilenceReport<'a> {
+    project_path: &'a Path,
+    dry_run: bool,
+    duration_before: f64,
+    duration_after: f64,
+    removed: f64,
+    segments: &'a [TimelineSegment],
+}
+
+/// Cuts the silences out of a project's timeline, writing it back unless `dry_run`.
+pub fn trim_silence(
+    project_path: &Path,
+    threshold_db: f32,
+    options: SilenceCutOptions,
+    dry_run: bool,
+    format: OutputFormat,
+) -> Result<(), String> {
+    let lengths = [
+        ("--min-silence", options.min_silence),
+        ("--padding", options.padding),
+        ("--min-clip", options.min_clip),
+        ("--transition", options.transition),
+    ];
+    if let Some((flag, value)) = lengths
+        .iter()
+        .find(|(_, value)| !value.is_finite() || *value < 0.0)
+    {
+        return Err(format!("{flag} must be zero or more seconds, got {value}"));
+    }
+
+    let meta = RecordingMeta::load_for_project(project_path)
+        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+    let studio_meta = meta
+        .studio_meta()
+        .ok_or("Silence can only be trimmed from studio recordings")?;
+    let mut config = load_config(project_path)?;
+
+    let audible = cap_editor::recording_audible_ranges(&meta, studio_meta, &config, threshold_db)?;
+    if audible.iter().all(Option::is_none) {
+        return Err("Recording has no microphone or system audio to find silences in".to_string());
+    }
+
+    let timeline = match config.timeline.take() {
+        Some(timeline) => timeline,
+        None => {
+            let recordings = ProjectRecordingsMeta::new(&meta.project_path, studio_meta)?;
+            let durations = recordings
+                .segments
+                .iter()
+                .map(|segment| segment.duration())
+                .collect::<Vec<_>>();
+            TimelineConfiguration::from_recording_durations(&durations)
+                .ok_or("Recording has no media to trim")?
+        }
+    };
+
+    let cut = timeline
+        .without_silences(&audible, &options)
+        .ok_or_else(|| format!("Nothing in the recording is louder than {threshold_db} dB"))?;
+    let trimmed = &cut.timeline;
+
+    let duration_before = timeline.duration();
+    let duration_after = trimmed.duration();
+    let removed = (duration_before - duration_after).max(0.0);
+    let report = TrimSilenceReport {
+        project_path,
+        dry_run,
+        duration_before,
+        duration_after,
+        removed,
+        segments: &trimmed.segments,
+    };
+
+    if !dry_run {
+        config.annotations = cut.map_annotations(&config.annotations);
+        config.timeline = Some(trimmed.clone());
+        config
+            .write(project_path)
+            .map_err(|e| format!("Failed to write project config: {e}"))?;
+    }
+
+    match format {
+        OutputFormat::Json => write_json(&report),
+        OutputFormat::Text => {
+            let verb = if dry_run { "Would remove" } else { "Removed" };
+            println!(
+                "{verb} {removed:.2}s of silence from {} ({duration_before:.2}s -> {duration_after:.2}s, {} -> {} clips)",
+                project_path.display(),
+                timeline.segments.len(),
+                trimmed.segments.len(),
+            );
+            Ok(())
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LoudnessReport<'a> {
+    project_path: &'a Path,
+    duration: f64,
+    /// `None` when nothing is louder than the -70 LUFS gate.
+    integrated_lufs: Option<f64>,
+    loudness_range_lu: Option<f64>,
+    /// `None` for a silent mix.
+    true_peak_dbtp: Option<f64>,
+    target_lufs: f64,
+    gain_db: f64,
+}
+
+/// Measures a project's audio mix as an export would render it.
+pub fn loudness(project_path: &Path, target_lufs: f64, format: OutputFormat) -> Result<(), String> {
+    if !target_lufs.is_finite() || target_lufs >= 0.0 {
+        return Err(format!(
+            "--target must be a negative LUFS value, got {target_lufs}"
+        ));
+    }
+
+    let meta = RecordingMeta::load_for_project(project_path)
+        .map_err(|e| format!("Failed to load recording meta: {e}"))?;
+    let studio_meta = meta
+        .studio_meta()
+        .ok_or("Loudness can only be measured for studio recordings")?;
+    let config = load_config(project_path)?;
+
+    let segments = cap_editor::recording_audio_segments(&meta, studio_meta)?;
+    let music = cap_editor::load_music_tracks_uncached(&config, project_path);
+    let has_recording_audio =
+        !config.audio.mute && segments.iter().any(|segment| !segment.tracks.is_empty());
+    if !has_recording_audio && music.is_empty() {
+        return Err("Project has no audio to measure".to_string());
+    }
+
+    let recordings = ProjectRecordingsMeta::new(&meta.project_path, studio_meta)?;
+    let duration = cap_rendering::get_duration(&recordings, &meta, studio_meta, &config);
+    let meter = AudioRenderer::new(segments)
+        .with_music(music)
+        .measure_loudness(&config, duration);
+
+    let target = LoudnessTarget {
+        integrated_lufs: target_lufs,
+        ..Default::default()
+    };
+    let report = LoudnessReport {
+        project_path,
+        duration,
+        integrated_lufs: meter.integrated(),
+        loudness_range_lu: meter.range(),
+        true_peak_dbtp: Some(meter.true_peak()).filter(|peak| peak.is_finite()),
+        target_lufs,
+        gain_db: target.gain_db(meter.integrated()),
+    };
+
+    match format {
+        OutputFormat::Json => write_json(&report),
+        OutputFormat::Text => {
+            let Some(integrated) = report.integrated_lufs else {
+                println!("{} is silent ({duration:.2}s)", project_path.display());
+                return Ok(());
+            };
+            println!(
+                "{}: {integrated:.1} LUFS integrated, {} range, {} true peak ({duration:.2}s)",
+                project_path.display(),
+                report
+                    .loudness_range_lu
+                    .map_or("no".to_string(), |range| format!("{range:.1} LU")),
+                report
+                    .true_peak_dbtp
+                    .map_or("no".to_string(), |peak| format!("{peak:.1} dBTP")),
+            );
+            println!(
+                "Exporting with --loudness {target_lufs} applies {:+.1} dB",
+                report.gain_db
+            );
+            Ok(())
+        }
+    }
+}
@@ -2,7 +2,7 @@ use cap_project::{
     InstantRecordingMeta, Platform, ProjectConfiguration, RecordingMeta, RecordingMetaInner,
 };
 use cap_recording::{
-    CameraFeed, MicrophoneFeed,
+    CameraFeed, MicrophoneFeed, StreamTarget, StudioQuality,
     feeds::{camera, microphone},
     instant_recording,
     screen_capture::ScreenCaptureTarget,
@@ -11,7 +11,10 @@ use cap_recording::{
 use clap::{Args, ValueEnum};
 use futures::FutureExt;
 use kameo::Actor as _;
-use scap_targets::{DisplayId, WindowId};
+use scap_targets::{
+    Display, DisplayId, WindowId,
+    bounds::{LogicalBounds, LogicalPosition, LogicalSize},
+};
 use serde::Serialize;
 use std::{
     env::current_dir,
@@ -26,43 +29,67 @@ use tracing::debug;
 use uuid::Uuid;
 
 use crate::{
-    OutputFormat, resolve_format,
+    OutputFormat,
+    control::{self, ControlRequest},
+    resolve_format,
     session::{self, Session, SessionStatus},
     write_json, write_json_line,
 };
 
+/// Hands a detached worker its `--stream-url`. The URL usually carries the stream key, so it stays out
+/// of the worker's arguments, which any user on the machine can list.
+const STREAM_URL_ENV: &str = "CAP_RECORD_STREAM_URL";
+
 /// Recording inputs shared between the foreground recorder, the `--detach` parent, and the
 /// re-exec'd background worker. Kept in one struct so the worker can be invoked with exactly the
-/// flags the parent received (see [`RecordParams::to_cli_args`]).
+/// flags the parent received (see [`RecordParams::to_cli_args`]), apart from `--stream-url`, which
+/// travels in [`STREAM_URL_ENV`].
 #[derive(Args, Clone)]
 pub struct RecordParams {
     #[command(flatten)]
-    target: RecordTargets,
+    pub(crate) target: RecordTargets,
     /// Recording mode to use
     #[arg(long, value_enum, default_value_t = RecordMode::Studio)]
-    mode: RecordMode,
+    pub(crate) mode: RecordMode,
     /// Capture from the camera with this device id (see `cap targets cameras`)
     #[arg(long)]
-    camera: Option<String>,
-    /// Capture from the microphone with this device name (see `cap targets mics`)
+    pub(crate) camera: Option<String>,
+    /// Capture from the microphone with this device name (see `cap targets mics`). Repeat to
+    /// record several microphones, each into its own track (studio mode only)
     #[arg(long)]
-    mic: Option<String>,
+    pub(crate) mic: Vec<String>,
     /// Whether to capture system audio
     #[arg(long)]
-    system_audio: bool,
+    pub(crate) system_audio: bool,
     /// Path to save the '.cap' project to (defaults to <recordingId>.cap in the working directory)
     #[arg(long)]
-    path: Option<PathBuf>,
+    pub(crate) path: Option<PathBuf>,
     /// Maximum fps to record at (clamped to 1-120; camera recordings follow the desktop camera cap)
     #[arg(long)]
-    fps: Option<u32>,
-    /// Stop automatically after N seconds
+    pub(crate) fps: Option<u32>,
+    /// Stop automatically after N seconds of recording (time spent paused doesn't count)
     #[arg(long)]
-    duration: Option<f64>,
+    pub(crate) duration: Option<f64>,
+    /// Record keystrokes for the keyboard overlay (studio only; on by default, `--keyboard false` to
+    /// turn it off)
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub(crate) keyboard: Option<bool>,
+    /// Record the cursor separately so the editor can restyle it (studio only; on by default,
+    /// `--custom-cursor false` to bake it into the video)
+    #[arg(long, num_args = 0..=1, default_missing_value = "true")]
+    pub(crate) custom_cursor: Option<bool>,
+    /// Encoder quality for studio recordings (defaults to balanced, or compatibility on machines with
+    /// little memory)
+    #[arg(long, value_enum)]
+    pub(crate) quality: Option<RecordQuality>,
+    /// Also broadcast the recording live to this rtmp://, rtmps:// or srt:// URL, reconnecting if
+    /// the connection drops
+    #[arg(long, value_parser = parse_stream_url)]
+    pub(crate) stream_url: Option<StreamTarget>,
 }
 
 impl RecordParams {
-    fn validate(&self) -> Result<(), String> {
+    pub(crate) fn validate(&self) -> Result<(), String> {
         if self.duration.is_some_and(|duration| {
             // `> u64::MAX` would panic in `Duration::from_secs_f64` (used by `wait_for_stop`).
             !duration.is_finite() || duration <= 0.0 || duration > u64::MAX as f64
@@ -72,6 +99,17 @@ impl RecordParams {
         if self.fps == Some(0) {
             return Err("--fps must be greater than 0".to_string());
         }
+        if self.mode == RecordMode::Instant
+            && (self.keyboard.is_some() || self.custom_cursor.is_some() || self.quality.is_some())
+        {
+            return Err(
+                "--keyboard, --custom-cursor and --quality only apply to studio recordings"
+                    .to_string(),
+            );
+        }
+        if self.mode == RecordMode::Instant && self.mic.len() > 1 {
+            return Err("Only studio recordings can use more than one --mic".to_string());
+        }
         Ok(())
     }
 
@@ -85,13 +123,17 @@ impl RecordParams {
             args.push("--window".to_string());
             args.push(id.to_string());
         }
+        if let Some(area) = &self.target.area {
+            args.push("--area".to_string());
+            args.push(area.to_string());
+        }
         args.push("--mode".to_string());
         args.push(self.mode.to_string());
         if let Some(camera) = &self.camera {
             args.push("--camera".to_string());
             args.push(camera.clone());
         }
-        if let Some(mic) = &self.mic {
+        for mic in &self.mic {
             args.push("--mic".to_string());
             args.push(mic.clone());
         }
@@ -110,8 +152,31 @@ impl RecordParams {
             args.push("--duration".to_string());
             args.push(duration.to_string());
         }
+        // The value is optional for these flags, so it's attached to keep it from being read as the
+        // next argument or vice versa.
+        if let Some(keyboard) = self.keyboard {
+            args.push(format!("--keyboard={keyboard}"));
+        }
+        if let Some(custom_cursor) = self.custom_cursor {
+            args.push(format!("--custom-cursor={custom_cursor}"));
+        }
+        if let Some(quality) = self.quality {
+            args.push("--quality".to_string());
+            args.push(quality.to_string());
+        }
         args
     }
+
+    /// Take `--stream-url` from [`STREAM_URL_ENV`] when the parent passed it that way.
+    fn with_stream_url_from_env(mut self) -> Result<Self, String> {
+        if let Some(url) = std::env::var_os(STREAM_URL_ENV) {
+            let url = url
+                .into_string()
+                .map_err(|_| format!("{STREAM_URL_ENV} is not valid UTF-8"))?;
+            self.stream_url = Some(parse_stream_url(&url)?);
+        }
+        Ok(self)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
@@ -129,6 +194,33 @@ impl std::fmt::Display for RecordMode {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum RecordQuality {
+    Compatibility,
+    Balanced,
+    Ultra,
+}
+
+impl std::fmt::Display for RecordQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compatibility => f.write_str("compatibility"),
+            Self::Balanced => f.write_str("balanced"),
+            Self::Ultra => f.write_str("ultra"),
+        }
+    }
+}
+
+impl From<RecordQuality> for StudioQuality {
+    fn from(value: RecordQuality) -> Self {
+        match value {
+            RecordQuality::Compatibility => Self::Compatibility,
+            RecordQuality::Balanced => Self::Balanced,
+            RecordQuality::Ultra => Self::Ultra,
+        }
+    }
+}
+
 #[derive(Args)]
 pub struct RecordStart {
     #[command(flatten)]
@@ -167,6 +259,54 @@ pub struct RecordStopArgs {
     format: OutputFormat,
 }
 
+#[derive(Args)]
+pub struct RecordStatusArgs {
+    /// Only show the recording with this recordingId
+    #[arg(long)]
+    id: Option<String>,
+    /// Only show the recording of this '.cap' project path (alternative to --id)
+    #[arg(long)]
+    path: Option<PathBuf>,
+    /// Stream the recording's health events and stats until it stops (NDJSON with --format json)
+    #[arg(long)]
+    follow: bool,
+    /// Output format for status events
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Args)]
+pub struct RecordMarkerArgs {
+    /// recordingId returned by `cap record start --detach`
+    #[arg(long)]
+    id: Option<String>,
+    /// The '.cap' project path of the recording to mark (alternative to --id)
+    #[arg(long)]
+    path: Option<PathBuf>,
+    /// Chapter title for the marker (defaults to "Chapter N")
+    #[arg(long)]
+    name: Option<String>,
+    /// Output format for status events
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Args)]
+pub struct RecordPauseArgs {
+    /// recordingId returned by `cap record start --detach`
+    #[arg(long)]
+    id: Option<String>,
+    /// The '.cap' project path of the recording (alternative to --id)
+    #[arg(long)]
+    path: Option<PathBuf>,
+    /// Seconds to wait for the recording to take the request before giving up
+    #[arg(long, default_value_t = 10.0)]
+    timeout: f64,
+    /// Output format for status events
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
 impl RecordStart {
     pub async fn run(self, json: bool) -> Result<(), String> {
         let format = resolve_format(json, self.format);
@@ -230,7 +370,7 @@ async fn foreground_inner(params: RecordParams, format: OutputFormat) -> Result<
         println!("Press Enter to stop (or send SIGINT/SIGTERM)");
     }
 
-    let completed = finalize(actor, params.duration, interactive, None).await?;
+    let completed = finalize(&actor, params.duration, interactive, None, None, None).await?;
     crate::automation::run_recording_finished(
         completed.project_path(),
         automation_mode(params.mode),
@@ -259,6 +399,48 @@ async fn run_detached(params: RecordParams, format: OutputFormat) -> Result<(),
 }
 
 async fn detached_inner(params: RecordParams, format: OutputFormat) -> Result<(), String> {
+    let recording = spawn_detached(params).await?;
+
+    if let Err(error) = emit_record_event(
+        format,
+        &RecordEvent::Started {
+            recording_id: &recording.recording_id,
+            pid: recording.pid,
+            path: &recording.path.display().to_string(),
+        },
+    ) {
+        // The worker is already recording in its own process group. If we cannot hand the caller the
+        // recordingId it could never stop it, so tear the recording down rather than leak an orphan.
+        recording.abandon();
+        return Err(format!(
+            "{error}; background recording {} was stopped because its start event could not be delivered",
+            recording.recording_id
+        ));
+    }
+
+    Ok(())
+}
+
+/// A background recording worker that has started (or already finished) recording.
+pub(crate) struct DetachedRecording {
+    pub(crate) recording_id: String,
+    pub(crate) pid: u32,
+    pub(crate) path: PathBuf,
+}
+
+impl DetachedRecording {
+    /// Stop a worker whose caller can no longer be told its recordingId.
+    pub(crate) fn abandon(&self) {
+        let _ = session::request_stop(&self.recording_id);
+        if session::process_alive(self.pid) {
+            session::terminate(self.pid);
+        }
+    }
+}
+
+/// Re-exec the binary as a `__session-run` worker for `params` and wait until its session file
+/// reports that recording has begun. `params` must already have passed [`RecordParams::validate`].
+pub(crate) async fn spawn_detached(params: RecordParams) -> Result<DetachedRecording, String> {
     let recording_id = new_recording_id();
     // Validate the target up front so a bad --screen/--window fails fast with a clear message rather
     // than surfacing later from the background worker's log.
@@ -296,10 +478,15 @@ async fn detached_inner(params: RecordParams, format: OutputFormat) -> Result<()
         .arg("--session-id")
         .arg(&recording_id)
         .args(worker_params.to_cli_args())
+        .env_remove(STREAM_URL_ENV)
         .stdin(Stdio::null())
         .stdout(Stdio::from(log))
         .stderr(Stdio::from(log_err));
 
+    if let Some(stream_url) = &worker_params.stream_url {
+        command.env(STREAM_URL_ENV, stream_url.url());
+    }
+
     // Detach from the parent so closing the parent's shell/pipeline does not signal the recording
     // worker. On unix this means a new process group; on Windows, no console + a new process group so
     // a Ctrl-C / console-close in the launching terminal does not reach the worker.
@@ -323,26 +510,11 @@ async fn detached_inner(params: RecordParams, format: OutputFormat) -> Result<()
 
     let session = wait_for_session_ready(&recording_id, child).await?;
 
-    if let Err(error) = emit_record_event(
-        format,
-        &RecordEvent::Started {
-            recording_id: &recording_id,
-            pid,
-            path: &session.path.display().to_string(),
-        },
-    ) {
-        // The worker is already recording in its own process group. If we cannot hand the caller the
-        // recordingId it could never stop it, so tear the recording down rather than leak an orphan.
-        let _ = session::request_stop(&recording_id);
-        if session::process_alive(pid) {
-            session::terminate(pid);
-        }
-        return Err(format!(
-            "{error}; background recording {recording_id} was stopped because its start event could not be delivered"
-        ));
-    }
-
-    Ok(())
+    Ok(DetachedRecording {
+        recording_id,
+        pid,
+        path: session.path,
+    })
 }
 
 async fn wait_for_session_ready(
@@ -413,12 +585,16 @@ impl SessionRunArgs {
                     pid: std::process::id(),
                     path: prior.as_ref().map(|s| s.path.clone()).unwrap_or_default(),
                     status: SessionStatus::Error,
+                    paused: false,
+                    pause_error: None,
                     started_at: prior
                         .as_ref()
                         .and_then(|s| s.started_at)
                         .or_else(session::now_unix),
                     recording_meta_exists: None,
                     error: Some(error.clone()),
+                    stats: prior.and_then(|s| s.stats),
+                    control: None,
                     recording_id,
                 });
                 Err(error)
@@ -428,28 +604,98 @@ impl SessionRunArgs {
 }
 
 async fn session_worker(params: RecordParams, recording_id: &str) -> Result<(), String> {
+    let params = params.with_stream_url_from_env()?;
     let target = resolve_target(&params)?;
     let path = params
         .path
         .clone()
         .ok_or_else(|| "internal: detached worker started without --path".to_string())?;
-    let actor = start_recording(&params, target, path.clone()).await?;
+    let mut actor = start_recording(&params, target, path.clone()).await?;
+    let health_rx = actor.take_health_rx();
+
+    // Listen before the session is written, so anyone who sees it recording can reach the endpoint.
+    let (control_endpoint, listener) = match control::endpoint(recording_id).and_then(|endpoint| {
+        control::bind(&endpoint)
+            .map(|listener| (endpoint.clone(), listener))
+            .map_err(|e| format!("Could not listen on {endpoint}: {e}"))
+    }) {
+        Ok((endpoint, listener)) => (Some(endpoint), Some(listener)),
+        Err(error) => {
+            debug!("{error}; only the session files will control this recording");
+            (None, None)
+        }
+    };
 
     // Stamp the start time once; reusing it for the Stopped write keeps `startedAt` meaning the start
     // (not the stop), which `list_sessions` relies on to sort recordings newest-first.
     let started_at = session::now_unix();
-    session::write_session(&Session {
+    let recording = Session {
         recording_id: recording_id.to_string(),
         pid: std::process::id(),
         path: path.clone(),
         status: SessionStatus::Recording,
+        paused: false,
+        pause_error: None,
         started_at,
         recording_meta_exists: None,
         error: None,
-    })?;
+        stats: None,
+        control: control_endpoint,
+    };
+    session::write_session(&recording)?;
+    let recording = std::sync::Mutex::new(recording);
+    let control = SessionControl {
+        actor: &actor,
+        recording: &recording,
+        stop: tokio::sync::Notify::new(),
+        pausing: tokio::sync::Mutex::new(()),
+    };
+
+    let markers = match &actor {
+        ActorHandle::Studio(studio) => Some(MarkerForwarder {
+            actor: studio.clone(),
+            path: session::marker_file(recording_id)?,
+            offset: tokio::sync::Mutex::new(0),
+        }),
+        ActorHandle::Instant(_) => None,
+    };
 
     let stop_path = session::stop_file(recording_id)?;
-    let completed = finalize(actor, params.duration, false, Some(&stop_path)).await?;
+    let pause_path = session::pause_file(recording_id)?;
+    let completed = tokio::select! {
+        completed = finalize(
+            &actor,
+            params.duration,
+            false,
+            Some(&stop_path),
+            Some(&control.stop),
+            markers.as_ref(),
+        ) => completed,
+        _ = async {
+            match &markers {
+                Some(markers) => markers.follow().await,
+                None => std::future::pending::<()>().await,
+            }
+        } => {
+            unreachable!("marker requests are followed until the recording stops")
+        }
+        _ = forward_pause_requests(&control, &pause_path) => {
+            unreachable!("pause requests are followed until the recording stops")
+        }
+        _ = report_health(&actor, health_rx, &path, &recording) => {
+            unreachable!("health is reported until the recording stops")
+        }
+        _ = async {
+            match listener {
+                Some(listener) => control::serve(listener, |request| control.handle(request)).await,
+                None => std::future::pending().await,
+            }
+        } => {
+            unreachable!("the control endpoint is served until the recording stops")
+        }
+    };
+    let stats = lock_session(&recording).stats;
+    let completed = completed?;
     crate::automation::run_recording_finished(
         completed.project_path(),
         automation_mode(params.mode),
@@ -465,13 +711,388 @@ async fn session_worker(params: RecordParams, recording_id: &str) -> Result<(),
         pid: std::process::id(),
         path: completed.project_path().to_path_buf(),
         status: SessionStatus::Stopped,
+        paused: false,
+        pause_error: None,
         started_at,
         recording_meta_exists: Some(recording_meta_exists),
         error: None,
+        stats,
+        control: None,
     })
 }
 
-impl RecordStopArgs {
+/// Drops a marker on the recording for every request `cap record marker` appends to the session's
+/// `.markers` file.
+struct MarkerForwarder {
+    actor: StudioActorHandle,
+    path: PathBuf,
+    /// How far the file has been read; held while its requests are forwarded, so none is added twice.
+    offset: tokio::sync::Mutex<usize>,
+}
+
+impl MarkerForwarder {
+    /// Tail the file until the recording stops.
+    async fn follow(&self) {
+        loop {
+            self.forward_pending().await;
+            tokio::time::sleep(Duration::from_millis(150)).await;
+        }
+    }
+
+    /// Add the requests appended since the last read, each at the time it was made.
+    async fn forward_pending(&self) {
+        let mut offset = self.offset.lock().await;
+        for request in session::read_marker_requests(&self.path, &mut offset) {
+            let requested_at = request.requested_instant();
+            match self.actor.add_marker_at(request.name, requested_at).await {
+                Ok(name) => debug!("Added marker '{name}'"),
+                Err(error) => debug!("Could not add marker: {error}"),
+            }
+        }
+    }
+}
+
+/// Pause the recording while the session's `.pause` file exists, which `cap record pause` creates and
+/// `cap record resume` removes. A change the recording refuses is put back in the file and noted as
+/// the session's `pauseError`, so it is tried once and its requester hears why.
+async fn forward_pause_requests(control: &SessionControl<'_>, path: &Path) {
+    loop {
+        control.follow_pause_file(path).await;
+        tokio::time::sleep(Duration::from_millis(150)).await;
+    }
+}
+
+/// Carries out what a detached worker is asked to do through its control endpoint or session files.
+struct SessionControl<'a> {
+    actor: &'a ActorHandle,
+    recording: &'a std::sync::Mutex<Session>,
+    /// Notified by a `stop` request.
+    stop: tokio::sync::Notify,
+    /// Held while the pause state changes, so a request that arrives both ways is applied once.
+    pausing: tokio::sync::Mutex<()>,
+}
+
+impl SessionControl<'_> {
+    async fn handle(&self, request: ControlRequest) -> Result<serde_json::Value, String> {
+        match request {
+            ControlRequest::Stop => {
+                self.stop.notify_one();
+                Ok(serde_json::Value::Null)
+            }
+            ControlRequest::Pause => self.request_pause(true).await,
+            ControlRequest::Resume => self.request_pause(false).await,
+            ControlRequest::AddMarker { name } => {
+                let name = self
+                    .studio()?
+                    .add_marker(name)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                debug!("Added marker '{name}'");
+                Ok(serde_json::json!({ "name": name }))
+            }
+            ControlRequest::SetMicrophone { name } => {
+                let studio = self.studio()?;
+                let feed = match &name {
+                    Some(name) => Some(lock_microphone(name).await?),
+                    None => None,
+                };
+                studio.set_mic_feed(feed).await.map_err(|e| e.to_string())?;
+                Ok(serde_json::json!({ "name": name }))
+            }
+            ControlRequest::SetCamera { device_id } => {
+                let studio = self.studio()?;
+                let feed = match &device_id {
+                    Some(device_id) => Some(lock_camera(device_id).await?),
+                    None => None,
+                };
+                studio
+                    .set_camera_feed(feed)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                Ok(serde_json::json!({ "deviceId": device_id }))
+            }
+            ControlRequest::Stats => {
+                let stats = self.actor.stats().await?;
+                let (latest, path) = {
+                    let session = lock_session(self.recording);
+                    (session.stats.unwrap_or_default(), session.path.clone())
+                };
+                serde_json::to_value(session::SessionStats {
+                    elapsed_secs: stats.elapsed.as_secs_f64(),
+                    video_frames: stats.video_frames,
+                    bytes_written: session::bytes_written(&path).await,
+                    ..latest
+                })
+                .map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    async fn request_pause(&self, paused: bool) -> Result<serde_json::Value, String> {
+        let _pausing = self.pausing.lock().await;
+        self.set_paused(paused).await?;
+        // Keep the `.pause` file in step once the change has landed, or its poller would undo it.
+        let recording_id = lock_session(self.recording).recording_id.clone();
+        session::request_pause(&recording_id, paused)?;
+        Ok(serde_json::json!({ "paused": paused }))
+    }
+
+    /// Apply what the `.pause` file asks for, if the recording isn't in that state already. On
+    /// refusal the file goes back to the recording's state and the reason is kept in the session.
+    async fn follow_pause_file(&self, path: &Path) {
+        let _pausing = self.pausing.lock().await;
+        let requested = session::pause_requested(path);
+        let recording_id = {
+            let session = lock_session(self.recording);
+            if session.paused == requested {
+                return;
+            }
+            session.recording_id.clone()
+        };
+        let Err(error) = self.set_paused(requested).await else {
+            return;
+        };
+
+        debug!("Could not change pause state: {error}");
+        update_session(self.recording, |session| session.pause_error = Some(error));
+        if let Err(error) = session::request_pause(&recording_id, !requested) {
+            debug!("{error}");
+        }
+    }
+
+    /// Pause or resume the recording, and note the change in the session file so `cap record pause`
+    /// can tell it landed. Callers hold `pausing`.
+    async fn set_paused(&self, paused: bool) -> Result<(), String> {
+        if lock_session(self.recording).paused == paused {
+            return Ok(());
+        }
+        if paused {
+            self.actor.pause().await?;
+        } else {
+            self.actor.resume().await?;
+        }
+        debug!("Recording {}", if paused { "paused" } else { "resumed" });
+        update_session(self.recording, |session| {
+            session.paused = paused;
+            session.pause_error = None;
+        });
+        Ok(())
+    }
+
+    fn studio(&self) -> Result<&StudioActorHandle, String> {
+        match self.actor {
+            ActorHandle::Studio(studio) => Ok(studio),
+            ActorHandle::Instant(_) => {
+                Err("Only studio recordings support markers and input changes".to_string())
+            }
+        }
+    }
+}
+
+/// Append the recording's health events to the session's `.events` log as they arrive, and its stats
+/// every [`session::STATS_INTERVAL`], which also go into the session file for `cap record status`.
+async fn report_health(
+    actor: &ActorHandle,
+    mut health_rx: Option<cap_recording::HealthReceiver>,
+    project_path: &Path,
+    recording: &std::sync::Mutex<Session>,
+) {
+    let recording_id = lock_session(recording).recording_id.clone();
+    let mut interval = tokio::time::interval(session::STATS_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut dropped_frames = 0;
+    let mut stream_dropped_frames = 0;
+    let mut previous: Option<(Instant, u64)> = None;
+
+    loop {
+        let event = tokio::select! {
+            event = next_health_event(&mut health_rx) => event,
+            _ = interval.tick() => {
+                let stats = match actor.stats().await {
+                    Ok(stats) => stats,
+                    Err(error) => {
+                        debug!("Could not read recording stats: {error}");
+                        continue;
+                    }
+                };
+                let now = Instant::now();
+                let fps = previous.map_or(0.0, |(at, frames)| {
+                    stats.video_frames.saturating_sub(frames) as f64
+                        / now.duration_since(at).as_secs_f64().max(f64::EPSILON)
+                });
+                previous = Some((now, stats.video_frames));
+
+                let stats = session::SessionStats {
+                    elapsed_secs: stats.elapsed.as_secs_f64(),
+                    fps,
+                    video_frames: stats.video_frames,
+                    dropped_frames,
+                    stream_dropped_frames,
+                    bytes_written: session::bytes_written(project_path).await,
+                };
+                record_session_event(
+                    &recording_id,
+                    session::SessionEvent::Stats {
+                        at: session::now_unix().unwrap_or_default(),
+                        stats,
+                    },
+                );
+                update_session(recording, |session| session.stats = Some(stats));
+                continue;
+            }
+        };
+
+        let Some(event) = event else {
+            health_rx = None;
+            continue;
+        };
+        if let cap_recording::PipelineHealthEvent::FramesDropped { source, count } = &event {
+            if source == cap_recording::STREAM_HEALTH_SOURCE {
+                stream_dropped_frames += count;
+            } else {
+                dropped_frames += count;
+            }
+        }
+        debug!("Recording health: {event}");
+        record_session_event(
+            &recording_id,
+            session::SessionEvent::Health {
+                at: session::now_unix().unwrap_or_default(),
+                event,
+            },
+        );
+    }
+}
+
+/// The next health event, or `None` once the recording has closed the channel. Never resolves
+/// without a channel.
+async fn next_health_event(
+    health_rx: &mut Option<cap_recording::HealthReceiver>,
+) -> Option<cap_recording::PipelineHealthEvent> {
+    match health_rx {
+        Some(rx) => rx.recv().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn record_session_event(recording_id: &str, event: session::SessionEvent) {
+    if let Err(error) = session::append_event(recording_id, &event) {
+        debug!("{error}");
+    }
+}
+
+/// The worker's session, shared by the tasks that update it so none of them writes over another's
+/// changes.
+fn lock_session(recording: &std::sync::Mutex<Session>) -> std::sync::MutexGuard<'_, Session> {
+    recording.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn update_session(recording: &std::sync::Mutex<Session>, update: impl FnOnce(&mut Session)) {
+    let mut session = lock_session(recording);
+    update(&mut session);
+    if let Err(error) = session::write_session(&session) {
+        debug!("{error}");
+    }
+}
+
+impl RecordPauseArgs {
+    pub async fn run(self, json: bool, paused: bool) -> Result<(), String> {
+        let format = resolve_format(json, self.format);
+        match self.run_inner(format, paused).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                if format == OutputFormat::Json {
+                    let _ = write_json_line(&RecordEvent::Error { error: &error });
+                }
+                Err(error)
+            }
+        }
+    }
+
+    async fn run_inner(self, format: OutputFormat, paused: bool) -> Result<(), String> {
+        let recording_id = set_session_paused(
+            self.id.as_deref(),
+            self.path.as_deref(),
+            paused,
+            self.timeout,
+        )
+        .await?;
+        let event = if paused {
+            RecordEvent::Paused {
+                recording_id: &recording_id,
+            }
+        } else {
+            RecordEvent::Resumed {
+                recording_id: &recording_id,
+            }
+        };
+        emit_record_event(format, &event)
+    }
+}
+
+/// Ask the detached recording identified by `id` (or `path`, or the only active session) to pause or
+/// resume, and wait up to `timeout` seconds for its worker to confirm. Returns the recordingId.
+async fn set_session_paused(
+    id: Option<&str>,
+    path: Option<&Path>,
+    paused: bool,
+    timeout: f64,
+) -> Result<String, String> {
+    let session = resolve_session(id, path)?;
+    let id = session.recording_id.clone();
+    if session.status != SessionStatus::Recording || !session::process_alive(session.pid) {
+        return Err(format!("Recording '{id}' is not running"));
+    }
+
+    let timeout_after = Duration::from_secs_f64(timeout.max(0.0));
+    let action = if paused { "pause" } else { "resume" };
+    if let Some(endpoint) = &session.control {
+        let call = control::call(endpoint, action, serde_json::Value::Null);
+        match tokio::time::timeout(timeout_after, call).await {
+            Ok(Some(result)) => return result.map(|_| id),
+            Ok(None) => {}
+            Err(_) => {
+                return Err(format!(
+                    "timed out after {timeout}s waiting for recording '{id}' to {action}"
+                ));
+            }
+        }
+    }
+
+    session::request_pause(&id, paused)?;
+
+    let pause_path = session::pause_file(&id)?;
+    let deadline = Instant::now() + timeout_after;
+    loop {
+        // Checked before the session is read: the worker notes why it refused before it puts the file
+        // back.
+        let withdrawn = session::pause_requested(&pause_path) != paused;
+        let current = session::read_session(&id)?;
+        if current.status != SessionStatus::Recording || !session::process_alive(current.pid) {
+            return Err(format!(
+                "Recording '{id}' stopped before it could be paused or resumed"
+            ));
+        }
+        if current.paused == paused {
+            return Ok(id);
+        }
+        if withdrawn {
+            return Err(current
+                .pause_error
+                .unwrap_or_else(|| format!("Recording '{id}' could not {action}")));
+        }
+
+        if Instant::now() >= deadline {
+            return Err(format!(
+                "timed out after {timeout}s waiting for recording '{id}' to {action}"
+            ));
+        }
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+    }
+}
+
+impl RecordMarkerArgs {
     pub async fn run(self, json: bool) -> Result<(), String> {
         let format = resolve_format(json, self.format);
         match self.run_inner(format).await {
@@ -487,92 +1108,166 @@ impl RecordStopArgs {
 
     async fn run_inner(self, format: OutputFormat) -> Result<(), String> {
         let session = resolve_session(self.id.as_deref(), self.path.as_deref())?;
-        let id = session.recording_id.clone();
+        if session.status != SessionStatus::Recording || !session::process_alive(session.pid) {
+            return Err(format!(
+                "Recording '{}' is not running",
+                session.recording_id
+            ));
+        }
 
-        if session.status == SessionStatus::Stopped {
-            emit_record_event(
-                format,
-                &RecordEvent::Stopped {
-                    path: &session.path.display().to_string(),
-                    recording_meta_exists: session.recording_meta_exists.unwrap_or(true),
-                },
-            )?;
-            cleanup_session(&id, &session.path);
-            return Ok(());
+        if let Some(endpoint) = &session.control {
+            let params = serde_json::json!({ "name": self.name });
+            if let Some(result) = control::call(endpoint, "addMarker", params).await {
+                let added = result?;
+                return emit_record_event(
+                    format,
+                    &RecordEvent::Marker {
+                        recording_id: &session.recording_id,
+                        name: added["name"].as_str().or(self.name.as_deref()),
+                    },
+                );
+            }
         }
 
-        session::request_stop(&id)?;
+        // Without the control endpoint the request is queued for the worker and not confirmed; a
+        // marker it can't add (say, while paused) is only noted in its debug log.
+        session::request_marker(
+            &session.recording_id,
+            &session::MarkerRequest::now(self.name.clone()),
+        )?;
+        emit_record_event(
+            format,
+            &RecordEvent::Marker {
+                recording_id: &session.recording_id,
+                name: self.name.as_deref(),
+            },
+        )
+    }
+}
 
-        let deadline = Instant::now() + Duration::from_secs_f64(self.timeout.max(0.0));
-        loop {
-            let current = session::read_session(&id).unwrap_or_else(|_| session.clone());
-            match current.status {
-                SessionStatus::Stopped => {
-                    emit_record_event(
-                        format,
-                        &RecordEvent::Stopped {
-                            path: &current.path.display().to_string(),
-                            recording_meta_exists: current.recording_meta_exists.unwrap_or(true),
-                        },
-                    )?;
-                    cleanup_session(&id, &current.path);
-                    return Ok(());
-                }
-                SessionStatus::Error => {
-                    cleanup_session(&id, &current.path);
-                    return Err(current
-                        .error
-                        .unwrap_or_else(|| "recording failed".to_string()));
+impl RecordStopArgs {
+    pub async fn run(self, json: bool) -> Result<(), String> {
+        let format = resolve_format(json, self.format);
+        match self.run_inner(format).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                if format == OutputFormat::Json {
+                    let _ = write_json_line(&RecordEvent::Error { error: &error });
                 }
-                SessionStatus::Recording => {}
+                Err(error)
             }
+        }
+    }
 
-            if !session::process_alive(current.pid) {
-                let recording_meta_exists = current.path.join("recording-meta.json").exists();
-                if !recording_meta_exists {
-                    return fail_dead_session(
-                        current,
-                        "recording process exited without finalizing the recording".to_string(),
-                    );
-                }
+    async fn run_inner(self, format: OutputFormat) -> Result<(), String> {
+        let stopped = stop_session(self.id.as_deref(), self.path.as_deref(), self.timeout).await?;
+        emit_record_event(
+            format,
+            &RecordEvent::Stopped {
+                path: &stopped.path.display().to_string(),
+                recording_meta_exists: stopped.recording_meta_exists,
+            },
+        )?;
+        cleanup_session(&stopped.recording_id, &stopped.path);
+        Ok(())
+    }
+}
 
-                if let Err(error) = crate::project::validate_project(&current.path) {
-                    return fail_dead_session(current, error);
-                }
+/// A detached recording whose worker has finalized the `.cap`. The session files are left in place
+/// until the caller has delivered the result and runs [`cleanup_session`].
+pub(crate) struct StoppedRecording {
+    pub(crate) recording_id: String,
+    pub(crate) path: PathBuf,
+    pub(crate) recording_meta_exists: bool,
+}
+
+/// Ask the detached recording identified by `id` (or `path`, or the only active session) to stop and
+/// wait up to `timeout` seconds for its worker to finalize it.
+pub(crate) async fn stop_session(
+    id: Option<&str>,
+    path: Option<&Path>,
+    timeout: f64,
+) -> Result<StoppedRecording, String> {
+    let session = resolve_session(id, path)?;
+    let id = session.recording_id.clone();
+
+    if session.status == SessionStatus::Stopped {
+        return Ok(StoppedRecording {
+            recording_meta_exists: session.recording_meta_exists.unwrap_or(true),
+            path: session.path,
+            recording_id: id,
+        });
+    }
+
+    let stopping = match &session.control {
+        Some(endpoint) => control::call(endpoint, "stop", serde_json::Value::Null).await,
+        None => None,
+    };
+    if !matches!(stopping, Some(Ok(_))) {
+        session::request_stop(&id)?;
+    }
 
+    let deadline = Instant::now() + Duration::from_secs_f64(timeout.max(0.0));
+    loop {
+        let current = session::read_session(&id).unwrap_or_else(|_| session.clone());
+        match current.status {
+            SessionStatus::Stopped => {
+                return Ok(StoppedRecording {
+                    recording_meta_exists: current.recording_meta_exists.unwrap_or(true),
+                    path: current.path,
+                    recording_id: id,
+                });
+            }
+            SessionStatus::Error => {
                 cleanup_session(&id, &current.path);
-                return emit_record_event(
-                    format,
-                    &RecordEvent::Stopped {
-                        path: &current.path.display().to_string(),
-                        recording_meta_exists: true,
-                    },
+                return Err(current
+                    .error
+                    .unwrap_or_else(|| "recording failed".to_string()));
+            }
+            SessionStatus::Recording => {}
+        }
+
+        if !session::process_alive(current.pid) {
+            let recording_meta_exists = current.path.join("recording-meta.json").exists();
+            if !recording_meta_exists {
+                return fail_dead_session(
+                    current,
+                    "recording process exited without finalizing the recording".to_string(),
                 );
             }
 
-            if Instant::now() >= deadline {
-                if session::process_alive(current.pid) {
-                    session::terminate(current.pid);
-                }
-                return Err(format!(
-                    "timed out after {}s waiting for recording '{id}' to stop",
-                    self.timeout
-                ));
+            if let Err(error) = crate::project::validate_project(&current.path) {
+                return fail_dead_session(current, error);
             }
 
-            tokio::time::sleep(Duration::from_millis(150)).await;
+            return Ok(StoppedRecording {
+                recording_meta_exists: true,
+                path: current.path,
+                recording_id: id,
+            });
         }
+
+        if Instant::now() >= deadline {
+            if session::process_alive(current.pid) {
+                session::terminate(current.pid);
+            }
+            return Err(format!(
+                "timed out after {timeout}s waiting for recording '{id}' to stop"
+            ));
+        }
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
     }
 }
 
-fn cleanup_session(id: &str, project_path: &Path) {
+pub(crate) fn cleanup_session(id: &str, project_path: &Path) {
     if let Err(error) = session::archive_log(id, project_path) {
         debug!("{error}");
     }
     session::cleanup(id);
 }
 
-fn fail_dead_session(session: Session, error: String) -> Result<(), String> {
+fn fail_dead_session<T>(session: Session, error: String) -> Result<T, String> {
     let _ = session::write_session(&Session {
         status: SessionStatus::Error,
         error: Some(error.clone()),
@@ -619,7 +1314,7 @@ fn resolve_session(id: Option<&str>, path: Option<&Path>) -> Result<Session, Str
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct SessionStatusRow {
+pub(crate) struct SessionStatusRow {
     recording_id: String,
     pid: u32,
     path: PathBuf,
@@ -629,14 +1324,143 @@ struct SessionStatusRow {
     started_at: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<session::SessionStats>,
 }
 
-pub fn status(format: OutputFormat) -> Result<(), String> {
-    let rows: Vec<SessionStatusRow> = session::list_sessions()?
-        .into_iter()
-        .map(session_status_row)
-        .collect();
+impl RecordStatusArgs {
+    pub async fn run(self, json: bool) -> Result<(), String> {
+        let format = resolve_format(json, self.format);
+        if !self.follow {
+            let result = self.run_inner(format);
+            if format == OutputFormat::Json
+                && let Err(error) = &result
+            {
+                let _ = write_json(&serde_json::json!({ "error": error }));
+            }
+            return result;
+        }
+
+        match follow_session(self.id.as_deref(), self.path.as_deref(), format).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                if format == OutputFormat::Json {
+                    let _ = write_json_line(&RecordEvent::Error { error: &error });
+                }
+                Err(error)
+            }
+        }
+    }
+
+    fn run_inner(&self, format: OutputFormat) -> Result<(), String> {
+        if self.id.is_none() && self.path.is_none() {
+            return print_status_rows(&status_rows()?, format);
+        }
+
+        let row = session_status_row(resolve_session(self.id.as_deref(), self.path.as_deref())?);
+        match format {
+            OutputFormat::Json => write_json(&row),
+            OutputFormat::Text => print_status_rows(&[row], format),
+        }
+    }
+}
 
+/// Print the health events and stats of the detached recording identified by `id` (or `path`, or
+/// the only active session) as its worker appends them, from the start of the recording until it
+/// stops.
+async fn follow_session(
+    id: Option<&str>,
+    path: Option<&Path>,
+    format: OutputFormat,
+) -> Result<(), String> {
+    let mut last = resolve_session(id, path)?;
+    let id = last.recording_id.clone();
+    let events_path = session::events_file(&id)?;
+    let mut offset = 0;
+
+    loop {
+        // Read the session before the events, so every event written before it stopped is printed.
+        let current = match session::read_session(&id) {
+            Ok(current) => Some(current),
+            // `cap record stop` removes the session files once it has the result.
+            Err(_) if last.status == SessionStatus::Recording => None,
+            Err(error) => return Err(error),
+        };
+        for event in session::read_events(&events_path, &mut offset) {
+            emit_session_event(format, &event)?;
+        }
+
+        let Some(current) = current else {
+            // Cleanup leaves the log for followers, so everything in it has now been printed.
+            let recording_meta_exists = last.path.join("recording-meta.json").exists();
+            return emit_record_event(
+                format,
+                &RecordEvent::Stopped {
+                    path: &last.path.display().to_string(),
+                    recording_meta_exists,
+                },
+            );
+        };
+        match current.status {
+            SessionStatus::Recording if session::process_alive(current.pid) => {}
+            SessionStatus::Recording => {
+                return Err(format!(
+                    "Recording '{id}' failed: recording process is not running"
+                ));
+            }
+            SessionStatus::Stopped => {
+                return emit_record_event(
+                    format,
+                    &RecordEvent::Stopped {
+                        path: &current.path.display().to_string(),
+                        recording_meta_exists: current.recording_meta_exists.unwrap_or(false),
+                    },
+                );
+            }
+            SessionStatus::Error => {
+                return Err(current
+                    .error
+                    .unwrap_or_else(|| format!("Recording '{id}' failed")));
+            }
+        }
+        last = current;
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
+fn emit_session_event(format: OutputFormat, event: &session::SessionEvent) -> Result<(), String> {
+    match format {
+        OutputFormat::Json => write_json_line(event),
+        OutputFormat::Text => {
+            match event {
+                session::SessionEvent::Health { event, .. } => println!("health: {event}"),
+                session::SessionEvent::Stats { stats, .. } => {
+                    println!("stats: {}", format_stats(stats))
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn format_stats(stats: &session::SessionStats) -> String {
+    let stream_dropped = if stats.stream_dropped_frames > 0 {
+        format!("  {} dropped from stream", stats.stream_dropped_frames)
+    } else {
+        String::new()
+    };
+    format!(
+        "{:.0}s  {:.1} fps  {} frames  {} dropped{stream_dropped}  {:.1} MB",
+        stats.elapsed_secs,
+        stats.fps,
+        stats.video_frames,
+        stats.dropped_frames,
+        stats.bytes_written as f64 / 1_048_576.0
+    )
+}
+
+fn print_status_rows(rows: &[SessionStatusRow], format: OutputFormat) -> Result<(), String> {
     match format {
         OutputFormat::Json => write_json(&rows),
         OutputFormat::Text => {
@@ -656,12 +1480,24 @@ pub fn status(format: OutputFormat) -> Result<(), String> {
                     row.pid,
                     row.path.display()
                 );
+                if let Some(stats) = &row.stats
+                    && row.status == SessionStatus::Recording
+                {
+                    println!("    {}", format_stats(stats));
+                }
             }
             Ok(())
         }
     }
 }
 
+pub(crate) fn status_rows() -> Result<Vec<SessionStatusRow>, String> {
+    Ok(session::list_sessions()?
+        .into_iter()
+        .map(session_status_row)
+        .collect())
+}
+
 fn session_status_row(session: Session) -> SessionStatusRow {
     let alive = session.status == SessionStatus::Recording && session::process_alive(session.pid);
     session_status_row_with_alive(session, alive)
@@ -688,6 +1524,7 @@ fn session_status_row_with_alive(session: Session, alive: bool) -> SessionStatus
         alive,
         started_at: session.started_at,
         error,
+        stats: session.stats,
     }
 }
 
@@ -697,6 +1534,37 @@ enum ActorHandle {
 }
 
 impl ActorHandle {
+    fn take_health_rx(&mut self) -> Option<cap_recording::HealthReceiver> {
+        match self {
+            Self::Studio(actor) => actor.take_health_rx(),
+            Self::Instant(actor) => actor.take_health_rx(),
+        }
+    }
+
+    async fn stats(&self) -> Result<cap_recording::RecordingStats, String> {
+        match self {
+            Self::Studio(actor) => actor.stats().await,
+            Self::Instant(actor) => actor.stats().await,
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    async fn pause(&self) -> Result<(), String> {
+        match self {
+            Self::Studio(actor) => actor.pause().await,
+            Self::Instant(actor) => actor.pause().await,
+        }
+        .map_err(|e| e.to_string())
+    }
+
+    async fn resume(&self) -> Result<(), String> {
+        match self {
+            Self::Studio(actor) => actor.resume().await,
+            Self::Instant(actor) => actor.resume().await,
+        }
+        .map_err(|e| e.to_string())
+    }
+
     async fn stop(&self) -> Result<CompletedRecording, String> {
         match self {
             Self::Studio(actor) => actor
@@ -744,74 +1612,46 @@ async fn start_recording(
     // Feeds must be locked and attached before build(); the lock keeps the device open for the whole
     // recording, so the feed actor handle itself does not need to be retained.
     if let Some(device_id) = params.camera.as_deref() {
-        let info = cap_camera::list_cameras()
-            .find(|c| c.device_id() == device_id)
-            .ok_or_else(|| {
-                let available: Vec<String> = cap_camera::list_cameras()
-                    .map(|c| c.device_id().to_string())
-                    .collect();
-                format!(
-                    "Camera with id '{device_id}' not found. Available device ids: {available:?} \
-                     (see `cap targets cameras`)"
-                )
-            })?;
-        let id = camera::DeviceOrModelID::from_info(&info);
-
-        let camera_feed = CameraFeed::spawn(CameraFeed::default());
-        camera_feed
-            .ask(camera::SetInput { id, settings: None })
-            .await
-            .map_err(|e| format!("Failed to set camera input: {e}"))?
-            .await
-            .map_err(|e| format!("Camera failed to connect: {e}"))?;
-        let lock = camera_feed
-            .ask(camera::Lock)
-            .await
-            .map_err(|e| format!("Failed to lock camera feed: {e}"))?;
-        let lock = Arc::new(lock);
+        let lock = lock_camera(device_id).await?;
         studio_builder = studio_builder.with_camera_feed(lock.clone());
         instant_builder = instant_builder.with_camera_feed(lock);
         camera_active = true;
     }
 
-    if let Some(mic_name) = params.mic.as_deref() {
-        let available = MicrophoneFeed::list();
-        if !available.contains_key(mic_name) {
-            let names: Vec<&str> = available.keys().map(String::as_str).collect();
-            return Err(format!(
-                "Microphone '{mic_name}' not found. Available: {names:?} (see `cap targets mics`)"
-            ));
-        }
-
-        let (error_tx, _error_rx) = flume::bounded(16);
-        let mic_feed = MicrophoneFeed::spawn(MicrophoneFeed::new(error_tx));
-        mic_feed
-            .ask(microphone::SetInput {
-                label: mic_name.to_string(),
-                settings: None,
-            })
-            .await
-            .map_err(|e| format!("Failed to set mic input: {e}"))?
-            .await
-            .map_err(|e| format!("Mic failed to connect: {e}"))?;
-        // The stream needs a moment to warm up before locking on slower devices.
-        tokio::time::sleep(Duration::from_millis(100)).await;
-        let lock = mic_feed
-            .ask(microphone::Lock)
-            .await
-            .map_err(|e| format!("Failed to lock mic feed: {e}"))?;
-        let lock = Arc::new(lock);
+    let mut mics = params.mic.iter();
+    if let Some(mic_name) = mics.next() {
+        let lock = lock_microphone(mic_name).await?;
         studio_builder = studio_builder.with_mic_feed(lock.clone());
         instant_builder = instant_builder.with_mic_feed(lock);
     }
+    for mic_name in mics {
+        studio_builder = studio_builder.with_additional_mic_feed(lock_microphone(mic_name).await?);
+    }
+
+    if let Some(stream_target) = params.stream_url.clone() {
+        let with_audio = !params.mic.is_empty() || params.system_audio;
+        let live_stream = cap_recording::LiveStream::new(stream_target, with_audio)
+            .map_err(|e| format!("Failed to start live stream: {e:#}"))?;
+        studio_builder = studio_builder.with_live_stream(live_stream.clone());
+        instant_builder = instant_builder.with_live_stream(live_stream);
+    }
 
     match params.mode {
         RecordMode::Studio => {
-            let builder = cap_recording::RecordingDefaults::default().apply_to_studio_builder(
+            let mut builder = cap_recording::RecordingDefaults::default().apply_to_studio_builder(
                 studio_builder,
                 camera_active,
                 params.fps,
             );
+            if let Some(keyboard) = params.keyboard {
+                builder = builder.with_keyboard_capture(keyboard);
+            }
+            if let Some(custom_cursor) = params.custom_cursor {
+                builder = builder.with_custom_cursor(custom_cursor);
+            }
+            if let Some(quality) = params.quality {
+                builder = builder.with_quality(quality.into());
+            }
 
             builder
                 .build(
@@ -847,6 +1687,63 @@ async fn start_recording(
     }
 }
 
+async fn lock_camera(device_id: &str) -> Result<Arc<camera::CameraFeedLock>, String> {
+    let info = cap_camera::list_cameras()
+        .find(|c| c.device_id() == device_id)
+        .ok_or_else(|| {
+            let available: Vec<String> = cap_camera::list_cameras()
+                .map(|c| c.device_id().to_string())
+                .collect();
+            format!(
+                "Camera with id '{device_id}' not found. Available device ids: {available:?} \
+                 (see `cap targets cameras`)"
+            )
+        })?;
+    let id = camera::DeviceOrModelID::from_info(&info);
+
+    let camera_feed = CameraFeed::spawn(CameraFeed::default());
+    camera_feed
+        .ask(camera::SetInput { id, settings: None })
+        .await
+        .map_err(|e| format!("Failed to set camera input: {e}"))?
+        .await
+        .map_err(|e| format!("Camera failed to connect: {e}"))?;
+    let lock = camera_feed
+        .ask(camera::Lock)
+        .await
+        .map_err(|e| format!("Failed to lock camera feed: {e}"))?;
+    Ok(Arc::new(lock))
+}
+
+async fn lock_microphone(mic_name: &str) -> Result<Arc<microphone::MicrophoneFeedLock>, String> {
+    let available = MicrophoneFeed::list();
+    if !available.contains_key(mic_name) {
+        let names: Vec<&str> = available.keys().map(String::as_str).collect();
+        return Err(format!(
+            "Microphone '{mic_name}' not found. Available: {names:?} (see `cap targets mics`)"
+        ));
+    }
+
+    let (error_tx, _error_rx) = flume::bounded(16);
+    let mic_feed = MicrophoneFeed::spawn(MicrophoneFeed::new(error_tx));
+    mic_feed
+        .ask(microphone::SetInput {
+            label: mic_name.to_string(),
+            settings: None,
+        })
+        .await
+        .map_err(|e| format!("Failed to set mic input: {e}"))?
+        .await
+        .map_err(|e| format!("Mic failed to connect: {e}"))?;
+    // The stream needs a moment to warm up before locking on slower devices.
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let lock = mic_feed
+        .ask(microphone::Lock)
+        .await
+        .map_err(|e| format!("Failed to lock mic feed: {e}"))?;
+    Ok(Arc::new(lock))
+}
+
 #[cfg(target_os = "macos")]
 async fn acquire_shareable_content_for_target(
     target: &ScreenCaptureTarget,
@@ -929,13 +1826,19 @@ fn shareable_content_display_ids(
 /// Studio recordings are fragmented while recording, so a graceful stop finalizes those fragments
 /// before the `.cap` is returned.
 async fn finalize(
-    actor: ActorHandle,
+    actor: &ActorHandle,
     duration: Option<f64>,
     interactive: bool,
     stop_file: Option<&Path>,
+    stop_requested: Option<&tokio::sync::Notify>,
+    markers: Option<&MarkerForwarder>,
 ) -> Result<CompletedRecording, String> {
     let outcome = std::panic::AssertUnwindSafe(async {
-        wait_for_stop(duration, interactive, stop_file).await;
+        wait_for_stop(actor, duration, interactive, stop_file, stop_requested).await;
+        // Markers asked for just before the stop still belong to the recording.
+        if let Some(markers) = markers {
+            markers.forward_pending().await;
+        }
         actor.stop().await.map_err(|e| e.to_string())
     })
     .catch_unwind()
@@ -1065,7 +1968,6 @@ fn resolve_target(params: &RecordParams) -> Result<ScreenCaptureTarget, String>
         (Some(id), _) => cap_recording::screen_capture::list_displays()
             .into_iter()
             .find(|s| &s.0.id == id)
-            .map(|(s, _)| ScreenCaptureTarget::Display { id: s.id })
             .ok_or_else(|| {
                 let available: Vec<String> = cap_recording::screen_capture::list_displays()
                     .into_iter()
@@ -1075,6 +1977,10 @@ fn resolve_target(params: &RecordParams) -> Result<ScreenCaptureTarget, String>
                     "Screen with id '{id}' not found. Available screen ids: {available:?} \
                      (see `cap targets screens`)"
                 )
+            })
+            .and_then(|(s, _)| match params.target.area {
+                Some(area) => area.target(s.id),
+                None => Ok(ScreenCaptureTarget::Display { id: s.id }),
             }),
         (_, Some(id)) => cap_recording::screen_capture::list_windows()
             .into_iter()
@@ -1092,17 +1998,24 @@ fn resolve_target(params: &RecordParams) -> Result<ScreenCaptureTarget, String>
     }
 }
 
-/// Block until the recording should stop: the duration elapses, the user presses Enter (interactive
-/// only), the process receives SIGINT/SIGTERM, or a detached worker's stop file appears. Every branch
+/// Block until the recording should stop: the duration has been recorded (time spent paused doesn't
+/// count), the user presses Enter (interactive only), the process receives SIGINT/SIGTERM, or a
+/// detached worker's stop file appears or its control endpoint gets a `stop` request. Every branch
 /// resolves so the caller can finalize the recording gracefully instead of being killed mid-write.
-async fn wait_for_stop(duration: Option<f64>, interactive: bool, stop_file: Option<&Path>) {
+async fn wait_for_stop(
+    actor: &ActorHandle,
+    duration: Option<f64>,
+    interactive: bool,
+    stop_file: Option<&Path>,
+    stop_requested: Option<&tokio::sync::Notify>,
+) {
     #[cfg(unix)]
     let mut term = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()).ok();
 
     tokio::select! {
         _ = async {
             match duration {
-                Some(d) => tokio::time::sleep(Duration::from_secs_f64(d)).await,
+                Some(d) => wait_for_recorded(actor, Duration::from_secs_f64(d)).await,
                 None => std::future::pending::<()>().await,
             }
         } => {}
@@ -1135,17 +2048,106 @@ async fn wait_for_stop(duration: Option<f64>, interactive: bool, stop_file: Opti
                 None => std::future::pending::<()>().await,
             }
         } => {}
+        _ = async {
+            match stop_requested {
+                Some(stop_requested) => stop_requested.notified().await,
+                None => std::future::pending::<()>().await,
+            }
+        } => {}
+    }
+}
+
+/// Resolves once the actor has recorded `duration`, measured on its own clock so pauses push the
+/// stop back. An actor that can no longer report its stats is treated as done.
+async fn wait_for_recorded(actor: &ActorHandle, duration: Duration) {
+    loop {
+        let Ok(stats) = actor.stats().await else {
+            return;
+        };
+        match duration.checked_sub(stats.elapsed) {
+            Some(remaining) if !remaining.is_zero() => tokio::time::sleep(remaining).await,
+            _ => return,
+        }
     }
 }
 
 #[derive(Args, Clone)]
-struct RecordTargets {
+pub(crate) struct RecordTargets {
     /// ID of the screen to capture
     #[arg(long, group = "target")]
-    screen: Option<DisplayId>,
+    pub(crate) screen: Option<DisplayId>,
     /// ID of the window to capture
     #[arg(long, group = "target")]
-    window: Option<WindowId>,
+    pub(crate) window: Option<WindowId>,
+    /// Capture only this part of the --screen, as `x,y,width,height` in logical pixels from the
+    /// screen's top-left corner
+    #[arg(long, requires = "screen", value_parser = parse_area)]
+    pub(crate) area: Option<CaptureAreaArg>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct CaptureAreaArg {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl CaptureAreaArg {
+    /// This area of `screen`, which it must lie within.
+    fn target(self, screen: DisplayId) -> Result<ScreenCaptureTarget, String> {
+        if let Some(size) = Display::from_id(&screen).and_then(|display| display.logical_size())
+            && (self.x + self.width > size.width() || self.y + self.height > size.height())
+        {
+            return Err(format!(
+                "Area {self} does not fit on screen '{screen}', which is {}x{}",
+                size.width(),
+                size.height()
+            ));
+        }
+
+        Ok(ScreenCaptureTarget::Area {
+            screen,
+            bounds: LogicalBounds::new(
+                LogicalPosition::new(self.x, self.y),
+                LogicalSize::new(self.width, self.height),
+            ),
+        })
+    }
+}
+
+impl std::fmt::Display for CaptureAreaArg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{},{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+fn parse_stream_url(value: &str) -> Result<StreamTarget, String> {
+    StreamTarget::parse(value).map_err(|e| e.to_string())
+}
+
+fn parse_area(value: &str) -> Result<CaptureAreaArg, String> {
+    let invalid = || format!("Invalid area '{value}', expected x,y,width,height");
+    let parts = value
+        .split(',')
+        .map(|part| part.trim().parse::<f64>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let [x, y, width, height] = parts[..] else {
+        return Err(invalid());
+    };
+    if [x, y, width, height].iter().any(|value| !value.is_finite()) || x < 0.0 || y < 0.0 {
+        return Err(invalid());
+    }
+    if width <= 0.0 || height <= 0.0 {
+        return Err("Area width and height must be positive".to_string());
+    }
+
+    Ok(CaptureAreaArg {
+        x,
+        y,
+        width,
+        height,
+    })
 }
 
 // `rename_all` only renames the variant tags (started/stopped/error); `rename_all_fields` is what
@@ -1167,6 +2169,17 @@ enum RecordEvent<'a> {
         path: &'a str,
         recording_meta_exists: bool,
     },
+    Marker {
+        recording_id: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<&'a str>,
+    },
+    Paused {
+        recording_id: &'a str,
+    },
+    Resumed {
+        recording_id: &'a str,
+    },
     Error {
         error: &'a str,
     },
@@ -1190,6 +2203,18 @@ fn emit_record_event(format: OutputFormat, event: &RecordEvent<'_>) -> Result<()
                         println!("warning: recording-meta.json was not written");
                     }
                 }
+                RecordEvent::Marker { recording_id, name } => match name {
+                    Some(name) => {
+                        println!("Marker '{name}' requested for recording {recording_id}")
+                    }
+                    None => println!("Marker requested for recording {recording_id}"),
+                },
+                RecordEvent::Paused { recording_id } => {
+                    println!("Recording {recording_id} paused")
+                }
+                RecordEvent::Resumed { recording_id } => {
+                    println!("Recording {recording_id} resumed")
+                }
                 RecordEvent::Error { error } => println!("Recording error: {error}"),
             }
             Ok(())
@@ -1222,6 +2247,73 @@ mod tests {
         assert_eq!(stopped["type"], "stopped");
         assert_eq!(stopped["recordingMetaExists"], true);
         assert!(stopped.get("recording_meta_exists").is_none());
+
+        let marker = serde_json::to_value(RecordEvent::Marker {
+            recording_id: "abc",
+            name: None,
+        })
+        .unwrap();
+        assert_eq!(marker["type"], "marker");
+        assert_eq!(marker["recordingId"], "abc");
+        assert!(marker.get("name").is_none());
+    }
+
+    #[test]
+    fn area_flag_parses_logical_bounds() {
+        assert_eq!(
+            parse_area("10, 20.5,640,480").unwrap(),
+            CaptureAreaArg {
+                x: 10.0,
+                y: 20.5,
+                width: 640.0,
+                height: 480.0
+            }
+        );
+        assert_eq!(
+            parse_area("10,20.5,640,480").unwrap().to_string(),
+            "10,20.5,640,480"
+        );
+        assert!(parse_area("10,20,640").is_err());
+        assert!(parse_area("-1,0,640,480").is_err());
+        assert!(parse_area("0,0,0,480").is_err());
+        assert!(parse_area("0,0,inf,480").is_err());
+    }
+
+    #[test]
+    fn worker_args_keep_studio_options() {
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[command(flatten)]
+            params: RecordParams,
+        }
+
+        let parse = |args: &[String]| {
+            <Cli as clap::Parser>::try_parse_from(
+                std::iter::once("cap".to_string()).chain(args.iter().cloned()),
+            )
+            .unwrap()
+            .params
+        };
+        let params = parse(&[
+            "--keyboard".to_string(),
+            "--custom-cursor".to_string(),
+            "false".to_string(),
+            "--quality".to_string(),
+            "ultra".to_string(),
+            "--stream-url".to_string(),
+            "rtmp://live.example.com/app/key".to_string(),
+        ]);
+        assert_eq!(params.keyboard, Some(true));
+        assert_eq!(params.custom_cursor, Some(false));
+        assert_eq!(params.quality, Some(RecordQuality::Ultra));
+
+        let args = params.to_cli_args();
+        assert!(!args.iter().any(|arg| arg.contains("app/key")));
+        let worker = parse(&args);
+        assert_eq!(worker.keyboard, Some(true));
+        assert_eq!(worker.custom_cursor, Some(false));
+        assert_eq!(worker.quality, Some(RecordQuality::Ultra));
+        assert!(worker.stream_url.is_none());
     }
 
     #[test]
@@ -1232,9 +2324,13 @@ mod tests {
                 pid: 123,
                 path: PathBuf::from("recording.cap"),
                 status: SessionStatus::Recording,
+                paused: false,
+                pause_error: None,
                 started_at: Some(1),
                 recording_meta_exists: None,
                 error: None,
+                stats: None,
+                control: None,
             },
             false,
         );
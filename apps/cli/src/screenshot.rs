@@ -25,7 +25,7 @@ pub struct Screenshot {
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ScreenshotResult {
+pub struct ScreenshotResult {
     path: PathBuf,
     width: u32,
     height: u32,
@@ -46,43 +46,57 @@ impl Screenshot {
     }
 
     async fn run_inner(self, format: OutputFormat) -> Result<(), String> {
-        let target = match (self.screen, self.window) {
-            (Some(id), _) => resolve_screen(&id)?,
-            (_, Some(id)) => resolve_window(&id)?,
-            _ => {
-                return Err(
-                    "No target specified; pass --screen <id> or --window <id> (see `cap targets`)"
-                        .to_string(),
-                );
-            }
-        };
-
-        let automation_target = target.clone();
-
-        let image = capture_screenshot(target)
-            .await
-            .map_err(|e| format!("Screenshot failed: {e}"))?;
-        let (width, height) = (image.width(), image.height());
-        image
-            .save(&self.path)
-            .map_err(|e| format!("Failed to write screenshot to {}: {e}", self.path.display()))?;
-
-        crate::automation::run_screenshot(&self.path, &automation_target).await;
+        let target = resolve_target(self.screen.as_ref(), self.window.as_ref())?;
+        let result = capture(target, self.path).await?;
 
         match format {
-            OutputFormat::Json => write_json(&ScreenshotResult {
-                path: self.path,
-                width,
-                height,
-            }),
+            OutputFormat::Json => write_json(&result),
             OutputFormat::Text => {
-                println!("Screenshot saved: {}", self.path.display());
+                println!("Screenshot saved: {}", result.path.display());
                 Ok(())
             }
         }
     }
 }
 
+/// Capture `target` to `path` (format inferred from the extension) and run screenshot automations.
+pub async fn capture(
+    target: ScreenCaptureTarget,
+    path: PathBuf,
+) -> Result<ScreenshotResult, String> {
+    let automation_target = target.clone();
+
+    let image = capture_screenshot(target)
+        .await
+        .map_err(|e| format!("Screenshot failed: {e}"))?;
+    let (width, height) = (image.width(), image.height());
+    image
+        .save(&path)
+        .map_err(|e| format!("Failed to write screenshot to {}: {e}", path.display()))?;
+
+    crate::automation::run_screenshot(&path, &automation_target).await;
+
+    Ok(ScreenshotResult {
+        path,
+        width,
+        height,
+    })
+}
+
+pub fn resolve_target(
+    screen: Option<&DisplayId>,
+    window: Option<&WindowId>,
+) -> Result<ScreenCaptureTarget, String> {
+    match (screen, window) {
+        (Some(id), _) => resolve_screen(id),
+        (_, Some(id)) => resolve_window(id),
+        _ => Err(
+            "No target specified; pass --screen <id> or --window <id> (see `cap targets`)"
+                .to_string(),
+        ),
+    }
+}
+
 fn resolve_screen(id: &DisplayId) -> Result<ScreenCaptureTarget, String> {
     cap_recording::screen_capture::list_displays()
         .into_iter()
@@ -861,6 +861,7 @@ mod fixture {
                 device_id: None,
                 gap_summary: finished_mic.audio_gap_summary.map(to_project_gap_summary),
             }),
+            additional_mics: Vec::new(),
             system_audio: Some(AudioMeta {
                 path: RelativePathBuf::from("content/segments/segment-0/system_audio.ogg"),
                 start_time: Some(sys_start),
@@ -878,6 +879,7 @@ mod fixture {
                 segments: vec![segment],
                 cursors: Default::default(),
                 status: Some(StudioRecordingStatus::Complete),
+                markers: Vec::new(),
             },
         };
 
@@ -911,6 +913,7 @@ mod fixture {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             clips: vec![ClipConfiguration {
                 index: 0,
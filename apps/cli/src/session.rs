@@ -3,14 +3,28 @@
 //! A detached recording runs in a re-exec'd worker process. The worker writes a `<id>.json` session
 //! file describing the live recording; `cap record stop` requests a stop by creating a `<id>.stop`
 //! file (which the worker polls for, so it works on Windows where there is no SIGTERM) and waits for
-//! the worker to flip the session status. `cap record status` lists these files.
+//! the worker to flip the session status. `cap record marker` appends a request line to an
+//! `<id>.markers` file, which the worker tails the same way. `cap record pause` creates an `<id>.pause`
+//! file and `cap record resume` removes it; the worker follows it and sets `paused` in the session
+//! file once the recording has changed state, or puts the file back and sets `pauseError` if the
+//! recording refused. The worker also appends the pipeline's health events
+//! and periodic stats to an `<id>.events` log, keeps the latest stats in the session file, and
+//! `cap record status --follow` tails the log. The log outlives the other files so a follower can
+//! read the last of it, and is pruned once it has sat unwritten for a while. `cap record status`
+//! lists these files.
+//!
+//! While it records, the worker also listens on a control endpoint (see [`crate::control`]) that
+//! `cap record stop`, `pause`, `resume` and `marker` use first; the files above are the fallback for
+//! when it can't be reached.
 
 use std::{
+    io::{Read, Seek, SeekFrom, Write},
     path::{Path, PathBuf},
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
-use serde::{Deserialize, Serialize};
+use cap_recording::PipelineHealthEvent;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 
 #[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -27,12 +41,62 @@ pub struct Session {
     pub pid: u32,
     pub path: PathBuf,
     pub status: SessionStatus,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+    /// Why the recording last refused a pause or resume asked for through the `.pause` file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pause_error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub started_at: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recording_meta_exists: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// The latest stats the worker reported while recording.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<SessionStats>,
+    /// The worker's control endpoint (see [`crate::control`]), while it is listening on one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control: Option<String>,
+}
+
+/// How a running recording is doing, reported every [`STATS_INTERVAL`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStats {
+    /// Seconds recorded, not counting pauses.
+    pub elapsed_secs: f64,
+    /// Screen frames encoded per second since the previous report.
+    pub fps: f64,
+    pub video_frames: u64,
+    /// Frames the muxers dropped because the encoder could not keep up.
+    pub dropped_frames: u64,
+    /// Frames left out of the `--stream-url` broadcast, which the recording itself still has.
+    #[serde(default)]
+    pub stream_dropped_frames: u64,
+    /// Size of the `.cap` project on disk.
+    pub bytes_written: u64,
+}
+
+pub const STATS_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// A line of a session's `.events` log.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum SessionEvent {
+    Health {
+        at: u64,
+        event: PipelineHealthEvent,
+    },
+    Stats {
+        at: u64,
+        #[serde(flatten)]
+        stats: SessionStats,
+    },
 }
 
 pub fn now_unix() -> Option<u64> {
@@ -56,10 +120,26 @@ pub fn stop_file(id: &str) -> Result<PathBuf, String> {
     Ok(sessions_dir()?.join(format!("{id}.stop")))
 }
 
+pub fn marker_file(id: &str) -> Result<PathBuf, String> {
+    Ok(sessions_dir()?.join(format!("{id}.markers")))
+}
+
+pub fn pause_file(id: &str) -> Result<PathBuf, String> {
+    Ok(sessions_dir()?.join(format!("{id}.pause")))
+}
+
 pub fn log_file(id: &str) -> Result<PathBuf, String> {
     Ok(sessions_dir()?.join(format!("{id}.log")))
 }
 
+pub fn socket_file(id: &str) -> Result<PathBuf, String> {
+    Ok(sessions_dir()?.join(format!("{id}.sock")))
+}
+
+pub fn events_file(id: &str) -> Result<PathBuf, String> {
+    Ok(sessions_dir()?.join(format!("{id}.events")))
+}
+
 pub fn write_session(session: &Session) -> Result<(), String> {
     let dir = sessions_dir()?;
     std::fs::create_dir_all(&dir).map_err(|e| format!("Could not create sessions dir: {e}"))?;
@@ -109,13 +189,160 @@ pub fn stop_requested(path: &Path) -> bool {
     path.exists()
 }
 
+/// Ask the worker to pause the recording, or to resume it by withdrawing the request.
+pub fn request_pause(id: &str, paused: bool) -> Result<(), String> {
+    let path = pause_file(id)?;
+    if paused {
+        let dir = sessions_dir()?;
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Could not create sessions dir: {e}"))?;
+        std::fs::write(path, b"").map_err(|e| format!("Could not request pause: {e}"))
+    } else {
+        match std::fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Could not request resume: {e}")),
+        }
+    }
+}
+
+pub fn pause_requested(path: &Path) -> bool {
+    path.exists()
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct MarkerRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Unix time in milliseconds when the marker was asked for, so the worker can place it there
+    /// rather than when it next polls the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requested_at: Option<u64>,
+}
+
+impl MarkerRequest {
+    pub fn now(name: Option<String>) -> Self {
+        Self {
+            name,
+            requested_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_millis() as u64),
+        }
+    }
+
+    /// The moment this request was made on this process's clock, or now for requests without
+    /// a timestamp (or stamped in the future).
+    pub fn requested_instant(&self) -> Instant {
+        let now = Instant::now();
+        self.requested_at
+            .and_then(|millis| UNIX_EPOCH.checked_add(Duration::from_millis(millis)))
+            .and_then(|requested| SystemTime::now().duration_since(requested).ok())
+            .and_then(|age| now.checked_sub(age))
+            .unwrap_or(now)
+    }
+}
+
+pub fn request_marker(id: &str, request: &MarkerRequest) -> Result<(), String> {
+    append_line(&marker_file(id)?, request).map_err(|e| format!("Could not request marker: {e}"))
+}
+
+pub fn append_event(id: &str, event: &SessionEvent) -> Result<(), String> {
+    append_line(&events_file(id)?, event).map_err(|e| format!("Could not record event: {e}"))
+}
+
+fn append_line<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let dir = sessions_dir()?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Could not create sessions dir: {e}"))?;
+    let mut line = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+    line.push(b'\n');
+    // One append-mode write per line, so concurrent writers never interleave one.
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(&line))
+        .map_err(|e| e.to_string())
+}
+
+/// Marker requests appended to `path` since byte `offset`, which is advanced past them.
+pub fn read_marker_requests(path: &Path, offset: &mut usize) -> Vec<MarkerRequest> {
+    read_lines(path, offset)
+}
+
+/// Events appended to `path` since byte `offset`, which is advanced past them.
+pub fn read_events(path: &Path, offset: &mut usize) -> Vec<SessionEvent> {
+    read_lines(path, offset)
+}
+
+/// JSON lines appended to `path` since byte `offset`. A trailing line without its newline is still
+/// being written and is left for the next read.
+fn read_lines<T: DeserializeOwned>(path: &Path, offset: &mut usize) -> Vec<T> {
+    let mut unread = Vec::new();
+    let read = std::fs::File::open(path).and_then(|mut file| {
+        file.seek(SeekFrom::Start(*offset as u64))?;
+        file.read_to_end(&mut unread)
+    });
+    if read.is_err() {
+        return Vec::new();
+    }
+    let Some(complete) = unread.iter().rposition(|byte| *byte == b'\n') else {
+        return Vec::new();
+    };
+    *offset += complete + 1;
+
+    unread[..complete]
+        .split(|byte| *byte == b'\n')
+        .filter_map(|line| serde_json::from_slice(line).ok())
+        .collect()
+}
+
+/// How long the `.events` log of a finished session is kept for a `status --follow` that has yet to
+/// read the end of it.
+const EVENTS_RETENTION: Duration = Duration::from_secs(10 * 60);
+
+/// Remove a finished session's files. Its `.events` log is left for followers and pruned by a later
+/// cleanup.
 pub fn cleanup(id: &str) {
-    for path in [session_file(id), stop_file(id), log_file(id)]
-        .into_iter()
-        .flatten()
+    for path in [
+        session_file(id),
+        stop_file(id),
+        marker_file(id),
+        pause_file(id),
+        log_file(id),
+        socket_file(id),
+    ]
+    .into_iter()
+    .flatten()
     {
         let _ = std::fs::remove_file(path);
     }
+    if let Ok(dir) = sessions_dir() {
+        prune_finished_events(&dir, EVENTS_RETENTION);
+    }
+}
+
+/// Remove `.events` logs under `dir` whose session is gone and that haven't been written to for
+/// `retention`.
+fn prune_finished_events(dir: &Path, retention: Duration) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("events")
+            || path.with_extension("json").exists()
+        {
+            continue;
+        }
+        let idle = entry
+            .metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        if idle.is_some_and(|idle| idle >= retention) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
 }
 
 pub fn archive_log(id: &str, project_path: &Path) -> Result<Option<PathBuf>, String> {
@@ -139,6 +366,27 @@ pub fn archive_log(id: &str, project_path: &Path) -> Result<Option<PathBuf>, Str
     Ok(Some(destination))
 }
 
+/// Total size of the files under `path`, walked on the blocking pool.
+pub async fn bytes_written(path: &Path) -> u64 {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || directory_size(&path))
+        .await
+        .unwrap_or(0)
+}
+
+fn directory_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => directory_size(&entry.path()),
+            _ => entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+        })
+        .sum()
+}
+
 /// Whether `pid` is still running. On unix `kill(pid, 0)` probes existence without signalling; on
 /// Windows a zero-timeout wait on the process handle does the equivalent.
 #[cfg(unix)]
@@ -180,3 +428,111 @@ pub fn terminate(pid: u32) {
 
 #[cfg(not(unix))]
 pub fn terminate(_pid: u32) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_requests_are_read_once_and_only_when_complete() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc.markers");
+        std::fs::write(&path, b"{\"name\":\"Intro\"}\n{}\n{\"name\":\"De").unwrap();
+
+        let mut offset = 0;
+        assert_eq!(
+            read_marker_requests(&path, &mut offset),
+            vec![
+                MarkerRequest {
+                    name: Some("Intro".to_string()),
+                    requested_at: None,
+                },
+                MarkerRequest::default()
+            ]
+        );
+        assert!(read_marker_requests(&path, &mut offset).is_empty());
+
+        std::fs::write(&path, b"{\"name\":\"Intro\"}\n{}\n{\"name\":\"Demo\"}\n").unwrap();
+        assert_eq!(
+            read_marker_requests(&path, &mut offset),
+            vec![MarkerRequest {
+                name: Some("Demo".to_string()),
+                requested_at: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn marker_requests_are_placed_when_they_were_made() {
+        let request = MarkerRequest {
+            requested_at: MarkerRequest::now(None)
+                .requested_at
+                .map(|millis| millis - 2_000),
+            ..MarkerRequest::default()
+        };
+        let age = Instant::now() - request.requested_instant();
+        assert!(age >= Duration::from_millis(1_900) && age < Duration::from_secs(3));
+
+        let future = MarkerRequest {
+            requested_at: MarkerRequest::now(None)
+                .requested_at
+                .map(|millis| millis + 60_000),
+            ..MarkerRequest::default()
+        };
+        assert!(Instant::now() - future.requested_instant() < Duration::from_millis(100));
+    }
+
+    #[test]
+    fn events_are_read_back_as_they_were_logged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("abc.events");
+        std::fs::write(
+            &path,
+            concat!(
+                "{\"type\":\"health\",\"at\":10,\"event\":",
+                "{\"kind\":\"framesDropped\",\"source\":\"screen\",\"count\":3}}\n",
+                "{\"type\":\"stats\",\"at\":12,\"elapsedSecs\":4.0,\"fps\":30.0,",
+                "\"videoFrames\":120,\"droppedFrames\":3,\"bytesWritten\":2048}\n",
+            ),
+        )
+        .unwrap();
+
+        let mut offset = 0;
+        let events = read_events(&path, &mut offset);
+        assert!(matches!(
+            &events[..],
+            [
+                SessionEvent::Health {
+                    at: 10,
+                    event: PipelineHealthEvent::FramesDropped { source, count: 3 },
+                },
+                SessionEvent::Stats {
+                    at: 12,
+                    stats: SessionStats {
+                        video_frames: 120,
+                        dropped_frames: 3,
+                        bytes_written: 2048,
+                        ..
+                    },
+                },
+            ] if source == "screen"
+        ));
+        assert!(read_events(&path, &mut offset).is_empty());
+    }
+
+    #[test]
+    fn only_events_logs_of_finished_sessions_are_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["live.json", "live.events", "done.events", "done.log"] {
+            std::fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        prune_finished_events(dir.path(), Duration::from_secs(60));
+        assert!(dir.path().join("done.events").exists());
+
+        prune_finished_events(dir.path(), Duration::ZERO);
+        assert!(!dir.path().join("done.events").exists());
+        assert!(dir.path().join("live.events").exists());
+        assert!(dir.path().join("done.log").exists());
+    }
+}
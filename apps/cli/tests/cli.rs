@@ -59,6 +59,7 @@ fn help_succeeds_and_lists_commands() {
         "upload",
         "update",
         "screenshot",
+        "captions",
         "caps",
         "account",
         "organizations",
@@ -275,6 +276,42 @@ fn project_validate_complete_project_is_valid() {
     assert_eq!(json["recordingType"], "studio");
 }
 
+#[test]
+fn project_trim_silence_without_audio_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("recording.cap");
+    write_single_segment_meta(&project);
+
+    let output = run(&[
+        "project",
+        "trim-silence",
+        project.to_str().unwrap(),
+        "--dry-run",
+        "--json",
+    ]);
+    assert!(!output.status.success());
+    let json = parse_json(&output);
+    assert!(
+        json["error"]
+            .as_str()
+            .unwrap()
+            .contains("no microphone or system audio")
+    );
+    assert!(!project.join("project-config.json").exists());
+}
+
+#[test]
+fn project_loudness_without_audio_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("recording.cap");
+    write_single_segment_meta(&project);
+
+    let output = run(&["project", "loudness", project.to_str().unwrap(), "--json"]);
+    assert!(!output.status.success());
+    let json = parse_json(&output);
+    assert_eq!(json["error"], "Project has no audio to measure");
+}
+
 #[test]
 fn project_validate_detects_missing_media() {
     let dir = tempfile::tempdir().unwrap();
@@ -436,6 +473,35 @@ fn export_rejects_duplicate_output() {
     assert!(!output.status.success());
 }
 
+#[test]
+fn export_rejects_time_range_with_segment() {
+    let output = run(&[
+        "export",
+        "/tmp/whatever.cap",
+        "--from",
+        "10",
+        "--segment",
+        "intro",
+    ]);
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("cannot be used with"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn export_rejects_bad_range_time() {
+    let output = run(&["export", "/tmp/whatever.cap", "--to", "1:xx"]);
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("Invalid time"),
+        "stderr: {}",
+        stderr(&output)
+    );
+}
+
 #[test]
 fn targets_mics_json_is_parseable() {
     let output = run(&["targets", "mics", "--format", "json"]);
@@ -541,6 +607,23 @@ fn export_global_json_implies_progress_stream() {
     assert!(json["error"].is_string());
 }
 
+#[test]
+fn export_batch_invalid_manifest_emits_json_error_event() {
+    let dir = tempfile::tempdir().unwrap();
+    let manifest = dir.path().join("batch.json");
+    std::fs::write(&manifest, r#"{"jobs": []}"#).unwrap();
+
+    let output = run(&["export", "batch", manifest.to_str().unwrap(), "--json"]);
+    assert!(!output.status.success());
+    let json = parse_json(&output);
+    assert_eq!(json["type"], "Error");
+    assert!(
+        json["error"].as_str().unwrap().contains("no jobs"),
+        "stdout: {}",
+        stdout(&output)
+    );
+}
+
 #[test]
 fn export_preview_missing_project_emits_json_error() {
     let output = run(&[
@@ -770,6 +853,43 @@ fn agent_installer_dry_run_is_machine_readable() {
     assert_eq!(json["changes"].as_array().map(Vec::len), Some(2));
 }
 
+#[test]
+fn captions_import_unknown_subtitle_format_emits_json_error() {
+    let dir = tempfile::tempdir().unwrap();
+    let subtitles = dir.path().join("subs.txt");
+    std::fs::write(&subtitles, "1\n00:00:00,000 --> 00:00:01,000\nHi\n").unwrap();
+
+    let output = run(&[
+        "--json",
+        "captions",
+        "import",
+        dir.path().join("missing.cap").to_str().unwrap(),
+        subtitles.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let error = parse_json(&output)["error"].as_str().unwrap().to_string();
+    assert!(error.contains("--format srt|vtt|ass"), "{error}");
+}
+
+#[test]
+fn captions_generate_missing_model_emits_json_error() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let output = run(&[
+        "--json",
+        "captions",
+        "generate",
+        dir.path().join("missing.cap").to_str().unwrap(),
+        "--model",
+        dir.path().join("ggml-missing.bin").to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(1));
+    let error = parse_json(&output)["error"].as_str().unwrap().to_string();
+    assert!(error.contains("Model not found"), "{error}");
+}
+
 #[test]
 fn new_commands_emit_json_errors_for_local_format_flag() {
     let caps_output = cap()
@@ -886,6 +1006,7 @@ fn mcp_stdout_is_protocol_only_and_exposes_expected_tools() {
         tool("caps_import_loom")["annotations"]["idempotentHint"],
         false
     );
+    assert_eq!(tool("record_start")["annotations"]["openWorldHint"], false);
     for tool in tool_list {
         if tool["annotations"]["readOnlyHint"] == false {
             assert_eq!(
@@ -935,6 +1056,16 @@ fn mcp_stdout_is_protocol_only_and_exposes_expected_tools() {
         "developer_domain_add",
         "developer_auto_top_up_update",
         "developer_credits_checkout",
+        "targets_list",
+        "record_start",
+        "record_stop",
+        "record_status",
+        "project_inspect",
+        "project_validate",
+        "project_config_get",
+        "project_config_set",
+        "project_export",
+        "screenshot",
     ] {
         assert!(serialized.contains(name), "missing MCP tool {name}");
     }
@@ -1085,6 +1216,15 @@ fn record_stop_unknown_id_fails_with_json_error() {
     assert!(json["error"].is_string());
 }
 
+#[test]
+fn record_marker_unknown_id_fails_with_json_error() {
+    let output = run(&["record", "marker", "--id", "does-not-exist", "--json"]);
+    assert!(!output.status.success());
+    let json = parse_json(&output);
+    assert_eq!(json["type"], "error");
+    assert!(json["error"].is_string());
+}
+
 #[test]
 fn doctor_exits_zero_even_when_checks_fail() {
     // doctor is a report, not a gate: agents branch on `ok`/`captureReady`, so it must exit 0.
@@ -168,6 +168,7 @@ async fn load_recording(
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             });
         }
     }
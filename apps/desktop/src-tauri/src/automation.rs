@@ -142,11 +142,7 @@ impl AutomationHost for DesktopAutomationHost {
         let output_path = match destination {
             ExportDestination::ProjectFolder => None,
             ExportDestination::CustomPath { dir } => {
-                let ext = match profile.format {
-                    ExportFormat::Mp4 => "mp4",
-                    ExportFormat::Gif => "gif",
-                    ExportFormat::Mov => "mov",
-                };
+                let ext = profile.format.extension();
                 let name = project_path
                     .file_stem()
                     .map(|s| s.to_string_lossy().to_string())
@@ -155,7 +151,8 @@ impl AutomationHost for DesktopAutomationHost {
             }
         };
 
-        let mut builder = cap_export::ExporterBase::builder(project_path.clone());
+        let mut builder = cap_export::ExporterBase::builder(project_path.clone())
+            .with_range(settings.range().cloned());
 
         if let Some(ref out) = output_path {
             builder = builder.with_output_path(out.clone());
@@ -167,6 +164,23 @@ impl AutomationHost for DesktopAutomationHost {
             crate::export::ExportSettings::Mp4(s) => s.export(base, |_| true).await,
             crate::export::ExportSettings::Gif(s) => s.export(base, |_| true).await,
             crate::export::ExportSettings::Mov(s) => s.export(base, |_| true).await,
+            crate::export::ExportSettings::Webm(s) => s.export(base, |_| true).await,
+            crate::export::ExportSettings::Webp(s) => {
+                s.export(
+                    cap_export::animated::AnimatedImageFormat::WebP,
+                    base,
+                    |_| true,
+                )
+                .await
+            }
+            crate::export::ExportSettings::Apng(s) => {
+                s.export(
+                    cap_export::animated::AnimatedImageFormat::Apng,
+                    base,
+                    |_| true,
+                )
+                .await
+            }
         }
         .map_err(|e| format!("Export failed: {e}"))?;
 
@@ -568,6 +582,8 @@ fn build_desktop_export_settings(profile: &ExportProfile) -> crate::export::Expo
                 custom_bpp: None,
                 force_ffmpeg_decoder: false,
                 optimize_filesize: false,
+                loudness: None,
+                range: profile.range.clone(),
             })
         }
         ExportFormat::Gif => {
@@ -575,6 +591,7 @@ fn build_desktop_export_settings(profile: &ExportProfile) -> crate::export::Expo
                 fps: profile.fps,
                 resolution_base: profile.resolution_base,
                 quality: None,
+                range: profile.range.clone(),
             })
         }
         ExportFormat::Mov => {
@@ -582,8 +599,37 @@ fn build_desktop_export_settings(profile: &ExportProfile) -> crate::export::Expo
                 fps: profile.fps,
                 resolution_base: profile.resolution_base,
                 cursor_only: false,
+                range: profile.range.clone(),
+            })
+        }
+        ExportFormat::Webm | ExportFormat::WebmAv1 => {
+            crate::export::ExportSettings::Webm(cap_export::webm::WebmExportSettings {
+                fps: profile.fps,
+                resolution_base: profile.resolution_base,
+                codec: if profile.format == ExportFormat::WebmAv1 {
+                    cap_export::webm::WebmCodec::Av1
+                } else {
+                    cap_export::webm::WebmCodec::Vp9
+                },
+                compression,
+                force_ffmpeg_decoder: false,
+                loudness: None,
+                range: profile.range.clone(),
             })
         }
+        ExportFormat::Webp | ExportFormat::Apng => {
+            let settings = cap_export::animated::AnimatedImageExportSettings {
+                fps: profile.fps,
+                resolution_base: profile.resolution_base,
+                quality: None,
+                range: profile.range.clone(),
+            };
+            if profile.format == ExportFormat::Webp {
+                crate::export::ExportSettings::Webp(settings)
+            } else {
+                crate::export::ExportSettings::Apng(settings)
+            }
+        }
     }
 }
 
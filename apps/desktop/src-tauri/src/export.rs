@@ -1,7 +1,7 @@
 use crate::editor_window::{OptionalWindowEditorInstance, WindowEditorInstance};
 use crate::{FramesRendered, get_video_metadata};
 use cap_export::{ExporterBase, make_cursor_only_project};
-use cap_project::{RecordingMeta, TimelineFrameMapping, XY};
+use cap_project::{ExportRange, RecordingMeta, TimelineFrameMapping, XY};
 use cap_rendering::{
     FrameRenderer, ProjectRecordingsMeta, ProjectUniforms, RenderSegment, RenderVideoConstants,
     RendererLayers, TransitionRenderInput, ZoomTransformTimeline,
@@ -869,12 +869,15 @@ async fn wait_for_export_preview_idle_or_cancel(
     Ok(())
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
 #[serde(tag = "format")]
 pub enum ExportSettings {
     Mp4(cap_export::mp4::Mp4ExportSettings),
     Gif(cap_export::gif::GifExportSettings),
     Mov(cap_export::mov::MovExportSettings),
+    Webm(cap_export::webm::WebmExportSettings),
+    Webp(cap_export::animated::AnimatedImageExportSettings),
+    Apng(cap_export::animated::AnimatedImageExportSettings),
 }
 
 impl ExportSettings {
@@ -883,13 +886,31 @@ impl ExportSettings {
             ExportSettings::Mp4(settings) => settings.fps,
             ExportSettings::Gif(settings) => settings.fps,
             ExportSettings::Mov(settings) => settings.fps,
+            ExportSettings::Webm(settings) => settings.fps,
+            ExportSettings::Webp(settings) | ExportSettings::Apng(settings) => settings.fps,
         }
     }
 
     fn force_ffmpeg_decoder(&self) -> bool {
         match self {
             ExportSettings::Mp4(settings) => settings.force_ffmpeg_decoder,
-            ExportSettings::Gif(_) | ExportSettings::Mov(_) => false,
+            ExportSettings::Webm(settings) => settings.force_ffmpeg_decoder,
+            ExportSettings::Gif(_)
+            | ExportSettings::Mov(_)
+            | ExportSettings::Webp(_)
+            | ExportSettings::Apng(_) => false,
+        }
+    }
+
+    fn range(&self) -> Option<&ExportRange> {
+        match self {
+            ExportSettings::Mp4(settings) => settings.range.as_ref(),
+            ExportSettings::Gif(settings) => settings.range.as_ref(),
+            ExportSettings::Mov(settings) => settings.range.as_ref(),
+            ExportSettings::Webm(settings) => settings.range.as_ref(),
+            ExportSettings::Webp(settings) | ExportSettings::Apng(settings) => {
+                settings.range.as_ref()
+            }
         }
     }
 
@@ -923,8 +944,9 @@ async fn do_export(
         return Err("Export cancelled".to_string());
     }
 
-    let mut exporter_builder =
-        ExporterBase::builder(project_path.to_path_buf()).with_force_ffmpeg_decoder(force_ffmpeg);
+    let mut exporter_builder = ExporterBase::builder(project_path.to_path_buf())
+        .with_force_ffmpeg_decoder(force_ffmpeg)
+        .with_range(settings.range().cloned());
 
     if settings.cursor_only() {
         let meta = RecordingMeta::load_for_project(project_path).map_err(|e| e.to_string())?;
@@ -943,7 +965,7 @@ async fn do_export(
         return Err("Export cancelled".to_string());
     }
 
-    match settings {
+    match settings.clone() {
         ExportSettings::Mp4(mp4_settings) => {
             let progress = progress.clone();
             let cancel_token = cancel_token.clone();
@@ -992,6 +1014,43 @@ async fn do_export(
                 })
                 .await
         }
+        ExportSettings::Webm(webm_settings) => {
+            let progress = progress.clone();
+            let cancel_token = cancel_token.clone();
+            webm_settings
+                .export(exporter_base, move |frame_index| {
+                    if cancel_token.is_cancelled() {
+                        return false;
+                    }
+
+                    progress.send(FramesRendered {
+                        rendered_count: (frame_index + 1).min(total_frames),
+                        total_frames,
+                    })
+                })
+                .await
+        }
+        ExportSettings::Webp(animated_settings) | ExportSettings::Apng(animated_settings) => {
+            let format = if matches!(settings, ExportSettings::Webp(_)) {
+                cap_export::animated::AnimatedImageFormat::WebP
+            } else {
+                cap_export::animated::AnimatedImageFormat::Apng
+            };
+            let progress = progress.clone();
+            let cancel_token = cancel_token.clone();
+            animated_settings
+                .export(format, exporter_base, move |frame_index| {
+                    if cancel_token.is_cancelled() {
+                        return false;
+                    }
+
+                    progress.send(FramesRendered {
+                        rendered_count: (frame_index + 1).min(total_frames),
+                        total_frames,
+                    })
+                })
+                .await
+        }
     }
 }
 
@@ -1326,7 +1385,12 @@ pub async fn get_export_estimates(
     let metadata = get_video_metadata(path.clone()).await?;
 
     let meta = RecordingMeta::load_for_project(&path).map_err(|e| e.to_string())?;
-    let project_config = meta.project_config();
+    let mut project_config = meta.project_config();
+    if let Some(range) = settings.range()
+        && project_config.timeline.is_some()
+    {
+        range.apply(&mut project_config)?;
+    }
     let duration_seconds = if let Some(timeline) = &project_config.timeline {
         timeline.duration()
     } else {
@@ -1337,6 +1401,8 @@ pub async fn get_export_estimates(
         ExportSettings::Mp4(s) => (s.resolution_base, s.fps),
         ExportSettings::Gif(s) => (s.resolution_base, s.fps),
         ExportSettings::Mov(s) => (s.resolution_base, s.fps),
+        ExportSettings::Webm(s) => (s.resolution_base, s.fps),
+        ExportSettings::Webp(s) | ExportSettings::Apng(s) => (s.resolution_base, s.fps),
     };
 
     let (width, height) = (resolution.x, resolution.y);
@@ -1377,6 +1443,47 @@ pub async fn get_export_estimates(
 
             (size_mb, time_estimate)
         }
+        ExportSettings::Webm(webm_settings) => {
+            let bits_per_pixel = webm_settings.compression.bits_per_pixel() as f64;
+            let effective_fps = ((fps_f64 - 30.0).max(0.0) * 0.6) + fps_f64.min(30.0);
+            let video_bitrate = total_pixels * bits_per_pixel * effective_fps;
+            let audio_bitrate = 128_000.0;
+            let total_bitrate = video_bitrate + audio_bitrate;
+            // Constant-quality VP9/AV1 lands well under the bitrate ceiling.
+            let encoder_efficiency = 0.35;
+            let size_mb =
+                (total_bitrate * encoder_efficiency * duration_seconds) / (8.0 * 1024.0 * 1024.0);
+
+            // Software VP9/AV1 encoding, not rendering, bounds the export speed.
+            let effective_encode_fps = match (width, height) {
+                (w, _) if w >= 3840 => 12.0,
+                (w, _) if w >= 1920 => 45.0,
+                _ => 90.0,
+            };
+            let time_estimate = total_frames / effective_encode_fps;
+
+            (size_mb, time_estimate)
+        }
+        ExportSettings::Webp(_) | ExportSettings::Apng(_) => {
+            // Lossy WebP lands around a third of a GIF; lossless APNG around
+            // twice, since it keeps every colour.
+            let efficiency = if matches!(settings, ExportSettings::Webp(_)) {
+                0.025
+            } else {
+                0.15
+            };
+            let bytes_per_frame = total_pixels * 0.5;
+            let size_mb = (bytes_per_frame * efficiency * total_frames) / (1024.0 * 1024.0);
+
+            let frames_per_sec = match (width, height) {
+                (w, h) if w <= 1280 && h <= 720 => 20.0,
+                (w, h) if w <= 1920 && h <= 1080 => 10.0,
+                _ => 4.0,
+            };
+            let time_estimate = total_frames / frames_per_sec;
+
+            (size_mb, time_estimate)
+        }
         ExportSettings::Mov(_) => {
             let size_mb = estimate_cursor_only_size_mb(total_pixels, total_frames);
             let effective_render_fps = match (width, height) {
@@ -1520,10 +1627,10 @@ async fn generate_export_preview_inner(
         match timeline.get_frame_mapping(frame_time) {
             Some(TimelineFrameMapping::Transition {
                 outgoing,
-                kind,
+                style,
                 progress,
                 ..
-            }) => Some((outgoing, kind, progress)),
+            }) => Some((outgoing, style, progress)),
             _ => None,
         }
     });
@@ -1546,7 +1653,7 @@ async fn generate_export_preview_inner(
             segment_time as f32,
             !project_config.camera.hide,
             render_segment.render_display,
-            clip_config.map(|v| v.offsets).unwrap_or_default(),
+            clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
         )
         .await
         .ok_or_else(|| "Failed to decode frame".to_string())?;
@@ -1586,13 +1693,13 @@ async fn generate_export_preview_inner(
         render_constants.is_software_adapter,
     );
 
-    let frame = if let Some((outgoing, kind, progress)) = transition_mapping {
+    let frame = if let Some((outgoing, style, progress)) = transition_mapping {
         let outgoing_segment = &render_segments[outgoing.segment.recording_clip as usize];
         let outgoing_offsets = project_config
             .clips
             .iter()
             .find(|clip| clip.index == outgoing.segment.recording_clip)
-            .map(|clip| clip.offsets)
+            .map(|clip| clip.offsets.clone())
             .unwrap_or_default();
         let outgoing_frames = outgoing_segment
             .decoders
@@ -1637,7 +1744,7 @@ async fn generate_export_preview_inner(
                     cursor: &render_segment.cursor,
                     render_display: render_segment.render_display,
                 },
-                kind,
+                style,
                 progress as f32,
                 &mut layers,
             )
@@ -1728,11 +1835,14 @@ mod tests {
             custom_bpp: None,
             force_ffmpeg_decoder: true,
             optimize_filesize: false,
+            loudness: None,
+            range: None,
         });
         let gif_settings = ExportSettings::Gif(cap_export::gif::GifExportSettings {
             fps: 15,
             resolution_base: XY { x: 1280, y: 720 },
             quality: None,
+            range: None,
         });
 
         assert!(mp4_settings.force_ffmpeg_decoder());
@@ -1761,6 +1871,7 @@ mod tests {
             fps: 15,
             resolution_base: XY { x: 1280, y: 720 },
             quality: None,
+            range: None,
         });
 
         assert!(!should_force_ffmpeg_export(dir.path(), &gif_settings));
@@ -1774,6 +1885,7 @@ mod tests {
             fps: 15,
             resolution_base: XY { x: 1280, y: 720 },
             quality: None,
+            range: None,
         });
 
         assert!(!should_force_ffmpeg_export(dir.path(), &gif_settings));
@@ -1842,10 +1954,10 @@ async fn generate_export_preview_fast_inner(
         match timeline.get_frame_mapping(frame_time) {
             Some(TimelineFrameMapping::Transition {
                 outgoing,
-                kind,
+                style,
                 progress,
                 ..
-            }) => Some((outgoing, kind, progress)),
+            }) => Some((outgoing, style, progress)),
             _ => None,
         }
     });
@@ -1868,7 +1980,7 @@ async fn generate_export_preview_fast_inner(
             segment_time as f32,
             !project_config.camera.hide,
             !settings.cursor_only,
-            clip_config.map(|v| v.offsets).unwrap_or_default(),
+            clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
         )
         .await;
     let segment_frames = segment_frames.ok_or_else(|| "Failed to decode frame".to_string())?;
@@ -1908,13 +2020,13 @@ async fn generate_export_preview_fast_inner(
         editor.render_constants.is_software_adapter,
     );
 
-    let frame = if let Some((outgoing, kind, progress)) = transition_mapping {
+    let frame = if let Some((outgoing, style, progress)) = transition_mapping {
         let outgoing_media = &editor.segment_medias[outgoing.segment.recording_clip as usize];
         let outgoing_offsets = project_config
             .clips
             .iter()
             .find(|clip| clip.index == outgoing.segment.recording_clip)
-            .map(|clip| clip.offsets)
+            .map(|clip| clip.offsets.clone())
             .unwrap_or_default();
         let outgoing_frames = outgoing_media
             .decoders
@@ -1959,7 +2071,7 @@ async fn generate_export_preview_fast_inner(
                     cursor: &segment_media.cursor,
                     render_display: !settings.cursor_only,
                 },
-                kind,
+                style,
                 progress as f32,
                 &mut layers,
             )
@@ -56,6 +56,7 @@ pub enum HotkeyAction {
     StopRecording,
     RestartRecording,
     TogglePauseRecording,
+    AddRecordingMarker,
     CycleRecordingMode,
     OpenRecordingPicker,
     OpenRecordingPickerDisplay,
@@ -159,6 +160,9 @@ async fn handle_hotkey(app: AppHandle, action: HotkeyAction) -> Result<(), Strin
         HotkeyAction::TogglePauseRecording => {
             recording::toggle_pause_recording(app.clone(), app.state()).await
         }
+        HotkeyAction::AddRecordingMarker => recording::add_recording_marker(app.state(), None)
+            .await
+            .map(|_| ()),
         HotkeyAction::CycleRecordingMode => {
             let current = RecordingSettingsStore::get(&app)
                 .ok()
@@ -4182,7 +4182,7 @@ async fn get_display_frame_for_cropping(
         .clips
         .iter()
         .find(|v| v.index == segment.recording_clip)
-        .map(|v| v.offsets)
+        .map(|v| v.offsets.clone())
         .unwrap_or(ClipOffsets::default());
     let lookup_elapsed_ms = lookup_started_at.elapsed().as_secs_f64() * 1000.0;
 
@@ -4840,6 +4840,7 @@ pub async fn run(recording_logging_handle: LoggingHandle, logs_dir: PathBuf) {
             recording::pause_recording,
             recording::resume_recording,
             recording::toggle_pause_recording,
+            recording::add_recording_marker,
             recording::set_mic_recording_muted,
             recording::restart_recording,
             recording::delete_recording,
@@ -728,6 +728,15 @@ impl InProgressRecording {
         }
     }
 
+    /// Instant recordings have no editor timeline to put chapters on, so only
+    /// studio recordings take markers.
+    pub async fn add_marker(&self, name: Option<String>) -> anyhow::Result<Option<String>> {
+        match self {
+            Self::Instant { .. } => Ok(None),
+            Self::Studio { handle, .. } => handle.add_marker(name).await.map(Some),
+        }
+    }
+
     pub async fn is_paused(&self) -> anyhow::Result<bool> {
         match self {
             Self::Instant { handle, .. } => handle.is_paused().await,
@@ -1712,6 +1721,7 @@ pub async fn start_recording(
                         segments: Default::default(),
                         cursors: Default::default(),
                         status: Some(StudioRecordingStatus::InProgress),
+                        markers: Vec::new(),
                     },
                 }))
             }
@@ -2338,7 +2348,10 @@ pub async fn start_recording(
                         cap_recording::PipelineHealthEvent::CaptureTargetLost { target } => {
                             Some(format!("Capture target lost: {target}"))
                         }
-                        cap_recording::PipelineHealthEvent::SourceRestarted => None,
+                        cap_recording::PipelineHealthEvent::SourceRestarted
+                        | cap_recording::PipelineHealthEvent::FramesDropped { .. }
+                        | cap_recording::PipelineHealthEvent::StreamConnected { .. }
+                        | cap_recording::PipelineHealthEvent::StreamDisconnected { .. } => None,
                     };
 
                     if let Some(reason) = reason {
@@ -2390,6 +2403,22 @@ pub async fn resume_recording(app: AppHandle, state: MutableState<'_, App>) -> R
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+#[instrument(skip(state))]
+pub async fn add_recording_marker(
+    state: MutableState<'_, App>,
+    name: Option<String>,
+) -> Result<Option<String>, String> {
+    let state = state.read().await;
+
+    let Some(recording) = state.current_recording() else {
+        return Err("No recording in progress".to_string());
+    };
+
+    recording.add_marker(name).await.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[specta::specta]
 #[instrument(skip(state))]
@@ -3860,6 +3889,7 @@ fn project_config_from_recording(
         caption_segments: Vec::new(),
         keyboard_segments: Vec::new(),
         audio_segments: Vec::new(),
+        volume_keyframes: Default::default(),
     });
 
     config
@@ -133,13 +133,16 @@ impl RecordingHealthAccumulator {
                     None
                 }
             }
-            PipelineHealthEvent::DiskSpaceLow { .. }
+            PipelineHealthEvent::FramesDropped { .. }
+            | PipelineHealthEvent::DiskSpaceLow { .. }
             | PipelineHealthEvent::DiskSpaceExhausted { .. }
             | PipelineHealthEvent::DeviceLost { .. }
             | PipelineHealthEvent::EncoderRebuilt { .. }
             | PipelineHealthEvent::SourceAudioReset { .. }
             | PipelineHealthEvent::RecoveryFragmentCorrupt { .. }
-            | PipelineHealthEvent::CaptureTargetLost { .. } => None,
+            | PipelineHealthEvent::CaptureTargetLost { .. }
+            | PipelineHealthEvent::StreamConnected { .. }
+            | PipelineHealthEvent::StreamDisconnected { .. } => None,
         }
     }
 
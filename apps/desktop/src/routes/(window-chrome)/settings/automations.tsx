@@ -1379,6 +1379,10 @@ function ExportParams(props: {
 							{ value: "mp4", label: "MP4" },
 							{ value: "gif", label: "GIF" },
 							{ value: "mov", label: "MOV" },
+							{ value: "webm", label: "WebM (VP9)" },
+							{ value: "webmAv1", label: "WebM (AV1)" },
+							{ value: "webp", label: "Animated WebP" },
+							{ value: "apng", label: "Animated PNG" },
 						]}
 						onChange={(v) =>
 							updateProfile((p) => {
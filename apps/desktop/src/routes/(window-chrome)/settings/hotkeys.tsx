@@ -28,6 +28,7 @@ const ACTION_TEXT = {
 	restartRecording: "Restart recording",
 	stopRecording: "Stop recording",
 	togglePauseRecording: "Pause/resume recording",
+	addRecordingMarker: "Add chapter marker",
 	cycleRecordingMode: "Cycle recording mode",
 	openRecordingPicker: "Open recording picker",
 	openRecordingPickerDisplay: "Record display",
@@ -91,6 +92,7 @@ function Inner(props: { initialStore: HotkeysStore | null }) {
 			"stopRecording",
 			"restartRecording",
 			"togglePauseRecording",
+			"addRecordingMarker",
 			"cycleRecordingMode",
 			"openRecordingPickerDisplay",
 			"openRecordingPickerWindow",
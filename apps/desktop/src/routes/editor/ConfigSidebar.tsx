@@ -66,6 +66,7 @@ import {
 	type CursorType,
 	commands,
 	type KeyboardTrackSegment,
+	type MicTrackConfiguration,
 	type SceneMode,
 	type SceneSegment,
 	type SplitLayout,
@@ -80,6 +81,7 @@ import IconLucideEyeOff from "~icons/lucide/eye-off";
 import IconLucideGrid from "~icons/lucide/grid";
 import IconLucideImageOff from "~icons/lucide/image-off";
 import IconLucideKeyboard from "~icons/lucide/keyboard";
+import IconLucideMic from "~icons/lucide/mic";
 import IconLucideMonitor from "~icons/lucide/monitor";
 import IconLucideMoon from "~icons/lucide/moon";
 import IconLucideMusic from "~icons/lucide/music";
@@ -95,6 +97,8 @@ import { AudioLibraryPanel } from "./AudioLibrary";
 import {
 	AUDIO_TRACK_BG_CLASS,
 	type AudioTrackSegment,
+	DEFAULT_MUSIC_DUCKING,
+	MAX_DUCK_DEPTH_DB,
 	MAX_VOLUME_DB,
 	MIN_VOLUME_DB,
 } from "./audio";
@@ -684,19 +688,30 @@ export function ConfigSidebar() {
                 />
               </Subfield> */}
 
-						{/* <ComingSoonTooltip>
-                <Subfield name="Improve Mic Quality">
-                  <Toggle disabled />
-                </Subfield>
-              </ComingSoonTooltip> */}
+						{meta().hasMicrophone && (
+							<Subfield name="Improve Mic Quality">
+								<Toggle
+									disabled={project.audio.mute}
+									checked={project.audio.improve}
+									onChange={(v) => setProject("audio", "improve", v)}
+								/>
+							</Subfield>
+						)}
 					</Field>
 					{meta().hasMicrophone && (
 						<Field
 							name="Microphone Volume"
 							icon={<IconCapMicrophone class="size-4" />}
+							value={
+								<Toggle
+									disabled={project.audio.mute}
+									checked={!project.audio.micMute}
+									onChange={(v) => setProject("audio", "micMute", !v)}
+								/>
+							}
 						>
 							<Slider
-								disabled={project.audio.mute}
+								disabled={project.audio.mute || project.audio.micMute}
 								value={[project.audio.micVolumeDb ?? 0]}
 								onChange={(v) => setProject("audio", "micVolumeDb", v[0])}
 								minValue={-30}
@@ -708,6 +723,54 @@ export function ConfigSidebar() {
 							/>
 						</Field>
 					)}
+					<Index each={meta().additionalMicrophones}>
+						{(recorded, index) => {
+							const track = () => project.audio.additionalMics?.[index];
+							const setTrack = (update: Partial<MicTrackConfiguration>) =>
+								setProject("audio", "additionalMics", (mics = []) =>
+									Array.from(
+										{ length: Math.max(mics.length, index + 1) },
+										(_, i) => ({
+											mute: false,
+											volumeDb: 0,
+											stereoMode: "stereo" as const,
+											...mics[i],
+											...(i === index ? update : {}),
+										}),
+									),
+								);
+
+							return (
+								<Show when={recorded()}>
+									<Field
+										name={`Microphone ${index + 2} Volume`}
+										icon={<IconCapMicrophone class="size-4" />}
+										value={
+											<Toggle
+												disabled={project.audio.mute}
+												checked={!track()?.mute}
+												onChange={(v) => setTrack({ mute: !v })}
+											/>
+										}
+									>
+										<Slider
+											disabled={project.audio.mute || track()?.mute}
+											value={[track()?.volumeDb ?? 0]}
+											onChange={(v) => setTrack({ volumeDb: v[0] })}
+											minValue={-30}
+											maxValue={10}
+											step={0.1}
+											formatTooltip={(v) =>
+												v <= -30
+													? "Muted"
+													: `${v > 0 ? "+" : ""}${v.toFixed(1)} dB`
+											}
+										/>
+									</Field>
+								</Show>
+							);
+						}}
+					</Index>
 					{meta().hasSystemAudio && (
 						<Field
 							name="System Audio Volume"
@@ -3570,6 +3633,49 @@ function AudioSegmentConfig(props: {
 					formatTooltip="s"
 				/>
 			</Field>
+			<Field
+				name="Duck Under Voice"
+				icon={<IconLucideMic class="size-4" />}
+				value={
+					<Toggle
+						checked={!!props.segment.ducking}
+						onChange={(value) =>
+							updateSegment((segment) => {
+								segment.ducking = value ? { ...DEFAULT_MUSIC_DUCKING } : null;
+							})
+						}
+					/>
+				}
+			/>
+			<Show when={props.segment.ducking}>
+				{(ducking) => (
+					<Field name="Duck Depth" icon={<IconLucideVolume2 class="size-4" />}>
+						<Slider
+							value={[
+								clampNumber(
+									ducking().depthDb ?? DEFAULT_MUSIC_DUCKING.depthDb,
+									0,
+									MAX_DUCK_DEPTH_DB,
+								),
+							]}
+							onChange={([value]) =>
+								updateSegment((segment) => {
+									if (!segment.ducking) return;
+									segment.ducking.depthDb = clampNumber(
+										value,
+										0,
+										MAX_DUCK_DEPTH_DB,
+									);
+								})
+							}
+							minValue={0}
+							maxValue={MAX_DUCK_DEPTH_DB}
+							step={1}
+							formatTooltip="dB"
+						/>
+					</Field>
+				)}
+			</Show>
 		</div>
 	);
 }
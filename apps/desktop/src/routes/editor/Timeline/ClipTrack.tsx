@@ -19,6 +19,9 @@ import { produce } from "solid-js/store";
 
 import type { TimelineSegment } from "~/utils/tauri";
 import {
+	type ClipTransitionDirection,
+	type ClipTransitionEasing,
+	type ClipTransitionKind,
 	clampTransitionDuration,
 	clipTimelineDuration,
 	clipTimelineOffsets,
@@ -27,6 +30,7 @@ import {
 	getClipTransition,
 	MIN_CLIP_TRANSITION_DURATION,
 	maxTransitionDuration,
+	transitionUsesDirection,
 } from "../clip-transitions";
 import { useEditorContext } from "../context";
 import { useSegmentContext, useTimelineContext } from "./context";
@@ -41,6 +45,36 @@ import {
 	useSetPreviewTime,
 } from "./Track";
 
+const CLIP_TRANSITION_TYPES: [ClipTransitionKind, string][] = [
+	["cross-fade", "Crossfade"],
+	["fade-through-black", "Fade"],
+	["wipe", "Wipe"],
+	["slide", "Slide"],
+	["iris", "Iris"],
+	["zoom-through", "Zoom"],
+	["blur-dissolve", "Blur"],
+];
+
+const CLIP_TRANSITION_EASINGS: [ClipTransitionEasing, string][] = [
+	["linear", "Linear"],
+	["ease-in", "In"],
+	["ease-out", "Out"],
+	["ease-in-out", "In-out"],
+];
+
+const CLIP_TRANSITION_DIRECTIONS: [ClipTransitionDirection, string][] = [
+	["left", "←"],
+	["right", "→"],
+	["up", "↑"],
+	["down", "↓"],
+];
+
+function clipTransitionLabel(type: ClipTransitionKind) {
+	return (
+		CLIP_TRANSITION_TYPES.find(([value]) => value === type)?.[1] ?? "Crossfade"
+	);
+}
+
 const CANVAS_HEIGHT = 52;
 const WAVEFORM_MIN_DB = -60;
 const WAVEFORM_SAMPLE_STEP = 0.1;
@@ -375,6 +409,7 @@ export function ClipTrack(
 		if (drag?.index !== index) return transition;
 		if (drag.duration === 0) return null;
 		return {
+			...transition,
 			segmentIndex: index,
 			type: transition?.type ?? ("cross-fade" as const),
 			duration: drag.duration,
@@ -700,6 +735,7 @@ export function ClipTrack(
 															index,
 															nextDuration > 0
 																? {
+																		...initialTransition,
 																		type:
 																			initialTransition?.type ?? "cross-fade",
 																		duration: nextDuration,
@@ -792,7 +828,7 @@ export function ClipTrack(
 													"background-image":
 														"linear-gradient(135deg, transparent 42%, rgb(96 165 250 / 0.7) 43%, rgb(96 165 250 / 0.7) 57%, transparent 58%)",
 												}}
-												title={`${transition().type === "cross-fade" ? "Crossfade" : "Fade through black"} · ${transition().duration.toFixed(2)}s`}
+												title={`${clipTransitionLabel(transition().type)} · ${transition().duration.toFixed(2)}s`}
 												onMouseDown={(event) => event.stopPropagation()}
 											>
 												<span class="sr-only">Edit clip transition</span>
@@ -810,13 +846,8 @@ export function ClipTrack(
 															{transition().duration.toFixed(2)}s
 														</span>
 													</div>
-													<div class="grid grid-cols-2 gap-1 rounded-lg bg-gray-2 p-1">
-														{(
-															[
-																["cross-fade", "Crossfade"],
-																["fade-through-black", "Fade"],
-															] as const
-														).map(([type, label]) => (
+													<div class="grid grid-cols-3 gap-1 rounded-lg bg-gray-2 p-1">
+														{CLIP_TRANSITION_TYPES.map(([type, label]) => (
 															<button
 																type="button"
 																aria-pressed={transition().type === type}
@@ -828,8 +859,8 @@ export function ClipTrack(
 																)}
 																onClick={() =>
 																	projectActions.setClipTransition(i(), {
+																		...transition(),
 																		type,
-																		duration: transition().duration,
 																	})
 																}
 															>
@@ -837,6 +868,67 @@ export function ClipTrack(
 															</button>
 														))}
 													</div>
+													<div
+														class="grid grid-cols-4 gap-1 rounded-lg bg-gray-2 p-1"
+														aria-label="Transition easing"
+													>
+														{CLIP_TRANSITION_EASINGS.map(([easing, label]) => (
+															<button
+																type="button"
+																aria-pressed={
+																	(transition().easing ?? "linear") === easing
+																}
+																class={cx(
+																	"rounded-md px-2 py-1.5 text-xs transition-colors",
+																	(transition().easing ?? "linear") === easing
+																		? "bg-gray-4 text-gray-12"
+																		: "text-gray-10 hover:text-gray-12",
+																)}
+																onClick={() =>
+																	projectActions.setClipTransition(i(), {
+																		...transition(),
+																		easing,
+																	})
+																}
+															>
+																{label}
+															</button>
+														))}
+													</div>
+													<Show when={transitionUsesDirection(transition().type)}>
+														<div
+															class="grid grid-cols-4 gap-1 rounded-lg bg-gray-2 p-1"
+															aria-label="Transition direction"
+														>
+															{CLIP_TRANSITION_DIRECTIONS.map(
+																([direction, label]) => (
+																	<button
+																		type="button"
+																		aria-label={`Move ${direction}`}
+																		aria-pressed={
+																			(transition().direction ?? "left") ===
+																			direction
+																		}
+																		class={cx(
+																			"rounded-md px-2 py-1.5 text-xs transition-colors",
+																			(transition().direction ?? "left") ===
+																				direction
+																				? "bg-gray-4 text-gray-12"
+																				: "text-gray-10 hover:text-gray-12",
+																		)}
+																		onClick={() =>
+																			projectActions.setClipTransition(i(), {
+																				...transition(),
+																				direction,
+																			})
+																		}
+																	>
+																		{label}
+																	</button>
+																),
+															)}
+														</div>
+													</Show>
 													<input
 														type="range"
 														aria-label="Transition duration"
@@ -849,7 +941,7 @@ export function ClipTrack(
 														value={transition().duration}
 														onChange={(event) =>
 															projectActions.setClipTransition(i(), {
-																type: transition().type,
+																...transition(),
 																duration: event.currentTarget.valueAsNumber,
 															})
 														}
@@ -1,3 +1,5 @@
+import type { MusicDucking } from "~/utils/tauri";
+
 export type AudioTrackSegment = {
 	start: number;
 	end: number;
@@ -10,6 +12,7 @@ export type AudioTrackSegment = {
 	fadeIn: number;
 	fadeOut: number;
 	duration: number | null;
+	ducking?: MusicDucking | null;
 };
 
 export const AUDIO_IMPORT_EXTENSIONS = [
@@ -24,6 +27,14 @@ export const AUDIO_IMPORT_EXTENSIONS = [
 export const MIN_AUDIO_SEGMENT_DURATION = 0.5;
 export const MIN_VOLUME_DB = -30;
 export const MAX_VOLUME_DB = 12;
+export const MAX_DUCK_DEPTH_DB = 30;
+
+export const DEFAULT_MUSIC_DUCKING = {
+	thresholdDb: -40,
+	depthDb: 12,
+	attack: 0.15,
+	release: 0.6,
+} satisfies MusicDucking;
 
 export const AUDIO_TRACK_BG_CLASS = "bg-[var(--track-audio)]";
 
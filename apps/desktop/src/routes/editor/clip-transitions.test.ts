@@ -15,6 +15,7 @@ import {
 	transitionsAfterClipDelete,
 	transitionsAfterClipMove,
 	transitionsAfterClipSplit,
+	transitionUsesDirection,
 } from "./clip-transitions";
 
 const segment = (duration: number) => ({
@@ -200,6 +201,22 @@ describe("clip transitions", () => {
 		]);
 	});
 
+	it("keeps easing and direction when clamping a transition", () => {
+		const segments = [segment(1), segment(4)];
+		const wipe: ClipTransition = {
+			...transition(1, 2, "wipe"),
+			easing: "ease-in-out",
+			direction: "up",
+		};
+
+		expect(getClipTransition(segments, [wipe], 1)).toEqual({
+			...wipe,
+			duration: 0.5,
+		});
+		expect(transitionUsesDirection("wipe")).toBe(true);
+		expect(transitionUsesDirection("iris")).toBe(false);
+	});
+
 	it("preserves only clip adjacencies that survive a reorder", () => {
 		const transitions = [transition(1, 0.5), transition(3, 0.75)];
 
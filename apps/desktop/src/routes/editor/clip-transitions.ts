@@ -3,16 +3,37 @@ import type { TimelineSegment } from "~/utils/tauri";
 export const DEFAULT_CLIP_TRANSITION_DURATION = 0.5;
 export const MIN_CLIP_TRANSITION_DURATION = 0.05;
 
-export type ClipTransitionKind = "cross-fade" | "fade-through-black";
+export type ClipTransitionKind =
+	| "cross-fade"
+	| "fade-through-black"
+	| "wipe"
+	| "slide"
+	| "iris"
+	| "zoom-through"
+	| "blur-dissolve";
+
+export type ClipTransitionEasing =
+	| "linear"
+	| "ease-in"
+	| "ease-out"
+	| "ease-in-out";
+
+export type ClipTransitionDirection = "left" | "right" | "up" | "down";
 
 export type ClipTransition = {
 	segmentIndex: number;
 	type: ClipTransitionKind;
 	duration: number;
+	easing?: ClipTransitionEasing;
+	direction?: ClipTransitionDirection;
 };
 
 export type ClipTransitionInput = Omit<ClipTransition, "segmentIndex">;
 
+export function transitionUsesDirection(type: ClipTransitionKind) {
+	return type === "wipe" || type === "slide";
+}
+
 export function clipDuration(segment: TimelineSegment) {
 	return Math.max(0, (segment.end - segment.start) / segment.timescale);
 }
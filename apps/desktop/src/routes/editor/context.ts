@@ -1538,6 +1538,11 @@ function transformMeta({ pretty_name, ...rawMeta }: RecordingMeta) {
 			if (meta.type === "single") return !!meta.audio;
 			return !!meta.segments[0].mic;
 		})(),
+		// One entry per additional mic slot; false where that mic failed to record.
+		additionalMicrophones: (() => {
+			if (meta.type === "single") return [];
+			return (meta.segments[0].additional_mics ?? []).map((mic) => !!mic);
+		})(),
 		hasRecordedCursorData: (() => {
 			if (meta.type === "single") return !!meta.cursor;
 			return meta.segments.some((s) => !!s.cursor);
@@ -178,6 +178,7 @@ export function defaultActionForType(type: ActionType): Action {
 					resolutionBase: { x: 1920, y: 1080 },
 					compression: "web",
 					presetName: null,
+					range: null,
 				},
 				destination: "projectFolder",
 			};
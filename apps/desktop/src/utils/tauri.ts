@@ -47,6 +47,9 @@ async resumeRecording() : Promise<null> {
 async togglePauseRecording() : Promise<null> {
     return await TAURI_INVOKE("toggle_pause_recording");
 },
+async addRecordingMarker(name: string | null) : Promise<string | null> {
+    return await TAURI_INVOKE("add_recording_marker", { name });
+},
 async setMicRecordingMuted(muted: boolean) : Promise<null> {
     return await TAURI_INVOKE("set_mic_recording_muted", { muted });
 },
@@ -566,12 +569,48 @@ videoImportProgress: "video-import-progress"
 /** user-defined types **/
 
 export type Action = { type: "copyToClipboard"; source?: ClipboardSource } | { type: "saveToLocation"; dir: string; filenameTemplate?: string | null } | { type: "export"; profile: ExportProfile; destination?: ExportDestination } | { type: "upload"; organizationId?: string | null; copyLink?: boolean; openInBrowser?: boolean } | { type: "revealInFileManager" } | { type: "openFile" } | { type: "runCommand"; program: string; args?: string[]; cwd?: string | null; env?: { [key in string]: string }; useShell?: boolean } | { type: "webhook"; url: string; method?: string; headers?: { [key in string]: string }; bodyTemplate?: string | null } | { type: "recognizeTextToClipboard" } | { type: "notify"; titleTemplate?: string; bodyTemplate?: string } | { type: "openEditor" } | { type: "skipEditor" } | { type: "applyPreset"; name: string } | { type: "deleteLocalFiles" }
-export type Annotation = { id: string; type: AnnotationType; x: number; y: number; width: number; height: number; strokeColor: string; strokeWidth: number; fillColor: string; opacity: number; rotation: number; text: string | null; maskType?: MaskType | null; maskLevel?: number | null }
+export type AnimatedImageExportSettings = { fps: number; resolution_base: XY<number>; quality: AnimatedImageQuality | null; 
+/**
+ * Part of the timeline to export; all of it when unset.
+ */
+range?: ExportRange | null }
+export type AnimatedImageQuality = { 
+/**
+ * Encoding quality from 1-100 (default: 90), ignored by lossless APNG
+ */
+quality: number | null; 
+/**
+ * Whether to prioritize speed over file size (default: false)
+ */
+fast: boolean | null }
+export type Annotation = { id: string; type: AnnotationType; x: number; y: number; width: number; height: number; strokeColor: string; strokeWidth: number; fillColor: string; opacity: number; rotation: number; text: string | null; maskType?: MaskType | null; maskLevel?: number | null; 
+/**
+ * Timeline time, in seconds, at which the annotation appears in studio
+ * renders.
+ */
+start?: number; 
+/**
+ * Timeline time at which it disappears; `None` keeps it on screen until
+ * the end.
+ */
+end?: number | null; 
+/**
+ * Seconds spent fading in after `start` and out before `end`.
+ */
+fadeDuration?: number }
 export type AnnotationType = "arrow" | "circle" | "rectangle" | "text" | "mask"
 export type AppTheme = "system" | "light" | "dark"
 export type AspectRatio = "wide" | "vertical" | "square" | "classic" | "tall"
 export type Audio = { duration: number; sample_rate: number; channels: number; start_time: number }
-export type AudioConfiguration = { mute: boolean; improve: boolean; micVolumeDb: number; micStereoMode: StereoMode; systemVolumeDb: number }
+export type AudioConfiguration = { mute: boolean; improve: boolean; 
+/**
+ * Mutes the first microphone without touching the other tracks.
+ */
+micMute: boolean; micVolumeDb: number; micStereoMode: StereoMode; systemVolumeDb: number; 
+/**
+ * Settings for each of `MultipleSegment::additional_mics`, in order.
+ */
+additionalMics?: MicTrackConfiguration[] }
 /**
  * Overlap-trim accounting captured by the recorder's audio gap tracker, persisted so the
  * editor can compensate for stale-startup audio drift from typed data instead of scraping
@@ -615,7 +654,15 @@ trimStart?: number; volumeDb?: number; fadeIn?: number; fadeOut?: number;
  * Source duration in seconds, persisted so the UI can clamp resizing
  * without re-decoding the file.
  */
-duration?: number | null }
+duration?: number | null; 
+/**
+ * Lowers this segment while the recording's voice is audible.
+ */
+ducking?: MusicDucking | null; 
+/**
+ * Volume automation, timed from `start`.
+ */
+volumeKeyframes?: VolumeKeyframe[] }
 export type AuthSecret = { api_key: string } | { token: string; expires: number }
 export type AuthStore = { secret: AuthSecret; user_id: string | null; plan: Plan | null; organizations?: Organization[]; organizations_updated_at?: number | null }
 export type AutomationActionCheck = { actionType: string; capability: string; supported: boolean }
@@ -690,10 +737,24 @@ export type ClipConfiguration = { index: number; offsets: ClipOffsets;
  * Cleared by the editor UI once the user edits an offset.
  */
 offsetsAutoCalculated?: boolean }
-export type ClipOffsets = { camera?: number; mic?: number; system_audio?: number }
+export type ClipOffsets = { camera?: number; mic?: number; system_audio?: number; 
+/**
+ * Offsets of the segment's `additional_mics`, in order.
+ */
+additional_mics?: number[] }
 export type ClipSpeedAudioMode = "mute" | "maintainPitch" | "matchSpeed"
-export type ClipTransition = { segmentIndex: number; type: ClipTransitionType; duration: number }
-export type ClipTransitionType = "cross-fade" | "fade-through-black"
+export type ClipTransition = { segmentIndex: number; type: ClipTransitionType; duration: number; easing?: ClipTransitionEasing; direction?: ClipTransitionDirection }
+/**
+ * The way a wipe's edge or a slide's clips move across the frame. Other
+ * transition types ignore it.
+ */
+export type ClipTransitionDirection = "left" | "right" | "up" | "down"
+/**
+ * How a transition's progress is paced over its duration. Video and audio
+ * follow the same curve.
+ */
+export type ClipTransitionEasing = "linear" | "ease-in" | "ease-out" | "ease-in-out"
+export type ClipTransitionType = "cross-fade" | "fade-through-black" | "wipe" | "slide" | "iris" | "zoom-through" | "blur-dissolve"
 export type ClipboardSource = "raw" | "rendered"
 export type CommercialLicense = { licenseKey: string; expiryDate: number | null; refresh: number; activatedOn: number }
 export type Condition = { type: "captureTargetIs"; target: CaptureTargetKind } | { type: "recordingModeIs"; mode: AutomationRecordingMode } | { type: "durationAtLeast"; secs: number } | { type: "durationAtMost"; secs: number } | { type: "windowTitleContains"; pattern: string } | { type: "organizationIs"; id: string }
@@ -718,11 +779,19 @@ export type EditorStateChanged = { playhead_position: number }
 export type ExportCompression = "Maximum" | "Social" | "Web" | "Potato"
 export type ExportDestination = "projectFolder" | { customPath: { dir: string } }
 export type ExportEstimates = { duration_seconds: number; estimated_time_seconds: number; estimated_size_mb: number }
-export type ExportFormat = "mp4" | "gif" | "mov"
+export type ExportFormat = "mp4" | "gif" | "mov" | "webm" | "webmAv1" | "webp" | "apng"
 export type ExportPreviewResult = { jpeg_base64: string; estimated_size_mb: number; actual_width: number; actual_height: number; frame_render_time_ms: number; total_frames: number }
 export type ExportPreviewSettings = { fps: number; resolution_base: XY<number>; compression_bpp: number; cursor_only?: boolean }
-export type ExportProfile = { format: ExportFormat; fps?: number; resolutionBase?: XY<number>; compression?: AutomationExportCompression | null; presetName?: string | null }
-export type ExportSettings = ({ format: "Mp4" } & Mp4ExportSettings) | ({ format: "Gif" } & GifExportSettings) | ({ format: "Mov" } & MovExportSettings)
+export type ExportProfile = { format: ExportFormat; fps?: number; resolutionBase?: XY<number>; compression?: AutomationExportCompression | null; presetName?: string | null; 
+/**
+ * Part of the recording's timeline to export; all of it when unset.
+ */
+range?: ExportRange | null }
+/**
+ * Which part of the timeline an export covers.
+ */
+export type ExportRange = { type: "Time"; from: number | null; to: number | null } | { type: "Segments"; segments: SegmentSelector[] }
+export type ExportSettings = ({ format: "Mp4" } & Mp4ExportSettings) | ({ format: "Gif" } & GifExportSettings) | ({ format: "Mov" } & MovExportSettings) | ({ format: "Webm" } & WebmExportSettings) | ({ format: "Webp" } & AnimatedImageExportSettings) | ({ format: "Apng" } & AnimatedImageExportSettings)
 export type FileType = "recording" | "screenshot"
 export type Flags = { captions: boolean }
 export type FrameConfiguration = { style: FrameStyle; theme: FrameTheme; 
@@ -781,7 +850,11 @@ previousRecordingsPaths?: string[];
  * update, since a new ort/wgpu/driver stack may have fixed the crash).
  */
 cameraBlurDisabledByCrash?: string | null; updateChannel?: UpdateChannel }
-export type GifExportSettings = { fps: number; resolution_base: XY<number>; quality: GifQuality | null }
+export type GifExportSettings = { fps: number; resolution_base: XY<number>; quality: GifQuality | null; 
+/**
+ * Part of the timeline to export; all of it when unset.
+ */
+range?: ExportRange | null }
 export type GifQuality = { 
 /**
  * Encoding quality from 1-100 (default: 90)
@@ -795,7 +868,7 @@ export type GlideDirection = "none" | "left" | "right" | "up" | "down"
 export type HapticPattern = "alignment" | "levelChange" | "generic"
 export type HapticPerformanceTime = "default" | "now" | "drawCompleted"
 export type Hotkey = { code: string; meta: boolean; ctrl: boolean; alt: boolean; shift: boolean }
-export type HotkeyAction = "startStudioRecording" | "startInstantRecording" | "stopRecording" | "restartRecording" | "togglePauseRecording" | "cycleRecordingMode" | "openRecordingPicker" | "openRecordingPickerDisplay" | "openRecordingPickerWindow" | "openRecordingPickerArea" | "screenshotDisplay" | "screenshotWindow" | "screenshotArea" | "other"
+export type HotkeyAction = "startStudioRecording" | "startInstantRecording" | "stopRecording" | "restartRecording" | "togglePauseRecording" | "addRecordingMarker" | "cycleRecordingMode" | "openRecordingPicker" | "openRecordingPickerDisplay" | "openRecordingPickerWindow" | "openRecordingPickerArea" | "screenshotDisplay" | "screenshotWindow" | "screenshotArea" | "other"
 export type HotkeysConfiguration = { show: boolean }
 export type HotkeysStore = { hotkeys: { [key in HotkeyAction]: Hotkey } }
 export type ImportStage = "Probing" | "Converting" | "Finalizing" | "Complete" | "Failed"
@@ -814,6 +887,7 @@ export type KeyboardTrackSegment = { id: string; start: number; end: number; dis
 export type LogicalBounds = { position: LogicalPosition; size: LogicalSize }
 export type LogicalPosition = { x: number; y: number }
 export type LogicalSize = { width: number; height: number }
+export type LoudnessTarget = { integratedLufs: number; truePeakDbtp: number }
 export type MacOSVersionInfo = { major: number; minor: number; patch: number; displayName: string; buildNumber: string; isAppleSilicon: boolean }
 export type MainWindowRecordingStartBehaviour = "close" | "minimise"
 export type MaskKeyframes = { position?: MaskVectorKeyframe[]; size?: MaskVectorKeyframe[]; intensity?: MaskScalarKeyframe[] }
@@ -823,16 +897,54 @@ export type MaskSegment = { start: number; end: number; track?: number; enabled?
 export type MaskType = "blur" | "pixelate"
 export type MaskVectorKeyframe = { time: number; x: number; y: number }
 export type MatchMode = "all" | "any"
+/**
+ * Mixing settings for one additional microphone track.
+ */
+export type MicTrackConfiguration = { mute: boolean; volumeDb: number; stereoMode: StereoMode }
 export type MicrophoneDeviceSettings = { sampleRate: number | null; channels: number | null }
 export type MicrophoneFormatInfo = { sampleRate: number; channels: number }
 export type MicrophoneInfo = { name: string; sampleRate: number; channels: number; formats: MicrophoneFormatInfo[] }
 export type ModelDownloadState = "downloading" | "completed" | "failed"
 export type ModelDownloadStatus = { state: ModelDownloadState; progress: number; message: string }
 export type ModelIDType = string
-export type MovExportSettings = { fps: number; resolution_base: XY<number>; cursor_only?: boolean }
-export type Mp4ExportSettings = { fps: number; resolution_base: XY<number>; compression: ExportCompression; custom_bpp: number | null; force_ffmpeg_decoder?: boolean; optimize_filesize?: boolean }
-export type MultipleSegment = { display: VideoMeta; camera?: VideoMeta | null; mic?: AudioMeta | null; system_audio?: AudioMeta | null; cursor?: string | null; keyboard?: string | null }
-export type MultipleSegments = { segments: MultipleSegment[]; cursors: Cursors; status?: StudioRecordingStatus | null }
+export type MovExportSettings = { fps: number; resolution_base: XY<number>; cursor_only?: boolean; 
+/**
+ * Part of the timeline to export; all of it when unset.
+ */
+range?: ExportRange | null }
+export type Mp4ExportSettings = { fps: number; resolution_base: XY<number>; compression: ExportCompression; custom_bpp: number | null; force_ffmpeg_decoder?: boolean; optimize_filesize?: boolean; loudness?: LoudnessTarget | null; 
+/**
+ * Part of the timeline to export; all of it when unset.
+ */
+range?: ExportRange | null }
+export type MultipleSegment = { display: VideoMeta; camera?: VideoMeta | null; mic?: AudioMeta | null; 
+/**
+ * Microphones recorded alongside `mic`, each into its own file, in the
+ * order they were asked for. A mic that produced no recording is `None`,
+ * so the ones after it keep their position.
+ */
+additional_mics?: (AudioMeta | null)[]; system_audio?: AudioMeta | null; cursor?: string | null; keyboard?: string | null }
+export type MultipleSegments = { segments: MultipleSegment[]; cursors: Cursors; status?: StudioRecordingStatus | null; markers?: RecordingMarker[] }
+/**
+ * How far and how fast music is turned down under speech.
+ */
+export type MusicDucking = { 
+/**
+ * Voice level in dBFS at or above which the music ducks.
+ */
+thresholdDb?: number; 
+/**
+ * How many dB the music is lowered by under speech.
+ */
+depthDb?: number; 
+/**
+ * Seconds the music takes to duck, finishing as the speech starts.
+ */
+attack?: number; 
+/**
+ * Seconds the music takes to come back up once the speech stops.
+ */
+release?: number }
 export type NewNotification = { title: string; body: string; is_error: boolean }
 export type NewScreenshotAdded = { path: string }
 export type NewStudioRecordingAdded = { path: string }
@@ -864,6 +976,7 @@ export type RecordingAction = "Started" | "InvalidAuthentication" | "UpgradeRequ
 export type RecordingDeleted = { path: string }
 export type RecordingEvent = { variant: "Countdown"; value: number } | { variant: "Started" } | { variant: "Stopped" } | { variant: "Paused" } | { variant: "Resumed" } | { variant: "Failed"; error: string } | { variant: "StartFailed"; error: string } | { variant: "InputLost"; input: RecordingInputKind } | { variant: "InputRestored"; input: RecordingInputKind } | { variant: "Degraded"; reason: string } | { variant: "Recovered" }
 export type RecordingInputKind = "microphone" | "camera"
+export type RecordingMarker = { segment: number; time: number; name: string }
 export type RecordingMeta = (StudioRecordingMeta | InstantRecordingMeta) & { platform?: Platform | null; pretty_name: string; sharing?: SharingMeta | null; upload?: UploadMeta | null }
 export type RecordingMetaWithMetadata = ((StudioRecordingMeta | InstantRecordingMeta) & { platform?: Platform | null; pretty_name: string; sharing?: SharingMeta | null; upload?: UploadMeta | null }) & { mode: RecordingMode; status: StudioRecordingStatus; clip_count: number; sort_time_millis: number }
 export type RecordingMode = "studio" | "instant" | "screenshot"
@@ -901,7 +1014,16 @@ export type ScreenshotOcrResult = { text: string; lines: ScreenshotOcrLine[]; en
 export type ScreenshotProjectExport = { imageBytes: number[]; config: ProjectConfiguration; imageWidth: number; imageHeight: number }
 export type ScreenshotProjectShareState = { config: ProjectConfiguration; sharing: ScreenshotSharingState | null }
 export type ScreenshotSharingState = { link: string; contentHash: string | null }
-export type SegmentRecordings = { display: Video; camera: Video | null; mic: Audio | null; system_audio: Audio | null }
+export type SegmentRecordings = { display: Video; camera: Video | null; mic: Audio | null; 
+/**
+ * In the recording's order, with `None` where one produced no recording.
+ */
+additional_mics: (Audio | null)[]; system_audio: Audio | null }
+/**
+ * A clip picked by its `TimelineSegment::name` or its position on the
+ * timeline.
+ */
+export type SegmentSelector = number | string
 export type SerializedEditorInstance = { framesSocketUrl: string; recordingDuration: number; savedProjectConfig: ProjectConfiguration; recordings: ProjectRecordingsMeta; path: string }
 export type SerializedScreenshotEditorInstance = { framesSocketUrl: string; path: string; config: ProjectConfiguration | null; prettyName: string; imageWidth: number; imageHeight: number }
 export type SetCaptureAreaPending = boolean
@@ -918,7 +1040,11 @@ export type StudioRecordingStatus = { status: "InProgress" } | { status: "NeedsR
 export type SystemDiagnostics = { macosVersion: MacOSVersionInfo | null; availableEncoders: string[]; screenCaptureSupported: boolean; metalSupported: boolean; gpuName: string | null }
 export type TargetUnderCursor = { display_id: DisplayId | null; window: WindowUnderCursor | null }
 export type TextSegment = { start: number; end: number; track?: number; enabled?: boolean; content?: string; center?: XY<number>; size?: XY<number>; fontFamily?: string; fontSize?: number; fontWeight?: number; italic?: boolean; color?: string; fadeDuration?: number }
-export type TimelineConfiguration = { segments: TimelineSegment[]; transitions: ClipTransition[]; zoomSegments: ZoomSegment[]; sceneSegments?: SceneSegment[]; maskSegments?: MaskSegment[]; textSegments?: TextSegment[]; captionSegments?: CaptionTrackSegment[]; keyboardSegments?: KeyboardTrackSegment[]; audioSegments?: AudioTrackSegment[] }
+export type TimelineConfiguration = { segments: TimelineSegment[]; transitions: ClipTransition[]; zoomSegments: ZoomSegment[]; sceneSegments?: SceneSegment[]; maskSegments?: MaskSegment[]; textSegments?: TextSegment[]; captionSegments?: CaptionTrackSegment[]; keyboardSegments?: KeyboardTrackSegment[]; audioSegments?: AudioTrackSegment[]; 
+/**
+ * Volume automation for the recording's mic and system audio.
+ */
+volumeKeyframes?: VolumeKeyframes }
 export type TimelineSegment = { recordingSegment?: number; timescale: number; start: number; end: number; name?: string | null; speedAudioMode?: ClipSpeedAudioMode | null }
 export type TranscriptionEngine = "Whisper" | "Parakeet"
 export type Trigger = "screenshotTaken" | "studioRecordingFinished" | "instantRecordingFinished" | "recordingStarted" | "uploadCompleted" | "videoImported" | "recordingDeleted"
@@ -936,6 +1062,37 @@ export type VideoImportProgress = { project_path: string; stage: ImportStage; pr
 export type VideoMeta = { path: string; fps?: number; start_time?: number | null; device_id?: string | null }
 export type VideoRecordingMetadata = { duration: number; size: number }
 export type VideoUploadInfo = { id: string; link: string; config: S3UploadMeta }
+/**
+ * How the volume moves from one keyframe to the next.
+ */
+export type VolumeInterpolation = 
+/**
+ * Evenly in dB, which is heard as an even fade.
+ */
+"decibel" | 
+/**
+ * Evenly in amplitude, which drops off quickly at the quiet end.
+ */
+"linear"
+export type VolumeKeyframe = { time: number; 
+/**
+ * Gain in dB added to the track's volume.
+ */
+volumeDb: number; 
+/**
+ * How the volume moves from this keyframe to the next.
+ */
+interpolation?: VolumeInterpolation }
+/**
+ * Volume keyframes for the recording's tracks, in output time.
+ */
+export type VolumeKeyframes = { mic?: VolumeKeyframe[]; systemAudio?: VolumeKeyframe[] }
+export type WebmCodec = "Vp9" | "Av1"
+export type WebmExportSettings = { fps: number; resolution_base: XY<number>; codec?: WebmCodec; compression: ExportCompression; force_ffmpeg_decoder?: boolean; loudness?: LoudnessTarget | null; 
+/**
+ * Part of the timeline to export; all of it when unset.
+ */
+range?: ExportRange | null }
 export type WindowExclusion = { bundleIdentifier?: string | null; ownerName?: string | null; windowTitle?: string | null }
 export type WindowId = string
 export type WindowPosition = { x: number; y: number; displayId?: DisplayId | null }
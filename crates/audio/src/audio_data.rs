@@ -106,7 +106,6 @@ impl AudioData {
         self.samples.len() / self.channels as usize
     }
 
-    #[cfg(test)]
     pub(crate) fn from_raw_f32(samples: Vec<f32>, channels: u16) -> Self {
         Self { samples, channels }
     }
@@ -1,14 +1,20 @@
 mod audio_data;
 mod calibration_store;
 mod latency;
+mod loudness;
 mod renderer;
+mod silence;
 mod sync_analysis;
+mod voice;
 
 pub use audio_data::*;
 pub use calibration_store::*;
 pub use latency::*;
+pub use loudness::*;
 pub use renderer::*;
+pub use silence::*;
 pub use sync_analysis::*;
+pub use voice::*;
 
 pub trait FromSampleBytes: cpal::SizedSample + std::fmt::Debug + Send + 'static {
     const BYTE_SIZE: usize;
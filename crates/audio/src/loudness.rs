@@ -0,0 +1,413 @@
+//! Loudness metering to ITU-R BS.1770 / EBU R128, and the true-peak limiter
+//! that keeps a normalised mix under its ceiling.
+//!
+//! Both work on interleaved audio at [`AudioData::SAMPLE_RATE`] and can be fed
+//! in chunks of any size, so a mix can be measured or limited as it renders.
+
+use std::{collections::VecDeque, f64::consts::PI};
+
+use crate::{AudioData, voice::Biquad};
+
+/// Stage one of the K-weighting filter: a high shelf for the head's acoustic
+/// effect. BS.1770 coefficients for 48 kHz.
+const K_SHELF: ([f64; 3], [f64; 2]) = (
+    [1.53512485958697, -2.69169618940638, 1.19839281085285],
+    [-1.69065929318241, 0.73248077421585],
+);
+/// Stage two of the K-weighting filter: the RLB high-pass.
+const K_HIGH_PASS: ([f64; 3], [f64; 2]) = ([1.0, -2.0, 1.0], [-1.99004745483398, 0.99007225036621]);
+
+/// Blocks are measured in 100 ms steps, the overlap BS.1770 asks for.
+const STEP_FRAMES: usize = AudioData::SAMPLE_RATE as usize / 10;
+/// Steps in a 400 ms gating block.
+const BLOCK_STEPS: usize = 4;
+/// Steps in a 3 s short-term window, which loudness range is measured over.
+const SHORT_TERM_STEPS: usize = 30;
+
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = -10.0;
+const RANGE_RELATIVE_GATE_LU: f64 = -20.0;
+const RANGE_LOW_PERCENTILE: f64 = 0.10;
+const RANGE_HIGH_PERCENTILE: f64 = 0.95;
+
+/// True peaks are found by upsampling four times, as BS.1770 annex 2 does.
+const OVERSAMPLING: usize = 4;
+const INTERPOLATION_TAPS: usize = 12;
+/// How many samples the interpolator runs behind its input.
+const INTERPOLATION_DELAY: usize = INTERPOLATION_TAPS / 2;
+
+/// Samples the limiter looks ahead, so it can turn the gain down smoothly
+/// before a peak instead of clipping it (2 ms).
+const LIMITER_LOOKAHEAD: usize = AudioData::SAMPLE_RATE as usize / 500;
+const LIMITER_RELEASE_SECS: f64 = 0.05;
+
+fn power_to_lufs(power: f64) -> f64 {
+    -0.691 + 10.0 * power.log10()
+}
+
+fn lufs_to_power(lufs: f64) -> f64 {
+    10f64.powf((lufs + 0.691) / 10.0)
+}
+
+/// Mean loudness of the powers that pass the absolute gate and the gate
+/// `relative_lu` below their own mean, or `None` when none pass.
+fn gated_loudness(powers: &[f64], relative_lu: f64) -> Option<(f64, f64)> {
+    let absolute = lufs_to_power(ABSOLUTE_GATE_LUFS);
+    let mean = |gate: f64| {
+        let (sum, count) = powers
+            .iter()
+            .filter(|power| **power > gate)
+            .fold((0.0, 0usize), |(sum, count), power| {
+                (sum + power, count + 1)
+            });
+        (count > 0).then(|| sum / count as f64)
+    };
+
+    let relative_gate = lufs_to_power(power_to_lufs(mean(absolute)?) + relative_lu);
+    let gate = relative_gate.max(absolute);
+    Some((power_to_lufs(mean(gate)?), gate))
+}
+
+/// Four-times upsampler for true-peak detection: a windowed-sinc
+/// interpolator, one polyphase branch per output position.
+struct TruePeakInterpolator {
+    phases: [[f32; INTERPOLATION_TAPS]; OVERSAMPLING],
+    /// The last `INTERPOLATION_TAPS` frames, newest first per channel.
+    history: Vec<VecDeque<f32>>,
+}
+
+impl TruePeakInterpolator {
+    fn new(channels: usize) -> Self {
+        let mut phases = [[0.0; INTERPOLATION_TAPS]; OVERSAMPLING];
+        for (phase, taps) in phases.iter_mut().enumerate() {
+            let fraction = phase as f64 / OVERSAMPLING as f64;
+            let half_width = INTERPOLATION_DELAY as f64 + 0.5;
+            for (tap, coefficient) in taps.iter_mut().enumerate() {
+                let t = tap as f64 - INTERPOLATION_DELAY as f64 + fraction;
+                let sinc = if t == 0.0 {
+                    1.0
+                } else {
+                    (PI * t).sin() / (PI * t)
+                };
+                let window = 0.5 * (1.0 + (PI * t / half_width).cos());
+                *coefficient = (sinc * window) as f32;
+            }
+            let sum = taps.iter().sum::<f32>();
+            taps.iter_mut().for_each(|coefficient| *coefficient /= sum);
+        }
+
+        Self {
+            phases,
+            history: vec![VecDeque::from(vec![0.0; INTERPOLATION_TAPS]); channels],
+        }
+    }
+
+    /// Pushes one frame and returns the largest absolute level, over all
+    /// channels, of the signal from `INTERPOLATION_DELAY` frames back up to
+    /// just before the frame after it.
+    fn push(&mut self, frame: &[f32]) -> f32 {
+        let mut peak = 0.0f32;
+        for (history, sample) in self.history.iter_mut().zip(frame) {
+            history.pop_back();
+            history.push_front(*sample);
+            for taps in &self.phases {
+                let value = taps
+                    .iter()
+                    .zip(history.iter())
+                    .map(|(coefficient, sample)| coefficient * sample)
+                    .sum::<f32>();
+                peak = peak.max(value.abs());
+            }
+        }
+        peak
+    }
+}
+
+/// Integrated loudness, loudness range and true peak of everything pushed
+/// into it.
+pub struct LoudnessMeter {
+    channels: usize,
+    filters: Vec<[Biquad; 2]>,
+    interpolator: TruePeakInterpolator,
+    true_peak: f32,
+    step_energy: f64,
+    step_frames: usize,
+    steps: VecDeque<f64>,
+    block_powers: Vec<f64>,
+    short_term_powers: Vec<f64>,
+}
+
+impl LoudnessMeter {
+    pub fn new(channels: u16) -> Self {
+        let channels = channels.max(1) as usize;
+        Self {
+            channels,
+            filters: (0..channels)
+                .map(|_| {
+                    [
+                        Biquad::new(K_SHELF.0, K_SHELF.1),
+                        Biquad::new(K_HIGH_PASS.0, K_HIGH_PASS.1),
+                    ]
+                })
+                .collect(),
+            interpolator: TruePeakInterpolator::new(channels),
+            true_peak: 0.0,
+            step_energy: 0.0,
+            step_frames: 0,
+            steps: VecDeque::with_capacity(SHORT_TERM_STEPS),
+            block_powers: Vec::new(),
+            short_term_powers: Vec::new(),
+        }
+    }
+
+    /// Measures interleaved `samples`. A trailing partial frame is ignored.
+    pub fn push(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(self.channels) {
+            self.true_peak = self.true_peak.max(self.interpolator.push(frame));
+
+            for (filters, sample) in self.filters.iter_mut().zip(frame) {
+                let weighted = filters
+                    .iter_mut()
+                    .fold(*sample, |sample, filter| filter.process(sample));
+                self.step_energy += (weighted as f64).powi(2);
+            }
+
+            self.step_frames += 1;
+            if self.step_frames == STEP_FRAMES {
+                self.finish_step();
+            }
+        }
+    }
+
+    fn finish_step(&mut self) {
+        if self.steps.len() == SHORT_TERM_STEPS {
+            self.steps.pop_front();
+        }
+        self.steps.push_back(self.step_energy / STEP_FRAMES as f64);
+        self.step_energy = 0.0;
+        self.step_frames = 0;
+
+        let window_power = |len: usize| self.steps.iter().rev().take(len).sum::<f64>() / len as f64;
+        if self.steps.len() >= BLOCK_STEPS {
+            self.block_powers.push(window_power(BLOCK_STEPS));
+        }
+        if self.steps.len() == SHORT_TERM_STEPS {
+            self.short_term_powers.push(window_power(SHORT_TERM_STEPS));
+        }
+    }
+
+    /// Gated integrated loudness in LUFS, or `None` if nothing was louder than
+    /// the -70 LUFS absolute gate.
+    pub fn integrated(&self) -> Option<f64> {
+        gated_loudness(&self.block_powers, RELATIVE_GATE_LU).map(|(lufs, _)| lufs)
+    }
+
+    /// Loudness range in LU (EBU Tech 3342), or `None` when less than three
+    /// seconds were loud enough to measure.
+    pub fn range(&self) -> Option<f64> {
+        let (_, gate) = gated_loudness(&self.short_term_powers, RANGE_RELATIVE_GATE_LU)?;
+        let mut gated = self
+            .short_term_powers
+            .iter()
+            .filter(|power| **power > gate)
+            .map(|power| power_to_lufs(*power))
+            .collect::<Vec<_>>();
+        gated.sort_by(f64::total_cmp);
+
+        let percentile = |p: f64| gated[((gated.len() - 1) as f64 * p).round() as usize];
+        Some(percentile(RANGE_HIGH_PERCENTILE) - percentile(RANGE_LOW_PERCENTILE))
+    }
+
+    /// Highest true peak in dBTP; negative infinity for silence.
+    pub fn true_peak(&self) -> f64 {
+        20.0 * (self.true_peak as f64).log10()
+    }
+}
+
+/// Look-ahead limiter that holds the true peak of its output at or below a
+/// ceiling, with every channel turned down together.
+///
+/// Output runs [`TruePeakLimiter::latency`] frames behind the input: the
+/// first frames out are silence, and that many more frames have to be pushed
+/// through to flush the end of a signal.
+pub struct TruePeakLimiter {
+    channels: usize,
+    ceiling: f32,
+    release: f64,
+    interpolator: TruePeakInterpolator,
+    /// Required gains over the hold window, as `(frame, gain)` with gains
+    /// increasing, so the front is the window's minimum.
+    hold: VecDeque<(u64, f32)>,
+    envelope: f64,
+    /// The smoothed envelope over the look-ahead window, and its sum.
+    smoothing: VecDeque<f64>,
+    smoothing_sum: f64,
+    delay: VecDeque<f32>,
+    frame: u64,
+}
+
+impl TruePeakLimiter {
+    pub fn new(channels: u16, ceiling_db: f64) -> Self {
+        let channels = channels.max(1) as usize;
+        let latency = LIMITER_LOOKAHEAD + INTERPOLATION_DELAY - 1;
+        Self {
+            channels,
+            ceiling: 10f64.powf(ceiling_db / 20.0) as f32,
+            release: 1.0 - (-1.0 / (LIMITER_RELEASE_SECS * AudioData::SAMPLE_RATE as f64)).exp(),
+            interpolator: TruePeakInterpolator::new(channels),
+            hold: VecDeque::with_capacity(LIMITER_LOOKAHEAD + 1),
+            envelope: 1.0,
+            smoothing: VecDeque::from(vec![1.0; LIMITER_LOOKAHEAD]),
+            smoothing_sum: LIMITER_LOOKAHEAD as f64,
+            delay: VecDeque::from(vec![0.0; latency * channels]),
+            frame: 0,
+        }
+    }
+
+    /// Frames between a sample going in and coming back out.
+    pub fn latency(&self) -> usize {
+        self.delay.len() / self.channels
+    }
+
+    /// Limits interleaved `samples` in place. A trailing partial frame is
+    /// left untouched.
+    pub fn process(&mut self, samples: &mut [f32]) {
+        for frame in samples.chunks_exact_mut(self.channels) {
+            let peak = self.interpolator.push(frame);
+            let required = if peak > self.ceiling {
+                self.ceiling / peak
+            } else {
+                1.0
+            };
+
+            // The peaks found at this frame sit between the two samples
+            // `INTERPOLATION_DELAY` frames back, so the lowest gain needed over
+            // one more than the look-ahead window covers both of them.
+            while self.hold.back().is_some_and(|(_, gain)| *gain >= required) {
+                self.hold.pop_back();
+            }
+            self.hold.push_back((self.frame, required));
+            while self
+                .hold
+                .front()
+                .is_some_and(|(frame, _)| *frame + (LIMITER_LOOKAHEAD as u64) < self.frame)
+            {
+                self.hold.pop_front();
+            }
+            self.frame += 1;
+
+            let held = self.hold.front().map_or(1.0, |(_, gain)| *gain) as f64;
+            self.envelope = if held < self.envelope {
+                held
+            } else {
+                self.envelope + (held - self.envelope) * self.release
+            };
+
+            // Averaging the held gain over the look-ahead ramps it down before
+            // a peak rather than stepping, and never rises above the gain any
+            // sample in the window needs.
+            self.smoothing_sum += self.envelope - self.smoothing.pop_front().unwrap_or(1.0);
+            self.smoothing.push_back(self.envelope);
+            let gain = (self.smoothing_sum / LIMITER_LOOKAHEAD as f64).min(1.0) as f32;
+
+            for sample in frame.iter_mut() {
+                self.delay.push_back(*sample);
+                *sample = self.delay.pop_front().unwrap_or(0.0) * gain;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(frequency: f64, amplitude: f64, phase: f64, secs: f64) -> Vec<f32> {
+        let frames = (secs * AudioData::SAMPLE_RATE as f64) as usize;
+        (0..frames)
+            .map(|frame| {
+                let t = frame as f64 / AudioData::SAMPLE_RATE as f64;
+                (amplitude * (2.0 * PI * frequency * t + phase).sin()) as f32
+            })
+            .collect()
+    }
+
+    fn stereo(mono: Vec<f32>) -> Vec<f32> {
+        mono.into_iter()
+            .flat_map(|sample| [sample, sample])
+            .collect()
+    }
+
+    #[test]
+    fn full_scale_1khz_sine_reads_minus_three_lufs() {
+        let mut meter = LoudnessMeter::new(1);
+        meter.push(&sine(1000.0, 1.0, 0.0, 5.0));
+
+        // BS.1770's reference: a 0 dBFS 1 kHz sine in one channel reads
+        // -3.01 LKFS.
+        let integrated = meter.integrated().unwrap();
+        assert!((integrated - -3.01).abs() < 0.05, "{integrated}");
+        assert!(meter.range().unwrap() < 0.1);
+        assert!(meter.true_peak().abs() < 0.05);
+    }
+
+    #[test]
+    fn quiet_parts_are_gated_out_and_silence_has_no_loudness() {
+        let mut steady = LoudnessMeter::new(2);
+        steady.push(&stereo(sine(1000.0, 0.1, 0.0, 10.0)));
+
+        let mut with_pauses = LoudnessMeter::new(2);
+        with_pauses.push(&stereo(sine(1000.0, 0.1, 0.0, 10.0)));
+        with_pauses.push(&stereo(sine(1000.0, 0.0005, 0.0, 10.0)));
+
+        let difference = with_pauses.integrated().unwrap() - steady.integrated().unwrap();
+        assert!(difference.abs() < 0.1, "{difference}");
+        assert!(steady.range().unwrap() < 0.1);
+
+        let mut silent = LoudnessMeter::new(2);
+        silent.push(&vec![0.0; 96_000]);
+        assert_eq!(silent.integrated(), None);
+        assert_eq!(silent.range(), None);
+    }
+
+    #[test]
+    fn true_peak_finds_peaks_between_samples() {
+        // A quarter-rate sine sampled 45 degrees off its peaks never has a
+        // sample above 0.707, but the waveform reaches 1.0.
+        let samples = stereo(sine(12_000.0, 1.0, PI / 4.0, 1.0));
+        let sample_peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+
+        let mut meter = LoudnessMeter::new(2);
+        meter.push(&samples);
+
+        assert!(sample_peak < 0.71);
+        assert!(meter.true_peak() > -0.5, "{}", meter.true_peak());
+    }
+
+    #[test]
+    fn limiter_holds_the_true_peak_under_the_ceiling_without_moving_the_audio() {
+        let mut samples = sine(440.0, 0.25, 0.0, 1.0);
+        samples.extend(sine(12_000.0, 2.0, PI / 4.0, 0.5));
+        samples.extend(sine(440.0, 0.25, 0.0, 1.0));
+        let mut samples = stereo(samples);
+        let input = samples.clone();
+
+        let mut limiter = TruePeakLimiter::new(2, -1.0);
+        let latency = limiter.latency();
+        samples.extend(vec![0.0; latency * 2]);
+        limiter.process(&mut samples);
+        let output = &samples[latency * 2..];
+
+        let mut meter = LoudnessMeter::new(2);
+        meter.push(output);
+        assert!(meter.true_peak() <= -0.9, "{}", meter.true_peak());
+
+        // Quiet audio well clear of the peak passes through untouched and on
+        // time.
+        for (index, (out, original)) in output.iter().zip(&input).enumerate().take(80_000) {
+            assert!((out - original).abs() < 1e-6, "sample {index}");
+        }
+        let tail = input.len() - 40_000;
+        assert!((output[tail] - input[tail]).abs() < 1e-5);
+    }
+}
@@ -11,6 +11,8 @@ pub struct AudioRendererTrack<'a> {
     pub gain: f32,
     pub stereo_mode: StereoMode,
     pub offset: isize,
+    /// Linear gain for each rendered sample, applied on top of `gain`.
+    pub gain_envelope: Option<&'a [f32]>,
 }
 
 pub fn render_audio(
@@ -55,6 +57,10 @@ pub fn render_audio(
             if gain == f32::NEG_INFINITY {
                 continue;
             }
+            let gain = match track.gain_envelope {
+                Some(envelope) => gain * envelope.get(i).copied().unwrap_or(1.0),
+                None => gain,
+            };
 
             if data.channels() == 1 {
                 if let Some(sample) = data.samples().get(source_index) {
@@ -117,6 +123,7 @@ mod tests {
             gain: 0.0,
             stereo_mode: StereoMode::Stereo,
             offset,
+            gain_envelope: None,
         }
     }
 
@@ -188,4 +195,19 @@ mod tests {
         assert!((out[4 * 2] - 0.5).abs() < 1e-6);
         assert!((out[9 * 2] - 0.5).abs() < 1e-6);
     }
+
+    #[test]
+    fn gain_envelope_scales_each_sample() {
+        let data = AudioData::from_raw_f32(vec![0.5; 8], 2);
+        let envelope = [1.0, 0.5, 0.0, 2.0];
+
+        let mut out = vec![0.0; 4 * 2];
+        let tracks = [AudioRendererTrack {
+            gain_envelope: Some(&envelope),
+            ..track(&data, 0)
+        }];
+        render_audio(&tracks, 0, 4, 0, &mut out);
+
+        assert_eq!(out, vec![0.5, 0.5, 0.25, 0.25, 0.0, 0.0, 1.0, 1.0]);
+    }
 }
@@ -0,0 +1,98 @@
+//! Level-based detection of the audible parts of a decoded track, the input to
+//! silence removal.
+
+use crate::AudioData;
+
+/// Level below which a window counts as silence when no threshold is given.
+pub const DEFAULT_SILENCE_THRESHOLD_DB: f32 = -40.0;
+
+/// Length of the windows whose RMS level is compared against the threshold.
+/// Short enough to find the gap between sentences, long enough that a single
+/// zero crossing doesn't read as silence.
+pub const LEVEL_WINDOW_SECS: f64 = 0.02;
+
+/// The mean square of `audio`'s samples, over all channels, in each
+/// consecutive [`LEVEL_WINDOW_SECS`] window. The last window may be shorter.
+pub fn window_powers(audio: &AudioData) -> Vec<f32> {
+    let channels = audio.channels().max(1) as usize;
+    let window_frames = (AudioData::SAMPLE_RATE as f64 * LEVEL_WINDOW_SECS) as usize;
+
+    audio
+        .samples()
+        .chunks(window_frames * channels)
+        .map(|window| {
+            (window
+                .iter()
+                .map(|sample| (*sample as f64).powi(2))
+                .sum::<f64>()
+                / window.len() as f64) as f32
+        })
+        .collect()
+}
+
+/// The `(start, end)` seconds of `audio` whose RMS level, over all channels,
+/// is at or above `threshold_db` dBFS. Touching windows are merged, so the
+/// ranges are sorted and never overlap.
+pub fn audible_ranges(audio: &AudioData, threshold_db: f32) -> Vec<(f64, f64)> {
+    let channels = audio.channels().max(1) as usize;
+    let window_frames = (AudioData::SAMPLE_RATE as f64 * LEVEL_WINDOW_SECS) as usize;
+    let frames = audio.samples().len() / channels;
+    // Comparing mean squares avoids a sqrt and log per window.
+    let threshold_power = 10f32.powf(threshold_db / 10.0);
+
+    let mut ranges: Vec<(f64, f64)> = Vec::new();
+    for (index, power) in window_powers(audio).into_iter().enumerate() {
+        if power < threshold_power {
+            continue;
+        }
+
+        let start_frame = index * window_frames;
+        let end_frame = (start_frame + window_frames).min(frames);
+        let start = start_frame as f64 / AudioData::SAMPLE_RATE as f64;
+        let end = end_frame as f64 / AudioData::SAMPLE_RATE as f64;
+        match ranges.last_mut() {
+            Some(last) if last.1 >= start => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(secs: f64, amplitude: f32, channels: u16) -> Vec<f32> {
+        let frames = (secs * AudioData::SAMPLE_RATE as f64) as usize;
+        (0..frames)
+            .flat_map(|frame| {
+                let phase = frame as f32 * 440.0 / AudioData::SAMPLE_RATE as f32;
+                let sample = amplitude * (phase * std::f32::consts::TAU).sin();
+                std::iter::repeat_n(sample, channels as usize)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_speech_between_silences() {
+        let mut samples = tone(1.0, 0.0, 2);
+        samples.extend(tone(0.5, 0.5, 2));
+        samples.extend(tone(1.0, 0.001, 2));
+        samples.extend(tone(0.25, 0.5, 2));
+        let audio = AudioData::from_raw_f32(samples, 2);
+
+        let ranges = audible_ranges(&audio, DEFAULT_SILENCE_THRESHOLD_DB);
+
+        assert_eq!(ranges.len(), 2);
+        assert!((ranges[0].0 - 1.0).abs() < 1e-6 && (ranges[0].1 - 1.5).abs() < 1e-6);
+        assert!((ranges[1].0 - 2.5).abs() < 1e-6 && (ranges[1].1 - 2.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn silent_track_has_no_audible_ranges() {
+        let audio = AudioData::from_raw_f32(tone(2.0, 0.0, 1), 1);
+
+        assert!(audible_ranges(&audio, DEFAULT_SILENCE_THRESHOLD_DB).is_empty());
+    }
+}
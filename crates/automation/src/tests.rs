@@ -165,6 +165,7 @@ fn studio_export_rule() -> AutomationRule {
                 resolution_base: cap_project::XY { x: 1920, y: 1080 },
                 compression: Some(AutomationExportCompression::Web),
                 preset_name: None,
+                range: None,
             },
             destination: ExportDestination::ProjectFolder,
         }],
@@ -506,6 +507,7 @@ fn serialize_all_condition_and_action_shapes_roundtrip() {
                     resolution_base: cap_project::XY { x: 1920, y: 1080 },
                     compression: Some(AutomationExportCompression::Web),
                     preset_name: Some("My Preset".to_string()),
+                    range: None,
                 },
                 destination: ExportDestination::CustomPath {
                     dir: "/tmp/out".to_string(),
@@ -825,3 +827,39 @@ fn multiple_rules_same_trigger() {
     let matched = evaluate(&store, &Trigger::ScreenshotTaken, &TriggerContext::new());
     assert_eq!(matched.len(), 2);
 }
+
+#[test]
+fn webm_export_formats_round_trip_and_share_extension() {
+    let profile: ExportProfile = serde_json::from_str(r#"{"format":"webmAv1"}"#).unwrap();
+    assert_eq!(profile.format, ExportFormat::WebmAv1);
+    assert_eq!(profile.format.extension(), "webm");
+    assert_eq!(
+        serde_json::to_value(ExportFormat::Webm).unwrap(),
+        serde_json::json!("webm")
+    );
+}
+
+#[test]
+fn animated_image_formats_use_image_extensions() {
+    let profile: ExportProfile = serde_json::from_str(r#"{"format":"apng"}"#).unwrap();
+    assert_eq!(profile.format, ExportFormat::Apng);
+    assert_eq!(profile.format.extension(), "png");
+    assert_eq!(ExportFormat::Webp.extension(), "webp");
+}
+
+#[test]
+fn export_profiles_can_export_part_of_the_timeline() {
+    let profile: ExportProfile =
+        serde_json::from_str(r#"{"format":"mp4","range":{"type":"Time","from":10,"to":null}}"#)
+            .unwrap();
+    assert_eq!(
+        profile.range,
+        Some(cap_project::ExportRange::Time {
+            from: Some(10.0),
+            to: None
+        })
+    );
+
+    let whole: ExportProfile = serde_json::from_str(r#"{"format":"mp4"}"#).unwrap();
+    assert_eq!(whole.range, None);
+}
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
 
-use cap_project::XY;
+use cap_project::{ExportRange, XY};
 
 #[derive(Serialize, Deserialize, Type, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -174,6 +174,9 @@ pub struct ExportProfile {
     pub compression: Option<AutomationExportCompression>,
     #[serde(default)]
     pub preset_name: Option<String>,
+    /// Part of the recording's timeline to export; all of it when unset.
+    #[serde(default)]
+    pub range: Option<ExportRange>,
 }
 
 fn default_fps() -> u32 {
@@ -190,6 +193,27 @@ pub enum ExportFormat {
     Mp4,
     Gif,
     Mov,
+    /// WebM with VP9 video and Opus audio.
+    Webm,
+    /// WebM with AV1 video and Opus audio.
+    WebmAv1,
+    /// Animated WebP, transparent when the background is a clear colour.
+    Webp,
+    /// Animated PNG, transparent when the background is a clear colour.
+    Apng,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Mp4 => "mp4",
+            Self::Gif => "gif",
+            Self::Mov => "mov",
+            Self::Webm | Self::WebmAv1 => "webm",
+            Self::Webp => "webp",
+            Self::Apng => "png",
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Type, Debug, Clone, Copy, PartialEq, Eq)]
@@ -453,7 +453,7 @@ async fn benchmark_playback(
             .clips
             .iter()
             .find(|clip| clip.index == segment.recording_clip)
-            .map(|clip| clip.offsets)
+            .map(|clip| clip.offsets.clone())
             .unwrap_or_default();
 
         let decode_start = Instant::now();
@@ -549,6 +549,8 @@ async fn benchmark_export(recording_path: &Path) -> Result<ExportMetrics> {
         custom_bpp: None,
         force_ffmpeg_decoder: false,
         optimize_filesize: false,
+        loudness: None,
+        range: None,
     };
 
     let total_frames = exporter_base.total_frames(settings.fps);
@@ -665,7 +667,7 @@ async fn render_single_frame(context: &FixtureContext) -> Result<()> {
         .clips
         .iter()
         .find(|clip| clip.index == 0)
-        .map(|clip| clip.offsets)
+        .map(|clip| clip.offsets.clone())
         .unwrap_or_default();
     let frames = segment_media
         .decoders
@@ -0,0 +1,252 @@
+use strum::{EnumString, IntoStaticStr};
+
+use crate::{CursorShape, ResolvedCursor};
+
+/// Linux (X11) Cursors, named after the CSS cursors that freedesktop cursor
+/// themes provide. Each one also lists the older X cursor font names that
+/// themes alias to it.
+/// https://www.freedesktop.org/wiki/Specifications/cursor-spec/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString, IntoStaticStr)]
+pub enum CursorShapeLinux {
+    /// `default`, `left_ptr`, `arrow`, `top_left_arrow`
+    Default,
+    /// `context-menu`
+    ContextMenu,
+    /// `help`, `question_arrow`, `whats_this`, `left_ptr_help`
+    Help,
+    /// `pointer`, `hand`, `hand1`, `hand2`, `pointing_hand`
+    Pointer,
+    /// `progress`, `left_ptr_watch`, `half-busy`
+    Progress,
+    /// `wait`, `watch`
+    Wait,
+    /// `cell`, `plus`
+    Cell,
+    /// `crosshair`, `cross`, `tcross`, `cross_reverse`, `diamond_cross`
+    Crosshair,
+    /// `text`, `xterm`, `ibeam`
+    Text,
+    /// `vertical-text`
+    VerticalText,
+    /// `alias`, `link`, `dnd-link`
+    Alias,
+    /// `copy`, `dnd-copy`
+    Copy,
+    /// `move`, `fleur`, `dnd-move`, `size_all`
+    Move,
+    /// `not-allowed`, `no-drop`, `dnd-no-drop`, `crossed_circle`, `forbidden`,
+    /// `circle`
+    NotAllowed,
+    /// `grab`, `openhand`
+    Grab,
+    /// `grabbing`, `closedhand`
+    Grabbing,
+    /// `all-scroll`
+    AllScroll,
+    /// `col-resize`, `sb_h_double_arrow`, `split_h`
+    ColResize,
+    /// `row-resize`, `sb_v_double_arrow`, `split_v`
+    RowResize,
+    /// `n-resize`, `top_side`
+    NResize,
+    /// `e-resize`, `right_side`
+    EResize,
+    /// `s-resize`, `bottom_side`
+    SResize,
+    /// `w-resize`, `left_side`
+    WResize,
+    /// `ne-resize`, `top_right_corner`
+    NeResize,
+    /// `nw-resize`, `top_left_corner`
+    NwResize,
+    /// `se-resize`, `bottom_right_corner`
+    SeResize,
+    /// `sw-resize`, `bottom_left_corner`
+    SwResize,
+    /// `ew-resize`, `h_double_arrow`, `size_hor`
+    EwResize,
+    /// `ns-resize`, `v_double_arrow`, `size_ver`
+    NsResize,
+    /// `nesw-resize`, `fd_double_arrow`, `size_bdiag`
+    NeswResize,
+    /// `nwse-resize`, `bd_double_arrow`, `size_fdiag`
+    NwseResize,
+    /// `zoom-in`
+    ZoomIn,
+    /// `zoom-out`
+    ZoomOut,
+    /// `center_ptr`, `sb_up_arrow`
+    UpArrow,
+    /// `pencil`, `draft`
+    Pencil,
+}
+
+impl CursorShapeLinux {
+    pub fn resolve(&self) -> Option<ResolvedCursor> {
+        Some(match self {
+            Self::Default => ResolvedCursor {
+                raw: include_str!("../assets/mac/arrow.svg"),
+                hotspot: (0.302, 0.226),
+            },
+            Self::ContextMenu => ResolvedCursor {
+                raw: include_str!("../assets/mac/contextual_menu.svg"),
+                hotspot: (0.278, 0.295),
+            },
+            Self::Help => ResolvedCursor {
+                raw: include_str!("../assets/windows/idchelp.svg"),
+                hotspot: (0.056, 0.127),
+            },
+            Self::Pointer => ResolvedCursor {
+                raw: include_str!("../assets/mac/pointing_hand.svg"),
+                hotspot: (0.342, 0.172),
+            },
+            Self::Progress => ResolvedCursor {
+                raw: include_str!("../assets/windows/appstarting.svg"),
+                hotspot: (0.055, 0.368),
+            },
+            Self::Wait => ResolvedCursor {
+                raw: include_str!("../assets/windows/wait.svg"),
+                hotspot: (0.5, 0.52),
+            },
+            Self::Cell => ResolvedCursor {
+                raw: include_str!("../assets/windows/cross.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::Crosshair => ResolvedCursor {
+                raw: include_str!("../assets/mac/crosshair.svg"),
+                hotspot: (0.52, 0.51),
+            },
+            Self::Text => ResolvedCursor {
+                raw: include_str!("../assets/mac/ibeam.svg"),
+                hotspot: (0.484, 0.520),
+            },
+            Self::VerticalText => ResolvedCursor {
+                raw: include_str!("../assets/mac/ibeam_vertical.svg"),
+                hotspot: (0.51, 0.49),
+            },
+            Self::Alias => ResolvedCursor {
+                raw: include_str!("../assets/mac/drag_link.svg"),
+                hotspot: (0.621, 0.309),
+            },
+            Self::Copy => ResolvedCursor {
+                raw: include_str!("../assets/mac/drag_copy.svg"),
+                hotspot: (0.255, 0.1),
+            },
+            Self::Move | Self::AllScroll => ResolvedCursor {
+                raw: include_str!("../assets/windows/sizeall.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::NotAllowed => ResolvedCursor {
+                raw: include_str!("../assets/mac/operation_not_allowed.svg"),
+                hotspot: (0.24, 0.1),
+            },
+            Self::Grab => ResolvedCursor {
+                raw: include_str!("../assets/mac/open_hand.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::Grabbing => ResolvedCursor {
+                raw: include_str!("../assets/mac/closed_hand.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::ColResize | Self::EwResize => ResolvedCursor {
+                raw: include_str!("../assets/mac/resize_left_right.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::RowResize | Self::NsResize => ResolvedCursor {
+                raw: include_str!("../assets/mac/resize_up_down.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::NResize => ResolvedCursor {
+                raw: include_str!("../assets/mac/resize_up.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::EResize => ResolvedCursor {
+                raw: include_str!("../assets/mac/resize_right.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::SResize => ResolvedCursor {
+                raw: include_str!("../assets/mac/resize_down.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::WResize => ResolvedCursor {
+                raw: include_str!("../assets/mac/resize_left.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::NeResize | Self::SwResize | Self::NeswResize => ResolvedCursor {
+                raw: include_str!("../assets/windows/size-nesw.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::NwResize | Self::SeResize | Self::NwseResize => ResolvedCursor {
+                raw: include_str!("../assets/windows/idcsizenwse.svg"),
+                hotspot: (0.5, 0.5),
+            },
+            Self::ZoomIn => ResolvedCursor {
+                raw: include_str!("../assets/mac/tahoe/zoom-in.svg"),
+                hotspot: (0.549, 0.550),
+            },
+            Self::ZoomOut => ResolvedCursor {
+                raw: include_str!("../assets/mac/tahoe/zoom-out.svg"),
+                hotspot: (0.551, 0.552),
+            },
+            Self::UpArrow => ResolvedCursor {
+                raw: include_str!("../assets/windows/uparrow.svg"),
+                hotspot: (0.5, 0.05),
+            },
+            Self::Pencil => ResolvedCursor {
+                raw: include_str!("../assets/windows/pen.svg"),
+                hotspot: (0.055, 0.945),
+            },
+        })
+    }
+
+    /// Derive the cursor type from its name
+    /// X11 cursors loaded from a theme are named through XFixes, either with
+    /// the CSS name the toolkit asked for or with an X cursor font name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "default" | "left_ptr" | "arrow" | "top_left_arrow" => Self::Default,
+            "context-menu" => Self::ContextMenu,
+            "help" | "question_arrow" | "whats_this" | "left_ptr_help" => Self::Help,
+            "pointer" | "hand" | "hand1" | "hand2" | "pointing_hand" => Self::Pointer,
+            "progress" | "left_ptr_watch" | "half-busy" => Self::Progress,
+            "wait" | "watch" => Self::Wait,
+            "cell" | "plus" => Self::Cell,
+            "crosshair" | "cross" | "tcross" | "cross_reverse" | "diamond_cross" => Self::Crosshair,
+            "text" | "xterm" | "ibeam" => Self::Text,
+            "vertical-text" => Self::VerticalText,
+            "alias" | "link" | "dnd-link" => Self::Alias,
+            "copy" | "dnd-copy" => Self::Copy,
+            "move" | "fleur" | "dnd-move" | "size_all" => Self::Move,
+            "not-allowed" | "no-drop" | "dnd-no-drop" | "crossed_circle" | "forbidden"
+            | "circle" => Self::NotAllowed,
+            "grab" | "openhand" => Self::Grab,
+            "grabbing" | "closedhand" => Self::Grabbing,
+            "all-scroll" => Self::AllScroll,
+            "col-resize" | "sb_h_double_arrow" | "split_h" => Self::ColResize,
+            "row-resize" | "sb_v_double_arrow" | "split_v" => Self::RowResize,
+            "n-resize" | "top_side" => Self::NResize,
+            "e-resize" | "right_side" => Self::EResize,
+            "s-resize" | "bottom_side" => Self::SResize,
+            "w-resize" | "left_side" => Self::WResize,
+            "ne-resize" | "top_right_corner" => Self::NeResize,
+            "nw-resize" | "top_left_corner" => Self::NwResize,
+            "se-resize" | "bottom_right_corner" => Self::SeResize,
+            "sw-resize" | "bottom_left_corner" => Self::SwResize,
+            "ew-resize" | "h_double_arrow" | "size_hor" => Self::EwResize,
+            "ns-resize" | "v_double_arrow" | "size_ver" => Self::NsResize,
+            "nesw-resize" | "fd_double_arrow" | "size_bdiag" => Self::NeswResize,
+            "nwse-resize" | "bd_double_arrow" | "size_fdiag" => Self::NwseResize,
+            "zoom-in" => Self::ZoomIn,
+            "zoom-out" => Self::ZoomOut,
+            "center_ptr" | "sb_up_arrow" => Self::UpArrow,
+            "pencil" | "draft" => Self::Pencil,
+            _ => return None,
+        })
+    }
+}
+
+impl From<CursorShapeLinux> for CursorShape {
+    fn from(value: CursorShapeLinux) -> Self {
+        CursorShape::Linux(value)
+    }
+}
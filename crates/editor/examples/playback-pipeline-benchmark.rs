@@ -314,6 +314,7 @@ async fn load_recording(
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             });
         }
     }
@@ -518,7 +519,7 @@ async fn run_full_pipeline_benchmark(
             .clips
             .iter()
             .find(|v| v.index == segment.recording_clip)
-            .map(|v| v.offsets)
+            .map(|v| v.offsets.clone())
             .unwrap_or_default();
 
         let decode_start = Instant::now();
@@ -730,7 +731,7 @@ async fn run_scrubbing_benchmark(
             .clips
             .iter()
             .find(|v| v.index == segment.recording_clip)
-            .map(|v| v.offsets)
+            .map(|v| v.offsets.clone())
             .unwrap_or_default();
 
         let decode_start = Instant::now();
@@ -1,12 +1,13 @@
 use cap_audio::{
-    AudioData, AudioRendererTrack, FromSampleBytes, StereoMode, cast_bytes_to_f32_slice,
-    cast_f32_slice_to_bytes,
+    AudioData, AudioRendererTrack, FromSampleBytes, LoudnessMeter, StereoMode,
+    cast_bytes_to_f32_slice, cast_f32_slice_to_bytes,
 };
 use cap_media::MediaError;
 use cap_media_info::AudioInfo;
 use cap_project::{
-    AudioConfiguration, ClipOffsets, ClipSpeedAudioMode, ClipTransitionType, ProjectConfiguration,
-    TimelineConfiguration, TimelineFrameMapping, TimelineSource,
+    AudioConfiguration, ClipOffsets, ClipSpeedAudioMode, ClipTransitionStyle, ClipTransitionType,
+    MusicDucking, ProjectConfiguration, TimelineConfiguration, TimelineFrameMapping,
+    TimelineSource, VolumeEnvelope, VolumeKeyframe, VolumeKeyframes,
 };
 use ffmpeg::{
     ChannelLayout, Dictionary, filter, format as avformat, frame::Audio as FFAudio,
@@ -18,8 +19,9 @@ use ringbuf::{
 };
 use std::{
     collections::{HashMap, VecDeque},
+    hash::{DefaultHasher, Hash, Hasher},
     sync::{
-        Arc,
+        Arc, OnceLock,
         atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
     },
 };
@@ -30,6 +32,9 @@ use tracing::{info, warn};
 /// of the recording audio in output/timeline time.
 pub type MusicTracks = HashMap<String, Arc<AudioData>>;
 
+/// Samples rendered at a time while measuring loudness (100 ms).
+const LOUDNESS_CHUNK_SAMPLES: usize = AudioData::SAMPLE_RATE as usize / 10;
+
 pub struct AudioRenderer {
     data: Vec<AudioSegment>,
     cursor: AudioRendererCursor,
@@ -60,25 +65,41 @@ pub struct AudioSegment {
 #[derive(Clone)]
 pub struct AudioSegmentTrack {
     data: Arc<AudioData>,
-    get_gain: fn(&AudioConfiguration) -> f32,
-    get_stereo_mode: fn(&AudioConfiguration) -> StereoMode,
-    get_offset: fn(&ClipOffsets) -> f32,
+    get_gain: Arc<dyn Fn(&AudioConfiguration) -> f32 + Send + Sync>,
+    get_stereo_mode: Arc<dyn Fn(&AudioConfiguration) -> StereoMode + Send + Sync>,
+    get_offset: Arc<dyn Fn(&ClipOffsets) -> f32 + Send + Sync>,
+    get_volume_keyframes: Option<fn(&VolumeKeyframes) -> &[VolumeKeyframe]>,
     timing_offset_secs: f32,
+    /// Set on voice tracks, and shared between clones.
+    voice: Option<Arc<VoiceAnalysis>>,
+}
+
+/// What is worked out from a voice track on first use.
+#[derive(Default)]
+struct VoiceAnalysis {
+    /// The `AudioConfiguration::improve` version of the track.
+    enhanced: OnceLock<Arc<AudioData>>,
+    /// Set once a worker has been started to fill `enhanced`.
+    enhancing: AtomicBool,
+    /// The track's `cap_audio::window_powers`, which music ducks under.
+    powers: OnceLock<Vec<f32>>,
 }
 
 impl AudioSegmentTrack {
     pub fn new(
         data: Arc<AudioData>,
-        get_gain: fn(&AudioConfiguration) -> f32,
-        get_stereo_mode: fn(&AudioConfiguration) -> StereoMode,
-        get_offset: fn(&ClipOffsets) -> f32,
+        get_gain: impl Fn(&AudioConfiguration) -> f32 + Send + Sync + 'static,
+        get_stereo_mode: impl Fn(&AudioConfiguration) -> StereoMode + Send + Sync + 'static,
+        get_offset: impl Fn(&ClipOffsets) -> f32 + Send + Sync + 'static,
     ) -> Self {
         Self {
             data,
-            get_gain,
-            get_stereo_mode,
-            get_offset,
+            get_gain: Arc::new(get_gain),
+            get_stereo_mode: Arc::new(get_stereo_mode),
+            get_offset: Arc::new(get_offset),
+            get_volume_keyframes: None,
             timing_offset_secs: 0.0,
+            voice: None,
         }
     }
 
@@ -87,10 +108,89 @@ impl AudioSegmentTrack {
         self
     }
 
+    /// Picks the track's keyframes out of `TimelineConfiguration::volume_keyframes`.
+    pub fn with_volume_keyframes(
+        mut self,
+        get_volume_keyframes: fn(&VolumeKeyframes) -> &[VolumeKeyframe],
+    ) -> Self {
+        self.get_volume_keyframes = Some(get_volume_keyframes);
+        self
+    }
+
+    /// Marks the track as speech, so `AudioConfiguration::improve` cleans it up
+    /// and ducking music is lowered under it.
+    pub fn as_voice(mut self) -> Self {
+        self.voice = Some(Arc::default());
+        self
+    }
+
     pub fn data(&self) -> &Arc<AudioData> {
         &self.data
     }
 
+    /// The samples to play under `config`. Never does the enhancement itself:
+    /// with `improve` on, a voice track plays unprocessed until
+    /// [`Self::prepare_playback_data`] or [`Self::wait_for_playback_data`] has
+    /// produced the enhanced copy.
+    pub fn playback_data(&self, config: &AudioConfiguration) -> &Arc<AudioData> {
+        match &self.voice {
+            Some(voice) if config.improve => voice.enhanced.get().unwrap_or(&self.data),
+            _ => &self.data,
+        }
+    }
+
+    /// Starts enhancing a voice track on a worker thread when `improve` is on,
+    /// so playback can pick the result up without running it on the audio
+    /// callback. Only the first call starts a worker.
+    pub fn prepare_playback_data(&self, config: &AudioConfiguration) {
+        let Some(voice) = &self.voice else {
+            return;
+        };
+        if !config.improve
+            || voice.enhanced.get().is_some()
+            || voice.enhancing.swap(true, Ordering::AcqRel)
+        {
+            return;
+        }
+
+        let worker_voice = voice.clone();
+        let data = self.data.clone();
+        let spawned = std::thread::Builder::new()
+            .name("cap-voice-enhance".to_string())
+            .spawn(move || {
+                worker_voice
+                    .enhanced
+                    .get_or_init(|| Arc::new(cap_audio::enhance_voice(&data)));
+            });
+        if let Err(error) = spawned {
+            warn!(%error, "Failed to start voice enhancement; playing unprocessed audio");
+            voice.enhancing.store(false, Ordering::Release);
+        }
+    }
+
+    /// Like [`Self::playback_data`], but with `improve` on it blocks until the
+    /// enhanced copy exists, enhancing on the calling thread if no worker has
+    /// started. Exports use this so they always hear the same result.
+    pub fn wait_for_playback_data(&self, config: &AudioConfiguration) -> &Arc<AudioData> {
+        match &self.voice {
+            Some(voice) if config.improve => voice
+                .enhanced
+                .get_or_init(|| Arc::new(cap_audio::enhance_voice(&self.data))),
+            _ => &self.data,
+        }
+    }
+
+    /// The mean square level of each `cap_audio::LEVEL_WINDOW_SECS` window of
+    /// a voice track, measured on first use. `None` for other tracks.
+    pub fn voice_powers(&self) -> Option<&[f32]> {
+        self.voice.as_ref().map(|voice| {
+            voice
+                .powers
+                .get_or_init(|| cap_audio::window_powers(&self.data))
+                .as_slice()
+        })
+    }
+
     pub fn gain(&self, config: &AudioConfiguration) -> f32 {
         (self.get_gain)(config)
     }
@@ -102,6 +202,14 @@ impl AudioSegmentTrack {
     pub fn offset(&self, offsets: &ClipOffsets) -> f32 {
         (self.get_offset)(offsets) + self.timing_offset_secs
     }
+
+    pub fn volume_keyframes<'a>(
+        &self,
+        timeline: &'a TimelineConfiguration,
+    ) -> Option<&'a [VolumeKeyframe]> {
+        self.get_volume_keyframes
+            .map(|get_volume_keyframes| get_volume_keyframes(&timeline.volume_keyframes))
+    }
 }
 
 struct TimelineCursor<'a> {
@@ -124,6 +232,10 @@ struct SpeedAudioProcessorKey {
     mic_stereo_mode: u8,
     mic_offset_bits: u32,
     system_offset_bits: u32,
+    mic_mute: bool,
+    additional_mics_hash: u64,
+    improve: bool,
+    volume_keyframes_hash: u64,
 }
 
 struct SpeedAudioProcessorSlot {
@@ -141,6 +253,7 @@ struct SpeedAudioProcessor {
     graph: filter::Graph,
     pending: VecDeque<f32>,
     input_data: Vec<f32>,
+    segment_index: usize,
     recording_clip: u32,
     input_samples: usize,
     segment_end_samples: usize,
@@ -181,6 +294,26 @@ impl AudioRenderer {
         self
     }
 
+    /// Enhances the voice tracks in the background when `config.improve` is
+    /// on; see [`AudioSegmentTrack::prepare_playback_data`].
+    pub fn prepare_voice_enhancement(&self, config: &AudioConfiguration) {
+        self.tracks()
+            .for_each(|track| track.prepare_playback_data(config));
+    }
+
+    /// Blocks until every voice track has its enhanced copy when
+    /// `config.improve` is on, so what is rendered next never falls back to
+    /// unprocessed audio.
+    pub fn wait_for_voice_enhancement(&self, config: &AudioConfiguration) {
+        self.tracks().for_each(|track| {
+            track.wait_for_playback_data(config);
+        });
+    }
+
+    fn tracks(&self) -> impl Iterator<Item = &AudioSegmentTrack> {
+        self.data.iter().flat_map(|segment| &segment.tracks)
+    }
+
     pub fn set_playhead(&mut self, playhead: f64, project: &ProjectConfiguration) {
         self.elapsed_samples = self.playhead_to_samples(playhead);
         self.speed_audio_processors = [None, None];
@@ -224,6 +357,31 @@ impl AudioRenderer {
             })
     }
 
+    /// Plays the first `duration` seconds of the mix into a loudness meter,
+    /// then rewinds to the start.
+    pub fn measure_loudness(
+        &mut self,
+        project: &ProjectConfiguration,
+        duration: f64,
+    ) -> LoudnessMeter {
+        let mut meter = LoudnessMeter::new(Self::CHANNELS);
+        self.wait_for_voice_enhancement(&project.audio);
+        self.set_playhead(0.0, project);
+
+        let mut remaining = self.playhead_to_samples(duration);
+        while remaining > 0 {
+            let samples = remaining.min(LOUDNESS_CHUNK_SAMPLES);
+            match self.render_frame_raw(samples, project) {
+                Some((_, data)) => meter.push(&data),
+                None => meter.push(&vec![0.0; samples * usize::from(Self::CHANNELS)]),
+            }
+            remaining -= samples;
+        }
+
+        self.set_playhead(0.0, project);
+        meter
+    }
+
     pub fn render_frame_raw(
         &mut self,
         samples: usize,
@@ -236,7 +394,15 @@ impl AudioRenderer {
             let (written, mut buf) = self.render_timeline_frame_raw(samples, project, timeline)?;
 
             if !self.music.is_empty() && !timeline.audio_segments.is_empty() {
-                mix_music(&self.music, timeline, frame_start, written, &mut buf);
+                let voice_power = |time| voice_power_at(&self.data, project, timeline, time);
+                mix_music(
+                    &self.music,
+                    timeline,
+                    frame_start,
+                    written,
+                    &mut buf,
+                    voice_power,
+                );
             }
 
             return Some((written, buf));
@@ -280,7 +446,13 @@ impl AudioRenderer {
             };
 
             if cursor.segment.timescale == 1.0 {
-                self.render_current_chunk(project, chunk_samples, written * 2, &mut ret);
+                self.render_current_chunk(
+                    project,
+                    Some(cursor.segment_index),
+                    chunk_samples,
+                    written * 2,
+                    &mut ret,
+                );
                 self.cursor.samples += chunk_samples;
             } else {
                 self.render_speed_audio_chunk(
@@ -358,7 +530,7 @@ impl AudioRenderer {
                 TimelineFrameMapping::Transition {
                     outgoing,
                     incoming,
-                    kind,
+                    style,
                     progress,
                     duration,
                     ..
@@ -387,7 +559,7 @@ impl AudioRenderer {
                         &outgoing_buffer,
                         &incoming_buffer,
                         &mut output[written * 2..(written + chunk_samples) * 2],
-                        kind,
+                        style,
                         progress,
                         duration,
                     );
@@ -427,7 +599,7 @@ impl AudioRenderer {
         }
 
         let mut ret = vec![0.0; samples * 2];
-        let rendered = self.render_current_chunk(project, samples, 0, &mut ret);
+        let rendered = self.render_current_chunk(project, None, samples, 0, &mut ret);
 
         if rendered == 0 {
             self.elapsed_samples += samples;
@@ -472,11 +644,19 @@ impl AudioRenderer {
     fn render_current_chunk(
         &self,
         project: &ProjectConfiguration,
+        segment_index: Option<usize>,
         samples: usize,
         out_offset: usize,
         out: &mut [f32],
     ) -> usize {
-        self.render_chunk_at_cursor(project, self.cursor, samples, out_offset, out)
+        self.render_chunk_at_cursor(
+            project,
+            self.cursor,
+            segment_index,
+            samples,
+            out_offset,
+            out,
+        )
     }
 
     fn render_segment_chunk(
@@ -489,7 +669,14 @@ impl AudioRenderer {
     ) -> usize {
         if source.segment.timescale == 1.0 {
             let cursor = source_cursor(source, self.playhead_to_samples(source.source_time));
-            return self.render_chunk_at_cursor(project, cursor, samples, out_offset, out);
+            return self.render_chunk_at_cursor(
+                project,
+                cursor,
+                Some(source.segment_index),
+                samples,
+                out_offset,
+                out,
+            );
         }
 
         self.render_speed_audio_chunk(project, source, samples, out_offset, out)
@@ -523,7 +710,7 @@ impl AudioRenderer {
             .clips
             .iter()
             .find(|clip| clip.index == source.segment.recording_clip)
-            .map(|clip| clip.offsets)
+            .map(|clip| clip.offsets.clone())
             .unwrap_or_default();
         let key = SpeedAudioProcessorKey {
             segment_index: source.segment_index,
@@ -537,6 +724,10 @@ impl AudioRenderer {
             mic_stereo_mode: project_stereo_mode_key(&project.audio.mic_stereo_mode),
             mic_offset_bits: offsets.mic.to_bits(),
             system_offset_bits: offsets.system_audio.to_bits(),
+            mic_mute: project.audio.mic_mute,
+            additional_mics_hash: additional_mics_hash(project, &offsets),
+            improve: project.audio.improve,
+            volume_keyframes_hash: volume_keyframes_hash(project),
         };
         let requested_source_sample = source.source_time * Self::SAMPLE_RATE as f64;
         self.speed_audio_use_counter = self.speed_audio_use_counter.saturating_add(1);
@@ -604,15 +795,48 @@ impl AudioRenderer {
         }
     }
 
+    /// Renders from `cursor` in the clip at `segment_index` on the timeline,
+    /// which places the chunk in output time for volume keyframes.
     fn render_chunk_at_cursor(
         &self,
         project: &ProjectConfiguration,
         cursor: AudioRendererCursor,
+        segment_index: Option<usize>,
         samples: usize,
         out_offset: usize,
         out: &mut [f32],
     ) -> usize {
-        render_audio_data_chunk(&self.data, project, cursor, samples, out_offset, out)
+        let clock = segment_index.and_then(|index| OutputClock::new(project, index));
+        render_audio_data_chunk(&self.data, project, cursor, clock, samples, out_offset, out)
+    }
+}
+
+/// Where a clip's source samples play in output time.
+#[derive(Clone, Copy)]
+struct OutputClock {
+    /// Output time of source sample zero.
+    origin: f64,
+    seconds_per_sample: f64,
+}
+
+impl OutputClock {
+    /// `None` when the recording's tracks have no volume keyframes, so
+    /// there is nothing to place.
+    fn new(project: &ProjectConfiguration, segment_index: usize) -> Option<Self> {
+        let timeline = project.timeline.as_ref()?;
+        if timeline.volume_keyframes.is_empty() {
+            return None;
+        }
+        let segment = timeline.segments.get(segment_index)?;
+        let (output_start, _) = timeline.segment_output_spans()[segment_index];
+        Some(Self {
+            origin: output_start - segment.start / segment.timescale,
+            seconds_per_sample: 1.0 / (AudioRenderer::SAMPLE_RATE as f64 * segment.timescale),
+        })
+    }
+
+    fn time(&self, source_sample: usize) -> f64 {
+        self.origin + source_sample as f64 * self.seconds_per_sample
     }
 }
 
@@ -628,6 +852,7 @@ fn render_audio_data_chunk(
     data: &[AudioSegment],
     project: &ProjectConfiguration,
     cursor: AudioRendererCursor,
+    clock: Option<OutputClock>,
     samples: usize,
     out_offset: usize,
     out: &mut [f32],
@@ -645,7 +870,7 @@ fn render_audio_data_chunk(
         .clips
         .iter()
         .find(|clip| clip.index == cursor.clip_index)
-        .map(|clip| clip.offsets)
+        .map(|clip| clip.offsets.clone())
         .unwrap_or_default();
     let max_samples = tracks
         .iter()
@@ -663,10 +888,24 @@ fn render_audio_data_chunk(
     }
 
     let samples = samples.min(max_samples - cursor.samples);
+    let envelopes = tracks
+        .iter()
+        .map(|track| {
+            let clock = clock?;
+            let keyframes = track.volume_keyframes(project.timeline.as_ref()?)?;
+            let envelope = VolumeEnvelope::new(keyframes)?;
+            Some(
+                (0..samples)
+                    .map(|sample| envelope.gain(clock.time(cursor.samples + sample)))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
     let track_datas = tracks
         .iter()
-        .map(|track| AudioRendererTrack {
-            data: track.data().as_ref(),
+        .zip(&envelopes)
+        .map(|(track, envelope)| AudioRendererTrack {
+            data: track.playback_data(&project.audio).as_ref(),
             gain: if project.audio.mute {
                 f32::NEG_INFINITY
             } else {
@@ -679,6 +918,7 @@ fn render_audio_data_chunk(
             },
             stereo_mode: track.stereo_mode(&project.audio),
             offset: (track.offset(&offsets) * AudioRenderer::SAMPLE_RATE as f32).round() as isize,
+            gain_envelope: envelope.as_deref(),
         })
         .collect::<Vec<_>>();
 
@@ -688,6 +928,39 @@ fn render_audio_data_chunk(
 const SPEED_AUDIO_INPUT_BLOCK_SAMPLES: usize = 4_096;
 const SPEED_AUDIO_PREROLL_SAMPLES: usize = 2_400;
 
+fn additional_mics_hash(project: &ProjectConfiguration, offsets: &ClipOffsets) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    project.audio.additional_mics.len().hash(&mut hasher);
+    for mic in &project.audio.additional_mics {
+        mic.mute.hash(&mut hasher);
+        mic.volume_db.to_bits().hash(&mut hasher);
+        project_stereo_mode_key(&mic.stereo_mode).hash(&mut hasher);
+    }
+    offsets.additional_mics.len().hash(&mut hasher);
+    for offset in &offsets.additional_mics {
+        offset.to_bits().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn volume_keyframes_hash(project: &ProjectConfiguration) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Some(timeline) = &project.timeline {
+        for keyframes in [
+            &timeline.volume_keyframes.mic,
+            &timeline.volume_keyframes.system_audio,
+        ] {
+            keyframes.len().hash(&mut hasher);
+            for keyframe in keyframes {
+                keyframe.time.to_bits().hash(&mut hasher);
+                keyframe.volume_db.to_bits().hash(&mut hasher);
+                (keyframe.interpolation as u8).hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
 fn project_stereo_mode_key(mode: &cap_project::StereoMode) -> u8 {
     match mode {
         cap_project::StereoMode::Stereo => 0,
@@ -757,6 +1030,7 @@ impl SpeedAudioProcessor {
             graph,
             pending: VecDeque::with_capacity(SPEED_AUDIO_INPUT_BLOCK_SAMPLES * 2),
             input_data: Vec::with_capacity(SPEED_AUDIO_INPUT_BLOCK_SAMPLES * 2),
+            segment_index: key.segment_index,
             recording_clip: key.recording_clip,
             input_samples,
             segment_end_samples: key.segment_end_samples,
@@ -827,6 +1101,7 @@ impl SpeedAudioProcessor {
                 timescale: self.timescale,
                 samples: self.input_samples,
             },
+            OutputClock::new(project, self.segment_index),
             samples,
             0,
             &mut self.input_data,
@@ -899,7 +1174,7 @@ fn mix_transition_audio(
     outgoing: &[f32],
     incoming: &[f32],
     output: &mut [f32],
-    kind: ClipTransitionType,
+    style: ClipTransitionStyle,
     progress: f64,
     duration: f64,
 ) {
@@ -910,9 +1185,16 @@ fn mix_transition_audio(
         .zip(output.chunks_exact_mut(2))
         .enumerate()
     {
-        let progress = (progress + sample_index as f64 * progress_per_sample).clamp(0.0, 1.0);
-        let (outgoing_gain, incoming_gain) = match kind {
-            ClipTransitionType::CrossFade => {
+        let progress = style
+            .easing
+            .apply(progress + sample_index as f64 * progress_per_sample);
+        let (outgoing_gain, incoming_gain) = match style.kind {
+            ClipTransitionType::CrossFade
+            | ClipTransitionType::Wipe
+            | ClipTransitionType::Slide
+            | ClipTransitionType::Iris
+            | ClipTransitionType::ZoomThrough
+            | ClipTransitionType::BlurDissolve => {
                 let angle = progress * std::f64::consts::FRAC_PI_2;
                 (angle.cos() as f32, angle.sin() as f32)
             }
@@ -939,18 +1221,150 @@ fn music_gain(volume_db: f32) -> f32 {
     }
 }
 
+/// The loudest voice track's mean square level at output `time`, or `0.0`
+/// where no voice is heard. Tracks are read at the same source time and with
+/// the same muting as the recording mix.
+fn voice_power_at(
+    data: &[AudioSegment],
+    project: &ProjectConfiguration,
+    timeline: &TimelineConfiguration,
+    time: f64,
+) -> f32 {
+    if project.audio.mute || time < 0.0 {
+        return 0.0;
+    }
+    let Some((segment_time, segment)) = timeline.get_segment_time(time) else {
+        return 0.0;
+    };
+    let Some(audio_segment) = data.get(segment.recording_clip as usize) else {
+        return 0.0;
+    };
+
+    let offsets = project
+        .clips
+        .iter()
+        .find(|clip| clip.index == segment.recording_clip)
+        .map(|clip| clip.offsets.clone())
+        .unwrap_or_default();
+    audio_segment
+        .tracks
+        .iter()
+        .filter(|track| track.gain(&project.audio) >= -30.0)
+        .filter_map(|track| {
+            let powers = track.voice_powers()?;
+            let source_time = segment_time + track.offset(&offsets) as f64;
+            if source_time < 0.0 {
+                return None;
+            }
+            powers
+                .get((source_time / cap_audio::LEVEL_WINDOW_SECS) as usize)
+                .copied()
+        })
+        .fold(0.0, f32::max)
+}
+
+/// The gain a ducking music segment is played at over one frame of the mix.
+///
+/// Speech is looked for in `cap_audio::LEVEL_WINDOW_SECS` windows counted from
+/// the start of the output, so the envelope doesn't depend on how the mix is
+/// chunked and playback hears exactly what is exported. The music starts
+/// ducking `attack` seconds before the speech so the first word isn't covered.
+struct DuckingEnvelope {
+    first_window: i64,
+    /// How far ducked the music is at the start of each window, from `0.0`
+    /// (not at all) to `1.0` (by the full depth).
+    amounts: Vec<f64>,
+    depth_db: f64,
+}
+
+impl DuckingEnvelope {
+    fn new(
+        ducking: &MusicDucking,
+        frame_start: i64,
+        frame_end: i64,
+        voice_power: &impl Fn(f64) -> f32,
+    ) -> Self {
+        let window = cap_audio::LEVEL_WINDOW_SECS;
+        let attack = ducking.attack.max(0.0);
+        let release = ducking.release.max(0.0);
+        let sample_rate = AudioData::SAMPLE_RATE as f64;
+        let first_window = (frame_start as f64 / sample_rate / window).floor() as i64;
+        let last_window = (frame_end as f64 / sample_rate / window).ceil() as i64;
+        let lookbehind = (release / window).ceil() as i64;
+        let lookahead = (attack / window).ceil() as i64;
+
+        let threshold_power = 10f32.powf(ducking.threshold_db / 10.0);
+        let speech = (first_window - lookbehind..=last_window + lookahead)
+            .map(|index| voice_power((index as f64 + 0.5) * window) >= threshold_power)
+            .collect::<Vec<_>>();
+
+        let ramp = |windows: usize, length: f64| {
+            if windows == 0 {
+                1.0
+            } else if length <= 0.0 {
+                0.0
+            } else {
+                (1.0 - windows as f64 * window / length).max(0.0)
+            }
+        };
+        let mut amounts = vec![0.0; speech.len()];
+        let mut last_speech = None;
+        for (index, amount) in amounts.iter_mut().enumerate() {
+            if speech[index] {
+                last_speech = Some(index);
+            }
+            if let Some(last) = last_speech {
+                *amount = ramp(index - last, release);
+            }
+        }
+        let mut next_speech = None;
+        for (index, amount) in amounts.iter_mut().enumerate().rev() {
+            if speech[index] {
+                next_speech = Some(index);
+            }
+            if let Some(next) = next_speech {
+                *amount = amount.max(ramp(next - index, attack));
+            }
+        }
+
+        Self {
+            first_window: first_window - lookbehind,
+            amounts,
+            depth_db: ducking.depth_db.max(0.0) as f64,
+        }
+    }
+
+    fn gain(&self, out_sample: i64) -> f32 {
+        let position =
+            out_sample as f64 / AudioData::SAMPLE_RATE as f64 / cap_audio::LEVEL_WINDOW_SECS
+                - self.first_window as f64;
+        let index = (position.floor().max(0.0) as usize).min(self.amounts.len() - 1);
+        let next = self
+            .amounts
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.amounts[index]);
+        let fraction = (position - index as f64).clamp(0.0, 1.0);
+        let amount = self.amounts[index] + (next - self.amounts[index]) * fraction;
+        10f64.powf(-self.depth_db * amount / 20.0) as f32
+    }
+}
+
 /// Mixes timeline-positioned music tracks into an already-rendered, interleaved
 /// stereo buffer covering output samples `[frame_start, frame_start + samples)`.
 ///
 /// Each segment is placed in output time (`start`/`end`), reads its source from
-/// `trim_start`, and applies linear fade-in/out ramps. Sources may be mono or
-/// stereo; mono is centre-panned at -3dB to match `cap_audio::render_audio`.
+/// `trim_start`, and applies linear fade-in/out ramps. Segments with `ducking`
+/// are lowered wherever `voice_power`, the voice level at an output time,
+/// crosses their threshold. Sources may be mono or stereo; mono is
+/// centre-panned at -3dB to match `cap_audio::render_audio`.
 fn mix_music(
     music: &MusicTracks,
     timeline: &TimelineConfiguration,
     frame_start: usize,
     samples: usize,
     out: &mut [f32],
+    voice_power: impl Fn(f64) -> f32,
 ) {
     if samples == 0 {
         return;
@@ -995,6 +1409,10 @@ fn mix_music(
         let channels = data.channels() as usize;
         let src = data.samples();
         let src_frames = data.sample_count() as i64;
+        let volume = VolumeEnvelope::new(&segment.volume_keyframes);
+        let ducking = segment
+            .ducking
+            .map(|ducking| DuckingEnvelope::new(&ducking, lo, hi, &voice_power));
 
         for out_sample in lo..hi {
             let local = out_sample - start_sample;
@@ -1011,6 +1429,12 @@ fn mix_music(
             if fade_out > 0 && until_end <= fade_out {
                 g *= (until_end as f32 / fade_out as f32).clamp(0.0, 1.0);
             }
+            if let Some(volume) = &volume {
+                g *= volume.gain(local as f64 / sample_rate);
+            }
+            if let Some(ducking) = &ducking {
+                g *= ducking.gain(out_sample);
+            }
             if g <= 0.0 {
                 continue;
             }
@@ -1484,8 +1908,13 @@ impl<T: FromSampleBytes> StreamingAudioBuffer<T> {
             .saturating_mul(Self::BUFFER_SECS)
             .max(Self::PROCESSING_SAMPLES_COUNT * output_info.channels * 4);
 
+        let renderer = AudioRenderer::new(segments).with_music(music);
+        // `fill` runs on the device callback, which must never wait on the
+        // enhancement; it plays the unprocessed voice until this finishes.
+        renderer.prepare_voice_enhancement(&project.audio);
+
         let mut buffer = Self {
-            renderer: AudioRenderer::new(segments).with_music(music),
+            renderer,
             resampler: AudioResampler::new(output_info).unwrap(),
             resampled_buffer: HeapRb::new(capacity),
             project,
@@ -1617,6 +2046,9 @@ fn spawn_progressive_render(
         .spawn(move || {
             let render_start = std::time::Instant::now();
             let mut renderer = AudioRenderer::new(segments).with_music(music);
+            // Everything rendered here is kept for the whole session, so it
+            // must not be baked from the unprocessed voice.
+            renderer.wait_for_voice_enhancement(&project.audio);
 
             let chunk_size = 1024usize;
 
@@ -1747,8 +2179,8 @@ fn spawn_progressive_render(
 mod tests {
     use super::*;
     use cap_project::{
-        ClipConfiguration, ClipTransition, ProjectConfiguration, TimelineConfiguration,
-        TimelineSegment,
+        ClipConfiguration, ClipTransition, ClipTransitionEasing, ProjectConfiguration,
+        TimelineConfiguration, TimelineSegment,
     };
     use std::{path::Path, sync::Arc};
     use tempfile::TempDir;
@@ -1941,6 +2373,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             clips: vec![
                 ClipConfiguration {
@@ -1958,6 +2391,51 @@ mod tests {
         (dir, AudioRenderer::new(segments), project)
     }
 
+    #[test]
+    fn improve_plays_the_enhanced_copy_of_voice_tracks_only() {
+        let _ = ffmpeg::init();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("voice.wav");
+        write_step_wav(&path, &[1000, 2000]);
+        let data = Arc::new(AudioData::from_file(&path).unwrap());
+        let voice = AudioSegmentTrack::new(data.clone(), gain, stereo, no_offset).as_voice();
+        let system = AudioSegmentTrack::new(data.clone(), gain, stereo, no_offset);
+
+        let mut config = AudioConfiguration::default();
+        assert!(Arc::ptr_eq(voice.wait_for_playback_data(&config), &data));
+
+        config.improve = true;
+        assert!(Arc::ptr_eq(voice.playback_data(&config), &data));
+        let enhanced = voice.wait_for_playback_data(&config);
+        assert!(!Arc::ptr_eq(enhanced, &data));
+        assert_eq!(enhanced.sample_count(), data.sample_count());
+        assert!(Arc::ptr_eq(voice.clone().playback_data(&config), enhanced));
+        assert!(Arc::ptr_eq(system.wait_for_playback_data(&config), &data));
+    }
+
+    #[test]
+    fn prepared_voice_enhancement_is_picked_up_by_playback() {
+        let _ = ffmpeg::init();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("voice.wav");
+        write_step_wav(&path, &[1000, 2000]);
+        let data = Arc::new(AudioData::from_file(&path).unwrap());
+        let voice = AudioSegmentTrack::new(data.clone(), gain, stereo, no_offset).as_voice();
+        let config = AudioConfiguration {
+            improve: true,
+            ..Default::default()
+        };
+
+        voice.prepare_playback_data(&config);
+        voice.prepare_playback_data(&config);
+        let enhanced = voice.wait_for_playback_data(&config);
+
+        assert!(!Arc::ptr_eq(enhanced, &data));
+        assert!(Arc::ptr_eq(voice.playback_data(&config), enhanced));
+    }
+
     #[test]
     fn prerendered_audio_reports_audible_playhead_after_output_latency() {
         let _ = ffmpeg::init();
@@ -2074,6 +2552,7 @@ mod tests {
                     caption_segments: Vec::new(),
                     keyboard_segments: Vec::new(),
                     audio_segments: Vec::new(),
+                    volume_keyframes: Default::default(),
                 }),
                 clips: vec![ClipConfiguration {
                     index: 0,
@@ -2140,12 +2619,15 @@ mod tests {
         write_step_wav(&clip_path, section_values);
 
         let data = vec![AudioSegment {
-            tracks: vec![AudioSegmentTrack::new(
-                Arc::new(AudioData::from_file(&clip_path).unwrap()),
-                gain,
-                stereo,
-                no_offset,
-            )],
+            tracks: vec![
+                AudioSegmentTrack::new(
+                    Arc::new(AudioData::from_file(&clip_path).unwrap()),
+                    gain,
+                    stereo,
+                    no_offset,
+                )
+                .with_volume_keyframes(|keyframes| &keyframes.mic),
+            ],
         }];
 
         let project = ProjectConfiguration {
@@ -2159,6 +2641,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             clips: vec![ClipConfiguration {
                 index: 0,
@@ -2170,6 +2653,29 @@ mod tests {
         (dir, AudioRenderer::new(data), project)
     }
 
+    // Keyframes are in output time, so they stay put when the clip under them
+    // comes from later in the recording.
+    #[test]
+    fn volume_keyframes_follow_output_time_across_cuts() {
+        let (_dir, mut renderer, mut project) = single_clip_fixture(
+            &[8000, 8000, 8000, 8000],
+            vec![segment(0, 0.0, 1.0, 1.0), segment(0, 2.0, 4.0, 1.0)],
+        );
+        let keyframe = |time, volume_db| VolumeKeyframe {
+            time,
+            volume_db,
+            interpolation: Default::default(),
+        };
+        project.timeline.as_mut().unwrap().volume_keyframes.mic =
+            vec![keyframe(1.5, 0.0), keyframe(2.5, -20.0 * 2f32.log10())];
+
+        let stream = render_export_audio(&mut renderer, &project, 30, 3 * 30);
+
+        assert!((left_at_time(&stream, 0.5) - expected(8000)).abs() < 0.002);
+        assert!((left_at_time(&stream, 2.0) - expected(8000) / 2f32.sqrt()).abs() < 0.002);
+        assert!((left_at_time(&stream, 2.75) - expected(8000) / 2.0).abs() < 0.002);
+    }
+
     fn segment(recording_clip: u32, start: f64, end: f64, timescale: f64) -> TimelineSegment {
         TimelineSegment {
             recording_clip,
@@ -2262,6 +2768,8 @@ mod tests {
                     segment_index: 1,
                     kind,
                     duration: 0.5,
+                    easing: Default::default(),
+                    direction: Default::default(),
                 }],
                 zoom_segments: Vec::new(),
                 scene_segments: Vec::new(),
@@ -2270,6 +2778,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             clips: vec![
                 ClipConfiguration {
@@ -2303,6 +2812,18 @@ mod tests {
         assert!((midpoint - expected_midpoint).abs() < 0.01);
     }
 
+    #[test]
+    fn eased_crossfade_audio_follows_the_transition_curve() {
+        let (_dir, mut renderer, mut project) = transition_fixture(ClipTransitionType::Wipe);
+        project.timeline.as_mut().unwrap().transitions[0].easing = ClipTransitionEasing::EaseIn;
+        let stream = render_export_audio(&mut renderer, &project, 30, 45);
+        let angle = ClipTransitionEasing::EaseIn.apply(0.5) * std::f64::consts::FRAC_PI_2;
+        let expected_midpoint =
+            expected(8000) * angle.cos() as f32 + expected(16000) * angle.sin() as f32;
+
+        assert!((left_at_time(&stream, 0.75) - expected_midpoint).abs() < 0.01);
+    }
+
     #[test]
     fn fade_through_black_audio_reaches_silence_at_midpoint() {
         let (_dir, mut renderer, project) =
@@ -2366,6 +2887,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             clips: vec![ClipConfiguration {
                 index: 0,
@@ -2405,6 +2927,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             clips: vec![ClipConfiguration {
                 index: 0,
@@ -2530,6 +3053,8 @@ mod tests {
             fade_in,
             fade_out,
             duration: Some(end - start),
+            ducking: None,
+            volume_keyframes: Vec::new(),
         }
     }
 
@@ -2545,6 +3070,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments,
+                volume_keyframes: Default::default(),
             }),
             clips: vec![ClipConfiguration {
                 index: 0,
@@ -2631,6 +3157,47 @@ mod tests {
         assert!((left_at_second(&stream, 2) - expected(8000)).abs() < 0.02);
     }
 
+    #[test]
+    fn ducking_music_is_lowered_only_under_voice() {
+        let _ = ffmpeg::init();
+        let dir = tempfile::tempdir().unwrap();
+        let voice_path = dir.path().join("voice.wav");
+        let music_path = dir.path().join("music.wav");
+        write_step_wav(&voice_path, &[0, 8000, 0]);
+        write_step_wav(&music_path, &[8000, 8000, 8000]);
+
+        let mut music = MusicTracks::new();
+        music.insert(
+            "music.wav".to_string(),
+            Arc::new(AudioData::from_file(&music_path).unwrap()),
+        );
+        let data = vec![AudioSegment {
+            tracks: vec![
+                AudioSegmentTrack::new(
+                    Arc::new(AudioData::from_file(&voice_path).unwrap()),
+                    gain,
+                    stereo,
+                    no_offset,
+                )
+                .as_voice(),
+            ],
+        }];
+        let ducking = MusicDucking::default();
+        let mut project = music_project(vec![music_track_segment("music.wav", 0.0, 3.0, 0.0, 0.0)]);
+        project.timeline.as_mut().unwrap().audio_segments[0].ducking = Some(ducking);
+        let mut renderer = AudioRenderer::new(data).with_music(music);
+
+        let stream = render_export_audio(&mut renderer, &project, 30, 3 * 30);
+
+        let ducked = expected(8000) * 10f32.powf(-ducking.depth_db / 20.0);
+        assert!((left_at_time(&stream, 0.5) - expected(8000)).abs() < 0.02);
+        assert!((left_at_time(&stream, 1.5) - (expected(8000) + ducked)).abs() < 0.02);
+        assert!((left_at_time(&stream, 2.8) - expected(8000)).abs() < 0.02);
+        // Ducking starts ahead of the speech and ends after it.
+        assert!(left_at_time(&stream, 0.95) < expected(8000) - 0.02);
+        assert!(left_at_time(&stream, 2.2) < expected(8000) - 0.02);
+    }
+
     // A linear fade-in ramps the gain from 0 at the clip start to full at
     // `fade_in` seconds. Sampling the mid-point of each output second reveals
     // the ramp.
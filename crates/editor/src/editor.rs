@@ -1,7 +1,7 @@
 use std::sync::Arc;
 use std::time::Instant;
 
-use cap_project::{ClipTransitionType, CursorEvents, ProjectConfiguration};
+use cap_project::{ClipTransitionStyle, CursorEvents, ProjectConfiguration};
 use cap_rendering::{
     DecodedSegmentFrames, FrameLayout, FrameRenderStageTimings, FrameRenderer, Nv12RenderedFrame,
     ProjectUniforms, RenderVideoConstants, RenderedFrame, RendererLayers, TransitionRenderInput,
@@ -26,7 +26,7 @@ pub enum RendererMessage {
     RenderTransition {
         outgoing: RendererTransitionInput,
         incoming: RendererTransitionInput,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
         finished: oneshot::Sender<bool>,
         queued_at: Instant,
@@ -168,7 +168,7 @@ impl Renderer {
             Transition {
                 outgoing: RendererTransitionInput,
                 incoming: Box<RendererTransitionInput>,
-                kind: ClipTransitionType,
+                style: ClipTransitionStyle,
                 progress: f32,
             },
         }
@@ -211,7 +211,7 @@ impl Renderer {
                     Some(RendererMessage::RenderTransition {
                         outgoing,
                         incoming,
-                        kind,
+                        style,
                         progress,
                         finished,
                         queued_at,
@@ -219,7 +219,7 @@ impl Renderer {
                         input: PendingRenderInput::Transition {
                             outgoing,
                             incoming: Box::new(incoming),
-                            kind,
+                            style,
                             progress,
                         },
                         finished,
@@ -274,7 +274,7 @@ impl Renderer {
                     RendererMessage::RenderTransition {
                         outgoing,
                         incoming,
-                        kind,
+                        style,
                         progress,
                         finished,
                         queued_at,
@@ -292,7 +292,7 @@ impl Renderer {
                             input: PendingRenderInput::Transition {
                                 outgoing,
                                 incoming: Box::new(incoming),
-                                kind,
+                                style,
                                 progress,
                             },
                             finished,
@@ -341,7 +341,7 @@ impl Renderer {
                 PendingRenderInput::Transition {
                     outgoing,
                     incoming,
-                    kind,
+                    style,
                     progress,
                 } => frame_renderer
                     .render_transition_immediate(
@@ -357,7 +357,7 @@ impl Renderer {
                             cursor: &incoming.cursor,
                             render_display: true,
                         },
-                        kind,
+                        style,
                         progress,
                         &mut layers,
                     )
@@ -449,7 +449,7 @@ impl RendererHandle {
         &self,
         outgoing: RendererTransitionInput,
         incoming: RendererTransitionInput,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
     ) {
         let (finished_tx, _finished_rx) = oneshot::channel();
@@ -459,7 +459,7 @@ impl RendererHandle {
             .try_send(RendererMessage::RenderTransition {
                 outgoing,
                 incoming,
-                kind,
+                style,
                 progress,
                 finished: finished_tx,
                 queued_at: Instant::now(),
@@ -522,7 +522,7 @@ impl RendererHandle {
         &self,
         outgoing: RendererTransitionInput,
         incoming: RendererTransitionInput,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
     ) -> bool {
         let (finished_tx, finished_rx) = oneshot::channel();
@@ -530,7 +530,7 @@ impl RendererHandle {
         let message = RendererMessage::RenderTransition {
             outgoing,
             incoming,
-            kind,
+            style,
             progress,
             finished: finished_tx,
             queued_at: Instant::now(),
@@ -574,7 +574,7 @@ impl RendererHandle {
         &self,
         outgoing: RendererTransitionInput,
         incoming: RendererTransitionInput,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
     ) -> bool {
         let (finished_tx, finished_rx) = oneshot::channel();
@@ -582,7 +582,7 @@ impl RendererHandle {
         let message = RendererMessage::RenderTransition {
             outgoing,
             incoming,
-            kind,
+            style,
             progress,
             finished: finished_tx,
             queued_at: Instant::now(),
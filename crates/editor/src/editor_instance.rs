@@ -221,6 +221,7 @@ impl EditorInstance {
                     caption_segments: Vec::new(),
                     keyboard_segments: Vec::new(),
                     audio_segments: Vec::new(),
+                    volume_keyframes: Default::default(),
                 });
 
                 if let Err(e) = project.write(&recording_meta.project_path) {
@@ -238,18 +239,18 @@ impl EditorInstance {
                         .segments
                         .iter()
                         .enumerate()
-                        .map(|(i, segment)| {
-                            let calibration_offset = get_calibration_offset(
-                                segment.camera_device_id(),
-                                segment.mic_device_id(),
-                                &calibration_store,
-                            );
-                            cap_project::ClipConfiguration {
-                                index: i as u32,
-                                offsets: segment
-                                    .calculate_audio_offsets_with_calibration(calibration_offset),
-                                offsets_auto_calculated: true,
-                            }
+                        .map(|(i, segment)| cap_project::ClipConfiguration {
+                            index: i as u32,
+                            offsets: segment.calculate_audio_offsets_with_device_calibration(
+                                |mic_id| {
+                                    get_calibration_offset(
+                                        segment.camera_device_id(),
+                                        Some(mic_id),
+                                        &calibration_store,
+                                    )
+                                },
+                            ),
+                            offsets_auto_calculated: true,
                         })
                         .collect();
                 }
@@ -290,10 +291,11 @@ impl EditorInstance {
         // in particular can take seconds to wake.
         let has_declared_audio = match meta.as_ref() {
             StudioRecordingMeta::SingleSegment { segment } => segment.audio.is_some(),
-            StudioRecordingMeta::MultipleSegments { inner } => inner
-                .segments
-                .iter()
-                .any(|s| s.mic.is_some() || s.system_audio.is_some()),
+            StudioRecordingMeta::MultipleSegments { inner } => inner.segments.iter().any(|s| {
+                s.mic.is_some()
+                    || s.additional_mics.iter().any(Option::is_some)
+                    || s.system_audio.is_some()
+            }),
         };
         let has_music = project
             .timeline
@@ -533,10 +535,10 @@ impl EditorInstance {
                         match timeline.get_frame_mapping(frame_time) {
                             Some(TimelineFrameMapping::Transition {
                                 outgoing,
-                                kind,
+                                style,
                                 progress,
                                 ..
-                            }) => Some((outgoing, kind, progress)),
+                            }) => Some((outgoing, style, progress)),
                             _ => None,
                         }
                     });
@@ -554,7 +556,7 @@ impl EditorInstance {
                         .clips
                         .iter()
                         .find(|v| v.index == segment.recording_clip);
-                    let clip_offsets = clip_config.map(|v| v.offsets).unwrap_or_default();
+                    let clip_offsets = clip_config.map(|v| v.offsets.clone()).unwrap_or_default();
 
                     let new_cancel_token = CancellationToken::new();
                     prefetch_cancel_token = Some(new_cancel_token.clone());
@@ -570,7 +572,7 @@ impl EditorInstance {
                             segment_time as f32,
                             !project.camera.hide,
                             true,
-                            clip_offsets,
+                            clip_offsets.clone(),
                         ) => {
                             if preview_rx.has_changed().unwrap_or(false) {
                                 continue;
@@ -602,7 +604,7 @@ impl EditorInstance {
                             zoom_timeline
                                 .ensure_precomputed_until((frame_number as f32 + 1.0) / fps as f32);
 
-                            let outgoing_transition = if let Some((outgoing, kind, progress)) =
+                            let outgoing_transition = if let Some((outgoing, style, progress)) =
                                 transition_mapping
                             {
                                 let outgoing_media =
@@ -611,7 +613,7 @@ impl EditorInstance {
                                     .clips
                                     .iter()
                                     .find(|clip| clip.index == outgoing.segment.recording_clip)
-                                    .map(|clip| clip.offsets)
+                                    .map(|clip| clip.offsets.clone())
                                     .unwrap_or_default();
                                 let outgoing_frames = tokio::select! {
                                     biased;
@@ -652,7 +654,7 @@ impl EditorInstance {
                                         outgoing_frames,
                                         outgoing_uniforms,
                                         outgoing_media.cursor.clone(),
-                                        kind,
+                                        style,
                                         progress as f32,
                                     ))
                                 } else {
@@ -690,7 +692,7 @@ impl EditorInstance {
                                     outgoing_frames,
                                     outgoing_uniforms,
                                     outgoing_cursor,
-                                    kind,
+                                    style,
                                     progress,
                                 )) = &outgoing_transition
                                 {
@@ -706,7 +708,7 @@ impl EditorInstance {
                                                 uniforms,
                                                 cursor: segment_medias.cursor.clone(),
                                             },
-                                            *kind,
+                                            *style,
                                             *progress,
                                         )
                                         .await
@@ -739,7 +741,7 @@ impl EditorInstance {
                                             segment_time as f32,
                                             !project.camera.hide,
                                             true,
-                                            clip_offsets,
+                                            clip_offsets.clone(),
                                         )
                                         .await;
                                 }
@@ -794,7 +796,7 @@ impl EditorInstance {
                                             .find(|v| {
                                                 v.index == prefetch_segment.recording_clip
                                             })
-                                            .map(|v| v.offsets)
+                                            .map(|v| v.offsets.clone())
                                             .unwrap_or_default();
                                         tokio::select! {
                                             biased;
@@ -877,6 +879,7 @@ pub struct EditorState {
 
 pub struct SegmentMedia {
     pub audio: AudioLoader,
+    pub additional_mics: Vec<AudioLoader>,
     pub system_audio: AudioLoader,
     pub audio_timing_repair: SegmentAudioTimingRepair,
     pub cursor: Arc<CursorEvents>,
@@ -936,14 +939,15 @@ impl AudioLoader {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct SegmentAudioTimingRepair {
     pub mic_offset_secs: f32,
+    pub additional_mic_offsets_secs: Vec<f32>,
     pub system_audio_offset_secs: f32,
 }
 
 #[derive(Clone, Copy)]
-enum LegacyAudioLogTrack {
+pub(crate) enum LegacyAudioLogTrack {
     Mic,
     SystemAudio,
 }
@@ -957,19 +961,19 @@ impl LegacyAudioLogTrack {
     }
 }
 
-struct LegacyAudioTimingRepair {
+pub(crate) struct LegacyAudioTimingRepair {
     log: Option<String>,
 }
 
 impl LegacyAudioTimingRepair {
-    fn load(project_path: &Path) -> Self {
+    pub(crate) fn load(project_path: &Path) -> Self {
         let log_path = project_path.join("recording-logs.log");
         Self {
             log: std::fs::read_to_string(log_path).ok(),
         }
     }
 
-    fn offset(
+    pub(crate) fn offset(
         &self,
         segment_index: usize,
         track: LegacyAudioLogTrack,
@@ -1071,7 +1075,7 @@ fn legacy_audio_gap_summary_from_log(
     (summary.total_overlap_trimmed_ms > 0).then_some(summary)
 }
 
-fn audio_timing_repair_offset(summary: Option<&cap_project::AudioGapSummary>) -> f32 {
+pub(crate) fn audio_timing_repair_offset(summary: Option<&cap_project::AudioGapSummary>) -> f32 {
     let Some(summary) = summary else {
         return 0.0;
     };
@@ -1143,6 +1147,7 @@ pub async fn create_segments(
 
             Ok(vec![SegmentMedia {
                 audio,
+                additional_mics: Vec::new(),
                 system_audio: AudioLoader::none(),
                 audio_timing_repair: SegmentAudioTimingRepair {
                     mic_offset_secs: legacy_timing_repair.offset(
@@ -1150,6 +1155,7 @@ pub async fn create_segments(
                         LegacyAudioLogTrack::Mic,
                         s.audio.as_ref().and_then(|m| m.gap_summary.as_ref()),
                     ),
+                    additional_mic_offsets_secs: Vec::new(),
                     system_audio_offset_secs: 0.0,
                 },
                 cursor,
@@ -1173,6 +1179,23 @@ pub async fn create_segments(
                     })
                     .unwrap_or_else(AudioLoader::none);
 
+                let additional_mics = s
+                    .additional_mics
+                    .iter()
+                    .enumerate()
+                    .map(|(mic_index, audio)| {
+                        audio
+                            .as_ref()
+                            .map(|audio| {
+                                AudioLoader::spawn(
+                                    recording_meta.path(&audio.path),
+                                    format!("MultipleSegments {i} Audio {}", mic_index + 2),
+                                )
+                            })
+                            .unwrap_or_else(AudioLoader::none)
+                    })
+                    .collect();
+
                 let system_audio = s
                     .system_audio
                     .as_ref()
@@ -1203,6 +1226,7 @@ pub async fn create_segments(
 
                 Ok::<SegmentMedia, String>(SegmentMedia {
                     audio,
+                    additional_mics,
                     system_audio,
                     audio_timing_repair: SegmentAudioTimingRepair {
                         mic_offset_secs: legacy_timing_repair.offset(
@@ -1210,6 +1234,15 @@ pub async fn create_segments(
                             LegacyAudioLogTrack::Mic,
                             s.mic.as_ref().and_then(|m| m.gap_summary.as_ref()),
                         ),
+                        additional_mic_offsets_secs: s
+                            .additional_mics
+                            .iter()
+                            .map(|m| {
+                                audio_timing_repair_offset(
+                                    m.as_ref().and_then(|m| m.gap_summary.as_ref()),
+                                )
+                            })
+                            .collect(),
                         system_audio_offset_secs: legacy_timing_repair.offset(
                             i,
                             LegacyAudioLogTrack::SystemAudio,
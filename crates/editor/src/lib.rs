@@ -4,6 +4,7 @@ mod editor;
 mod editor_instance;
 mod playback;
 mod segments;
+mod silence;
 mod telemetry;
 
 pub use audio::{AudioRenderer, MusicTracks};
@@ -19,7 +20,10 @@ pub use editor_instance::{
     AudioLoader, EditorInstance, EditorState, SegmentMedia, create_segments,
 };
 pub use playback::{Playback, PlaybackEvent, PlaybackHandle, PlaybackStartError};
-pub use segments::{get_audio_segments, load_music_tracks, load_music_tracks_uncached};
+pub use segments::{
+    get_audio_segments, load_music_tracks, load_music_tracks_uncached, recording_audio_segments,
+};
+pub use silence::recording_audible_ranges;
 pub use telemetry::{
     PlaybackFrameSource, PlaybackRenderOutputFormat, PlaybackSkipReason, PlaybackTelemetry,
     PlaybackTelemetryEvent,
@@ -1,5 +1,5 @@
 use cap_project::{
-    ClipOffsets, ClipTransitionType, ProjectConfiguration, TimelineFrameMapping, XY,
+    ClipOffsets, ClipTransitionStyle, ProjectConfiguration, TimelineFrameMapping, XY,
 };
 use cap_rendering::{
     DecodedSegmentFrames, PrecomputedCursorTimeline, ProjectUniforms, RecordingSegmentDecoders,
@@ -131,7 +131,7 @@ struct PrefetchedFrame {
 struct PrefetchedTransition {
     segment_frames: DecodedSegmentFrames,
     segment_index: u32,
-    kind: ClipTransitionType,
+    style: ClipTransitionStyle,
     progress: f32,
 }
 
@@ -144,7 +144,7 @@ impl PrefetchedFrame {
                 (
                     Arc::new(transition.segment_frames),
                     transition.segment_index,
-                    transition.kind,
+                    transition.style,
                     transition.progress,
                 )
             }),
@@ -152,7 +152,7 @@ impl PrefetchedFrame {
     }
 }
 
-type CachedTransition = (Arc<DecodedSegmentFrames>, u32, ClipTransitionType, f32);
+type CachedTransition = (Arc<DecodedSegmentFrames>, u32, ClipTransitionStyle, f32);
 type CachedFrame = (Arc<DecodedSegmentFrames>, u32, Option<CachedTransition>);
 
 struct FrameCache {
@@ -174,11 +174,11 @@ impl FrameCache {
                     Arc::clone(frames),
                     *segment_index,
                     transition.as_ref().map(
-                        |(transition_frames, transition_index, kind, progress)| {
+                        |(transition_frames, transition_index, style, progress)| {
                             (
                                 Arc::clone(transition_frames),
                                 *transition_index,
-                                *kind,
+                                *style,
                                 *progress,
                             )
                         },
@@ -222,7 +222,7 @@ struct TransitionDecodeRequest {
     segment_time: f64,
     segment_index: u32,
     offsets: ClipOffsets,
-    kind: ClipTransitionType,
+    style: ClipTransitionStyle,
     progress: f32,
 }
 
@@ -292,7 +292,7 @@ async fn decode_prefetched_frame(request: PrefetchDecodeRequest) -> PrefetchDeco
         Some(PrefetchedTransition {
             segment_frames,
             segment_index: transition.segment_index,
-            kind: transition.kind,
+            style: transition.style,
             progress: transition.progress,
         })
     };
@@ -312,7 +312,7 @@ fn transition_decode_request(
     }
     let TimelineFrameMapping::Transition {
         outgoing,
-        kind,
+        style,
         progress,
         ..
     } = timeline.get_frame_mapping(frame_time)?
@@ -324,7 +324,7 @@ fn transition_decode_request(
         .clips
         .iter()
         .find(|clip| clip.index == outgoing.segment.recording_clip)
-        .map(|clip| clip.offsets)
+        .map(|clip| clip.offsets.clone())
         .unwrap_or_default();
 
     Some(TransitionDecodeRequest {
@@ -332,7 +332,7 @@ fn transition_decode_request(
         segment_time: outgoing.source_time,
         segment_index: outgoing.segment.recording_clip,
         offsets,
-        kind,
+        style,
         progress: progress as f32,
     })
 }
@@ -483,7 +483,7 @@ impl Playback {
                             .clips
                             .iter()
                             .find(|v| v.index == segment.recording_clip)
-                            .map(|v| v.offsets)
+                            .map(|v| v.offsets.clone())
                             .unwrap_or_default();
 
                         let decoders = segment_media.decoders.clone();
@@ -771,7 +771,7 @@ impl Playback {
                     let uniforms_duration = uniforms_start.elapsed();
                     let submit_start = Instant::now();
                     let submitted_frame_number = frame_number;
-                    let rendered = if let Some((outgoing_frames, outgoing_index, kind, progress)) =
+                    let rendered = if let Some((outgoing_frames, outgoing_index, style, progress)) =
                         transition
                     {
                         let outgoing_media = &self.segment_medias[outgoing_index as usize];
@@ -804,7 +804,7 @@ impl Playback {
                                 uniforms,
                                 cursor: segment_media.cursor.clone(),
                             },
-                            kind,
+                            style,
                             progress,
                         )
                     } else {
@@ -1168,8 +1168,8 @@ impl Playback {
                             Arc::clone(&segment_frames),
                             segment_index,
                             transition.as_ref().map(
-                                |(frames, transition_index, kind, progress)| {
-                                    (Arc::clone(frames), *transition_index, *kind, *progress)
+                                |(frames, transition_index, style, progress)| {
+                                    (Arc::clone(frames), *transition_index, *style, *progress)
                                 },
                             ),
                         );
@@ -1219,7 +1219,7 @@ impl Playback {
                     let uniforms_duration = uniforms_start.elapsed();
                     let submit_start = Instant::now();
                     let submitted_frame_number = frame_number;
-                    if let Some((outgoing_frames, outgoing_index, kind, progress)) = transition {
+                    if let Some((outgoing_frames, outgoing_index, style, progress)) = transition {
                         let outgoing_media = &self.segment_medias[outgoing_index as usize];
                         if let Some(timeline) =
                             outgoing_zoom_timelines.get_mut(outgoing_index as usize)
@@ -1249,7 +1249,7 @@ impl Playback {
                                 uniforms,
                                 cursor: segment_media.cursor.clone(),
                             },
-                            kind,
+                            style,
                             progress,
                         );
                     } else {
@@ -1,12 +1,16 @@
 use std::{path::Path, sync::Arc};
 
 use cap_audio::AudioData;
-use cap_project::ProjectConfiguration;
+use cap_project::{AudioMeta, ProjectConfiguration, RecordingMeta, StudioRecordingMeta};
 use tracing::warn;
 
 use crate::{
     SegmentMedia,
     audio::{AudioSegment, AudioSegmentTrack, MusicTracks},
+    editor_instance::{
+        LegacyAudioLogTrack, LegacyAudioTimingRepair, SegmentAudioTimingRepair,
+        audio_timing_repair_offset,
+    },
 };
 
 fn resolve_music_path(project_path: &Path, path: &str) -> std::path::PathBuf {
@@ -95,38 +99,179 @@ pub async fn get_audio_segments(segments: &[SegmentMedia]) -> Vec<AudioSegment>
 
     for s in segments {
         let audio = loaded_track(&s.audio, "mic audio").await;
+        let mut additional_mics = Vec::with_capacity(s.additional_mics.len());
+        for loader in &s.additional_mics {
+            additional_mics.push(loaded_track(loader, "additional mic audio").await);
+        }
         let system_audio = loaded_track(&s.system_audio, "system audio").await;
 
-        out.push(AudioSegment {
-            tracks: [
-                audio.map(|a| {
-                    AudioSegmentTrack::new(
-                        a,
-                        |c| c.mic_volume_db,
-                        |c| match c.mic_stereo_mode {
-                            cap_project::StereoMode::Stereo => cap_audio::StereoMode::Stereo,
-                            cap_project::StereoMode::MonoL => cap_audio::StereoMode::MonoL,
-                            cap_project::StereoMode::MonoR => cap_audio::StereoMode::MonoR,
-                        },
-                        |o| o.mic,
-                    )
-                    .with_timing_offset_secs(s.audio_timing_repair.mic_offset_secs)
-                }),
-                system_audio.map(|a| -> AudioSegmentTrack {
-                    AudioSegmentTrack::new(
-                        a,
-                        |c| c.system_volume_db,
-                        |_| cap_audio::StereoMode::Stereo,
-                        |o| o.system_audio,
-                    )
-                    .with_timing_offset_secs(s.audio_timing_repair.system_audio_offset_secs)
-                }),
-            ]
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>(),
-        });
+        out.push(audio_segment(
+            audio,
+            additional_mics,
+            system_audio,
+            s.audio_timing_repair.clone(),
+        ));
     }
 
     out
 }
+
+/// The recording audio of every segment, decoded up front, for working with a
+/// project's mix without opening its video. Unlike [`get_audio_segments`], a
+/// track that fails to decode is an error. Blocks while decoding.
+pub fn recording_audio_segments(
+    recording_meta: &RecordingMeta,
+    meta: &StudioRecordingMeta,
+) -> Result<Vec<AudioSegment>, String> {
+    let timing_repair = LegacyAudioTimingRepair::load(&recording_meta.project_path);
+    let decode = |audio: Option<&AudioMeta>| {
+        audio
+            .map(|audio| {
+                let path = recording_meta.path(&audio.path);
+                AudioData::from_file(&path)
+                    .map(Arc::new)
+                    .map_err(|e| format!("Failed to decode {}: {e}", path.display()))
+            })
+            .transpose()
+    };
+    let offset = |index: usize, track: LegacyAudioLogTrack, audio: Option<&AudioMeta>| {
+        timing_repair.offset(
+            index,
+            track,
+            audio.and_then(|audio| audio.gap_summary.as_ref()),
+        )
+    };
+
+    let tracks: Vec<(Option<&AudioMeta>, &[Option<AudioMeta>], Option<&AudioMeta>)> = match meta {
+        StudioRecordingMeta::SingleSegment { segment } => {
+            vec![(segment.audio.as_ref(), &[], None)]
+        }
+        StudioRecordingMeta::MultipleSegments { inner } => inner
+            .segments
+            .iter()
+            .map(|segment| {
+                (
+                    segment.mic.as_ref(),
+                    segment.additional_mics.as_slice(),
+                    segment.system_audio.as_ref(),
+                )
+            })
+            .collect(),
+    };
+
+    tracks
+        .into_iter()
+        .enumerate()
+        .map(|(index, (mic, additional_mics, system_audio))| {
+            Ok(audio_segment(
+                decode(mic)?,
+                additional_mics
+                    .iter()
+                    .map(|mic| decode(mic.as_ref()))
+                    .collect::<Result<_, _>>()?,
+                decode(system_audio)?,
+                SegmentAudioTimingRepair {
+                    mic_offset_secs: offset(index, LegacyAudioLogTrack::Mic, mic),
+                    additional_mic_offsets_secs: additional_mics
+                        .iter()
+                        .map(|mic| {
+                            audio_timing_repair_offset(
+                                mic.as_ref().and_then(|mic| mic.gap_summary.as_ref()),
+                            )
+                        })
+                        .collect(),
+                    system_audio_offset_secs: offset(
+                        index,
+                        LegacyAudioLogTrack::SystemAudio,
+                        system_audio,
+                    ),
+                },
+            ))
+        })
+        .collect()
+}
+
+fn audio_segment(
+    mic: Option<Arc<AudioData>>,
+    additional_mics: Vec<Option<Arc<AudioData>>>,
+    system_audio: Option<Arc<AudioData>>,
+    timing_repair: SegmentAudioTimingRepair,
+) -> AudioSegment {
+    let mic = mic.map(|a| {
+        AudioSegmentTrack::new(
+            a,
+            |c| {
+                if c.mic_mute {
+                    f32::NEG_INFINITY
+                } else {
+                    c.mic_volume_db
+                }
+            },
+            |c| stereo_mode(&c.mic_stereo_mode),
+            |o| o.mic,
+        )
+        .with_timing_offset_secs(timing_repair.mic_offset_secs)
+        .with_volume_keyframes(|keyframes| &keyframes.mic)
+        .as_voice()
+    });
+    // Each additional microphone reads its own entry in the project's audio
+    // settings and clip offsets, so tracks are matched by recorded position.
+    // Volume keyframes are shared with the main microphone.
+    let additional_mics = additional_mics
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, data)| {
+            data.map(|data| {
+                AudioSegmentTrack::new(
+                    data,
+                    move |c| match c.additional_mic(index) {
+                        Some(mic) if mic.mute => f32::NEG_INFINITY,
+                        Some(mic) => mic.volume_db,
+                        None => 0.0,
+                    },
+                    move |c| {
+                        c.additional_mic(index)
+                            .map_or(cap_audio::StereoMode::Stereo, |mic| {
+                                stereo_mode(&mic.stereo_mode)
+                            })
+                    },
+                    move |o| o.additional_mic(index),
+                )
+                .with_timing_offset_secs(
+                    timing_repair
+                        .additional_mic_offsets_secs
+                        .get(index)
+                        .copied()
+                        .unwrap_or_default(),
+                )
+                .with_volume_keyframes(|keyframes| &keyframes.mic)
+                .as_voice()
+            })
+        });
+    let system_audio = system_audio.map(|a| -> AudioSegmentTrack {
+        AudioSegmentTrack::new(
+            a,
+            |c| c.system_volume_db,
+            |_| cap_audio::StereoMode::Stereo,
+            |o| o.system_audio,
+        )
+        .with_timing_offset_secs(timing_repair.system_audio_offset_secs)
+        .with_volume_keyframes(|keyframes| &keyframes.system_audio)
+    });
+
+    AudioSegment {
+        tracks: mic
+            .into_iter()
+            .chain(additional_mics)
+            .chain(system_audio)
+            .collect(),
+    }
+}
+
+fn stereo_mode(mode: &cap_project::StereoMode) -> cap_audio::StereoMode {
+    match mode {
+        cap_project::StereoMode::Stereo => cap_audio::StereoMode::Stereo,
+        cap_project::StereoMode::MonoL => cap_audio::StereoMode::MonoL,
+        cap_project::StereoMode::MonoR => cap_audio::StereoMode::MonoR,
+    }
+}
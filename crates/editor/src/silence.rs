@@ -0,0 +1,110 @@
+use cap_audio::{AudioData, audible_ranges};
+use cap_project::{AudioMeta, ProjectConfiguration, RecordingMeta, StudioRecordingMeta};
+
+use crate::editor_instance::{
+    LegacyAudioLogTrack, LegacyAudioTimingRepair, audio_timing_repair_offset,
+};
+
+/// Where each recording segment's microphones and system audio are at or above
+/// `threshold_db`, ready for `TimelineConfiguration::without_silences`.
+///
+/// Each track is shifted by the clip offsets and timing repair it is played
+/// back with, so the ranges are on the clock `TimelineSegment::start` uses and
+/// cuts land where the audio is actually heard. A segment with no audio
+/// tracks is `None`. Decodes every track, so call it off the async runtime.
+pub fn recording_audible_ranges(
+    recording_meta: &RecordingMeta,
+    meta: &StudioRecordingMeta,
+    project: &ProjectConfiguration,
+    threshold_db: f32,
+) -> Result<Vec<Option<Vec<(f64, f64)>>>, String> {
+    let timing_repair = LegacyAudioTimingRepair::load(&recording_meta.project_path);
+    let clip_offsets = |index: usize| {
+        project
+            .clips
+            .iter()
+            .find(|clip| clip.index == index as u32)
+            .map(|clip| clip.offsets.clone())
+            .unwrap_or_default()
+    };
+    let offset = |index: usize, track: LegacyAudioLogTrack, audio: &AudioMeta| {
+        let offsets = clip_offsets(index);
+        let clip_offset = match track {
+            LegacyAudioLogTrack::Mic => offsets.mic,
+            LegacyAudioLogTrack::SystemAudio => offsets.system_audio,
+        };
+        clip_offset + timing_repair.offset(index, track, audio.gap_summary.as_ref())
+    };
+
+    let segments: Vec<Vec<(&AudioMeta, f32)>> = match meta {
+        StudioRecordingMeta::SingleSegment { segment } => vec![
+            segment
+                .audio
+                .iter()
+                .map(|audio| (audio, offset(0, LegacyAudioLogTrack::Mic, audio)))
+                .collect(),
+        ],
+        StudioRecordingMeta::MultipleSegments { inner } => inner
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                let mic = segment
+                    .mic
+                    .iter()
+                    .map(|audio| (audio, offset(index, LegacyAudioLogTrack::Mic, audio)));
+                let additional_mics = segment
+                    .additional_mics
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(mic_index, audio)| Some((mic_index, audio.as_ref()?)))
+                    .map(|(mic_index, audio)| {
+                        (
+                            audio,
+                            clip_offsets(index).additional_mic(mic_index)
+                                + audio_timing_repair_offset(audio.gap_summary.as_ref()),
+                        )
+                    });
+                let system_audio = segment.system_audio.iter().map(|audio| {
+                    (
+                        audio,
+                        offset(index, LegacyAudioLogTrack::SystemAudio, audio),
+                    )
+                });
+                mic.chain(additional_mics).chain(system_audio).collect()
+            })
+            .collect(),
+    };
+
+    segments
+        .into_iter()
+        .map(|tracks| {
+            if tracks.is_empty() {
+                return Ok(None);
+            }
+
+            let mut ranges = Vec::new();
+            for (audio, offset) in tracks {
+                let path = recording_meta.path(&audio.path);
+                let data = AudioData::from_file(&path)
+                    .map_err(|e| format!("Failed to decode {}: {e}", path.display()))?;
+                // The renderer plays source time `t + offset` at clip time `t`.
+                ranges.extend(
+                    audible_ranges(&data, threshold_db)
+                        .into_iter()
+                        .map(|(start, end)| (start - offset as f64, end - offset as f64)),
+                );
+            }
+
+            ranges.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let mut merged: Vec<(f64, f64)> = Vec::with_capacity(ranges.len());
+            for (start, end) in ranges {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            Ok(Some(merged))
+        })
+        .collect()
+}
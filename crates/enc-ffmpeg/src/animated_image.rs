@@ -0,0 +1,330 @@
+use std::{path::Path, time::Duration};
+
+use ffmpeg::{
+    Dictionary,
+    codec::{context, encoder},
+    format, frame,
+};
+use tracing::debug;
+
+use crate::base::EncoderBase;
+
+/// Looping image formats that, unlike GIF, keep full colour and 8-bit alpha.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnimatedImageFormat {
+    WebP,
+    Apng,
+}
+
+impl AnimatedImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::WebP => "webp",
+            Self::Apng => "png",
+        }
+    }
+
+    fn muxer(self) -> &'static str {
+        match self {
+            Self::WebP => "webp",
+            Self::Apng => "apng",
+        }
+    }
+
+    /// FFmpeg encoders to try, preferred first. Plain `libwebp` can only
+    /// write animations through the muxer, which re-encodes every frame as a
+    /// keyframe, so it is the fallback.
+    fn encoder_names(self) -> &'static [&'static str] {
+        match self {
+            Self::WebP => &["libwebp_anim", "libwebp"],
+            Self::Apng => &["apng"],
+        }
+    }
+
+    fn pixel_format(self, alpha: bool) -> format::Pixel {
+        match (self, alpha) {
+            (Self::WebP, true) => format::Pixel::YUVA420P,
+            (Self::WebP, false) => format::Pixel::YUV420P,
+            (Self::Apng, true) => format::Pixel::RGBA,
+            (Self::Apng, false) => format::Pixel::RGB24,
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AnimatedImageError {
+    #[error("{0:?}")]
+    FFmpeg(#[from] ffmpeg::Error),
+    #[error("No {0:?} encoder available in this FFmpeg build")]
+    EncoderNotFound(AnimatedImageFormat),
+    #[error("Invalid frame data")]
+    InvalidFrameData,
+    #[error("Encoder already finished")]
+    EncoderFinished,
+}
+
+#[derive(Clone, Debug)]
+pub struct AnimatedImageOptions {
+    /// Encoding quality from 1-100 (default: 90). APNG is lossless, so only
+    /// WebP uses this.
+    pub quality: u8,
+    /// Whether to prioritize speed over file size (default: false)
+    pub fast: bool,
+    /// Whether to keep the alpha channel of the input frames (default: true)
+    pub alpha: bool,
+}
+
+impl Default for AnimatedImageOptions {
+    fn default() -> Self {
+        Self {
+            quality: 90,
+            fast: false,
+            alpha: true,
+        }
+    }
+}
+
+/// Encodes RGBA frames into an infinitely looping WebP or APNG, with the same
+/// frame-push API as `cap_enc_gif::GifEncoderWrapper`.
+pub struct AnimatedImageEncoder {
+    output: format::context::Output,
+    base: EncoderBase,
+    encoder: encoder::Video,
+    converter: ffmpeg::software::scaling::Context,
+    input_frame: frame::Video,
+    converted_frame: frame::Video,
+    width: u32,
+    height: u32,
+    frame_index: u32,
+    fps: u32,
+    finished: bool,
+}
+
+impl AnimatedImageEncoder {
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        format: AnimatedImageFormat,
+        width: u32,
+        height: u32,
+        fps: u32,
+    ) -> Result<Self, AnimatedImageError> {
+        Self::new_with_options(
+            path,
+            format,
+            width,
+            height,
+            fps,
+            AnimatedImageOptions::default(),
+        )
+    }
+
+    pub fn new_with_options<P: AsRef<Path>>(
+        path: P,
+        format: AnimatedImageFormat,
+        width: u32,
+        height: u32,
+        fps: u32,
+        options: AnimatedImageOptions,
+    ) -> Result<Self, AnimatedImageError> {
+        if fps == 0 || width == 0 || height == 0 {
+            return Err(AnimatedImageError::InvalidFrameData);
+        }
+
+        let (codec, codec_name) = format
+            .encoder_names()
+            .iter()
+            .find_map(|name| encoder::find_by_name(name).map(|codec| (codec, *name)))
+            .ok_or(AnimatedImageError::EncoderNotFound(format))?;
+        debug!("Using {format:?} encoder {codec_name}");
+
+        let mut output = format::output_as(&path, format.muxer())?;
+
+        let output_format = format.pixel_format(options.alpha);
+        let time_base = ffmpeg::Rational::new(1, fps as i32);
+
+        let mut encoder = context::Context::new_with_codec(codec).encoder().video()?;
+        encoder.set_width(width);
+        encoder.set_height(height);
+        encoder.set_format(output_format);
+        encoder.set_time_base(time_base);
+        encoder.set_frame_rate(Some(ffmpeg::Rational::new(fps as i32, 1)));
+
+        let encoder = encoder.open_with(encoder_options(format, &options))?;
+
+        let mut output_stream = output.add_stream(codec)?;
+        let stream_index = output_stream.index();
+        output_stream.set_time_base(time_base);
+        output_stream.set_rate(ffmpeg::Rational::new(fps as i32, 1));
+        output_stream.set_parameters(&encoder);
+
+        // Both muxers default to playing once.
+        let mut muxer_options = Dictionary::new();
+        match format {
+            AnimatedImageFormat::WebP => muxer_options.set("loop", "0"),
+            AnimatedImageFormat::Apng => muxer_options.set("plays", "0"),
+        }
+        output.write_header_with(muxer_options)?;
+
+        let converter = ffmpeg::software::scaling::Context::get(
+            format::Pixel::RGBA,
+            width,
+            height,
+            output_format,
+            width,
+            height,
+            ffmpeg::software::scaling::flag::Flags::BICUBIC,
+        )?;
+
+        Ok(Self {
+            output,
+            base: EncoderBase::new(stream_index),
+            encoder,
+            converter,
+            input_frame: frame::Video::new(format::Pixel::RGBA, width, height),
+            converted_frame: frame::Video::new(output_format, width, height),
+            width,
+            height,
+            frame_index: 0,
+            fps,
+            finished: false,
+        })
+    }
+
+    pub fn add_frame(
+        &mut self,
+        frame_data: &[u8],
+        bytes_per_row: usize,
+    ) -> Result<(), AnimatedImageError> {
+        if self.finished {
+            return Err(AnimatedImageError::EncoderFinished);
+        }
+
+        let w = self.width as usize;
+        let h = self.height as usize;
+        let expected_bytes_per_row = w * 4;
+
+        if bytes_per_row < expected_bytes_per_row
+            || frame_data.len() < bytes_per_row * h.saturating_sub(1) + expected_bytes_per_row
+        {
+            return Err(AnimatedImageError::InvalidFrameData);
+        }
+
+        let dst_stride = self.input_frame.stride(0);
+        let dst = self.input_frame.data_mut(0);
+        for y in 0..h {
+            let src_start = y * bytes_per_row;
+            let dst_start = y * dst_stride;
+            dst[dst_start..dst_start + expected_bytes_per_row]
+                .copy_from_slice(&frame_data[src_start..src_start + expected_bytes_per_row]);
+        }
+
+        self.converter
+            .run(&self.input_frame, &mut self.converted_frame)?;
+        self.converted_frame.set_pts(Some(self.frame_index as i64));
+        self.base
+            .update_pts(&mut self.converted_frame, Duration::MAX, &mut self.encoder);
+        self.base
+            .send_frame(&self.converted_frame, &mut self.output, &mut self.encoder)?;
+
+        self.frame_index += 1;
+        Ok(())
+    }
+
+    /// Flush the encoder and write the file trailer
+    pub fn finish(mut self) -> Result<(), AnimatedImageError> {
+        self.finish_inner()
+    }
+
+    fn finish_inner(&mut self) -> Result<(), AnimatedImageError> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.finished = true;
+
+        self.base.process_eof(&mut self.output, &mut self.encoder)?;
+        self.output.write_trailer()?;
+
+        Ok(())
+    }
+
+    /// Get the current frame count
+    pub fn frame_count(&self) -> u32 {
+        self.frame_index
+    }
+
+    /// Get the dimensions of the animation
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Get the target FPS
+    pub fn fps(&self) -> u32 {
+        self.fps
+    }
+}
+
+fn encoder_options(
+    format: AnimatedImageFormat,
+    options: &AnimatedImageOptions,
+) -> Dictionary<'static> {
+    let mut dict = Dictionary::new();
+
+    match format {
+        AnimatedImageFormat::WebP => {
+            dict.set("quality", &options.quality.clamp(1, 100).to_string());
+            dict.set("compression_level", if options.fast { "2" } else { "5" });
+            if options.quality >= 100 {
+                dict.set("lossless", "1");
+            }
+        }
+        AnimatedImageFormat::Apng => {
+            dict.set("pred", if options.fast { "none" } else { "mixed" });
+        }
+    }
+
+    dict
+}
+
+impl Drop for AnimatedImageEncoder {
+    fn drop(&mut self) {
+        let _ = self.finish_inner();
+    }
+}
+
+unsafe impl Send for AnimatedImageEncoder {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alpha_selects_a_pixel_format_with_an_alpha_plane() {
+        assert_eq!(
+            AnimatedImageFormat::WebP.pixel_format(true),
+            format::Pixel::YUVA420P
+        );
+        assert_eq!(
+            AnimatedImageFormat::Apng.pixel_format(true),
+            format::Pixel::RGBA
+        );
+        assert_eq!(
+            AnimatedImageFormat::Apng.pixel_format(false),
+            format::Pixel::RGB24
+        );
+    }
+
+    #[test]
+    fn rejects_zero_sized_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = AnimatedImageEncoder::new(
+            dir.path().join("out.webp"),
+            AnimatedImageFormat::WebP,
+            0,
+            64,
+            30,
+        );
+
+        assert!(matches!(result, Err(AnimatedImageError::InvalidFrameData)));
+    }
+}
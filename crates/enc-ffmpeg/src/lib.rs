@@ -9,6 +9,7 @@ pub use video::*;
 mod mux;
 pub use mux::*;
 
+pub mod animated_image;
 pub mod remux;
 pub mod dash_audio {
     pub use crate::mux::dash_audio::*;
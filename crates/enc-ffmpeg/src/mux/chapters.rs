@@ -0,0 +1,31 @@
+use ffmpeg::format;
+use std::time::Duration;
+
+/// A titled span of the output, written as a chapter by the mp4 and mov muxers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub start: Duration,
+    pub end: Duration,
+    pub title: String,
+}
+
+/// Registers `chapters` with the muxer. The mov muxer sizes its chapter track
+/// when the header is written, so this has to happen before that.
+pub(crate) fn add_chapters(
+    output: &mut format::context::Output,
+    chapters: &[Chapter],
+) -> Result<(), ffmpeg::Error> {
+    let time_base = ffmpeg::Rational::new(1, 1000);
+
+    for (id, chapter) in chapters.iter().enumerate() {
+        output.add_chapter(
+            id as i64,
+            time_base,
+            chapter.start.as_millis() as i64,
+            chapter.end.as_millis() as i64,
+            &chapter.title,
+        )?;
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+pub mod chapters;
 pub mod dash_audio;
 pub mod fragment_manifest;
 pub mod fragmented_audio;
@@ -7,3 +8,4 @@ pub mod mp4;
 pub mod ogg;
 pub mod segmented_audio;
 pub mod segmented_stream;
+pub mod webm;
@@ -1,7 +1,10 @@
 use ffmpeg::{format, frame};
 use std::{path::PathBuf, time::Duration};
 
-use crate::video::prores::{ProResEncoder, ProResEncoderError};
+use crate::{
+    mux::chapters::{Chapter, add_chapters},
+    video::prores::{ProResEncoder, ProResEncoderError},
+};
 
 pub struct MOVFile {
     output: format::context::Output,
@@ -27,7 +30,15 @@ pub enum FinishError {
 
 impl MOVFile {
     pub fn init(
+        output: PathBuf,
+        video: impl FnOnce(&mut format::context::Output) -> Result<ProResEncoder, ProResEncoderError>,
+    ) -> Result<Self, InitError> {
+        Self::init_with_chapters(output, &[], video)
+    }
+
+    pub fn init_with_chapters(
         mut output: PathBuf,
+        chapters: &[Chapter],
         video: impl FnOnce(&mut format::context::Output) -> Result<ProResEncoder, ProResEncoderError>,
     ) -> Result<Self, InitError> {
         output.set_extension("mov");
@@ -38,6 +49,7 @@ impl MOVFile {
 
         let mut output = format::output_as(&output, "mov").map_err(InitError::Ffmpeg)?;
         let video = video(&mut output).map_err(InitError::VideoInit)?;
+        add_chapters(&mut output, chapters).map_err(InitError::Ffmpeg)?;
 
         output.write_header().map_err(InitError::Ffmpeg)?;
 
@@ -6,6 +6,7 @@ use tracing::*;
 use crate::{
     audio::AudioEncoder,
     h264,
+    mux::chapters::{Chapter, add_chapters},
     video::h264::{H264Encoder, H264EncoderError},
 };
 
@@ -43,9 +44,23 @@ pub struct FinishResult {
 
 impl MP4File {
     pub fn init(
+        tag: &'static str,
+        output: PathBuf,
+        faststart: bool,
+        video: impl FnOnce(&mut format::context::Output) -> Result<H264Encoder, H264EncoderError>,
+        audio: impl FnOnce(
+            &mut format::context::Output,
+        )
+            -> Option<Result<Box<dyn AudioEncoder + Send>, Box<dyn std::error::Error>>>,
+    ) -> Result<Self, InitError> {
+        Self::init_with_chapters(tag, output, faststart, &[], video, audio)
+    }
+
+    pub fn init_with_chapters(
         tag: &'static str,
         mut output: PathBuf,
         faststart: bool,
+        chapters: &[Chapter],
         video: impl FnOnce(&mut format::context::Output) -> Result<H264Encoder, H264EncoderError>,
         audio: impl FnOnce(
             &mut format::context::Output,
@@ -69,6 +84,8 @@ impl MP4File {
 
         info!("Prepared encoders for mp4 file");
 
+        add_chapters(&mut output, chapters).map_err(InitError::Ffmpeg)?;
+
         if faststart {
             let mut opts = ffmpeg::Dictionary::new();
             opts.set("movflags", "+faststart");
@@ -31,6 +31,7 @@ pub struct H264EncoderBuilder {
     encoder_priority_override: Option<&'static [&'static str]>,
     is_export: bool,
     crf: Option<u8>,
+    global_header: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -77,6 +78,7 @@ impl H264EncoderBuilder {
             encoder_priority_override: None,
             is_export: false,
             crf: None,
+            global_header: false,
         }
     }
 
@@ -126,6 +128,13 @@ impl H264EncoderBuilder {
         self
     }
 
+    /// Put SPS/PPS in the codec extradata rather than in-band, as containers
+    /// like FLV require.
+    pub fn with_global_header(mut self) -> Self {
+        self.global_header = true;
+        self
+    }
+
     pub fn build(
         self,
         output: &mut format::context::Output,
@@ -194,6 +203,7 @@ impl H264EncoderBuilder {
                 self.bpp,
                 self.external_conversion,
                 self.crf,
+                self.global_header,
             ) {
                 Ok(encoder) => {
                     let is_hardware = is_hardware_h264(&codec_name);
@@ -252,6 +262,7 @@ impl H264EncoderBuilder {
         bpp: f32,
         external_conversion: bool,
         crf: Option<u8>,
+        global_header: bool,
     ) -> Result<H264Encoder, H264EncoderError> {
         let OpenedVideoEncoder {
             encoder,
@@ -263,7 +274,7 @@ impl H264EncoderBuilder {
             input_format,
             input_width,
             input_height,
-        } = open_video_encoder(
+        } = open_video_encoder_with_flags(
             codec,
             encoder_options,
             input_config,
@@ -272,6 +283,7 @@ impl H264EncoderBuilder {
             bpp,
             external_conversion,
             crf,
+            global_header,
         )?;
 
         let mut output_stream = output.add_stream(codec)?;
@@ -427,30 +439,6 @@ pub(crate) struct OpenedVideoEncoder {
     pub input_height: u32,
 }
 
-#[allow(clippy::too_many_arguments)]
-pub(crate) fn open_video_encoder(
-    codec: Codec,
-    encoder_options: Dictionary<'static>,
-    input_config: &VideoInfo,
-    output_width: u32,
-    output_height: u32,
-    bpp: f32,
-    external_conversion: bool,
-    crf: Option<u8>,
-) -> Result<OpenedVideoEncoder, H264EncoderError> {
-    open_video_encoder_inner(
-        codec,
-        encoder_options,
-        input_config,
-        output_width,
-        output_height,
-        bpp,
-        external_conversion,
-        crf,
-        false,
-    )
-}
-
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn open_video_encoder_with_flags(
     codec: Codec,
@@ -2,3 +2,4 @@ pub mod h264;
 pub mod h264_packet;
 pub mod hevc;
 pub mod prores;
+pub mod webm_video;
@@ -0,0 +1,325 @@
+use std::{thread, time::Duration};
+
+use cap_media_info::{VideoInfo, ensure_even};
+use ffmpeg::{
+    Dictionary,
+    codec::{codec::Codec, context, encoder},
+    color,
+    format::{self},
+    frame,
+    threading::Config,
+};
+use tracing::{debug, warn};
+
+use crate::base::EncoderBase;
+
+/// Royalty-free video codecs that can be muxed into WebM.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebmVideoCodec {
+    Vp9,
+    Av1,
+}
+
+impl WebmVideoCodec {
+    /// FFmpeg encoders to try, fastest first.
+    fn encoder_names(self) -> &'static [&'static str] {
+        match self {
+            Self::Vp9 => &["libvpx-vp9"],
+            Self::Av1 => &["libsvtav1", "libaom-av1"],
+        }
+    }
+}
+
+pub struct WebmVideoEncoderBuilder {
+    input_config: VideoInfo,
+    codec: WebmVideoCodec,
+    bpp: f32,
+    crf: u8,
+    output_size: Option<(u32, u32)>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum WebmVideoEncoderError {
+    #[error("{0:?}")]
+    FFmpeg(#[from] ffmpeg::Error),
+    #[error("No {0:?} encoder available in this FFmpeg build")]
+    CodecNotFound(WebmVideoCodec),
+    #[error("Invalid output dimensions {width}x{height}; expected non-zero width and height")]
+    InvalidOutputDimensions { width: u32, height: u32 },
+}
+
+impl WebmVideoEncoderBuilder {
+    pub const DEFAULT_CRF: u8 = 31;
+
+    pub fn new(input_config: VideoInfo, codec: WebmVideoCodec) -> Self {
+        Self {
+            input_config,
+            codec,
+            bpp: 0.1,
+            crf: Self::DEFAULT_CRF,
+            output_size: None,
+        }
+    }
+
+    /// Caps the bitrate at `bpp` bits per pixel per frame.
+    pub fn with_bpp(mut self, bpp: f32) -> Self {
+        self.bpp = bpp;
+        self
+    }
+
+    /// Constant quality on libvpx/libaom's 0-63 scale; lower is better.
+    pub fn with_crf(mut self, crf: u8) -> Self {
+        self.crf = crf.min(63);
+        self
+    }
+
+    pub fn with_output_size(
+        mut self,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, WebmVideoEncoderError> {
+        if width == 0 || height == 0 {
+            return Err(WebmVideoEncoderError::InvalidOutputDimensions { width, height });
+        }
+
+        self.output_size = Some((width, height));
+        Ok(self)
+    }
+
+    pub fn build(
+        self,
+        output: &mut format::context::Output,
+    ) -> Result<WebmVideoEncoder, WebmVideoEncoderError> {
+        let (raw_width, raw_height) = self
+            .output_size
+            .unwrap_or((self.input_config.width, self.input_config.height));
+        let output_width = ensure_even(raw_width);
+        let output_height = ensure_even(raw_height);
+
+        if raw_width != output_width || raw_height != output_height {
+            warn!(
+                raw_width,
+                raw_height,
+                output_width,
+                output_height,
+                "Auto-adjusted odd dimensions to even for {:?} encoding",
+                self.codec
+            );
+        }
+
+        let mut last_error = None;
+
+        for name in self.codec.encoder_names() {
+            let Some(codec) = encoder::find_by_name(name) else {
+                debug!("{name} not available");
+                continue;
+            };
+
+            match self.build_with_codec(codec, name, output, output_width, output_height) {
+                Ok(encoder) => {
+                    debug!("Using {:?} encoder {name}", self.codec);
+                    return Ok(encoder);
+                }
+                Err(err) => {
+                    debug!("{:?} encoder {name} init failed: {err:?}", self.codec);
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        Err(last_error.unwrap_or(WebmVideoEncoderError::CodecNotFound(self.codec)))
+    }
+
+    fn build_with_codec(
+        &self,
+        codec: Codec,
+        codec_name: &str,
+        output: &mut format::context::Output,
+        output_width: u32,
+        output_height: u32,
+    ) -> Result<WebmVideoEncoder, WebmVideoEncoderError> {
+        let input_config = &self.input_config;
+        let output_format = format::Pixel::YUV420P;
+
+        let converter = if input_config.pixel_format != output_format
+            || input_config.width != output_width
+            || input_config.height != output_height
+        {
+            Some(ffmpeg::software::scaling::Context::get(
+                input_config.pixel_format,
+                input_config.width,
+                input_config.height,
+                output_format,
+                output_width,
+                output_height,
+                ffmpeg::software::scaling::flag::Flags::BICUBIC,
+            )?)
+        } else {
+            None
+        };
+
+        let mut encoder_ctx = context::Context::new_with_codec(codec);
+        let thread_count = thread::available_parallelism()
+            .map(|v| v.get())
+            .unwrap_or(1);
+        encoder_ctx.set_threading(Config::count(thread_count));
+
+        let mut encoder = encoder_ctx.encoder().video()?;
+        encoder.set_width(output_width);
+        encoder.set_height(output_height);
+        encoder.set_format(output_format);
+        encoder.set_time_base(input_config.time_base);
+        encoder.set_frame_rate(Some(input_config.frame_rate));
+        encoder.set_colorspace(color::Space::BT709);
+        encoder.set_color_range(color::Range::MPEG);
+        unsafe {
+            (*encoder.as_mut_ptr()).color_primaries =
+                ffmpeg::ffi::AVColorPrimaries::AVCOL_PRI_BT709;
+            (*encoder.as_mut_ptr()).color_trc =
+                ffmpeg::ffi::AVColorTransferCharacteristic::AVCOL_TRC_BT709;
+        }
+
+        let fps = input_config.fps().max(1) as f32;
+        let max_bitrate = (output_width as f32 * output_height as f32 * fps * self.bpp) as usize;
+        let keyframe_interval = (fps * 2.0).round() as u32;
+        encoder.set_gop(keyframe_interval);
+
+        let options = encoder_options(codec_name, self.crf, thread_count);
+        // SVT-AV1 only honours a bitrate in VBR mode, so cap it through
+        // maxrate; libvpx/libaom use it as the constrained-quality ceiling.
+        if codec_name == "libsvtav1" {
+            encoder.set_max_bit_rate(max_bitrate);
+        } else {
+            encoder.set_bit_rate(max_bitrate);
+        }
+
+        let encoder = encoder.open_with(options)?;
+
+        let mut output_stream = output.add_stream(codec)?;
+        let stream_index = output_stream.index();
+        output_stream.set_time_base(input_config.time_base);
+        output_stream.set_rate(input_config.frame_rate);
+        output_stream.set_parameters(&encoder);
+
+        let converted_frame_pool = converter
+            .as_ref()
+            .map(|_| frame::Video::new(output_format, output_width, output_height));
+
+        Ok(WebmVideoEncoder {
+            base: EncoderBase::new(stream_index),
+            encoder,
+            converter,
+            converted_frame_pool,
+        })
+    }
+}
+
+fn encoder_options(codec_name: &str, crf: u8, thread_count: usize) -> Dictionary<'static> {
+    let mut options = Dictionary::new();
+    let crf = crf.to_string();
+
+    match codec_name {
+        "libvpx-vp9" => {
+            options.set("crf", &crf);
+            options.set("deadline", "good");
+            options.set("cpu-used", "4");
+            options.set("row-mt", "1");
+            options.set("tile-columns", "2");
+            options.set("auto-alt-ref", "1");
+            options.set("lag-in-frames", "25");
+        }
+        "libsvtav1" => {
+            options.set("crf", &crf);
+            options.set("preset", "8");
+        }
+        "libaom-av1" => {
+            options.set("crf", &crf);
+            options.set("cpu-used", "6");
+            options.set("row-mt", "1");
+            options.set("usage", "good");
+            options.set("threads", &thread_count.to_string());
+        }
+        _ => {}
+    }
+
+    options
+}
+
+pub struct WebmVideoEncoder {
+    base: EncoderBase,
+    encoder: encoder::Video,
+    converter: Option<ffmpeg::software::scaling::Context>,
+    converted_frame_pool: Option<frame::Video>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum QueueFrameError {
+    #[error("Converter: {0}")]
+    Converter(ffmpeg::Error),
+    #[error("Encode: {0}")]
+    Encode(ffmpeg::Error),
+}
+
+impl WebmVideoEncoder {
+    pub fn builder(input_config: VideoInfo, codec: WebmVideoCodec) -> WebmVideoEncoderBuilder {
+        WebmVideoEncoderBuilder::new(input_config, codec)
+    }
+
+    pub fn queue_frame(
+        &mut self,
+        frame: &mut frame::Video,
+        timestamp: Duration,
+        output: &mut format::context::Output,
+    ) -> Result<(), QueueFrameError> {
+        self.base.update_pts(frame, timestamp, &mut self.encoder);
+
+        let frame_to_send = if let Some(converter) = &mut self.converter {
+            let pts = frame.pts();
+            let converted = self.converted_frame_pool.as_mut().unwrap();
+            converter
+                .run(frame, converted)
+                .map_err(QueueFrameError::Converter)?;
+            converted.set_pts(pts);
+            converted as &frame::Video
+        } else {
+            frame as &frame::Video
+        };
+
+        self.base
+            .send_frame(frame_to_send, output, &mut self.encoder)
+            .map_err(QueueFrameError::Encode)?;
+
+        Ok(())
+    }
+
+    pub fn flush(&mut self, output: &mut format::context::Output) -> Result<(), ffmpeg::Error> {
+        self.base.process_eof(output, &mut self.encoder)
+    }
+}
+
+unsafe impl Send for WebmVideoEncoder {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn av1_prefers_svt_over_libaom() {
+        assert_eq!(
+            WebmVideoCodec::Av1.encoder_names(),
+            &["libsvtav1", "libaom-av1"]
+        );
+        assert_eq!(WebmVideoCodec::Vp9.encoder_names(), &["libvpx-vp9"]);
+    }
+
+    #[test]
+    fn crf_is_clamped_to_the_libvpx_scale() {
+        let builder = WebmVideoEncoderBuilder::new(
+            VideoInfo::from_raw(cap_media_info::RawVideoFormat::Nv12, 64, 64, 30),
+            WebmVideoCodec::Vp9,
+        )
+        .with_crf(80);
+
+        assert_eq!(builder.crf, 63);
+    }
+}
@@ -429,6 +429,8 @@ async fn run_mp4_export(
         custom_bpp: None,
         force_ffmpeg_decoder: false,
         optimize_filesize: false,
+        loudness: None,
+        range: None,
     };
 
     let total_frames = exporter_base.total_frames(fps);
@@ -482,6 +484,7 @@ async fn run_gif_export(
         fps,
         resolution_base: XY::new(width, height),
         quality: None,
+        range: None,
     };
 
     let total_frames = exporter_base.total_frames(fps);
@@ -19,6 +19,8 @@ async fn main() -> Result<(), String> {
         custom_bpp: None,
         force_ffmpeg_decoder: false,
         optimize_filesize: false,
+        loudness: None,
+        range: None,
     };
 
     let temp_out = tempfile::Builder::new()
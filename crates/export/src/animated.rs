@@ -0,0 +1,185 @@
+use cap_enc_ffmpeg::animated_image::{AnimatedImageEncoder, AnimatedImageOptions};
+use cap_project::{BackgroundSource, ExportRange, ProjectConfiguration, XY};
+use cap_rendering::{ProjectUniforms, RenderSegment, RenderedFrame};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+use tracing::trace;
+
+use crate::{ExportError, ExporterBase};
+
+pub use cap_enc_ffmpeg::animated_image::AnimatedImageFormat;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
+pub struct AnimatedImageQuality {
+    /// Encoding quality from 1-100 (default: 90), ignored by lossless APNG
+    pub quality: Option<u8>,
+    /// Whether to prioritize speed over file size (default: false)
+    pub fast: Option<bool>,
+}
+
+/// Settings shared by the animated WebP and APNG exporters.
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+pub struct AnimatedImageExportSettings {
+    pub fps: u32,
+    pub resolution_base: XY<u32>,
+    pub quality: Option<AnimatedImageQuality>,
+    /// Part of the timeline to export; all of it when unset.
+    #[serde(default)]
+    pub range: Option<ExportRange>,
+}
+
+impl Default for AnimatedImageExportSettings {
+    fn default() -> Self {
+        Self {
+            fps: 30,
+            resolution_base: XY { x: 1920, y: 1080 },
+            quality: None,
+            range: None,
+        }
+    }
+}
+
+/// A fully transparent colour background renders as alpha 0, so only then is
+/// an alpha plane worth encoding.
+pub fn has_transparent_background(project: &ProjectConfiguration) -> bool {
+    matches!(
+        project.background.source,
+        BackgroundSource::Color { alpha: 0, .. }
+    )
+}
+
+impl AnimatedImageExportSettings {
+    pub async fn export(
+        self,
+        format: AnimatedImageFormat,
+        base: ExporterBase,
+        mut on_progress: impl FnMut(u32) -> bool + Send + 'static,
+    ) -> Result<PathBuf, String> {
+        let meta = &base.studio_meta;
+
+        let (tx_image_data, mut video_rx) = tokio::sync::mpsc::channel::<(RenderedFrame, u32)>(4);
+
+        let fps = self.fps;
+
+        let output_size = ProjectUniforms::get_output_size(
+            &base.render_constants.options,
+            &base.project_config,
+            self.resolution_base,
+        );
+
+        let extension = format.extension();
+        let mut output_path = base.output_path.clone();
+        if output_path.extension() != Some(std::ffi::OsStr::new(extension)) {
+            output_path.set_extension(extension);
+        }
+
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+
+        trace!(
+            "Creating {format:?} encoder at path '{}'",
+            output_path.display()
+        );
+
+        let defaults = AnimatedImageOptions::default();
+        let options = AnimatedImageOptions {
+            quality: self
+                .quality
+                .and_then(|q| q.quality)
+                .unwrap_or(defaults.quality),
+            fast: self.quality.and_then(|q| q.fast).unwrap_or(defaults.fast),
+            alpha: has_transparent_background(&base.project_config),
+        };
+
+        let mut encoder = AnimatedImageEncoder::new_with_options(
+            &output_path,
+            format,
+            output_size.0,
+            output_size.1,
+            fps,
+            options,
+        )
+        .map_err(|e| format!("Failed to create {format:?} encoder: {e}"))?;
+
+        let encoder_thread = tokio::task::spawn_blocking(move || {
+            let mut frame_count = 0;
+
+            while let Some((frame, _frame_number)) = video_rx.blocking_recv() {
+                if !(on_progress)(frame_count) {
+                    return Err(ExportError::Other("Export cancelled".to_string()));
+                }
+
+                if let Err(e) = encoder.add_frame(&frame.data, frame.padded_bytes_per_row as usize)
+                {
+                    return Err(ExportError::Other(format!(
+                        "Failed to add frame to {format:?}: {e}"
+                    )));
+                }
+
+                frame_count += 1;
+            }
+
+            if let Err(e) = encoder.finish() {
+                return Err(ExportError::Other(format!(
+                    "Failed to finish {format:?}: {e}"
+                )));
+            }
+
+            Ok(output_path)
+        })
+        .then(|f| async {
+            f.map_err(|e| e.to_string())
+                .and_then(|v| v.map_err(|v| v.to_string()))
+        });
+
+        let render_video_task = cap_rendering::render_video_to_channel(
+            &base.render_constants,
+            &base.project_config,
+            tx_image_data,
+            &base.recording_meta,
+            meta,
+            base.segments
+                .iter()
+                .map(|s| RenderSegment {
+                    cursor: s.cursor.clone(),
+                    keyboard: s.keyboard.clone(),
+                    decoders: s.decoders.clone(),
+                    render_display: true,
+                })
+                .collect(),
+            fps,
+            self.resolution_base,
+            &base.recordings,
+        )
+        .then(|f| async { f.map_err(|v| v.to_string()) });
+
+        let (output_path, _) =
+            tokio::try_join!(encoder_thread, render_video_task).map_err(|e| e.to_string())?;
+
+        Ok(output_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_clear_colour_background_is_transparent() {
+        let mut project = ProjectConfiguration::default();
+        project.background.source = BackgroundSource::Color {
+            value: [255, 255, 255],
+            alpha: 0,
+        };
+        assert!(has_transparent_background(&project));
+
+        project.background.source = BackgroundSource::Color {
+            value: [255, 255, 255],
+            alpha: 255,
+        };
+        assert!(!has_transparent_background(&project));
+    }
+}
@@ -1,4 +1,4 @@
-use cap_project::XY;
+use cap_project::{ExportRange, XY};
 use cap_rendering::{ProjectUniforms, RenderSegment, RenderedFrame};
 use futures::FutureExt;
 use serde::{Deserialize, Serialize};
@@ -16,11 +16,14 @@ pub struct GifQuality {
     pub fast: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
 pub struct GifExportSettings {
     pub fps: u32,
     pub resolution_base: XY<u32>,
     pub quality: Option<GifQuality>,
+    /// Part of the timeline to export; all of it when unset.
+    #[serde(default)]
+    pub range: Option<ExportRange>,
 }
 
 impl Default for GifExportSettings {
@@ -29,6 +32,7 @@ impl Default for GifExportSettings {
             fps: 30,
             resolution_base: XY { x: 1920, y: 1080 },
             quality: None,
+            range: None,
         }
     }
 }
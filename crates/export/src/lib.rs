@@ -1,16 +1,23 @@
+pub mod animated;
 pub mod gif;
+pub mod loudness;
 pub mod mov;
 pub mod mp4;
 pub mod preview;
 pub mod settings;
+pub mod webm;
 
 use cap_editor::SegmentMedia;
 use cap_project::{
-    BackgroundSource, ProjectConfiguration, RecordingMeta, StudioRecordingMeta,
-    TimelineConfiguration, TimelineSegment,
+    BackgroundSource, Chapter, ExportRange, ProjectConfiguration, RecordingMeta,
+    StudioRecordingMeta, TimelineConfiguration, chapters_to_webvtt,
+};
+use cap_rendering::{ProjectRecordingsMeta, RenderVideoConstants, SharedWgpuDevice};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
-use cap_rendering::{ProjectRecordingsMeta, RenderVideoConstants};
-use std::{path::PathBuf, sync::Arc};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ExportError {
@@ -52,6 +59,8 @@ pub enum ExporterBuildError {
     MediaLoad(String),
     #[error("IO error at path '{0}': {1}")]
     IO(PathBuf, std::io::Error),
+    #[error("Invalid export range: {0}")]
+    Range(String),
 }
 
 pub struct ExporterBuilder {
@@ -59,6 +68,8 @@ pub struct ExporterBuilder {
     config: Option<ProjectConfiguration>,
     output_path: Option<PathBuf>,
     force_ffmpeg_decoder: bool,
+    shared_device: Option<SharedWgpuDevice>,
+    range: Option<ExportRange>,
 }
 
 impl ExporterBuilder {
@@ -77,6 +88,21 @@ impl ExporterBuilder {
         self
     }
 
+    /// Render on an existing device instead of opening a new one, so many
+    /// exports in one process share a single GPU context.
+    pub fn with_shared_device(mut self, shared_device: SharedWgpuDevice) -> Self {
+        self.shared_device = Some(shared_device);
+        self
+    }
+
+    /// Export only part of the timeline, or all of it for `None`. Everything
+    /// downstream, including `ExporterBase::total_frames`, sees the clipped
+    /// timeline.
+    pub fn with_range(mut self, range: Option<ExportRange>) -> Self {
+        self.range = range;
+        self
+    }
+
     pub async fn build(self) -> Result<ExporterBase, ExporterBuildError> {
         type Error = ExporterBuildError;
 
@@ -105,46 +131,36 @@ impl ExporterBuilder {
         // Desktop exports already carry a timeline by export time, so this only fires for un-edited
         // projects and changes nothing for them.
         if project_config.timeline.is_none() {
-            let segments: Vec<TimelineSegment> = recordings
+            let durations = recordings
                 .segments
                 .iter()
-                .enumerate()
-                .filter_map(|(i, segment)| {
-                    let duration = segment.duration();
-                    (duration > 0.0).then_some(TimelineSegment {
-                        recording_clip: i as u32,
-                        start: 0.0,
-                        end: duration,
-                        timescale: 1.0,
-                        name: None,
-                        speed_audio_mode: None,
-                    })
-                })
-                .collect();
-            if !segments.is_empty() {
-                project_config.timeline = Some(TimelineConfiguration {
-                    segments,
-                    transitions: Vec::new(),
-                    zoom_segments: Vec::new(),
-                    scene_segments: Vec::new(),
-                    mask_segments: Vec::new(),
-                    text_segments: Vec::new(),
-                    caption_segments: Vec::new(),
-                    keyboard_segments: Vec::new(),
-                    audio_segments: Vec::new(),
-                });
-            }
+                .map(|segment| segment.duration())
+                .collect::<Vec<_>>();
+            project_config.timeline = TimelineConfiguration::from_recording_durations(&durations);
+        }
+
+        if let Some(range) = &self.range {
+            range.apply(&mut project_config).map_err(Error::Range)?;
         }
 
-        let render_constants = Arc::new(
-            RenderVideoConstants::new(
+        let render_constants = match self.shared_device {
+            Some(shared_device) => RenderVideoConstants::new_with_device(
+                shared_device,
                 &recordings.segments,
                 recording_meta.clone(),
                 studio_meta.clone(),
-            )
-            .await
-            .map_err(Error::RendererSetup)?,
-        );
+            ),
+            None => {
+                RenderVideoConstants::new(
+                    &recordings.segments,
+                    recording_meta.clone(),
+                    studio_meta.clone(),
+                )
+                .await
+            }
+        }
+        .map(Arc::new)
+        .map_err(Error::RendererSetup)?;
 
         let segments =
             cap_editor::create_segments(&recording_meta, studio_meta, self.force_ffmpeg_decoder)
@@ -192,6 +208,7 @@ pub fn make_cursor_only_project(mut project_config: ProjectConfiguration) -> Pro
     project_config.camera.hide = true;
     project_config.captions = None;
     project_config.keyboard = None;
+    project_config.annotations.clear();
 
     if let Some(timeline) = project_config.timeline.as_mut() {
         timeline.mask_segments.clear();
@@ -226,12 +243,46 @@ impl ExporterBase {
         (fps as f64 * duration).ceil() as u32
     }
 
+    /// The recording's markers, placed on the timeline being exported.
+    pub fn chapters(&self) -> Vec<Chapter> {
+        self.project_config
+            .timeline
+            .as_ref()
+            .map(|timeline| timeline.chapters(self.studio_meta.markers()))
+            .unwrap_or_default()
+    }
+
     pub fn builder(project_path: PathBuf) -> ExporterBuilder {
         ExporterBuilder {
             project_path,
             config: None,
             output_path: None,
             force_ffmpeg_decoder: false,
+            shared_device: None,
+            range: None,
         }
     }
 }
+
+fn muxer_chapters(chapters: &[Chapter]) -> Vec<cap_enc_ffmpeg::chapters::Chapter> {
+    chapters
+        .iter()
+        .map(|chapter| cap_enc_ffmpeg::chapters::Chapter {
+            start: Duration::from_secs_f64(chapter.start.max(0.0)),
+            end: Duration::from_secs_f64(chapter.end.max(chapter.start).max(0.0)),
+            title: chapter.title.clone(),
+        })
+        .collect()
+}
+
+/// Writes `chapters` as `<output>.chapters.vtt` next to an export, for players
+/// that don't read chapters from the container.
+fn write_chapters_sidecar(output_path: &Path, chapters: &[Chapter]) -> Result<(), String> {
+    if chapters.is_empty() {
+        return Ok(());
+    }
+
+    let path = output_path.with_extension("chapters.vtt");
+    std::fs::write(&path, chapters_to_webvtt(chapters))
+        .map_err(|e| format!("Failed to write chapters to {}: {e}", path.display()))
+}
@@ -0,0 +1,169 @@
+use cap_audio::TruePeakLimiter;
+use cap_editor::AudioRenderer;
+use cap_project::ProjectConfiguration;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tracing::info;
+
+/// Most an export is turned up to reach its target. Anything quieter is
+/// mostly noise, and boosting it further only makes the noise obvious.
+const MAX_NORMALIZATION_GAIN_DB: f64 = 20.0;
+
+/// Integrated loudness an export's audio is normalised to, with a limiter
+/// keeping the true peak under a ceiling.
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LoudnessTarget {
+    /// Integrated loudness in LUFS: -16 suits podcasts and the web, -14 most
+    /// streaming services.
+    pub integrated_lufs: f64,
+    /// True-peak ceiling in dBTP.
+    pub true_peak_dbtp: f64,
+}
+
+impl Default for LoudnessTarget {
+    fn default() -> Self {
+        Self {
+            integrated_lufs: -16.0,
+            true_peak_dbtp: -1.0,
+        }
+    }
+}
+
+impl LoudnessTarget {
+    /// Gain in dB that brings a mix measured at `integrated` LUFS to the
+    /// target, before limiting. A mix with nothing to measure is left alone.
+    pub fn gain_db(&self, integrated: Option<f64>) -> f64 {
+        integrated.map_or(0.0, |integrated| {
+            (self.integrated_lufs - integrated).min(MAX_NORMALIZATION_GAIN_DB)
+        })
+    }
+}
+
+/// An export's audio mix, normalised when it has a [`LoudnessTarget`].
+pub(crate) struct ExportAudio {
+    renderer: AudioRenderer,
+    normalizer: Option<Normalizer>,
+}
+
+struct Normalizer {
+    gain: f32,
+    true_peak_dbtp: f64,
+    limiter: TruePeakLimiter,
+    primed: bool,
+}
+
+impl Normalizer {
+    fn new(gain: f32, true_peak_dbtp: f64) -> Self {
+        Self {
+            gain,
+            true_peak_dbtp,
+            limiter: TruePeakLimiter::new(AudioRenderer::CHANNELS, true_peak_dbtp),
+            primed: false,
+        }
+    }
+
+    fn apply(&mut self, samples: &mut [f32]) {
+        samples.iter_mut().for_each(|sample| *sample *= self.gain);
+        self.limiter.process(samples);
+    }
+}
+
+impl ExportAudio {
+    /// With a `target`, first measures the mix over the export's `duration`
+    /// seconds, which renders all of its audio once.
+    pub(crate) fn new(
+        mut renderer: AudioRenderer,
+        project: &ProjectConfiguration,
+        target: Option<LoudnessTarget>,
+        duration: f64,
+    ) -> Self {
+        renderer.wait_for_voice_enhancement(&project.audio);
+        let normalizer = target.map(|target| {
+            let meter = renderer.measure_loudness(project, duration);
+            let gain_db = target.gain_db(meter.integrated());
+            info!(
+                integrated_lufs = ?meter.integrated(),
+                true_peak_dbtp = meter.true_peak(),
+                target_lufs = target.integrated_lufs,
+                gain_db,
+                "Normalizing export loudness"
+            );
+            Normalizer::new(10f64.powf(gain_db / 20.0) as f32, target.true_peak_dbtp)
+        });
+
+        Self {
+            renderer,
+            normalizer,
+        }
+    }
+
+    pub(crate) fn set_playhead(&mut self, playhead: f64, project: &ProjectConfiguration) {
+        self.renderer.set_playhead(playhead, project);
+        if let Some(normalizer) = &mut self.normalizer {
+            *normalizer = Normalizer::new(normalizer.gain, normalizer.true_peak_dbtp);
+        }
+    }
+
+    pub(crate) fn render_frame(
+        &mut self,
+        samples: usize,
+        project: &ProjectConfiguration,
+    ) -> Option<ffmpeg::frame::Audio> {
+        let Some(normalizer) = &mut self.normalizer else {
+            return self.renderer.render_frame(samples, project);
+        };
+
+        // The limiter's output trails its input, so the mix is rendered that
+        // far ahead to keep the audio in sync with the video.
+        if !normalizer.primed {
+            let mut lead =
+                render_samples(&mut self.renderer, normalizer.limiter.latency(), project);
+            normalizer.apply(&mut lead);
+            normalizer.primed = true;
+        }
+
+        let mut data = render_samples(&mut self.renderer, samples, project);
+        normalizer.apply(&mut data);
+
+        let mut frame = ffmpeg::frame::Audio::new(
+            AudioRenderer::SAMPLE_FORMAT,
+            samples,
+            ffmpeg::ChannelLayout::STEREO,
+        );
+        frame.set_rate(AudioRenderer::SAMPLE_RATE);
+        for (bytes, sample) in frame.data_mut(0).chunks_exact_mut(4).zip(&data) {
+            bytes.copy_from_slice(&sample.to_ne_bytes());
+        }
+        Some(frame)
+    }
+}
+
+/// The next `samples` frames of the mix, padded with silence past its end.
+fn render_samples(
+    renderer: &mut AudioRenderer,
+    samples: usize,
+    project: &ProjectConfiguration,
+) -> Vec<f32> {
+    let mut data = renderer
+        .render_frame_raw(samples, project)
+        .map(|(_, data)| data)
+        .unwrap_or_default();
+    data.resize(samples * usize::from(AudioRenderer::CHANNELS), 0.0);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_reaches_the_target_but_never_boosts_silence_or_noise_far() {
+        let target = LoudnessTarget::default();
+
+        assert_eq!(target.gain_db(Some(-23.0)), 7.0);
+        assert_eq!(target.gain_db(Some(-10.0)), -6.0);
+        assert_eq!(target.gain_db(Some(-60.0)), MAX_NORMALIZATION_GAIN_DB);
+        assert_eq!(target.gain_db(None), 0.0);
+    }
+}
@@ -1,20 +1,23 @@
 use cap_enc_ffmpeg::{mov::MOVFile, prores::ProResEncoder};
 use cap_media_info::{RawVideoFormat, VideoInfo};
-use cap_project::XY;
+use cap_project::{ExportRange, XY};
 use cap_rendering::{ProjectUniforms, RenderSegment, RenderedFrame};
 use futures::FutureExt;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::{path::PathBuf, time::Duration};
 
-use crate::{ExportError, ExporterBase};
+use crate::{ExportError, ExporterBase, muxer_chapters, write_chapters_sidecar};
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
 pub struct MovExportSettings {
     pub fps: u32,
     pub resolution_base: XY<u32>,
     #[serde(default)]
     pub cursor_only: bool,
+    /// Part of the timeline to export; all of it when unset.
+    #[serde(default)]
+    pub range: Option<ExportRange>,
 }
 
 impl MovExportSettings {
@@ -46,11 +49,14 @@ impl MovExportSettings {
         let video_info =
             VideoInfo::from_raw(RawVideoFormat::Rgba, output_size.0, output_size.1, fps);
 
+        let chapters = base.chapters();
+        let muxer_chapters = muxer_chapters(&chapters);
         let encoder_thread = tokio::task::spawn_blocking(move || {
-            let mut mov_encoder = MOVFile::init(mov_output_path.clone(), |output| {
-                ProResEncoder::builder(video_info).build(output)
-            })
-            .map_err(|e| ExportError::Other(format!("Failed to create MOV encoder: {e}")))?;
+            let mut mov_encoder =
+                MOVFile::init_with_chapters(mov_output_path.clone(), &muxer_chapters, |output| {
+                    ProResEncoder::builder(video_info).build(output)
+                })
+                .map_err(|e| ExportError::Other(format!("Failed to create MOV encoder: {e}")))?;
 
             let mut reusable_frame = ffmpeg::frame::Video::new(
                 ffmpeg::format::Pixel::RGBA,
@@ -110,6 +116,8 @@ impl MovExportSettings {
         let (output_path, _) =
             tokio::try_join!(encoder_thread, render_video_task).map_err(|e| e.to_string())?;
 
+        write_chapters_sidecar(&output_path, &chapters)?;
+
         Ok(output_path)
     }
 }
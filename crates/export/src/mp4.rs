@@ -1,8 +1,12 @@
-use crate::ExporterBase;
+use crate::{
+    ExporterBase,
+    loudness::{ExportAudio, LoudnessTarget},
+    muxer_chapters, write_chapters_sidecar,
+};
 use cap_editor::{AudioRenderer, get_audio_segments, load_music_tracks_uncached};
 use cap_enc_ffmpeg::{AudioEncoder, aac::AACEncoder, h264::H264Encoder, mp4::*};
 use cap_media_info::{RawVideoFormat, VideoInfo};
-use cap_project::XY;
+use cap_project::{ExportRange, XY};
 use cap_rendering::{
     GpuOutputFormat, Nv12RenderedFrame, ProjectUniforms, RenderSegment, SharedNv12Buffer,
 };
@@ -62,7 +66,7 @@ pub struct FirstFrameQueuedBenchmark {
     pub nv12_render_startup_breakdown_ms: Option<cap_rendering::Nv12RenderStartupBreakdownMs>,
 }
 
-#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug)]
+#[derive(Serialize, Deserialize, Type, Clone, Debug)]
 pub struct Mp4ExportSettings {
     pub fps: u32,
     pub resolution_base: XY<u32>,
@@ -72,6 +76,11 @@ pub struct Mp4ExportSettings {
     pub force_ffmpeg_decoder: bool,
     #[serde(default)]
     pub optimize_filesize: bool,
+    #[serde(default)]
+    pub loudness: Option<LoudnessTarget>,
+    /// Part of the timeline to export; all of it when unset.
+    #[serde(default)]
+    pub range: Option<ExportRange>,
 }
 
 impl Mp4ExportSettings {
@@ -103,14 +112,20 @@ impl Mp4ExportSettings {
             height = output_size.1,
             "Exporting with NV12 pipeline (GPU when possible, CPU fallback otherwise)"
         );
-        self.export_nv12(
-            base,
-            output_size,
-            fps,
-            on_progress,
-            ExportNv12Mode::default(),
-        )
-        .await
+        let chapters = base.chapters();
+        let output_path = self
+            .export_nv12(
+                base,
+                output_size,
+                fps,
+                on_progress,
+                ExportNv12Mode::default(),
+            )
+            .await?;
+
+        write_chapters_sidecar(&output_path, &chapters)?;
+
+        Ok(output_path)
     }
 
     pub async fn benchmark_first_frame_with_breakdown(
@@ -186,19 +201,22 @@ impl Mp4ExportSettings {
             .filter(|_| !base.project_config.audio.mute)
             .is_some();
         let has_audio = has_recording_audio || !music.is_empty();
+        let audio_duration = base.total_frames(fps) as f64 / fps as f64;
 
         let record_first_queued_ms = mode.record_first_queued_ms_since_pipeline;
         let nv12_render_startup_breakdown_ms = mode.nv12_render_startup_breakdown_ms;
 
         let project_for_audio = base.project_config.clone();
+        let chapters = muxer_chapters(&base.chapters());
         let pipeline_start_for_encoder = pipeline_start;
         let encoder_thread = tokio::task::spawn_blocking(move || {
             trace!("Creating MP4File encoder (NV12 path)");
 
-            let mut encoder = MP4File::init(
+            let mut encoder = MP4File::init_with_chapters(
                 "output",
                 base.output_path.clone(),
                 self.optimize_filesize,
+                &chapters,
                 |o| {
                     let builder = H264Encoder::builder(video_info)
                         .with_bpp(self.effective_bpp())
@@ -225,7 +243,12 @@ impl Mp4ExportSettings {
             info!("Created MP4File encoder (NV12, external conversion, export settings)");
 
             let mut audio_renderer = if has_audio {
-                Some(AudioRenderer::new(audio_segments).with_music(music))
+                Some(ExportAudio::new(
+                    AudioRenderer::new(audio_segments).with_music(music),
+                    &project_for_audio,
+                    self.loudness,
+                    audio_duration,
+                ))
             } else {
                 None
             };
@@ -347,12 +370,12 @@ impl Mp4ExportSettings {
     }
 }
 
-struct ExportFrame {
-    nv12_data: SharedNv12Buffer,
-    width: u32,
-    height: u32,
-    y_stride: u32,
-    frame_number: u32,
+pub(crate) struct ExportFrame {
+    pub(crate) nv12_data: SharedNv12Buffer,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) y_stride: u32,
+    pub(crate) frame_number: u32,
 }
 
 struct FirstFrameNv12 {
@@ -460,7 +483,7 @@ fn nv12_from_rendered_frame(frame: Nv12RenderedFrame) -> ExportFrame {
 /// the stream stays gapless and strictly monotonic. The caller advances the
 /// cursor to `pts + samples`. Shared with the tests so they exercise this exact
 /// arithmetic rather than a re-implementation.
-fn audio_frame_budget(
+pub(crate) fn audio_frame_budget(
     frame_number: u64,
     sample_rate: u64,
     fps: u64,
@@ -473,7 +496,7 @@ fn audio_frame_budget(
     Some((cursor as i64, (end - cursor) as usize))
 }
 
-fn silent_audio_frame(samples: usize) -> ffmpeg::frame::Audio {
+pub(crate) fn silent_audio_frame(samples: usize) -> ffmpeg::frame::Audio {
     let mut frame = ffmpeg::frame::Audio::new(
         AudioRenderer::SAMPLE_FORMAT,
         samples,
@@ -486,7 +509,7 @@ fn silent_audio_frame(samples: usize) -> ffmpeg::frame::Audio {
     frame
 }
 
-fn fill_nv12_frame_direct(
+pub(crate) fn fill_nv12_frame_direct(
     frame: &mut ffmpeg::frame::Video,
     nv12_data: &[u8],
     width: u32,
@@ -616,7 +639,7 @@ const FRAME_RECEIVE_STEADY_TIMEOUT_SECS: u64 = 90;
 const MAX_CONSECUTIVE_FRAME_TIMEOUTS: u32 = 3;
 
 #[allow(clippy::too_many_arguments)]
-async fn export_render_to_channel(
+pub(crate) async fn export_render_to_channel(
     constants: &RenderVideoConstants,
     project: &ProjectConfiguration,
     sender: std::sync::mpsc::SyncSender<ExportFrame>,
@@ -69,10 +69,10 @@ async fn render_preview_with_base(
             match timeline.get_frame_mapping(frame_time) {
                 Some(TimelineFrameMapping::Transition {
                     outgoing,
-                    kind,
+                    style,
                     progress,
                     ..
-                }) => Some((outgoing, kind, progress)),
+                }) => Some((outgoing, style, progress)),
                 _ => None,
             }
         });
@@ -101,7 +101,7 @@ async fn render_preview_with_base(
             segment_time as f32,
             !exporter_base.project_config.camera.hide,
             !settings.cursor_only,
-            clip_config.map(|v| v.offsets).unwrap_or_default(),
+            clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
         )
         .await
         .ok_or_else(|| ExportError::Other("Failed to decode frame".to_string()))?;
@@ -142,7 +142,7 @@ async fn render_preview_with_base(
         exporter_base.render_constants.is_software_adapter,
     );
 
-    let frame = if let Some((outgoing, kind, progress)) = transition_mapping {
+    let frame = if let Some((outgoing, style, progress)) = transition_mapping {
         let outgoing_media = exporter_base
             .segments
             .get(outgoing.segment.recording_clip as usize)
@@ -154,7 +154,7 @@ async fn render_preview_with_base(
             .clips
             .iter()
             .find(|clip| clip.index == outgoing.segment.recording_clip)
-            .map(|clip| clip.offsets)
+            .map(|clip| clip.offsets.clone())
             .unwrap_or_default();
         let outgoing_frames = outgoing_media
             .decoders
@@ -200,7 +200,7 @@ async fn render_preview_with_base(
                     cursor: &segment_media.cursor,
                     render_display: !settings.cursor_only,
                 },
-                kind,
+                style,
                 progress as f32,
                 &mut layers,
             )
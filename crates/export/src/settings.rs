@@ -1,11 +1,15 @@
+use cap_project::ExportRange;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::animated::AnimatedImageExportSettings;
 use crate::gif::GifExportSettings;
+use crate::loudness::LoudnessTarget;
 use crate::mov::MovExportSettings;
 use crate::mp4::Mp4ExportSettings;
+use crate::webm::WebmExportSettings;
 
-#[derive(Serialize, Deserialize, Clone, Copy, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
 #[serde(tag = "format")]
 pub enum ExportSettings {
     #[serde(alias = "mp4")]
@@ -14,6 +18,12 @@ pub enum ExportSettings {
     Gif(GifExportSettings),
     #[serde(alias = "mov")]
     Mov(MovExportSettings),
+    #[serde(alias = "webm")]
+    Webm(WebmExportSettings),
+    #[serde(alias = "webp")]
+    Webp(AnimatedImageExportSettings),
+    #[serde(alias = "apng")]
+    Apng(AnimatedImageExportSettings),
 }
 
 impl ExportSettings {
@@ -22,13 +32,34 @@ impl ExportSettings {
             Self::Mp4(s) => s.fps,
             Self::Gif(s) => s.fps,
             Self::Mov(s) => s.fps,
+            Self::Webm(s) => s.fps,
+            Self::Webp(s) | Self::Apng(s) => s.fps,
         }
     }
 
     pub fn force_ffmpeg_decoder(&self) -> bool {
         match self {
             Self::Mp4(s) => s.force_ffmpeg_decoder,
-            Self::Gif(_) | Self::Mov(_) => false,
+            Self::Webm(s) => s.force_ffmpeg_decoder,
+            Self::Gif(_) | Self::Mov(_) | Self::Webp(_) | Self::Apng(_) => false,
+        }
+    }
+
+    pub fn loudness(&self) -> Option<LoudnessTarget> {
+        match self {
+            Self::Mp4(s) => s.loudness,
+            Self::Webm(s) => s.loudness,
+            Self::Gif(_) | Self::Mov(_) | Self::Webp(_) | Self::Apng(_) => None,
+        }
+    }
+
+    pub fn range(&self) -> Option<&ExportRange> {
+        match self {
+            Self::Mp4(s) => s.range.as_ref(),
+            Self::Gif(s) => s.range.as_ref(),
+            Self::Mov(s) => s.range.as_ref(),
+            Self::Webm(s) => s.range.as_ref(),
+            Self::Webp(s) | Self::Apng(s) => s.range.as_ref(),
         }
     }
 
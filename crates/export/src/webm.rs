@@ -0,0 +1,268 @@
+use cap_editor::{AudioRenderer, get_audio_segments, load_music_tracks_uncached};
+use cap_enc_ffmpeg::{
+    AudioEncoder,
+    opus::OpusEncoder,
+    webm::WebMFile,
+    webm_video::{WebmVideoCodec, WebmVideoEncoder},
+};
+use cap_media_info::{RawVideoFormat, VideoInfo};
+use cap_project::{ExportRange, XY};
+use cap_rendering::{ProjectUniforms, RenderSegment};
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::{path::PathBuf, time::Duration};
+use tracing::{info, trace};
+
+use crate::{
+    ExporterBase,
+    loudness::{ExportAudio, LoudnessTarget},
+    mp4::{
+        ExportCompression, ExportFrame, audio_frame_budget, export_render_to_channel,
+        fill_nv12_frame_direct, silent_audio_frame,
+    },
+    write_chapters_sidecar,
+};
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WebmCodec {
+    #[default]
+    Vp9,
+    Av1,
+}
+
+impl From<WebmCodec> for WebmVideoCodec {
+    fn from(value: WebmCodec) -> Self {
+        match value {
+            WebmCodec::Vp9 => Self::Vp9,
+            WebmCodec::Av1 => Self::Av1,
+        }
+    }
+}
+
+impl ExportCompression {
+    /// Constant-quality level on the 0-63 scale libvpx and AV1 encoders share.
+    pub fn webm_crf_value(&self) -> u8 {
+        match self {
+            Self::Maximum => 24,
+            Self::Social => 31,
+            Self::Web => 36,
+            Self::Potato => 42,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Clone, Debug)]
+pub struct WebmExportSettings {
+    pub fps: u32,
+    pub resolution_base: XY<u32>,
+    #[serde(default)]
+    pub codec: WebmCodec,
+    pub compression: ExportCompression,
+    #[serde(default)]
+    pub force_ffmpeg_decoder: bool,
+    #[serde(default)]
+    pub loudness: Option<LoudnessTarget>,
+    /// Part of the timeline to export; all of it when unset.
+    #[serde(default)]
+    pub range: Option<ExportRange>,
+}
+
+impl WebmExportSettings {
+    pub async fn export(
+        self,
+        base: ExporterBase,
+        on_progress: impl FnMut(u32) -> bool + Send + 'static,
+    ) -> Result<PathBuf, String> {
+        info!("Exporting webm with settings: {:?}", &self);
+
+        let fps = self.fps;
+        let meta = &base.studio_meta;
+
+        let output_size = ProjectUniforms::get_output_size(
+            &base.render_constants.options,
+            &base.project_config,
+            self.resolution_base,
+        );
+
+        let mut webm_output_path = base.output_path.clone();
+        if webm_output_path.extension() != Some(std::ffi::OsStr::new("webm")) {
+            webm_output_path.set_extension("webm");
+        }
+
+        let (frame_tx, frame_rx) = std::sync::mpsc::sync_channel::<ExportFrame>(4);
+
+        let mut video_info =
+            VideoInfo::from_raw(RawVideoFormat::Nv12, output_size.0, output_size.1, fps);
+        video_info.time_base = ffmpeg::Rational::new(1, fps as i32);
+
+        let audio_segments = get_audio_segments(&base.segments).await;
+        let music = load_music_tracks_uncached(&base.project_config, &base.project_path);
+
+        let has_recording_audio = audio_segments
+            .first()
+            .filter(|_| !base.project_config.audio.mute)
+            .is_some();
+        let has_audio = has_recording_audio || !music.is_empty();
+        let audio_duration = base.total_frames(fps) as f64 / fps as f64;
+
+        let project_for_audio = base.project_config.clone();
+        let chapters = base.chapters();
+        let encoder_output_path = webm_output_path.clone();
+        let encoder_thread = tokio::task::spawn_blocking(move || {
+            trace!("Creating WebMFile encoder");
+
+            let mut encoder = WebMFile::init(
+                encoder_output_path.clone(),
+                |o| {
+                    WebmVideoEncoder::builder(video_info, self.codec.into())
+                        .with_bpp(self.compression.bits_per_pixel())
+                        .with_crf(self.compression.webm_crf_value())
+                        .build(o)
+                },
+                |o| {
+                    has_audio.then(|| {
+                        OpusEncoder::init(AudioRenderer::info(), o)
+                            .map(|v| v.boxed())
+                            .map_err(Into::into)
+                    })
+                },
+            )
+            .map_err(|v| v.to_string())?;
+
+            let mut audio_renderer = if has_audio {
+                Some(ExportAudio::new(
+                    AudioRenderer::new(audio_segments).with_music(music),
+                    &project_for_audio,
+                    self.loudness,
+                    audio_duration,
+                ))
+            } else {
+                None
+            };
+
+            let mut reusable_frame = ffmpeg::frame::Video::new(
+                ffmpeg::format::Pixel::NV12,
+                output_size.0,
+                output_size.1,
+            );
+            let sample_rate = u64::from(AudioRenderer::SAMPLE_RATE);
+            let fps_u64 = u64::from(fps);
+            let mut audio_sample_cursor = 0u64;
+            let mut encoded_frames = 0u32;
+
+            while let Ok(input) = frame_rx.recv() {
+                if encoded_frames == 0
+                    && let Some(audio) = &mut audio_renderer
+                {
+                    audio.set_playhead(0.0, &project_for_audio);
+                }
+
+                let audio_frame = audio_renderer.as_mut().and_then(|audio| {
+                    let n = u64::from(input.frame_number);
+                    let (pts, samples) =
+                        audio_frame_budget(n, sample_rate, fps_u64, audio_sample_cursor)?;
+                    audio_sample_cursor = pts as u64 + samples as u64;
+                    let mut frame = audio
+                        .render_frame(samples, &project_for_audio)
+                        .unwrap_or_else(|| silent_audio_frame(samples));
+                    frame.set_pts(Some(pts));
+                    Some(frame)
+                });
+
+                fill_nv12_frame_direct(
+                    &mut reusable_frame,
+                    &input.nv12_data,
+                    input.width,
+                    input.height,
+                    input.y_stride,
+                    input.frame_number as i64,
+                );
+                encoder
+                    .queue_video_frame(&mut reusable_frame, Duration::MAX)
+                    .map_err(|err| err.to_string())?;
+                if let Some(audio) = audio_frame {
+                    encoder.queue_audio_frame(audio);
+                }
+                encoded_frames += 1;
+            }
+
+            info!(encoded_frames, "WebM encoder thread finished");
+
+            let res = encoder
+                .finish()
+                .map_err(|e| format!("Failed to finish encoding: {e}"))?;
+
+            if let Err(e) = res.video_finish {
+                return Err(format!("Video encoding failed: {e}"));
+            }
+            if let Err(e) = res.audio_finish {
+                return Err(format!("Audio encoding failed: {e}"));
+            }
+
+            Ok::<_, String>(())
+        })
+        .then(|r| async { r.map_err(|e| e.to_string()).and_then(|v| v) });
+
+        let render_video_task = export_render_to_channel(
+            &base.render_constants,
+            &base.project_config,
+            frame_tx,
+            &base.recording_meta,
+            meta,
+            base.segments
+                .iter()
+                .map(|s| RenderSegment {
+                    cursor: s.cursor.clone(),
+                    keyboard: s.keyboard.clone(),
+                    decoders: s.decoders.clone(),
+                    render_display: true,
+                })
+                .collect(),
+            fps,
+            self.resolution_base,
+            &base.recordings,
+            None,
+            None,
+            on_progress,
+            base.project_path.clone(),
+        )
+        .then(|v| async { v.map_err(|e| e.to_string()) });
+
+        tokio::try_join!(encoder_thread, render_video_task)?;
+
+        write_chapters_sidecar(&webm_output_path, &chapters)?;
+
+        Ok(webm_output_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_default_to_vp9_when_codec_is_omitted() {
+        let settings: WebmExportSettings = serde_json::from_str(
+            r#"{"fps":30,"resolution_base":{"x":1280,"y":720},"compression":"Web"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(settings.codec, WebmCodec::Vp9);
+        assert!(!settings.force_ffmpeg_decoder);
+    }
+
+    #[test]
+    fn webm_crf_decreases_with_higher_quality() {
+        let crfs = [
+            ExportCompression::Maximum,
+            ExportCompression::Social,
+            ExportCompression::Web,
+            ExportCompression::Potato,
+        ]
+        .map(|compression| compression.webm_crf_value());
+
+        assert!(crfs.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(crfs.iter().all(|crf| *crf <= 63));
+    }
+}
@@ -28,6 +28,8 @@ async fn run_export(project_path: PathBuf) -> Result<(PathBuf, Duration, u32), S
         custom_bpp: None,
         force_ffmpeg_decoder: false,
         optimize_filesize: false,
+        loudness: None,
+        range: None,
     };
 
     let start = Instant::now();
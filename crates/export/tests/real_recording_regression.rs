@@ -253,7 +253,9 @@ async fn export_sequence_render_matches_editor_reference_for_real_recording()
                     segment_time as f32,
                     !project_config.camera.hide,
                     render_segment.render_display,
-                    clip_config.map(|clip| clip.offsets).unwrap_or_default(),
+                    clip_config
+                        .map(|clip| clip.offsets.clone())
+                        .unwrap_or_default(),
                 )
                 .await
                 .ok_or_else(|| format!("Failed to decode reference frame {frame_number}"))?;
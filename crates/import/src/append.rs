@@ -0,0 +1,1301 @@
+use cap_enc_ffmpeg::remux::probe_video_can_decode;
+use cap_project::{
+    AudioMeta, ClipConfiguration, CursorEvents, CursorMeta, Cursors, InstantRecordingMeta,
+    MultipleSegment, MultipleSegments, ProjectConfiguration, RecordingMarker, RecordingMeta,
+    RecordingMetaInner, SingleSegment, StudioRecordingMeta, StudioRecordingStatus,
+    TimelineConfiguration, TimelineSegment, VideoMeta, XY,
+};
+use relative_path::{Component as RelativeComponent, RelativePathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    AUDIO_IMPORT_EXTENSIONS, CURSOR_EVENTS_IMPORT_EXTENSIONS, IMAGE_IMPORT_EXTENSIONS,
+    ImportProgress, ImportStage, KEYBOARD_IMPORT_EXTENSIONS, MIN_VALID_AUDIO_SIZE,
+    ProgressCallback, VIDEO_IMPORT_EXTENSIONS, get_video_duration_secs, has_supported_extension,
+    is_cap_project_path, is_supported_video_import_path, sanitize_filename,
+    transcode::transcode_video,
+};
+
+fn normalized_metadata_relative_path(
+    path: &RelativePathBuf,
+    asset_kind: &str,
+) -> Result<RelativePathBuf, String> {
+    let normalized = path.as_str().replace('\\', "/");
+    let path = RelativePathBuf::from(normalized);
+    let raw = path.as_str();
+    if raw.is_empty()
+        || raw.starts_with('/')
+        || raw.contains(':')
+        || path
+            .components()
+            .any(|component| matches!(component, RelativeComponent::ParentDir))
+    {
+        return Err(format!(
+            "Invalid {asset_kind} path in recording metadata: {raw}"
+        ));
+    }
+
+    Ok(path)
+}
+
+fn source_asset_path(
+    source_project_path: &Path,
+    source_relative_path: &RelativePathBuf,
+    asset_kind: &str,
+    allowed_extensions: &[&str],
+) -> Result<Option<PathBuf>, String> {
+    let source_relative_path = normalized_metadata_relative_path(source_relative_path, asset_kind)?;
+
+    if !has_supported_extension(Path::new(source_relative_path.as_str()), allowed_extensions) {
+        return Err(format!(
+            "Unsupported {asset_kind} file type: {}",
+            source_relative_path.as_str()
+        ));
+    }
+
+    let source_path = source_relative_path.to_path(source_project_path);
+    if !source_path.is_file() {
+        return Ok(None);
+    }
+
+    let source_root = source_project_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve source project path: {e}"))?;
+    let canonical_source_path = source_path
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {asset_kind} path: {e}"))?;
+
+    if !canonical_source_path.starts_with(&source_root) {
+        return Err(format!(
+            "{asset_kind} path escapes source project: {}",
+            source_relative_path.as_str()
+        ));
+    }
+
+    Ok(Some(canonical_source_path))
+}
+
+fn required_source_asset_path(
+    source_project_path: &Path,
+    source_relative_path: &RelativePathBuf,
+    asset_kind: &str,
+    allowed_extensions: &[&str],
+) -> Result<PathBuf, String> {
+    source_asset_path(
+        source_project_path,
+        source_relative_path,
+        asset_kind,
+        allowed_extensions,
+    )?
+    .ok_or_else(|| {
+        format!(
+            "Missing {asset_kind} file: {}",
+            source_relative_path.to_path(source_project_path).display()
+        )
+    })
+}
+
+fn legacy_cursor_relative_path(path: &str) -> Result<RelativePathBuf, String> {
+    normalized_metadata_relative_path(&RelativePathBuf::from(path), "cursor image")
+}
+
+pub fn same_project_path(a: &Path, b: &Path) -> bool {
+    let a = a.canonicalize().unwrap_or_else(|_| a.to_path_buf());
+    let b = b.canonicalize().unwrap_or_else(|_| b.to_path_buf());
+    a == b
+}
+
+fn ensure_multiple_segments(meta: &mut RecordingMeta) -> Result<&mut MultipleSegments, String> {
+    let RecordingMetaInner::Studio(studio_meta) = &mut meta.inner else {
+        return Err("Instant mode recordings cannot be edited".to_string());
+    };
+
+    if let StudioRecordingMeta::SingleSegment { segment } = studio_meta.as_ref() {
+        let segment = segment.clone();
+        **studio_meta = StudioRecordingMeta::MultipleSegments {
+            inner: MultipleSegments {
+                segments: vec![MultipleSegment {
+                    display: segment.display,
+                    camera: segment.camera,
+                    mic: segment.audio,
+                    additional_mics: Vec::new(),
+                    system_audio: None,
+                    cursor: segment.cursor,
+                    keyboard: None,
+                }],
+                cursors: Cursors::default(),
+                status: Some(StudioRecordingStatus::Complete),
+                markers: Vec::new(),
+            },
+        };
+    }
+
+    match studio_meta.as_mut() {
+        StudioRecordingMeta::MultipleSegments { inner } => Ok(inner),
+        StudioRecordingMeta::SingleSegment { .. } => {
+            Err("Failed to normalize project recording segments".to_string())
+        }
+    }
+}
+
+fn full_timeline_for_segments(
+    project_path: &Path,
+    segments: &[MultipleSegment],
+) -> Result<Vec<TimelineSegment>, String> {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let duration = get_video_duration_secs(&segment.display.path.to_path(project_path))?;
+            Ok(TimelineSegment {
+                recording_clip: index as u32,
+                timescale: 1.0,
+                start: 0.0,
+                end: duration,
+                name: None,
+                speed_audio_mode: None,
+            })
+        })
+        .collect()
+}
+
+fn get_source_video_duration_secs(
+    source_meta: &RecordingMeta,
+    video: &VideoMeta,
+) -> Result<f64, String> {
+    let source_path = required_source_asset_path(
+        &source_meta.project_path,
+        &video.path,
+        "video",
+        VIDEO_IMPORT_EXTENSIONS,
+    )?;
+    get_video_duration_secs(&source_path)
+}
+
+fn full_timeline_for_source_segments(
+    source_meta: &RecordingMeta,
+    segments: &[MultipleSegment],
+) -> Result<Vec<TimelineSegment>, String> {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            let duration = get_source_video_duration_secs(source_meta, &segment.display)?;
+            Ok(TimelineSegment {
+                recording_clip: index as u32,
+                timescale: 1.0,
+                start: 0.0,
+                end: duration,
+                name: None,
+                speed_audio_mode: None,
+            })
+        })
+        .collect()
+}
+
+fn ensure_project_timeline<'a>(
+    config: &'a mut ProjectConfiguration,
+    project_path: &Path,
+    segments: &[MultipleSegment],
+) -> Result<&'a mut TimelineConfiguration, String> {
+    if config.timeline.is_none() {
+        config.timeline = Some(TimelineConfiguration {
+            segments: full_timeline_for_segments(project_path, segments)?,
+            transitions: Vec::new(),
+            zoom_segments: Vec::new(),
+            scene_segments: Vec::new(),
+            mask_segments: Vec::new(),
+            text_segments: Vec::new(),
+            caption_segments: Vec::new(),
+            keyboard_segments: Vec::new(),
+            audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
+        });
+    }
+
+    config
+        .timeline
+        .as_mut()
+        .ok_or_else(|| "Failed to prepare project timeline".to_string())
+}
+
+fn add_clip_configs(
+    config: &mut ProjectConfiguration,
+    base_index: u32,
+    segments: &[MultipleSegment],
+) {
+    for (offset, segment) in segments.iter().enumerate() {
+        let index = base_index + offset as u32;
+        let offsets = segment.calculate_audio_offsets();
+
+        if let Some(existing) = config.clips.iter_mut().find(|clip| clip.index == index) {
+            existing.offsets = offsets;
+            existing.offsets_auto_calculated = true;
+        } else {
+            config.clips.push(ClipConfiguration {
+                index,
+                offsets,
+                offsets_auto_calculated: true,
+            });
+        }
+    }
+}
+
+fn unique_segment_dir(
+    project_path: &Path,
+    index: u32,
+) -> Result<(PathBuf, String), std::io::Error> {
+    let segments_root = project_path.join("content").join("segments");
+    std::fs::create_dir_all(&segments_root)?;
+
+    let mut counter = 0;
+    loop {
+        let name = if counter == 0 {
+            format!("segment-{index}")
+        } else {
+            format!("segment-{index}-import-{counter}")
+        };
+        let path = segments_root.join(&name);
+        if !path.exists() {
+            std::fs::create_dir_all(&path)?;
+            return Ok((path, format!("content/segments/{name}")));
+        }
+        counter += 1;
+    }
+}
+
+fn relative_file_extension(path: &RelativePathBuf, fallback: &str) -> String {
+    Path::new(path.as_str())
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| !ext.is_empty())
+        .unwrap_or(fallback)
+        .to_string()
+}
+
+fn relative_file_name(path: &RelativePathBuf, fallback: &str) -> String {
+    Path::new(path.as_str())
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| !name.is_empty())
+        .unwrap_or(fallback)
+        .to_string()
+}
+
+fn unique_file_name(dir: &Path, preferred: &str) -> String {
+    let sanitized = sanitize_filename(preferred);
+    let sanitized = if sanitized.is_empty() {
+        "file".to_string()
+    } else {
+        sanitized
+    };
+
+    let path = Path::new(&sanitized);
+    let stem = path
+        .file_stem()
+        .and_then(|value| value.to_str())
+        .filter(|value| !value.is_empty())
+        .unwrap_or("file")
+        .to_string();
+    let extension = path
+        .extension()
+        .and_then(|value| value.to_str())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string());
+
+    let mut counter = 0;
+    loop {
+        let candidate = if counter == 0 {
+            sanitized.clone()
+        } else if let Some(extension) = &extension {
+            format!("{stem}-{counter}.{extension}")
+        } else {
+            format!("{stem}-{counter}")
+        };
+
+        if !dir.join(&candidate).exists() {
+            return candidate;
+        }
+
+        counter += 1;
+    }
+}
+
+fn copy_file_to_relative_path(
+    source_path: &Path,
+    target_project_path: &Path,
+    target_relative_path: &RelativePathBuf,
+) -> Result<(), String> {
+    let target_path = target_relative_path.to_path(target_project_path);
+
+    if let Some(parent) = target_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create import directory: {e}"))?;
+    }
+
+    std::fs::copy(source_path, &target_path)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to copy {}: {e}", source_path.display()))
+}
+
+fn copy_video_meta(
+    source_project_path: &Path,
+    target_project_path: &Path,
+    source: &VideoMeta,
+    target_relative_dir: &str,
+    name: &str,
+    required: bool,
+) -> Result<Option<VideoMeta>, String> {
+    let Some(source_path) = source_asset_path(
+        source_project_path,
+        &source.path,
+        "video",
+        VIDEO_IMPORT_EXTENSIONS,
+    )?
+    else {
+        if required {
+            return Err(format!(
+                "Missing video file: {}",
+                source.path.to_path(source_project_path).display()
+            ));
+        }
+        return Ok(None);
+    };
+
+    let can_decode = probe_video_can_decode(&source_path)
+        .map_err(|e| format!("Cannot decode video {}: {e}", source_path.display()))?;
+    if !can_decode {
+        if required {
+            return Err(format!("Unsupported video file: {}", source_path.display()));
+        }
+        return Ok(None);
+    }
+
+    let extension = relative_file_extension(&source.path, "mp4");
+    let target_relative_path =
+        RelativePathBuf::from(format!("{target_relative_dir}/{name}.{extension}"));
+    copy_file_to_relative_path(&source_path, target_project_path, &target_relative_path)?;
+
+    let mut copied = source.clone();
+    copied.path = target_relative_path;
+    Ok(Some(copied))
+}
+
+fn copy_audio_meta(
+    source_project_path: &Path,
+    target_project_path: &Path,
+    source: &AudioMeta,
+    target_relative_dir: &str,
+    name: &str,
+) -> Result<Option<AudioMeta>, String> {
+    let Some(source_path) = source_asset_path(
+        source_project_path,
+        &source.path,
+        "audio",
+        AUDIO_IMPORT_EXTENSIONS,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    let extension = relative_file_extension(&source.path, "ogg");
+    let target_relative_path =
+        RelativePathBuf::from(format!("{target_relative_dir}/{name}.{extension}"));
+    copy_file_to_relative_path(&source_path, target_project_path, &target_relative_path)?;
+
+    let mut copied = source.clone();
+    copied.path = target_relative_path;
+    Ok(Some(copied))
+}
+
+fn copy_keyboard_path(
+    source_meta: &RecordingMeta,
+    source_segment: &MultipleSegment,
+    target_project_path: &Path,
+    target_relative_dir: &str,
+) -> Result<Option<RelativePathBuf>, String> {
+    if let Some(source_relative_path) = &source_segment.keyboard {
+        let file_name =
+            relative_file_name(source_relative_path, cap_project::KEYBOARD_EVENTS_FILE_NAME);
+        let Some(source_path) = source_asset_path(
+            &source_meta.project_path,
+            source_relative_path,
+            "keyboard events",
+            KEYBOARD_IMPORT_EXTENSIONS,
+        )?
+        else {
+            return Ok(None);
+        };
+
+        let target_relative_path = RelativePathBuf::from(format!(
+            "{target_relative_dir}/{}",
+            sanitize_filename(&file_name)
+        ));
+        copy_file_to_relative_path(&source_path, target_project_path, &target_relative_path)?;
+
+        return Ok(Some(target_relative_path));
+    };
+
+    let Some(display_dir) = source_segment.display.path.parent() else {
+        return Ok(None);
+    };
+
+    for file_name in [
+        cap_project::KEYBOARD_EVENTS_FILE_NAME,
+        cap_project::LEGACY_KEYBOARD_EVENTS_FILE_NAME,
+    ] {
+        let source_relative_path = display_dir.join(file_name);
+        let Some(source_path) = source_asset_path(
+            &source_meta.project_path,
+            &source_relative_path,
+            "keyboard events",
+            KEYBOARD_IMPORT_EXTENSIONS,
+        )?
+        else {
+            continue;
+        };
+
+        let target_relative_path = RelativePathBuf::from(format!(
+            "{target_relative_dir}/{}",
+            sanitize_filename(file_name)
+        ));
+        copy_file_to_relative_path(&source_path, target_project_path, &target_relative_path)?;
+
+        return Ok(Some(target_relative_path));
+    }
+
+    Ok(None)
+}
+
+fn normalize_cursors_to_correct(cursors: &mut Cursors) -> &mut HashMap<String, CursorMeta> {
+    if let Cursors::Old(old) = cursors {
+        let converted = old
+            .iter()
+            .map(|(id, path)| {
+                (
+                    id.clone(),
+                    CursorMeta {
+                        image_path: RelativePathBuf::from(path.as_str()),
+                        hotspot: XY::new(0.0, 0.0),
+                        shape: None,
+                    },
+                )
+            })
+            .collect();
+        *cursors = Cursors::Correct(converted);
+    }
+
+    match cursors {
+        Cursors::Correct(map) => map,
+        Cursors::Old(_) => unreachable!(),
+    }
+}
+
+fn unique_cursor_id(
+    cursors: &HashMap<String, CursorMeta>,
+    import_token: &str,
+    source_id: &str,
+) -> String {
+    let source_id = if source_id.is_empty() {
+        "cursor"
+    } else {
+        source_id
+    };
+    let base = format!("{import_token}-{source_id}");
+    if !cursors.contains_key(&base) {
+        return base;
+    }
+
+    let mut counter = 1;
+    loop {
+        let candidate = format!("{base}-{counter}");
+        if !cursors.contains_key(&candidate) {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+fn copy_source_cursor_images(
+    source_meta: &RecordingMeta,
+    source_cursors: &Cursors,
+    target_project_path: &Path,
+    target_cursors: &mut Cursors,
+    import_token: &str,
+) -> Result<HashMap<String, String>, String> {
+    let target_cursor_dir = target_project_path.join("content").join("cursors");
+    std::fs::create_dir_all(&target_cursor_dir)
+        .map_err(|e| format!("Failed to create cursor directory: {e}"))?;
+
+    let target_cursors = normalize_cursors_to_correct(target_cursors);
+    let mut id_map = HashMap::new();
+
+    match source_cursors {
+        Cursors::Correct(source_map) => {
+            for (source_id, cursor) in source_map {
+                let Some(source_path) = source_asset_path(
+                    &source_meta.project_path,
+                    &cursor.image_path,
+                    "cursor image",
+                    IMAGE_IMPORT_EXTENSIONS,
+                )?
+                else {
+                    continue;
+                };
+
+                let new_id = unique_cursor_id(target_cursors, import_token, source_id);
+                let source_file_name = relative_file_name(&cursor.image_path, "cursor.png");
+                let target_file_name =
+                    unique_file_name(&target_cursor_dir, &format!("{new_id}-{source_file_name}"));
+                let target_relative_path =
+                    RelativePathBuf::from(format!("content/cursors/{target_file_name}"));
+
+                copy_file_to_relative_path(
+                    &source_path,
+                    target_project_path,
+                    &target_relative_path,
+                )?;
+
+                target_cursors.insert(
+                    new_id.clone(),
+                    CursorMeta {
+                        image_path: target_relative_path,
+                        hotspot: cursor.hotspot,
+                        shape: cursor.shape,
+                    },
+                );
+                id_map.insert(source_id.clone(), new_id);
+            }
+        }
+        Cursors::Old(source_map) => {
+            for (source_id, source_path) in source_map {
+                let source_relative_path = legacy_cursor_relative_path(source_path)?;
+                let Some(source_path) = source_asset_path(
+                    &source_meta.project_path,
+                    &source_relative_path,
+                    "cursor image",
+                    IMAGE_IMPORT_EXTENSIONS,
+                )?
+                else {
+                    continue;
+                };
+
+                let new_id = unique_cursor_id(target_cursors, import_token, source_id);
+                let source_file_name = relative_file_name(&source_relative_path, "cursor.png");
+                let target_file_name =
+                    unique_file_name(&target_cursor_dir, &format!("{new_id}-{source_file_name}"));
+                let target_relative_path =
+                    RelativePathBuf::from(format!("content/cursors/{target_file_name}"));
+
+                copy_file_to_relative_path(
+                    &source_path,
+                    target_project_path,
+                    &target_relative_path,
+                )?;
+
+                target_cursors.insert(
+                    new_id.clone(),
+                    CursorMeta {
+                        image_path: target_relative_path,
+                        hotspot: XY::new(0.0, 0.0),
+                        shape: None,
+                    },
+                );
+                id_map.insert(source_id.clone(), new_id);
+            }
+        }
+    }
+
+    Ok(id_map)
+}
+
+fn copy_cursor_events_path(
+    source_meta: &RecordingMeta,
+    source_relative_path: &RelativePathBuf,
+    target_project_path: &Path,
+    target_relative_dir: &str,
+    cursor_id_map: &HashMap<String, String>,
+) -> Result<Option<RelativePathBuf>, String> {
+    let Some(source_path) = source_asset_path(
+        &source_meta.project_path,
+        source_relative_path,
+        "cursor events",
+        CURSOR_EVENTS_IMPORT_EXTENSIONS,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    let target_relative_path = RelativePathBuf::from(format!("{target_relative_dir}/cursor.json"));
+    let target_path = target_relative_path.to_path(target_project_path);
+    if let Some(parent) = target_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create cursor event directory: {e}"))?;
+    }
+
+    if cursor_id_map.is_empty() {
+        std::fs::copy(&source_path, &target_path)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to copy cursor events: {e}"))?;
+        return Ok(Some(target_relative_path));
+    }
+
+    match CursorEvents::load_from_file(&source_path) {
+        Ok(mut events) => {
+            for event in &mut events.moves {
+                if let Some(new_id) = cursor_id_map.get(&event.cursor_id) {
+                    event.cursor_id = new_id.clone();
+                }
+            }
+            for event in &mut events.clicks {
+                if let Some(new_id) = cursor_id_map.get(&event.cursor_id) {
+                    event.cursor_id = new_id.clone();
+                }
+            }
+
+            let file = std::fs::File::create(&target_path)
+                .map_err(|e| format!("Failed to create cursor event file: {e}"))?;
+            serde_json::to_writer_pretty(file, &events)
+                .map_err(|e| format!("Failed to write cursor event file: {e}"))?;
+        }
+        Err(_) => {
+            std::fs::copy(&source_path, &target_path)
+                .map(|_| ())
+                .map_err(|e| format!("Failed to copy cursor events: {e}"))?;
+        }
+    }
+
+    Ok(Some(target_relative_path))
+}
+
+fn single_segment_to_multiple(segment: &SingleSegment) -> MultipleSegment {
+    MultipleSegment {
+        display: segment.display.clone(),
+        camera: segment.camera.clone(),
+        mic: segment.audio.clone(),
+        additional_mics: Vec::new(),
+        system_audio: None,
+        cursor: segment.cursor.clone(),
+        keyboard: None,
+    }
+}
+
+fn studio_segments_for_import(studio_meta: &StudioRecordingMeta) -> Vec<MultipleSegment> {
+    match studio_meta {
+        StudioRecordingMeta::SingleSegment { segment } => {
+            vec![single_segment_to_multiple(segment)]
+        }
+        StudioRecordingMeta::MultipleSegments { inner } => inner.segments.clone(),
+    }
+}
+
+fn source_timeline_segments_for_import(
+    source_meta: &RecordingMeta,
+    source_segments: &[MultipleSegment],
+) -> Result<Vec<TimelineSegment>, String> {
+    let source_config = ProjectConfiguration::load(&source_meta.project_path).unwrap_or_default();
+    let Some(timeline) = source_config.timeline else {
+        return full_timeline_for_source_segments(source_meta, source_segments);
+    };
+
+    if timeline.segments.is_empty() {
+        return full_timeline_for_source_segments(source_meta, source_segments);
+    }
+
+    let mut duration_cache = HashMap::new();
+    let mut imported_segments = Vec::new();
+
+    for segment in timeline.segments {
+        let source_index = segment.recording_clip;
+        let Some(source_segment) = source_segments.get(source_index as usize) else {
+            continue;
+        };
+
+        let max_duration = if let Some(duration) = duration_cache.get(&source_index) {
+            *duration
+        } else {
+            let duration = get_source_video_duration_secs(source_meta, &source_segment.display)?;
+            duration_cache.insert(source_index, duration);
+            duration
+        };
+
+        if max_duration <= 0.0 {
+            continue;
+        }
+
+        let raw_start = if segment.start.is_finite() {
+            segment.start
+        } else {
+            0.0
+        };
+        let raw_end = if segment.end.is_finite() {
+            segment.end
+        } else {
+            max_duration
+        };
+        let start = raw_start.clamp(0.0, max_duration);
+        let end = raw_end.clamp(start, max_duration);
+        if end <= start {
+            continue;
+        }
+
+        imported_segments.push(TimelineSegment {
+            recording_clip: source_index,
+            timescale: if segment.timescale.is_finite() && segment.timescale > 0.0 {
+                segment.timescale
+            } else {
+                1.0
+            },
+            start,
+            end,
+            name: None,
+            speed_audio_mode: None,
+        });
+    }
+
+    if imported_segments.is_empty() {
+        full_timeline_for_source_segments(source_meta, source_segments)
+    } else {
+        Ok(imported_segments)
+    }
+}
+
+fn copy_source_segment(
+    source_meta: &RecordingMeta,
+    source_segment: &MultipleSegment,
+    target_project_path: &Path,
+    target_relative_dir: &str,
+    cursor_id_map: &HashMap<String, String>,
+) -> Result<MultipleSegment, String> {
+    let display = copy_video_meta(
+        &source_meta.project_path,
+        target_project_path,
+        &source_segment.display,
+        target_relative_dir,
+        "display",
+        true,
+    )?
+    .ok_or_else(|| "Missing display video".to_string())?;
+
+    let camera = source_segment
+        .camera
+        .as_ref()
+        .map(|camera| {
+            copy_video_meta(
+                &source_meta.project_path,
+                target_project_path,
+                camera,
+                target_relative_dir,
+                "camera",
+                false,
+            )
+        })
+        .transpose()?
+        .flatten();
+
+    let mic = source_segment
+        .mic
+        .as_ref()
+        .map(|mic| {
+            copy_audio_meta(
+                &source_meta.project_path,
+                target_project_path,
+                mic,
+                target_relative_dir,
+                "mic",
+            )
+        })
+        .transpose()?
+        .flatten();
+
+    let additional_mics = source_segment
+        .additional_mics
+        .iter()
+        .enumerate()
+        .map(|(index, mic)| {
+            mic.as_ref()
+                .map(|mic| {
+                    copy_audio_meta(
+                        &source_meta.project_path,
+                        target_project_path,
+                        mic,
+                        target_relative_dir,
+                        &format!("mic-{}", index + 2),
+                    )
+                })
+                .transpose()
+                .map(Option::flatten)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let system_audio = source_segment
+        .system_audio
+        .as_ref()
+        .map(|system_audio| {
+            copy_audio_meta(
+                &source_meta.project_path,
+                target_project_path,
+                system_audio,
+                target_relative_dir,
+                "system-audio",
+            )
+        })
+        .transpose()?
+        .flatten();
+
+    let cursor = source_segment
+        .cursor
+        .as_ref()
+        .map(|cursor| {
+            copy_cursor_events_path(
+                source_meta,
+                cursor,
+                target_project_path,
+                target_relative_dir,
+                cursor_id_map,
+            )
+        })
+        .transpose()?
+        .flatten();
+
+    let keyboard = copy_keyboard_path(
+        source_meta,
+        source_segment,
+        target_project_path,
+        target_relative_dir,
+    )?;
+
+    Ok(MultipleSegment {
+        display,
+        camera,
+        mic,
+        additional_mics,
+        system_audio,
+        cursor,
+        keyboard,
+    })
+}
+
+/// Transcodes a video file into a new recording segment at the end of an existing studio project's
+/// timeline. Returns the number of segments added.
+pub async fn append_video_to_project(
+    target_project_path: PathBuf,
+    source_path: PathBuf,
+    on_progress: ProgressCallback,
+) -> Result<usize, String> {
+    if !is_supported_video_import_path(&source_path) {
+        return Err(format!(
+            "Unsupported video file: {} (expected one of {})",
+            source_path.display(),
+            VIDEO_IMPORT_EXTENSIONS.join(", ")
+        ));
+    }
+
+    let mut target_meta = RecordingMeta::load_for_project(&target_project_path)
+        .map_err(|e| format!("Failed to load target project metadata: {e}"))?;
+    let mut config = ProjectConfiguration::load(&target_project_path).unwrap_or_default();
+    let existing_segments = {
+        let inner = ensure_multiple_segments(&mut target_meta)?;
+        inner.status = Some(StudioRecordingStatus::Complete);
+        inner.segments.clone()
+    };
+    ensure_project_timeline(&mut config, &target_project_path, &existing_segments)?;
+
+    let new_index = existing_segments.len() as u32;
+    let (_, target_relative_dir) = unique_segment_dir(&target_project_path, new_index)
+        .map_err(|e| format!("Failed to create imported segment directory: {e}"))?;
+
+    let output_video_relative_path =
+        RelativePathBuf::from(format!("{target_relative_dir}/display.mp4"));
+    let output_audio_relative_path =
+        RelativePathBuf::from(format!("{target_relative_dir}/audio.ogg"));
+    let output_video_path = output_video_relative_path.to_path(&target_project_path);
+    let output_audio_path = output_audio_relative_path.to_path(&target_project_path);
+
+    on_progress(ImportProgress::new(
+        ImportStage::Probing,
+        0.0,
+        "Analyzing video file...",
+    ));
+
+    let can_decode =
+        probe_video_can_decode(&source_path).map_err(|e| format!("Cannot decode video: {e}"))?;
+    if !can_decode {
+        return Err("Video format not supported or file is corrupted".to_string());
+    }
+
+    on_progress(ImportProgress::new(
+        ImportStage::Converting,
+        0.0,
+        "Starting conversion...",
+    ));
+
+    let on_progress_for_transcode = on_progress.clone();
+    let source_path_for_transcode = source_path.clone();
+    let output_video_path_for_transcode = output_video_path.clone();
+    let output_audio_path_for_transcode = output_audio_path.clone();
+    let target_project_path_for_transcode = target_project_path.clone();
+
+    let (fps, sample_rate) = tokio::task::spawn_blocking(move || {
+        transcode_video(
+            &source_path_for_transcode,
+            &output_video_path_for_transcode,
+            Some(&output_audio_path_for_transcode),
+            &target_project_path_for_transcode,
+            on_progress_for_transcode.as_ref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("Video import task failed: {e}"))?
+    .map_err(|e| e.to_string())?;
+
+    let duration = get_video_duration_secs(&output_video_path)?;
+    let audio_file_size = std::fs::metadata(&output_audio_path)
+        .map(|metadata| metadata.len())
+        .unwrap_or(0);
+    let system_audio = if sample_rate.is_some() && audio_file_size > MIN_VALID_AUDIO_SIZE {
+        Some(AudioMeta {
+            path: output_audio_relative_path,
+            start_time: Some(0.0),
+            device_id: None,
+            gap_summary: None,
+        })
+    } else {
+        None
+    };
+
+    let imported_segment = MultipleSegment {
+        display: VideoMeta {
+            path: output_video_relative_path,
+            fps,
+            start_time: Some(0.0),
+            device_id: None,
+        },
+        camera: None,
+        mic: None,
+        additional_mics: Vec::new(),
+        system_audio,
+        cursor: None,
+        keyboard: None,
+    };
+
+    {
+        let inner = ensure_multiple_segments(&mut target_meta)?;
+        inner.status = Some(StudioRecordingStatus::Complete);
+        inner.segments.push(imported_segment.clone());
+    }
+
+    ensure_project_timeline(&mut config, &target_project_path, &existing_segments)?
+        .segments
+        .push(TimelineSegment {
+            recording_clip: new_index,
+            timescale: 1.0,
+            start: 0.0,
+            end: duration,
+            name: None,
+            speed_audio_mode: None,
+        });
+    add_clip_configs(
+        &mut config,
+        new_index,
+        std::slice::from_ref(&imported_segment),
+    );
+
+    target_meta
+        .save_for_project()
+        .map_err(|e| format!("Failed to save project metadata: {e:?}"))?;
+    config
+        .write(&target_project_path)
+        .map_err(|e| format!("Failed to save project config: {e}"))?;
+
+    on_progress(ImportProgress::new(
+        ImportStage::Complete,
+        1.0,
+        "Import complete!",
+    ));
+
+    Ok(1)
+}
+
+/// Copies every recording segment (with camera, audio, cursor and keyboard data) of another `.cap`
+/// project onto the end of a studio project, keeping the source's timeline cuts. Complete instant
+/// recordings are appended as video. Returns the number of segments added.
+pub async fn append_cap_project_to_project(
+    target_project_path: PathBuf,
+    source_project_path: PathBuf,
+    on_progress: ProgressCallback,
+) -> Result<usize, String> {
+    let source_meta = RecordingMeta::load_for_project(&source_project_path)
+        .map_err(|e| format!("Failed to load source project metadata: {e}"))?;
+
+    let RecordingMetaInner::Studio(source_studio_meta) = &source_meta.inner else {
+        return match &source_meta.inner {
+            RecordingMetaInner::Instant(InstantRecordingMeta::Complete { .. }) => {
+                append_video_to_project(target_project_path, source_meta.output_path(), on_progress)
+                    .await
+            }
+            RecordingMetaInner::Instant(InstantRecordingMeta::InProgress { .. }) => {
+                Err("Source Cap project is still recording".to_string())
+            }
+            RecordingMetaInner::Instant(InstantRecordingMeta::Failed { error }) => {
+                Err(format!("Source Cap project failed: {error}"))
+            }
+            RecordingMetaInner::Studio(_) => unreachable!(),
+        };
+    };
+
+    let source_segments = studio_segments_for_import(source_studio_meta);
+    if source_segments.is_empty() {
+        return Err("Source Cap project has no recording segments".to_string());
+    }
+
+    let source_timeline = source_timeline_segments_for_import(&source_meta, &source_segments)?;
+    let source_cursors = match source_studio_meta.as_ref() {
+        StudioRecordingMeta::MultipleSegments { inner } => Some(&inner.cursors),
+        StudioRecordingMeta::SingleSegment { .. } => None,
+    };
+
+    let mut target_meta = RecordingMeta::load_for_project(&target_project_path)
+        .map_err(|e| format!("Failed to load target project metadata: {e}"))?;
+    let mut config = ProjectConfiguration::load(&target_project_path).unwrap_or_default();
+    let existing_segments = {
+        let inner = ensure_multiple_segments(&mut target_meta)?;
+        inner.status = Some(StudioRecordingStatus::Complete);
+        inner.segments.clone()
+    };
+    ensure_project_timeline(&mut config, &target_project_path, &existing_segments)?;
+
+    let (base_index, copied_segments, source_to_target_index) = {
+        let inner = ensure_multiple_segments(&mut target_meta)?;
+        inner.status = Some(StudioRecordingStatus::Complete);
+        let base_index = inner.segments.len() as u32;
+        let import_token = format!("import-{}", uuid::Uuid::new_v4().simple());
+        let cursor_id_map = if let Some(source_cursors) = source_cursors {
+            copy_source_cursor_images(
+                &source_meta,
+                source_cursors,
+                &target_project_path,
+                &mut inner.cursors,
+                &import_token,
+            )?
+        } else {
+            HashMap::new()
+        };
+
+        let mut copied_segments = Vec::new();
+        let mut source_to_target_index = HashMap::new();
+
+        for (source_index, source_segment) in source_segments.iter().enumerate() {
+            let target_index = base_index + copied_segments.len() as u32;
+            let (_, target_relative_dir) =
+                unique_segment_dir(&target_project_path, target_index)
+                    .map_err(|e| format!("Failed to create imported segment directory: {e}"))?;
+            let copied_segment = copy_source_segment(
+                &source_meta,
+                source_segment,
+                &target_project_path,
+                &target_relative_dir,
+                &cursor_id_map,
+            )?;
+
+            inner.segments.push(copied_segment.clone());
+            copied_segments.push(copied_segment);
+            source_to_target_index.insert(source_index as u32, target_index);
+        }
+
+        inner
+            .markers
+            .extend(source_studio_meta.markers().iter().filter_map(|marker| {
+                Some(RecordingMarker {
+                    segment: *source_to_target_index.get(&marker.segment)?,
+                    ..marker.clone()
+                })
+            }));
+
+        (base_index, copied_segments, source_to_target_index)
+    };
+
+    if copied_segments.is_empty() {
+        return Err("Source Cap project has no importable recording segments".to_string());
+    }
+
+    {
+        let timeline =
+            ensure_project_timeline(&mut config, &target_project_path, &existing_segments)?;
+        for source_segment in source_timeline {
+            let Some(target_index) = source_to_target_index.get(&source_segment.recording_clip)
+            else {
+                continue;
+            };
+
+            timeline.segments.push(TimelineSegment {
+                recording_clip: *target_index,
+                timescale: source_segment.timescale,
+                start: source_segment.start,
+                end: source_segment.end,
+                name: None,
+                speed_audio_mode: source_segment.speed_audio_mode,
+            });
+        }
+    }
+
+    add_clip_configs(&mut config, base_index, &copied_segments);
+
+    target_meta
+        .save_for_project()
+        .map_err(|e| format!("Failed to save project metadata: {e:?}"))?;
+    config
+        .write(&target_project_path)
+        .map_err(|e| format!("Failed to save project config: {e}"))?;
+
+    Ok(copied_segments.len())
+}
+
+/// Appends `source_path` — a video file or another `.cap` project — to the studio project at
+/// `target_project_path`. Returns the number of segments added.
+pub async fn append_to_project(
+    target_project_path: PathBuf,
+    source_path: PathBuf,
+    on_progress: ProgressCallback,
+) -> Result<usize, String> {
+    if same_project_path(&target_project_path, &source_path) {
+        return Err("Cannot import a recording into itself".to_string());
+    }
+
+    if is_cap_project_path(&source_path) {
+        append_cap_project_to_project(target_project_path, source_path, on_progress).await
+    } else if is_supported_video_import_path(&source_path) {
+        append_video_to_project(target_project_path, source_path, on_progress).await
+    } else {
+        Err("Select a video file or a Cap project folder".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_asset_path_allows_file_inside_source_project() {
+        let source_project = tempfile::tempdir().unwrap();
+        let source_relative_path = RelativePathBuf::from("content/segments/segment-0/display.mp4");
+        let source_path = source_relative_path.to_path(source_project.path());
+        std::fs::create_dir_all(source_path.parent().unwrap()).unwrap();
+        std::fs::write(&source_path, b"video").unwrap();
+
+        let resolved = source_asset_path(
+            source_project.path(),
+            &source_relative_path,
+            "video",
+            VIDEO_IMPORT_EXTENSIONS,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(resolved, source_path.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn source_asset_path_allows_backslash_separators() {
+        let source_project = tempfile::tempdir().unwrap();
+        let source_relative_path =
+            RelativePathBuf::from("content\\segments\\segment-0\\display.mp4");
+        let source_path = RelativePathBuf::from("content/segments/segment-0/display.mp4")
+            .to_path(source_project.path());
+        std::fs::create_dir_all(source_path.parent().unwrap()).unwrap();
+        std::fs::write(&source_path, b"video").unwrap();
+
+        let resolved = source_asset_path(
+            source_project.path(),
+            &source_relative_path,
+            "video",
+            VIDEO_IMPORT_EXTENSIONS,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(resolved, source_path.canonicalize().unwrap());
+    }
+
+    #[test]
+    fn source_asset_path_rejects_parent_traversal() {
+        let source_project = tempfile::tempdir().unwrap();
+        let source_relative_path = RelativePathBuf::from("../secret.mp4");
+
+        let error = source_asset_path(
+            source_project.path(),
+            &source_relative_path,
+            "video",
+            VIDEO_IMPORT_EXTENSIONS,
+        )
+        .unwrap_err();
+
+        assert!(error.contains("Invalid video path"));
+    }
+
+    #[test]
+    fn source_asset_path_rejects_absolute_path() {
+        let source_project = tempfile::tempdir().unwrap();
+        let source_relative_path = RelativePathBuf::from("/tmp/secret.mp4");
+
+        let error = source_asset_path(
+            source_project.path(),
+            &source_relative_path,
+            "video",
+            VIDEO_IMPORT_EXTENSIONS,
+        )
+        .unwrap_err();
+
+        assert!(error.contains("Invalid video path"));
+    }
+
+    #[test]
+    fn legacy_cursor_relative_path_rejects_windows_absolute_path() {
+        let error = legacy_cursor_relative_path("C:\\Users\\me\\cursor.png").unwrap_err();
+
+        assert!(error.contains("Invalid cursor image path"));
+    }
+
+    #[test]
+    fn source_asset_path_rejects_unsupported_extension() {
+        let source_project = tempfile::tempdir().unwrap();
+        let source_relative_path = RelativePathBuf::from("content/segments/segment-0/display.txt");
+
+        let error = source_asset_path(
+            source_project.path(),
+            &source_relative_path,
+            "video",
+            VIDEO_IMPORT_EXTENSIONS,
+        )
+        .unwrap_err();
+
+        assert!(error.contains("Unsupported video file type"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn source_asset_path_rejects_symlink_escape() {
+        let source_project = tempfile::tempdir().unwrap();
+        let external_dir = tempfile::tempdir().unwrap();
+        let external_file = external_dir.path().join("cursor.png");
+        std::fs::write(&external_file, b"cursor").unwrap();
+
+        let source_relative_path = RelativePathBuf::from("content/cursors/cursor.png");
+        let source_path = source_relative_path.to_path(source_project.path());
+        std::fs::create_dir_all(source_path.parent().unwrap()).unwrap();
+        std::os::unix::fs::symlink(&external_file, &source_path).unwrap();
+
+        let error = source_asset_path(
+            source_project.path(),
+            &source_relative_path,
+            "cursor image",
+            IMAGE_IMPORT_EXTENSIONS,
+        )
+        .unwrap_err();
+
+        assert!(error.contains("cursor image path escapes source project"));
+    }
+}
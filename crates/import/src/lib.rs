@@ -0,0 +1,489 @@
+mod append;
+mod transcode;
+
+pub use append::{
+    append_cap_project_to_project, append_to_project, append_video_to_project, same_project_path,
+};
+pub use transcode::transcode_video;
+
+use cap_enc_ffmpeg::remux::{get_media_duration, probe_video_can_decode};
+use cap_project::{
+    AudioMeta, Cursors, MultipleSegment, MultipleSegments, Platform, ProjectConfiguration,
+    RecordingMeta, RecordingMetaInner, SingleSegment, StudioRecordingMeta, StudioRecordingStatus,
+    VideoMeta,
+};
+use image::ImageEncoder;
+use relative_path::RelativePathBuf;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+pub const VIDEO_IMPORT_EXTENSIONS: &[&str] =
+    &["mp4", "mov", "avi", "mkv", "webm", "wmv", "m4v", "flv"];
+pub const IMAGE_IMPORT_EXTENSIONS: &[&str] =
+    &["png", "jpg", "jpeg", "webp", "gif", "bmp", "tif", "tiff"];
+const AUDIO_IMPORT_EXTENSIONS: &[&str] = &["ogg", "m4a", "mp3", "wav", "aac", "flac"];
+const KEYBOARD_IMPORT_EXTENSIONS: &[&str] = &["bin", "json"];
+const CURSOR_EVENTS_IMPORT_EXTENSIONS: &[&str] = &["json"];
+const MAX_IMAGE_DIMENSION: u32 = 16_384;
+const MIN_VALID_AUDIO_SIZE: u64 = 1000;
+
+const IMPORTED_DISPLAY_PATH: &str = "content/segments/segment-0/display.mp4";
+const IMPORTED_AUDIO_PATH: &str = "content/segments/segment-0/audio.ogg";
+
+#[derive(Serialize, Deserialize, Type, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportStage {
+    Probing,
+    Converting,
+    Finalizing,
+    Complete,
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+pub struct ImportProgress {
+    pub stage: ImportStage,
+    /// Fraction complete, `0.0..=1.0`.
+    pub progress: f64,
+    pub message: String,
+}
+
+impl ImportProgress {
+    pub fn new(stage: ImportStage, progress: f64, message: impl Into<String>) -> Self {
+        Self {
+            stage,
+            progress,
+            message: message.into(),
+        }
+    }
+}
+
+/// Receives progress for an import. Conversion runs on a blocking thread, so the callback is
+/// shared rather than borrowed.
+pub type ProgressCallback = Arc<dyn Fn(ImportProgress) + Send + Sync>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("Failed to open video file: {0}")]
+    OpenFailed(String),
+    #[error("No video stream found in file")]
+    NoVideoStream,
+    #[error("Failed to create decoder: {0}")]
+    DecoderFailed(String),
+    #[error("Failed to create encoder: {0}")]
+    EncoderFailed(String),
+    #[error("Failed to create project directory: {0}")]
+    DirectoryFailed(std::io::Error),
+    #[error("FFmpeg error: {0}")]
+    Ffmpeg(#[from] ffmpeg::Error),
+    #[error("Transcoding failed: {0}")]
+    TranscodeFailed(String),
+}
+
+fn check_project_exists(project_path: &Path) -> bool {
+    project_path.exists() && project_path.join("recording-meta.json").exists()
+}
+
+pub fn generate_project_name(source_path: &Path) -> String {
+    let stem = source_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Imported Video");
+
+    let now = chrono::Local::now();
+    let date_str = now.format("%Y-%m-%d at %H.%M.%S").to_string();
+
+    format!("{stem} {date_str}")
+}
+
+pub fn generate_image_project_name(source_path: &Path) -> String {
+    let stem = source_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Imported Image");
+
+    let now = chrono::Local::now();
+    let date_str = now.format("%Y-%m-%d at %H.%M.%S").to_string();
+
+    format!("{stem} {date_str}")
+}
+
+pub fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            _ => c,
+        })
+        .collect()
+}
+
+/// `<dir>/<name>.cap`, or `<dir>/<name> (N).cap` for the first `N` that doesn't exist yet.
+pub fn unique_project_path(dir: &Path, project_name: &str) -> PathBuf {
+    let sanitized_name = sanitize_filename(project_name);
+
+    let mut project_path = dir.join(format!("{sanitized_name}.cap"));
+    let mut counter = 1;
+    while project_path.exists() {
+        project_path = dir.join(format!("{sanitized_name} ({counter}).cap"));
+        counter += 1;
+    }
+
+    project_path
+}
+
+fn has_supported_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|ext| {
+            extensions
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+pub fn is_supported_video_import_path(path: &Path) -> bool {
+    path.is_file() && has_supported_extension(path, VIDEO_IMPORT_EXTENSIONS)
+}
+
+pub fn is_supported_image_import_path(path: &Path) -> bool {
+    path.is_file() && has_supported_extension(path, IMAGE_IMPORT_EXTENSIONS)
+}
+
+pub fn is_mp4_import_path(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("mp4"))
+}
+
+pub fn is_cap_project_path(path: &Path) -> bool {
+    path.is_dir() && path.join("recording-meta.json").is_file()
+}
+
+fn get_video_duration_secs(path: &Path) -> Result<f64, String> {
+    get_media_duration(path)
+        .map(|duration| duration.as_secs_f64())
+        .ok_or_else(|| format!("Could not determine video duration: {}", path.display()))
+}
+
+fn imported_video_meta(project_path: PathBuf, project_name: String, fps: u32) -> RecordingMeta {
+    RecordingMeta {
+        platform: Some(Platform::default()),
+        project_path,
+        pretty_name: project_name,
+        sharing: None,
+        inner: RecordingMetaInner::Studio(Box::new(StudioRecordingMeta::MultipleSegments {
+            inner: MultipleSegments {
+                segments: vec![MultipleSegment {
+                    display: VideoMeta {
+                        path: RelativePathBuf::from(IMPORTED_DISPLAY_PATH),
+                        fps,
+                        start_time: Some(0.0),
+                        device_id: None,
+                    },
+                    camera: None,
+                    mic: None,
+                    additional_mics: Vec::new(),
+                    system_audio: None,
+                    cursor: None,
+                    keyboard: None,
+                }],
+                cursors: Cursors::default(),
+                status: Some(StudioRecordingStatus::InProgress),
+                markers: Vec::new(),
+            },
+        })),
+        upload: None,
+    }
+}
+
+/// A video import whose project directory and in-progress metadata have been written, but whose
+/// video has not been converted yet. The desktop app hands [`VideoImport::project_path`] to the
+/// editor straight away and runs the conversion in the background.
+pub struct VideoImport {
+    source_path: PathBuf,
+    project_path: PathBuf,
+    project_name: String,
+}
+
+/// Probes `source_path` and creates a studio project at `project_path` for it.
+pub fn prepare_video_import(
+    source_path: PathBuf,
+    project_path: PathBuf,
+    project_name: String,
+    on_progress: &ProgressCallback,
+) -> Result<VideoImport, String> {
+    info!("Starting video import from: {:?}", source_path);
+
+    on_progress(ImportProgress::new(
+        ImportStage::Probing,
+        0.0,
+        "Analyzing video file...",
+    ));
+
+    let can_decode =
+        probe_video_can_decode(&source_path).map_err(|e| format!("Cannot decode video: {e}"))?;
+
+    if !can_decode {
+        on_progress(ImportProgress::new(
+            ImportStage::Failed,
+            0.0,
+            "Video format not supported",
+        ));
+        return Err("Video format not supported or file is corrupted".to_string());
+    }
+
+    std::fs::create_dir_all(
+        project_path
+            .join("content")
+            .join("segments")
+            .join("segment-0"),
+    )
+    .map_err(|e| e.to_string())?;
+
+    imported_video_meta(project_path.clone(), project_name.clone(), 30)
+        .save_for_project()
+        .map_err(|e| format!("Failed to save initial metadata: {e:?}"))?;
+
+    Ok(VideoImport {
+        source_path,
+        project_path,
+        project_name,
+    })
+}
+
+impl VideoImport {
+    pub fn project_path(&self) -> &Path {
+        &self.project_path
+    }
+
+    /// Converts the source video into the project and marks it complete. Returns the path of the
+    /// converted display video. Failures are reported through `on_progress` as well as returned.
+    pub async fn run(self, on_progress: ProgressCallback) -> Result<PathBuf, String> {
+        let result = self.convert(on_progress.clone()).await;
+        if let Err(error) = &result {
+            on_progress(ImportProgress::new(ImportStage::Failed, 0.0, error.clone()));
+        }
+        result
+    }
+
+    async fn convert(self, on_progress: ProgressCallback) -> Result<PathBuf, String> {
+        if !check_project_exists(&self.project_path) {
+            return Err("Import aborted before start: project directory missing".to_string());
+        }
+
+        on_progress(ImportProgress::new(
+            ImportStage::Converting,
+            0.0,
+            "Starting conversion...",
+        ));
+
+        let output_video_path =
+            RelativePathBuf::from(IMPORTED_DISPLAY_PATH).to_path(&self.project_path);
+        let output_audio_path =
+            RelativePathBuf::from(IMPORTED_AUDIO_PATH).to_path(&self.project_path);
+
+        let (fps, sample_rate) = {
+            let source_path = self.source_path.clone();
+            let output_video_path = output_video_path.clone();
+            let output_audio_path = output_audio_path.clone();
+            let project_path = self.project_path.clone();
+            let on_progress = on_progress.clone();
+
+            tokio::task::spawn_blocking(move || {
+                transcode_video(
+                    &source_path,
+                    &output_video_path,
+                    Some(&output_audio_path),
+                    &project_path,
+                    on_progress.as_ref(),
+                )
+            })
+            .await
+            .map_err(|e| format!("Transcoding task failed: {e}"))?
+            .map_err(|e| e.to_string())?
+        };
+
+        on_progress(ImportProgress::new(
+            ImportStage::Finalizing,
+            0.95,
+            "Creating project metadata...",
+        ));
+
+        let audio_file_size = std::fs::metadata(&output_audio_path)
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let mut meta = imported_video_meta(self.project_path.clone(), self.project_name, fps);
+        if let RecordingMetaInner::Studio(studio_meta) = &mut meta.inner
+            && let StudioRecordingMeta::MultipleSegments { inner } = studio_meta.as_mut()
+        {
+            inner.status = Some(StudioRecordingStatus::Complete);
+            if sample_rate.is_some() && audio_file_size > MIN_VALID_AUDIO_SIZE {
+                inner.segments[0].system_audio = Some(AudioMeta {
+                    path: RelativePathBuf::from(IMPORTED_AUDIO_PATH),
+                    start_time: Some(0.0),
+                    device_id: None,
+                    gap_summary: None,
+                });
+            }
+        }
+
+        meta.save_for_project()
+            .map_err(|e| format!("Failed to save metadata: {e:?}"))?;
+
+        on_progress(ImportProgress::new(
+            ImportStage::Complete,
+            1.0,
+            "Import complete!",
+        ));
+
+        info!("Video import complete: {:?}", self.project_path);
+
+        Ok(output_video_path)
+    }
+}
+
+/// Decodes an image and stores it as a screenshot project at `project_path`. Returns the path of the
+/// project's `original.png`.
+pub async fn import_image(
+    source_path: PathBuf,
+    project_path: PathBuf,
+    project_name: String,
+) -> Result<PathBuf, String> {
+    info!("Starting image import from: {:?}", source_path);
+
+    if !source_path.is_file() {
+        return Err("Image file does not exist".to_string());
+    }
+
+    let source_path_for_decode = source_path.clone();
+    let (image_width, image_height, image_data) =
+        tokio::task::spawn_blocking(move || -> Result<(u32, u32, Vec<u8>), String> {
+            let image = image::ImageReader::open(&source_path_for_decode)
+                .map_err(|e| format!("Failed to open image: {e}"))?
+                .with_guessed_format()
+                .map_err(|e| format!("Failed to detect image format: {e}"))?
+                .decode()
+                .map_err(|e| format!("Failed to decode image: {e}"))?;
+
+            let width = image.width();
+            let height = image.height();
+
+            if width > MAX_IMAGE_DIMENSION || height > MAX_IMAGE_DIMENSION {
+                return Err(format!("Image dimensions exceed maximum: {width}x{height}"));
+            }
+
+            if width
+                .checked_mul(height)
+                .and_then(|p| p.checked_mul(4))
+                .is_none()
+            {
+                return Err(format!("Image dimensions overflow: {width}x{height}"));
+            }
+
+            let rgba = image.to_rgba8();
+
+            Ok((width, height, rgba.into_raw()))
+        })
+        .await
+        .map_err(|e| format!("Failed to import image: {e}"))??;
+
+    std::fs::create_dir_all(&project_path)
+        .map_err(|e| format!("Failed to create screenshot project directory: {e}"))?;
+
+    let image_filename = "original.png";
+    let image_path = project_path.join(image_filename);
+    let image_path_for_write = image_path.clone();
+
+    tokio::task::spawn_blocking(move || -> Result<(), String> {
+        let file = std::fs::File::create(&image_path_for_write)
+            .map_err(|e| format!("Failed to create imported image file: {e}"))?;
+        let encoder = image::codecs::png::PngEncoder::new_with_quality(
+            std::io::BufWriter::new(file),
+            image::codecs::png::CompressionType::Default,
+            image::codecs::png::FilterType::Adaptive,
+        );
+
+        ImageEncoder::write_image(
+            encoder,
+            &image_data,
+            image_width,
+            image_height,
+            image::ColorType::Rgba8.into(),
+        )
+        .map_err(|e| format!("Failed to encode imported image: {e}"))
+    })
+    .await
+    .map_err(|e| format!("Failed to write imported image: {e}"))??;
+
+    let video_meta = VideoMeta {
+        path: RelativePathBuf::from(image_filename),
+        fps: 0,
+        start_time: Some(0.0),
+        device_id: None,
+    };
+
+    let segment = SingleSegment {
+        display: video_meta,
+        camera: None,
+        audio: None,
+        cursor: None,
+    };
+
+    let meta = RecordingMeta {
+        platform: Some(Platform::default()),
+        project_path: project_path.clone(),
+        pretty_name: project_name,
+        sharing: None,
+        inner: RecordingMetaInner::Studio(Box::new(StudioRecordingMeta::SingleSegment { segment })),
+        upload: None,
+    };
+
+    meta.save_for_project()
+        .map_err(|e| format!("Failed to save screenshot metadata: {e:?}"))?;
+
+    ProjectConfiguration::default()
+        .write(&project_path)
+        .map_err(|e| format!("Failed to save screenshot project config: {e}"))?;
+
+    Ok(image_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unique_project_path_appends_counter_for_existing_projects() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let first = unique_project_path(dir.path(), "clip: take 1");
+        assert_eq!(first, dir.path().join("clip_ take 1.cap"));
+
+        std::fs::create_dir_all(&first).unwrap();
+        let second = unique_project_path(dir.path(), "clip: take 1");
+        assert_eq!(second, dir.path().join("clip_ take 1 (1).cap"));
+    }
+
+    #[test]
+    fn supported_extensions_are_case_insensitive() {
+        assert!(has_supported_extension(
+            Path::new("capture.MOV"),
+            VIDEO_IMPORT_EXTENSIONS
+        ));
+        assert!(has_supported_extension(
+            Path::new("still.Png"),
+            IMAGE_IMPORT_EXTENSIONS
+        ));
+        assert!(!has_supported_extension(
+            Path::new("notes.txt"),
+            VIDEO_IMPORT_EXTENSIONS
+        ));
+    }
+}
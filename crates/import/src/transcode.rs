@@ -0,0 +1,349 @@
+use cap_enc_ffmpeg::{
+    AudioEncoder,
+    h264::{H264EncoderBuilder, H264Preset},
+    opus::OpusEncoder,
+    remux::get_media_duration,
+};
+use cap_media_info::{AudioInfo, FFRational, Pixel, VideoInfo, ensure_even};
+use ffmpeg::{
+    ChannelLayout,
+    codec::{self as avcodec},
+    format::{self as avformat},
+};
+use std::path::Path;
+use tracing::info;
+
+use crate::{ImportError, ImportProgress, ImportStage, check_project_exists};
+
+fn get_video_stream_info(
+    input: &avformat::context::Input,
+) -> Result<(usize, VideoInfo), ImportError> {
+    let stream = input
+        .streams()
+        .best(ffmpeg::media::Type::Video)
+        .ok_or(ImportError::NoVideoStream)?;
+
+    let stream_index = stream.index();
+    let decoder_ctx = avcodec::Context::from_parameters(stream.parameters())
+        .map_err(|e| ImportError::DecoderFailed(e.to_string()))?;
+    let decoder = decoder_ctx
+        .decoder()
+        .video()
+        .map_err(|e| ImportError::DecoderFailed(e.to_string()))?;
+
+    let rate = stream.avg_frame_rate();
+    let time_base = stream.time_base();
+
+    let pixel_format = match decoder.format() {
+        ffmpeg::format::Pixel::YUV420P => Pixel::YUV420P,
+        ffmpeg::format::Pixel::NV12 => Pixel::NV12,
+        ffmpeg::format::Pixel::BGRA => Pixel::BGRA,
+        ffmpeg::format::Pixel::RGBA => Pixel::RGBA,
+        ffmpeg::format::Pixel::RGB24 => Pixel::RGB24,
+        ffmpeg::format::Pixel::BGR24 => Pixel::BGR24,
+        _ => Pixel::YUV420P,
+    };
+
+    Ok((
+        stream_index,
+        VideoInfo {
+            pixel_format,
+            width: decoder.width(),
+            height: decoder.height(),
+            time_base: FFRational(time_base.numerator(), time_base.denominator()),
+            frame_rate: FFRational(rate.numerator(), rate.denominator()),
+        },
+    ))
+}
+
+fn get_audio_stream_info(input: &avformat::context::Input) -> Option<(usize, AudioInfo)> {
+    let stream = input.streams().best(ffmpeg::media::Type::Audio)?;
+    let stream_index = stream.index();
+
+    let decoder_ctx = avcodec::Context::from_parameters(stream.parameters()).ok()?;
+    let decoder = decoder_ctx.decoder().audio().ok()?;
+
+    let audio_info = AudioInfo::from_decoder(&decoder).ok()?;
+
+    Some((stream_index, audio_info))
+}
+
+/// Re-encodes `source_path` as an H.264 `output_path` (even dimensions, 1-120 fps) and, when the
+/// source has audio and `audio_output_path` is given, an Opus side file. Returns the output fps and
+/// the source audio sample rate.
+///
+/// The import is treated as cancelled if `project_path` stops looking like a project while
+/// converting, which is how the desktop app aborts an import (it deletes the project directory).
+pub fn transcode_video(
+    source_path: &Path,
+    output_path: &Path,
+    audio_output_path: Option<&Path>,
+    project_path: &Path,
+    on_progress: &(dyn Fn(ImportProgress) + Send + Sync),
+) -> Result<(u32, Option<u32>), ImportError> {
+    use std::time::Duration as StdDuration;
+
+    let mut input =
+        avformat::input(source_path).map_err(|e| ImportError::OpenFailed(e.to_string()))?;
+
+    let (video_stream_index, video_info) = get_video_stream_info(&input)?;
+    let audio_stream_info = get_audio_stream_info(&input);
+
+    let output_width = ensure_even(video_info.width);
+    let output_height = ensure_even(video_info.height);
+    let fps = if video_info.frame_rate.1 > 0 {
+        ((video_info.frame_rate.0 as f64 / video_info.frame_rate.1 as f64).round() as u32)
+            .clamp(1, 120)
+    } else {
+        30
+    };
+
+    let duration = get_media_duration(source_path);
+    let total_frames = duration
+        .map(|d| (d.as_secs_f64() * fps as f64) as u64)
+        .unwrap_or(1000);
+
+    let video_decoder_ctx =
+        avcodec::Context::from_parameters(input.stream(video_stream_index).unwrap().parameters())
+            .map_err(|e| ImportError::DecoderFailed(e.to_string()))?;
+    let mut video_decoder = video_decoder_ctx
+        .decoder()
+        .video()
+        .map_err(|e| ImportError::DecoderFailed(e.to_string()))?;
+
+    let video_time_base = input.stream(video_stream_index).unwrap().time_base();
+
+    let mut audio_decoder = audio_stream_info.as_ref().and_then(|(idx, _)| {
+        let stream = input.stream(*idx)?;
+        let decoder_ctx = avcodec::Context::from_parameters(stream.parameters()).ok()?;
+        let mut decoder = decoder_ctx.decoder().audio().ok()?;
+        if decoder.channel_layout().is_empty() {
+            decoder.set_channel_layout(ChannelLayout::default(decoder.channels() as i32));
+        }
+        decoder.set_packet_time_base(stream.time_base());
+        Some((*idx, decoder, stream.time_base()))
+    });
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(ImportError::DirectoryFailed)?;
+    }
+
+    let mut output =
+        avformat::output(output_path).map_err(|e| ImportError::EncoderFailed(e.to_string()))?;
+
+    let encoder_video_info = VideoInfo {
+        pixel_format: Pixel::YUV420P,
+        width: output_width,
+        height: output_height,
+        time_base: video_info.time_base,
+        frame_rate: FFRational(fps as i32, 1),
+    };
+
+    let mut video_encoder = H264EncoderBuilder::new(encoder_video_info)
+        .with_preset(H264Preset::Medium)
+        .with_output_size(output_width, output_height)
+        .map_err(|e| ImportError::EncoderFailed(e.to_string()))?
+        .build(&mut output)
+        .map_err(|e| ImportError::EncoderFailed(e.to_string()))?;
+
+    let mut audio_output: Option<avformat::context::Output> = None;
+    let mut audio_encoder: Option<Box<dyn AudioEncoder + Send>> = None;
+    let sample_rate = if let Some((_, audio_info)) = &audio_stream_info {
+        if let Some(audio_path) = audio_output_path {
+            let mut audio_out = avformat::output(audio_path).map_err(|e| {
+                ImportError::EncoderFailed(format!("Failed to create audio output: {e}"))
+            })?;
+
+            audio_encoder = Some(Box::new(
+                OpusEncoder::init(*audio_info, &mut audio_out)
+                    .map_err(|e| ImportError::EncoderFailed(e.to_string()))?,
+            ));
+            audio_out.write_header().map_err(|e| {
+                ImportError::EncoderFailed(format!("Failed to write audio header: {e}"))
+            })?;
+            audio_output = Some(audio_out);
+        }
+        Some(audio_info.sample_rate)
+    } else {
+        None
+    };
+
+    output
+        .write_header()
+        .map_err(|e| ImportError::EncoderFailed(format!("Failed to write header: {e}")))?;
+
+    let mut video_frame = ffmpeg::frame::Video::empty();
+    let mut audio_frame = ffmpeg::frame::Audio::empty();
+    let mut frames_processed = 0u64;
+    let mut last_progress = 0.0;
+
+    let mut scaler: Option<ffmpeg::software::scaling::Context> = None;
+
+    for (stream, packet) in input.packets() {
+        let stream_index = stream.index();
+
+        if stream_index == video_stream_index {
+            video_decoder.send_packet(&packet)?;
+
+            while video_decoder.receive_frame(&mut video_frame).is_ok() {
+                let timestamp = video_frame.pts().unwrap_or(0);
+                let time_secs = timestamp as f64 * video_time_base.numerator() as f64
+                    / video_time_base.denominator().max(1) as f64;
+                let duration = StdDuration::from_secs_f64(time_secs.max(0.0));
+
+                let frame_to_encode = if video_frame.format() != ffmpeg::format::Pixel::YUV420P
+                    || video_frame.width() != output_width
+                    || video_frame.height() != output_height
+                {
+                    if scaler.is_none() {
+                        scaler = Some(
+                            ffmpeg::software::scaling::Context::get(
+                                video_frame.format(),
+                                video_frame.width(),
+                                video_frame.height(),
+                                ffmpeg::format::Pixel::YUV420P,
+                                output_width,
+                                output_height,
+                                ffmpeg::software::scaling::Flags::BILINEAR,
+                            )
+                            .map_err(|e| {
+                                ImportError::TranscodeFailed(format!(
+                                    "Failed to create scaler: {e}"
+                                ))
+                            })?,
+                        );
+                    }
+                    let scaler = scaler.as_mut().unwrap();
+
+                    let mut scaled_frame = ffmpeg::frame::Video::empty();
+                    scaled_frame.set_format(ffmpeg::format::Pixel::YUV420P);
+                    scaled_frame.set_width(output_width);
+                    scaled_frame.set_height(output_height);
+                    let ret =
+                        unsafe { ffmpeg::ffi::av_frame_get_buffer(scaled_frame.as_mut_ptr(), 0) };
+                    if ret < 0 {
+                        return Err(ImportError::TranscodeFailed(
+                            "Failed to allocate frame buffer".to_string(),
+                        ));
+                    }
+
+                    scaler.run(&video_frame, &mut scaled_frame)?;
+                    scaled_frame.set_pts(video_frame.pts());
+                    scaled_frame
+                } else {
+                    video_frame.clone()
+                };
+
+                video_encoder
+                    .queue_frame(frame_to_encode, duration, &mut output)
+                    .map_err(|e| ImportError::TranscodeFailed(e.to_string()))?;
+
+                frames_processed += 1;
+
+                let progress = (frames_processed as f64 / total_frames as f64).min(0.99);
+                if progress - last_progress >= 0.01 {
+                    last_progress = progress;
+
+                    if !check_project_exists(project_path) {
+                        info!("Import cancelled: project directory was deleted");
+                        return Err(ImportError::TranscodeFailed("Import cancelled".to_string()));
+                    }
+
+                    on_progress(ImportProgress::new(
+                        ImportStage::Converting,
+                        progress,
+                        format!("Converting video... {}%", (progress * 100.0) as u32),
+                    ));
+                }
+            }
+        } else if let Some((audio_idx, decoder, _)) = audio_decoder.as_mut()
+            && stream_index == *audio_idx
+            && let (Some(encoder), Some(audio_out)) =
+                (audio_encoder.as_mut(), audio_output.as_mut())
+        {
+            decoder.send_packet(&packet)?;
+
+            while decoder.receive_frame(&mut audio_frame).is_ok() {
+                encoder.send_frame(audio_frame.clone(), audio_out);
+            }
+        }
+    }
+
+    video_decoder.send_eof()?;
+    while video_decoder.receive_frame(&mut video_frame).is_ok() {
+        let timestamp = video_frame.pts().unwrap_or(0);
+        let time_secs = timestamp as f64 * video_time_base.numerator() as f64
+            / video_time_base.denominator().max(1) as f64;
+        let duration = StdDuration::from_secs_f64(time_secs.max(0.0));
+
+        let frame_to_encode = if video_frame.format() != ffmpeg::format::Pixel::YUV420P
+            || video_frame.width() != output_width
+            || video_frame.height() != output_height
+        {
+            if let Some(scaler) = &mut scaler {
+                let mut scaled_frame = ffmpeg::frame::Video::empty();
+                scaled_frame.set_format(ffmpeg::format::Pixel::YUV420P);
+                scaled_frame.set_width(output_width);
+                scaled_frame.set_height(output_height);
+                let ret = unsafe { ffmpeg::ffi::av_frame_get_buffer(scaled_frame.as_mut_ptr(), 0) };
+                if ret < 0 {
+                    return Err(ImportError::TranscodeFailed(
+                        "Failed to allocate frame buffer".to_string(),
+                    ));
+                }
+                scaler.run(&video_frame, &mut scaled_frame)?;
+                scaled_frame.set_pts(video_frame.pts());
+                scaled_frame
+            } else {
+                video_frame.clone()
+            }
+        } else {
+            video_frame.clone()
+        };
+
+        video_encoder
+            .queue_frame(frame_to_encode, duration, &mut output)
+            .map_err(|e| ImportError::TranscodeFailed(e.to_string()))?;
+    }
+
+    if let Some((_, decoder, _)) = audio_decoder.as_mut() {
+        decoder.send_eof()?;
+        while decoder.receive_frame(&mut audio_frame).is_ok() {
+            if let (Some(encoder), Some(audio_out)) =
+                (audio_encoder.as_mut(), audio_output.as_mut())
+            {
+                encoder.send_frame(audio_frame.clone(), audio_out);
+            }
+        }
+    }
+
+    video_encoder
+        .flush(&mut output)
+        .map_err(|e| ImportError::TranscodeFailed(format!("Failed to flush video: {e}")))?;
+
+    if let (Some(encoder), Some(audio_out)) = (&mut audio_encoder, &mut audio_output) {
+        encoder
+            .flush(audio_out)
+            .map_err(|e| ImportError::TranscodeFailed(format!("Failed to flush audio: {e}")))?;
+        audio_out.write_trailer().map_err(|e| {
+            ImportError::TranscodeFailed(format!("Failed to write audio trailer: {e}"))
+        })?;
+    }
+
+    output
+        .write_trailer()
+        .map_err(|e| ImportError::TranscodeFailed(format!("Failed to write trailer: {e}")))?;
+
+    drop(output);
+
+    if let Ok(file) = std::fs::File::open(output_path) {
+        let _ = file.sync_all();
+    }
+    if let Some(audio_path) = audio_output_path
+        && let Ok(file) = std::fs::File::open(audio_path)
+    {
+        let _ = file.sync_all();
+    }
+
+    Ok((fps, sample_rate))
+}
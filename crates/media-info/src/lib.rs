@@ -296,7 +296,7 @@ pub enum RawVideoFormat {
     P010,
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct VideoInfo {
     pub pixel_format: Pixel,
     pub width: u32,
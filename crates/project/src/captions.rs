@@ -0,0 +1,1326 @@
+use std::{collections::HashMap, fmt, path::Path};
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{
+    CaptionSegment, CaptionSettings, CaptionTrackSegment, CaptionWord, CaptionsData,
+    TimelineConfiguration, XY,
+};
+
+// Kept in sync with MAX_CAPTION_WORD_DURATION in the desktop transcription and
+// rendering layers: a trailing word stretched across silence is capped so the
+// projected track follows speech.
+const MAX_CAPTION_WORD_DURATION: f32 = 2.5;
+
+const CAPTION_EDL_SEPARATOR: &str = "::edl";
+
+const CAPTION_ATTACHING_PUNCTUATION: &[char] = &[
+    ',', '.', '!', '?', ';', ':', '%', ')', ']', '}', '\'', '’', '、', '。', '！', '？', '；',
+    '：', '，',
+];
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SubtitleFormat {
+    Srt,
+    #[serde(rename = "vtt")]
+    WebVtt,
+    Ass,
+}
+
+impl SubtitleFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "srt" => Some(Self::Srt),
+            "vtt" | "webvtt" => Some(Self::WebVtt),
+            "ass" | "ssa" => Some(Self::Ass),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::WebVtt => "vtt",
+            Self::Ass => "ass",
+        }
+    }
+
+    pub fn parse(self, input: &str) -> Result<Vec<SubtitleCue>, SubtitleParseError> {
+        let input = input.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+        let cues = match self {
+            Self::Srt => parse_srt(&input)?,
+            Self::WebVtt => parse_webvtt(&input)?,
+            Self::Ass => parse_ass(&input)?,
+        };
+
+        if cues.is_empty() {
+            return Err(SubtitleParseError::NoCues);
+        }
+
+        Ok(cues)
+    }
+
+    pub fn write(self, cues: &[SubtitleCue], settings: &CaptionSettings) -> String {
+        match self {
+            Self::Srt => write_srt(cues),
+            Self::WebVtt => write_webvtt(cues),
+            Self::Ass => write_ass(cues, settings),
+        }
+    }
+}
+
+/// A single timed line of subtitle text, independent of the file format it was
+/// read from or will be written to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubtitleCue {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+impl From<&CaptionSegment> for SubtitleCue {
+    fn from(segment: &CaptionSegment) -> Self {
+        Self {
+            start: f64::from(segment.start),
+            end: f64::from(segment.end),
+            text: segment.text.trim().to_string(),
+        }
+    }
+}
+
+impl From<&CaptionTrackSegment> for SubtitleCue {
+    fn from(segment: &CaptionTrackSegment) -> Self {
+        Self {
+            start: segment.start,
+            end: segment.end,
+            text: segment.text.trim().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SubtitleParseError {
+    InvalidTimestamp { line: usize, value: String },
+    MissingWebVttHeader,
+    MissingAssEvents,
+    NoCues,
+}
+
+impl fmt::Display for SubtitleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidTimestamp { line, value } => {
+                write!(f, "invalid timestamp '{value}' on line {line}")
+            }
+            Self::MissingWebVttHeader => write!(f, "file does not start with a WEBVTT header"),
+            Self::MissingAssEvents => write!(f, "file has no [Events] section"),
+            Self::NoCues => write!(f, "file contains no subtitle cues"),
+        }
+    }
+}
+
+impl std::error::Error for SubtitleParseError {}
+
+/// Parses `HH:MM:SS,mmm`, `HH:MM:SS.mmm`, `MM:SS.mmm` and ASS-style
+/// `H:MM:SS.cc` timestamps into seconds.
+fn parse_timestamp(value: &str) -> Option<f64> {
+    let parts = value.trim().split(':').collect::<Vec<_>>();
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [hours, minutes, seconds] => (hours.parse::<u64>().ok()?, *minutes, *seconds),
+        [minutes, seconds] => (0, *minutes, *seconds),
+        _ => return None,
+    };
+    let minutes = minutes.parse::<u64>().ok()?;
+    let seconds = seconds.replace(',', ".").parse::<f64>().ok()?;
+    if minutes >= 60 || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+
+    Some((hours * 3600 + minutes * 60) as f64 + seconds)
+}
+
+fn parse_timing_line(line: &str, line_number: usize) -> Result<(f64, f64), SubtitleParseError> {
+    let invalid = |value: &str| SubtitleParseError::InvalidTimestamp {
+        line: line_number,
+        value: value.trim().to_string(),
+    };
+
+    let (start, rest) = line.split_once("-->").ok_or_else(|| invalid(line))?;
+    // WebVTT cue settings ("align:start position:10%") follow the end time.
+    let end = rest.split_whitespace().next().unwrap_or_default();
+    let start_time = parse_timestamp(start).ok_or_else(|| invalid(start))?;
+    let end_time = parse_timestamp(end).ok_or_else(|| invalid(end))?;
+
+    Ok((start_time, end_time.max(start_time)))
+}
+
+/// Drops inline markup (`<i>`, `<c.yellow>`, `<00:00:01.000>`, `{\an8}`) and
+/// joins the cue's lines, since captions are re-wrapped by the renderer.
+fn clean_cue_text(lines: &[&str]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        let mut cleaned = String::with_capacity(line.len());
+        let mut closing = None;
+        for character in line.chars() {
+            match (closing, character) {
+                (None, '<') => closing = Some('>'),
+                (None, '{') => closing = Some('}'),
+                (None, _) => cleaned.push(character),
+                (Some(end), _) if character == end => closing = None,
+                (Some(_), _) => {}
+            }
+        }
+
+        let cleaned = cleaned
+            .replace("&amp;", "&")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&nbsp;", " ");
+        let cleaned = cleaned.trim();
+        if cleaned.is_empty() {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(cleaned);
+    }
+    text
+}
+
+/// Splits the input into blank-line separated blocks, keeping the 1-based line
+/// number each block starts on for error reporting.
+fn blocks(input: &str) -> Vec<(usize, Vec<&str>)> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(usize, Vec<&str>)> = None;
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            blocks.extend(current.take());
+        } else {
+            current
+                .get_or_insert_with(|| (index + 1, Vec::new()))
+                .1
+                .push(line);
+        }
+    }
+    blocks.extend(current);
+
+    blocks
+}
+
+fn parse_timed_blocks<'a>(
+    blocks: impl IntoIterator<Item = (usize, Vec<&'a str>)>,
+) -> Result<Vec<SubtitleCue>, SubtitleParseError> {
+    let mut cues = Vec::new();
+
+    for (first_line, lines) in blocks {
+        // Cue numbers (SRT) and identifiers (WebVTT) precede the timing line.
+        let Some(timing_index) = lines.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+        let (start, end) = parse_timing_line(lines[timing_index], first_line + timing_index)?;
+        let text = clean_cue_text(&lines[timing_index + 1..]);
+        if text.is_empty() {
+            continue;
+        }
+
+        cues.push(SubtitleCue { start, end, text });
+    }
+
+    Ok(cues)
+}
+
+fn parse_srt(input: &str) -> Result<Vec<SubtitleCue>, SubtitleParseError> {
+    parse_timed_blocks(blocks(input))
+}
+
+fn parse_webvtt(input: &str) -> Result<Vec<SubtitleCue>, SubtitleParseError> {
+    if !input.trim_start().starts_with("WEBVTT") {
+        return Err(SubtitleParseError::MissingWebVttHeader);
+    }
+
+    parse_timed_blocks(blocks(input).into_iter().skip(1).filter(|(_, lines)| {
+        !lines.first().is_some_and(|line| {
+            line.starts_with("NOTE") || line.starts_with("STYLE") || line.starts_with("REGION")
+        })
+    }))
+}
+
+fn parse_ass(input: &str) -> Result<Vec<SubtitleCue>, SubtitleParseError> {
+    let mut in_events = false;
+    let mut saw_events = false;
+    let mut fields = vec![
+        "layer", "start", "end", "style", "name", "marginl", "marginr", "marginv", "effect", "text",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect::<Vec<_>>();
+    let mut cues = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[events]");
+            saw_events |= in_events;
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+
+        if let Some(format) = line.strip_prefix("Format:") {
+            fields = format
+                .split(',')
+                .map(|field| field.trim().to_ascii_lowercase())
+                .collect();
+            continue;
+        }
+
+        let Some(dialogue) = line.strip_prefix("Dialogue:") else {
+            continue;
+        };
+        // Text is always the last field and may itself contain commas.
+        let values = dialogue.splitn(fields.len(), ',').collect::<Vec<_>>();
+        let field = |name: &str| {
+            fields
+                .iter()
+                .position(|field| field == name)
+                .and_then(|position| values.get(position))
+                .copied()
+                .unwrap_or_default()
+        };
+
+        let invalid = |value: &str| SubtitleParseError::InvalidTimestamp {
+            line: index + 1,
+            value: value.trim().to_string(),
+        };
+        let start = parse_timestamp(field("start")).ok_or_else(|| invalid(field("start")))?;
+        let end = parse_timestamp(field("end")).ok_or_else(|| invalid(field("end")))?;
+
+        let text = field("text")
+            .replace("\\N", "\n")
+            .replace("\\n", "\n")
+            .replace("\\h", " ");
+        let text = clean_cue_text(&text.lines().collect::<Vec<_>>());
+        if text.is_empty() {
+            continue;
+        }
+
+        cues.push(SubtitleCue {
+            start,
+            end: end.max(start),
+            text,
+        });
+    }
+
+    if !saw_events {
+        return Err(SubtitleParseError::MissingAssEvents);
+    }
+
+    cues.sort_by(|a, b| a.start.total_cmp(&b.start));
+    Ok(cues)
+}
+
+fn split_timestamp(seconds: f64, units_per_second: u64) -> (u64, u64, u64, u64) {
+    let total = (seconds.max(0.0) * units_per_second as f64).round() as u64;
+    let fraction = total % units_per_second;
+    let total_seconds = total / units_per_second;
+    (
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60,
+        fraction,
+    )
+}
+
+pub fn format_srt_time(seconds: f64) -> String {
+    let (hours, minutes, seconds, millis) = split_timestamp(seconds, 1000);
+    format!("{hours:02}:{minutes:02}:{seconds:02},{millis:03}")
+}
+
+fn format_webvtt_time(seconds: f64) -> String {
+    let (hours, minutes, seconds, millis) = split_timestamp(seconds, 1000);
+    format!("{hours:02}:{minutes:02}:{seconds:02}.{millis:03}")
+}
+
+fn format_ass_time(seconds: f64) -> String {
+    let (hours, minutes, seconds, centis) = split_timestamp(seconds, 100);
+    format!("{hours}:{minutes:02}:{seconds:02}.{centis:02}")
+}
+
+fn write_srt(cues: &[SubtitleCue]) -> String {
+    let mut srt = String::new();
+    for (index, cue) in cues.iter().enumerate() {
+        srt.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            format_srt_time(cue.start),
+            format_srt_time(cue.end),
+            cue.text.trim()
+        ));
+    }
+    srt
+}
+
+pub(crate) fn write_webvtt(cues: &[SubtitleCue]) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+    for cue in cues {
+        let text = cue
+            .text
+            .trim()
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        vtt.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_webvtt_time(cue.start),
+            format_webvtt_time(cue.end),
+            text
+        ));
+    }
+    vtt
+}
+
+/// Converts a `#RRGGBB` color and 0-100 opacity into ASS's `&HAABBGGRR`, where
+/// alpha counts up from opaque (00) to transparent (FF).
+fn ass_color(hex: &str, opacity: u32) -> String {
+    let hex = hex.trim().trim_start_matches('#');
+    let channel = |index: usize| {
+        hex.get(index..index + 2)
+            .and_then(|value| u8::from_str_radix(value, 16).ok())
+            .unwrap_or(0xFF)
+    };
+    let alpha = 255 - (opacity.min(100) * 255 / 100);
+    format!(
+        "&H{alpha:02X}{:02X}{:02X}{:02X}",
+        channel(4),
+        channel(2),
+        channel(0)
+    )
+}
+
+/// ASS numpad alignment for a caption `position` such as `bottom-center`.
+fn ass_alignment(position: &str) -> u8 {
+    let row = if position.contains("top") { 7 } else { 1 };
+    let column = if position.contains("left") {
+        0
+    } else if position.contains("right") {
+        2
+    } else {
+        1
+    };
+    row + column
+}
+
+fn write_ass(cues: &[SubtitleCue], settings: &CaptionSettings) -> String {
+    let font = match settings.font.as_str() {
+        "System Sans-Serif" => "Arial",
+        "System Serif" => "Times New Roman",
+        "System Monospace" => "Courier New",
+        font => font,
+    };
+    let flag = |value: bool| if value { -1 } else { 0 };
+    let (border_style, outline) = if settings.outline {
+        (1, 2)
+    } else if settings.background_opacity > 0 {
+        (3, 0)
+    } else {
+        (1, 0)
+    };
+
+    let mut ass = String::from(
+        "[Script Info]\nScriptType: v4.00+\nPlayResX: 1920\nPlayResY: 1080\nScaledBorderAndShadow: yes\n\n",
+    );
+    ass.push_str("[V4+ Styles]\n");
+    ass.push_str(
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, \
+Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, \
+Alignment, MarginL, MarginR, MarginV, Encoding\n",
+    );
+    ass.push_str(&format!(
+        "Style: Default,{font},{},{},{},{},{},{},{},0,0,100,100,0,0,{border_style},{outline},0,{},60,60,60,1\n\n",
+        settings.size,
+        ass_color(&settings.color, 100),
+        ass_color(&settings.highlight_color, 100),
+        ass_color(&settings.outline_color, 100),
+        ass_color(&settings.background_color, settings.background_opacity),
+        flag(settings.font_weight >= 600),
+        flag(settings.italic),
+        ass_alignment(&settings.position),
+    ));
+    ass.push_str("[Events]\n");
+    ass.push_str(
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+    );
+
+    for cue in cues {
+        let text = cue.text.trim();
+        let text = if settings.uppercase {
+            text.to_uppercase()
+        } else {
+            text.to_string()
+        };
+        ass.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            format_ass_time(cue.start),
+            format_ass_time(cue.end),
+            text.replace('\n', "\\N")
+                .replace('{', "(")
+                .replace('}', ")")
+        ));
+    }
+
+    ass
+}
+
+/// Turns parsed cues into caption segments with stable ids. Cues carry no
+/// word timing, so the renderer shows each one as a single block.
+pub fn subtitle_cues_to_segments(cues: &[SubtitleCue]) -> Vec<CaptionSegment> {
+    cues.iter()
+        .enumerate()
+        .map(|(index, cue)| CaptionSegment {
+            id: format!("segment-{index}"),
+            start: cue.start as f32,
+            end: cue.end as f32,
+            text: cue.text.clone(),
+            words: Vec::new(),
+        })
+        .collect()
+}
+
+pub fn captions_to_srt(segments: &[CaptionSegment]) -> String {
+    write_srt(&segments.iter().map(SubtitleCue::from).collect::<Vec<_>>())
+}
+
+/// Parses a `captions.json` document, tolerating both camelCase and snake_case
+/// settings keys as written by older desktop builds.
+pub fn parse_captions_json(json: &str) -> Result<CaptionsData, String> {
+    match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(json_value) => {
+            if let Some(segments_array) = json_value.get("segments").and_then(|v| v.as_array()) {
+                let mut segments = Vec::new();
+
+                for segment in segments_array {
+                    if let (Some(id), Some(start), Some(end), Some(text)) = (
+                        segment.get("id").and_then(|v| v.as_str()),
+                        segment.get("start").and_then(|v| v.as_f64()),
+                        segment.get("end").and_then(|v| v.as_f64()),
+                        segment.get("text").and_then(|v| v.as_str()),
+                    ) {
+                        let mut words = Vec::new();
+                        if let Some(words_array) = segment.get("words").and_then(|v| v.as_array()) {
+                            for word in words_array {
+                                if let (Some(w_text), Some(w_start), Some(w_end)) = (
+                                    word.get("text").and_then(|v| v.as_str()),
+                                    word.get("start").and_then(|v| v.as_f64()),
+                                    word.get("end").and_then(|v| v.as_f64()),
+                                ) {
+                                    words.push(CaptionWord {
+                                        text: w_text.to_string(),
+                                        start: w_start as f32,
+                                        end: w_end as f32,
+                                    });
+                                }
+                            }
+                        }
+                        segments.push(CaptionSegment {
+                            id: id.to_string(),
+                            start: start as f32,
+                            end: end as f32,
+                            text: text.to_string(),
+                            words,
+                        });
+                    }
+                }
+
+                let settings = if let Some(settings_obj) = json_value.get("settings") {
+                    let enabled = settings_obj
+                        .get("enabled")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or_default();
+                    let font = settings_obj
+                        .get("font")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("System Sans-Serif")
+                        .to_string();
+                    let size = settings_obj
+                        .get("size")
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(24) as u32;
+                    let color = settings_obj
+                        .get("color")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("#A0A0A0")
+                        .to_string();
+
+                    let background_color = settings_obj
+                        .get("backgroundColor")
+                        .or_else(|| settings_obj.get("background_color"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("#000000")
+                        .to_string();
+
+                    let background_opacity = settings_obj
+                        .get("backgroundOpacity")
+                        .or_else(|| settings_obj.get("background_opacity"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(80) as u32;
+
+                    let position = settings_obj
+                        .get("position")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("bottom")
+                        .to_string();
+                    let italic = settings_obj
+                        .get("italic")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    let font_weight = settings_obj
+                        .get("fontWeight")
+                        .or_else(|| settings_obj.get("font_weight"))
+                        .and_then(|v| v.as_u64())
+                        .unwrap_or(700) as u32;
+                    let outline = settings_obj
+                        .get("outline")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let outline_color = settings_obj
+                        .get("outlineColor")
+                        .or_else(|| settings_obj.get("outline_color"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("#000000")
+                        .to_string();
+
+                    let export_with_subtitles = settings_obj
+                        .get("exportWithSubtitles")
+                        .or_else(|| settings_obj.get("export_with_subtitles"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let highlight_color = settings_obj
+                        .get("highlightColor")
+                        .or_else(|| settings_obj.get("highlight_color"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("#FFFFFF")
+                        .to_string();
+
+                    let fade_duration = settings_obj
+                        .get("fadeDuration")
+                        .or_else(|| settings_obj.get("fade_duration"))
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.15) as f32;
+
+                    let linger_duration = settings_obj
+                        .get("lingerDuration")
+                        .or_else(|| settings_obj.get("linger_duration"))
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.4) as f32;
+
+                    let word_transition_duration = settings_obj
+                        .get("wordTransitionDuration")
+                        .or_else(|| settings_obj.get("word_transition_duration"))
+                        .and_then(|v| v.as_f64())
+                        .unwrap_or(0.25) as f32;
+
+                    let active_word_highlight = settings_obj
+                        .get("activeWordHighlight")
+                        .or_else(|| settings_obj.get("active_word_highlight"))
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let preset = settings_obj
+                        .get("preset")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("classic")
+                        .to_string();
+
+                    let animation = settings_obj
+                        .get("animation")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("bounce")
+                        .to_string();
+
+                    let highlight_style = settings_obj
+                        .get("highlightStyle")
+                        .or_else(|| settings_obj.get("highlight_style"))
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("color")
+                        .to_string();
+
+                    let uppercase = settings_obj
+                        .get("uppercase")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+
+                    let manual_position = settings_obj
+                        .get("manualPosition")
+                        .or_else(|| settings_obj.get("manual_position"))
+                        .and_then(|value| {
+                            Some(XY {
+                                x: value.get("x")?.as_f64()? as f32,
+                                y: value.get("y")?.as_f64()? as f32,
+                            })
+                        });
+
+                    CaptionSettings {
+                        enabled,
+                        font,
+                        size,
+                        color,
+                        background_color,
+                        background_opacity,
+                        position,
+                        italic,
+                        font_weight,
+                        outline,
+                        outline_color,
+                        export_with_subtitles,
+                        highlight_color,
+                        fade_duration,
+                        linger_duration,
+                        word_transition_duration,
+                        active_word_highlight,
+                        manual_position,
+                        preset,
+                        animation,
+                        highlight_style,
+                        uppercase,
+                    }
+                } else {
+                    CaptionSettings::default()
+                };
+
+                Ok(CaptionsData {
+                    segments,
+                    settings,
+                    source_timed: json_value
+                        .get("sourceTimed")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false),
+                })
+            } else {
+                Err("Missing or invalid segments array in captions file".to_string())
+            }
+        }
+        Err(e) => Err(format!("Failed to parse captions JSON: {e}")),
+    }
+}
+
+/// Where one timeline clip's source range lands in output time.
+struct SourceClipMapping {
+    source_start: f64,
+    source_end: f64,
+    output_start: f64,
+    /// End of the output range this clip owns outright. An incoming
+    /// transition belongs to the incoming clip, so ranges never overlap.
+    owned_output_end: f64,
+    timescale: f64,
+}
+
+impl SourceClipMapping {
+    fn to_output(&self, start: f64, end: f64) -> Option<(f64, f64)> {
+        let start = start.max(self.source_start);
+        let end = end.min(self.source_end);
+        (start < end).then(|| {
+            (
+                self.output_start + (start - self.source_start) / self.timescale,
+                self.output_start + (end - self.source_start) / self.timescale,
+            )
+        })
+    }
+
+    fn to_source(&self, output_time: f64) -> f64 {
+        self.source_start + (output_time - self.output_start) * self.timescale
+    }
+}
+
+/// Source time is the concatenation of every recording segment, in recording
+/// order. `recording_durations` are the display durations of those segments.
+fn source_clip_mappings(
+    timeline: &TimelineConfiguration,
+    recording_durations: &[f64],
+) -> Vec<SourceClipMapping> {
+    let recording_offsets = recording_durations
+        .iter()
+        .scan(0.0, |offset, duration| {
+            let start = *offset;
+            *offset += duration;
+            Some(start)
+        })
+        .collect::<Vec<_>>();
+
+    let mut output_start = 0.0;
+    let mut mappings = Vec::with_capacity(timeline.segments.len());
+
+    for (index, segment) in timeline.segments.iter().enumerate() {
+        output_start -= timeline
+            .effective_transition(index)
+            .map_or(0.0, |transition| transition.duration);
+        let recording_offset = recording_offsets
+            .get(segment.recording_clip as usize)
+            .copied()
+            .unwrap_or(0.0);
+        let next_transition = timeline
+            .effective_transition(index + 1)
+            .map_or(0.0, |transition| transition.duration);
+
+        mappings.push(SourceClipMapping {
+            source_start: recording_offset + segment.start,
+            source_end: recording_offset + segment.end,
+            output_start,
+            owned_output_end: output_start + segment.duration() - next_transition,
+            timescale: segment.timescale,
+        });
+
+        output_start += segment.duration();
+    }
+
+    mappings
+}
+
+/// Recovers the originating source caption id from a derived track segment id.
+/// A source caption that spans timeline cuts is split into several track
+/// segments which all share its id.
+pub fn source_caption_id(track_id: &str) -> &str {
+    track_id
+        .split_once(CAPTION_EDL_SEPARATOR)
+        .map_or(track_id, |(id, _)| id)
+}
+
+fn clamp_caption_words(segment: &CaptionSegment) -> CaptionSegment {
+    if segment.words.is_empty() {
+        return segment.clone();
+    }
+
+    let words = segment
+        .words
+        .iter()
+        .map(|word| CaptionWord {
+            end: word.end.min(word.start + MAX_CAPTION_WORD_DURATION),
+            ..word.clone()
+        })
+        .collect::<Vec<_>>();
+    let last_word_end = words.last().map_or(segment.end, |word| word.end);
+
+    CaptionSegment {
+        end: segment.end.min(last_word_end),
+        words,
+        ..segment.clone()
+    }
+}
+
+fn caption_text_from_words(words: &[CaptionWord]) -> String {
+    let mut text = String::new();
+
+    for word in words {
+        let word = word.text.trim();
+        if word.is_empty() {
+            continue;
+        }
+        if !text.is_empty() && !word.starts_with(CAPTION_ATTACHING_PUNCTUATION) {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+
+    text
+}
+
+fn track_segment(
+    id: String,
+    start: f64,
+    end: f64,
+    text: String,
+    words: Vec<CaptionWord>,
+    overrides: Option<&CaptionTrackSegment>,
+) -> CaptionTrackSegment {
+    CaptionTrackSegment {
+        id,
+        start,
+        end,
+        text,
+        words,
+        fade_duration_override: overrides.and_then(|o| o.fade_duration_override),
+        linger_duration_override: overrides.and_then(|o| o.linger_duration_override),
+        position_override: overrides.and_then(|o| o.position_override.clone()),
+        color_override: overrides.and_then(|o| o.color_override.clone()),
+        background_color_override: overrides.and_then(|o| o.background_color_override.clone()),
+        font_size_override: overrides.and_then(|o| o.font_size_override),
+    }
+}
+
+impl CaptionsData {
+    /// Derives the output-time render track (`timeline.caption_segments`) for
+    /// `timeline`. Source-timed captions are projected through the edit list, so
+    /// cut material drops out and captions spanning a cut are split; legacy
+    /// output-timed captions are copied as-is. Style overrides on the existing
+    /// track are carried across by source caption id.
+    pub fn track_segments(
+        &self,
+        timeline: &TimelineConfiguration,
+        recording_durations: &[f64],
+    ) -> Vec<CaptionTrackSegment> {
+        let mut overrides = HashMap::new();
+        for segment in &timeline.caption_segments {
+            overrides
+                .entry(source_caption_id(&segment.id))
+                .or_insert(segment);
+        }
+        let overrides_for = |id: &str| overrides.get(id).copied();
+
+        let captions = self.segments.iter().map(clamp_caption_words);
+
+        if !self.source_timed || timeline.segments.is_empty() || recording_durations.is_empty() {
+            return captions
+                .map(|caption| {
+                    let overrides = overrides_for(&caption.id);
+                    track_segment(
+                        caption.id,
+                        f64::from(caption.start),
+                        f64::from(caption.end),
+                        caption.text,
+                        caption.words,
+                        overrides,
+                    )
+                })
+                .collect();
+        }
+
+        let mappings = source_clip_mappings(timeline, recording_durations);
+        let mut track = Vec::new();
+
+        for caption in captions {
+            let pieces = mappings
+                .iter()
+                .filter_map(|mapping| {
+                    if caption.words.is_empty() {
+                        let (start, end) =
+                            mapping.to_output(f64::from(caption.start), f64::from(caption.end))?;
+                        return Some((start, end, caption.text.clone(), Vec::new()));
+                    }
+
+                    let words = caption
+                        .words
+                        .iter()
+                        .filter_map(|word| {
+                            let (start, end) =
+                                mapping.to_output(f64::from(word.start), f64::from(word.end))?;
+                            Some(CaptionWord {
+                                text: word.text.clone(),
+                                start: start as f32,
+                                end: end as f32,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    let first = words.first()?;
+                    let last = words.last()?;
+
+                    Some((
+                        f64::from(first.start),
+                        f64::from(last.end),
+                        caption_text_from_words(&words),
+                        words,
+                    ))
+                })
+                .collect::<Vec<_>>();
+
+            let piece_count = pieces.len();
+            for (index, (start, end, text, words)) in pieces.into_iter().enumerate() {
+                let id = if piece_count == 1 {
+                    caption.id.clone()
+                } else {
+                    format!("{}{CAPTION_EDL_SEPARATOR}{index}", caption.id)
+                };
+                track.push(track_segment(
+                    id,
+                    start,
+                    end,
+                    text,
+                    words,
+                    overrides_for(&caption.id),
+                ));
+            }
+        }
+
+        track.sort_by(|a, b| a.start.total_cmp(&b.start));
+        track
+    }
+
+    /// Replaces the captions with cues timed against the edited output (for
+    /// example subtitles made from an exported video), storing them in source
+    /// time so they stay aligned as the timeline is edited further. A cue that
+    /// spans a cut becomes one source caption per clip it covers.
+    pub fn set_output_timed_cues(
+        &mut self,
+        cues: &[SubtitleCue],
+        timeline: &TimelineConfiguration,
+        recording_durations: &[f64],
+    ) {
+        let mappings = source_clip_mappings(timeline, recording_durations);
+        let mut segments = Vec::new();
+
+        for (index, cue) in cues.iter().enumerate() {
+            let pieces = mappings
+                .iter()
+                .filter_map(|mapping| {
+                    let start = cue.start.max(mapping.output_start);
+                    let end = cue.end.min(mapping.owned_output_end);
+                    (start < end).then(|| (mapping.to_source(start), mapping.to_source(end)))
+                })
+                .collect::<Vec<_>>();
+
+            let piece_count = pieces.len();
+            for (piece, (start, end)) in pieces.into_iter().enumerate() {
+                segments.push(CaptionSegment {
+                    id: if piece_count == 1 {
+                        format!("segment-{index}")
+                    } else {
+                        format!("segment-{index}-{piece}")
+                    },
+                    start: start as f32,
+                    end: end as f32,
+                    text: cue.text.clone(),
+                    words: Vec::new(),
+                });
+            }
+        }
+
+        self.segments = segments;
+        self.source_timed = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClipTransition, ClipTransitionType, TimelineSegment};
+
+    fn timeline(segments: &[(u32, f64, f64)]) -> TimelineConfiguration {
+        TimelineConfiguration {
+            segments: segments
+                .iter()
+                .map(|&(recording_clip, start, end)| TimelineSegment {
+                    recording_clip,
+                    timescale: 1.0,
+                    start,
+                    end,
+                    name: None,
+                    speed_audio_mode: None,
+                })
+                .collect(),
+            transitions: Vec::new(),
+            zoom_segments: Vec::new(),
+            scene_segments: Vec::new(),
+            mask_segments: Vec::new(),
+            text_segments: Vec::new(),
+            caption_segments: Vec::new(),
+            keyboard_segments: Vec::new(),
+            audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
+        }
+    }
+
+    fn caption(id: &str, start: f32, end: f32, text: &str) -> CaptionSegment {
+        CaptionSegment {
+            id: id.to_string(),
+            start,
+            end,
+            text: text.to_string(),
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_srt_with_tags_and_comma_millis() {
+        let cues = SubtitleFormat::Srt
+            .parse("\u{feff}1\r\n00:00:01,500 --> 00:00:03,250\r\n<i>Hello</i>\r\nworld\r\n\r\n2\r\n00:01:00,000 --> 00:01:02,000\r\n{\\an8}Second\r\n")
+            .unwrap();
+
+        assert_eq!(
+            cues,
+            vec![
+                SubtitleCue {
+                    start: 1.5,
+                    end: 3.25,
+                    text: "Hello world".to_string(),
+                },
+                SubtitleCue {
+                    start: 60.0,
+                    end: 62.0,
+                    text: "Second".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_webvtt_skipping_notes_and_settings() {
+        let cues = SubtitleFormat::WebVtt
+            .parse("WEBVTT - vendor\n\nNOTE reviewed\n\nintro\n00:01.000 --> 00:02.500 align:start\n<c.yellow>Hi</c> &amp; bye\n")
+            .unwrap();
+
+        assert_eq!(
+            cues,
+            vec![SubtitleCue {
+                start: 1.0,
+                end: 2.5,
+                text: "Hi & bye".to_string(),
+            }]
+        );
+        assert_eq!(
+            SubtitleFormat::WebVtt.parse("00:01.000 --> 00:02.000\nHi"),
+            Err(SubtitleParseError::MissingWebVttHeader)
+        );
+    }
+
+    #[test]
+    fn parses_ass_dialogue_with_commas_and_custom_format() {
+        let cues = SubtitleFormat::Ass
+            .parse("[Script Info]\nTitle: x\n\n[Events]\nFormat: Start, End, Style, Text\nDialogue: 0:00:02.50,0:00:04.00,Default,{\\b1}Well,\\Nhello there\n")
+            .unwrap();
+
+        assert_eq!(
+            cues,
+            vec![SubtitleCue {
+                start: 2.5,
+                end: 4.0,
+                text: "Well, hello there".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_timestamps_with_line_numbers() {
+        assert_eq!(
+            SubtitleFormat::Srt.parse("1\n00:00:01,000 --> nope\nHi\n"),
+            Err(SubtitleParseError::InvalidTimestamp {
+                line: 2,
+                value: "nope".to_string(),
+            })
+        );
+        assert_eq!(
+            SubtitleFormat::Srt.parse("\n\n"),
+            Err(SubtitleParseError::NoCues)
+        );
+    }
+
+    #[test]
+    fn writers_round_trip_through_parsers() {
+        let cues = vec![
+            SubtitleCue {
+                start: 0.999_6,
+                end: 3661.25,
+                text: "One & <two>".to_string(),
+            },
+            SubtitleCue {
+                start: 3662.0,
+                end: 3663.0,
+                text: "Three".to_string(),
+            },
+        ];
+        let settings = CaptionSettings::default();
+
+        for format in [
+            SubtitleFormat::Srt,
+            SubtitleFormat::WebVtt,
+            SubtitleFormat::Ass,
+        ] {
+            let parsed = format.parse(&format.write(&cues, &settings)).unwrap();
+            assert_eq!(parsed.len(), 2, "{format:?}");
+            assert!((parsed[0].start - 1.0).abs() < 1e-9, "{format:?}");
+            assert!((parsed[0].end - 3661.25).abs() < 1e-9, "{format:?}");
+            assert_eq!(parsed[1].text, "Three", "{format:?}");
+        }
+
+        assert_eq!(format_srt_time(3661.25), "01:01:01,250");
+        assert!(
+            SubtitleFormat::Srt
+                .write(&cues, &settings)
+                .contains("One & <two>")
+        );
+        assert!(
+            SubtitleFormat::WebVtt
+                .write(&cues, &settings)
+                .contains("One &amp; &lt;two&gt;")
+        );
+    }
+
+    #[test]
+    fn ass_style_follows_caption_settings() {
+        let settings = CaptionSettings {
+            color: "#FF8000".to_string(),
+            background_opacity: 50,
+            position: "top-right".to_string(),
+            ..Default::default()
+        };
+        let ass = SubtitleFormat::Ass.write(&[], &settings);
+
+        assert!(ass.contains("Style: Default,Arial,24,&H000080FF,"));
+        assert!(ass.contains("&H80000000,-1,0,"));
+        assert!(ass.contains(",3,0,0,9,60,60,60,1"));
+    }
+
+    #[test]
+    fn detects_format_from_extension() {
+        assert_eq!(
+            SubtitleFormat::from_path(Path::new("fr.VTT")),
+            Some(SubtitleFormat::WebVtt)
+        );
+        assert_eq!(
+            SubtitleFormat::from_path(Path::new("fr.ssa")),
+            Some(SubtitleFormat::Ass)
+        );
+        assert_eq!(SubtitleFormat::from_path(Path::new("fr.txt")), None);
+    }
+
+    #[test]
+    fn source_timed_captions_follow_cuts_and_reorders() {
+        // Two recordings of 10s; the timeline plays 2..6 of the second, then 1..4 of the first.
+        let mut timeline = timeline(&[(1, 2.0, 6.0), (0, 1.0, 4.0)]);
+        timeline.caption_segments.push(CaptionTrackSegment {
+            color_override: Some("#FF0000".to_string()),
+            ..track_segment(
+                "b::edl0".to_string(),
+                0.0,
+                1.0,
+                String::new(),
+                Vec::new(),
+                None,
+            )
+        });
+        let captions = CaptionsData {
+            segments: vec![
+                caption("a", 0.5, 2.0, "first"),
+                caption("b", 11.0, 13.0, "second"),
+                caption("cut", 5.0, 6.0, "dropped"),
+            ],
+            source_timed: true,
+            ..Default::default()
+        };
+
+        let track = captions.track_segments(&timeline, &[10.0, 10.0]);
+
+        assert_eq!(track.len(), 2);
+        assert_eq!(track[0].id, "b");
+        assert_eq!((track[0].start, track[0].end), (0.0, 1.0));
+        assert_eq!(track[0].color_override.as_deref(), Some("#FF0000"));
+        assert_eq!(track[1].id, "a");
+        assert_eq!((track[1].start, track[1].end), (4.0, 5.0));
+    }
+
+    #[test]
+    fn captions_spanning_a_cut_are_split() {
+        let timeline = timeline(&[(0, 0.0, 2.0), (0, 4.0, 6.0)]);
+        let captions = CaptionsData {
+            segments: vec![CaptionSegment {
+                words: vec![
+                    CaptionWord {
+                        text: "hello".to_string(),
+                        start: 1.0,
+                        end: 1.5,
+                    },
+                    CaptionWord {
+                        text: "cut".to_string(),
+                        start: 2.5,
+                        end: 3.0,
+                    },
+                    CaptionWord {
+                        text: "world".to_string(),
+                        start: 4.5,
+                        end: 5.0,
+                    },
+                    CaptionWord {
+                        text: "!".to_string(),
+                        start: 5.0,
+                        end: 5.2,
+                    },
+                ],
+                ..caption("c", 1.0, 5.2, "hello cut world!")
+            }],
+            source_timed: true,
+            ..Default::default()
+        };
+
+        let track = captions.track_segments(&timeline, &[6.0]);
+
+        assert_eq!(
+            track
+                .iter()
+                .map(|segment| (segment.id.as_str(), segment.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("c::edl0", "hello"), ("c::edl1", "world!")]
+        );
+        assert_eq!(source_caption_id(&track[1].id), "c");
+        assert!((track[1].start - 2.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn output_timed_cues_round_trip_through_projection() {
+        let mut timeline = timeline(&[(0, 0.0, 4.0), (1, 10.0, 16.0)]);
+        timeline.transitions.push(ClipTransition {
+            segment_index: 1,
+            kind: ClipTransitionType::CrossFade,
+            duration: 1.0,
+            easing: Default::default(),
+            direction: Default::default(),
+        });
+        let cues = vec![
+            SubtitleCue {
+                start: 1.0,
+                end: 2.0,
+                text: "one".to_string(),
+            },
+            SubtitleCue {
+                start: 2.5,
+                end: 5.0,
+                text: "across".to_string(),
+            },
+        ];
+        let durations = [20.0, 20.0];
+
+        let mut captions = CaptionsData::default();
+        captions.set_output_timed_cues(&cues, &timeline, &durations);
+
+        assert!(captions.source_timed);
+        assert_eq!(
+            captions
+                .segments
+                .iter()
+                .map(|segment| (segment.id.as_str(), segment.start, segment.end))
+                .collect::<Vec<_>>(),
+            vec![
+                ("segment-0", 1.0, 2.0),
+                ("segment-1-0", 2.5, 3.0),
+                ("segment-1-1", 30.0, 32.0),
+            ]
+        );
+
+        let track = captions.track_segments(&timeline, &durations);
+        assert_eq!(
+            track
+                .iter()
+                .map(|segment| (segment.start, segment.end))
+                .collect::<Vec<_>>(),
+            vec![(1.0, 2.0), (2.5, 3.0), (3.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn legacy_output_timed_captions_are_copied() {
+        let timeline = timeline(&[(0, 5.0, 10.0)]);
+        let captions = CaptionsData {
+            segments: vec![caption("a", 0.0, 1.0, "as is")],
+            ..Default::default()
+        };
+
+        let track = captions.track_segments(&timeline, &[10.0]);
+
+        assert_eq!(track.len(), 1);
+        assert_eq!((track[0].start, track[0].end), (0.0, 1.0));
+    }
+}
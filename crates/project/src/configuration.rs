@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use specta::Type;
 
+use crate::{VolumeKeyframe, VolumeKeyframes};
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum AspectRatio {
@@ -490,9 +492,14 @@ pub enum StereoMode {
 pub struct AudioConfiguration {
     pub mute: bool,
     pub improve: bool,
+    /// Mutes the first microphone without touching the other tracks.
+    pub mic_mute: bool,
     pub mic_volume_db: f32,
     pub mic_stereo_mode: StereoMode,
     pub system_volume_db: f32,
+    /// Settings for each of `MultipleSegment::additional_mics`, in order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub additional_mics: Vec<MicTrackConfiguration>,
 }
 
 impl Default for AudioConfiguration {
@@ -500,13 +507,32 @@ impl Default for AudioConfiguration {
         Self {
             mute: false,
             improve: false,
+            mic_mute: false,
             mic_volume_db: 0.0,
             mic_stereo_mode: StereoMode::default(),
             system_volume_db: 0.0,
+            additional_mics: Vec::new(),
         }
     }
 }
 
+impl AudioConfiguration {
+    /// Settings for additional microphone `index`. A track without an entry
+    /// plays at its recorded level.
+    pub fn additional_mic(&self, index: usize) -> Option<&MicTrackConfiguration> {
+        self.additional_mics.get(index)
+    }
+}
+
+/// Mixing settings for one additional microphone track.
+#[derive(Type, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MicTrackConfiguration {
+    pub mute: bool,
+    pub volume_db: f32,
+    pub stereo_mode: StereoMode,
+}
+
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum CursorType {
@@ -1010,6 +1036,12 @@ pub struct AudioTrackSegment {
     /// without re-decoding the file.
     #[serde(default)]
     pub duration: Option<f64>,
+    /// Lowers this segment while the recording's voice is audible.
+    #[serde(default)]
+    pub ducking: Option<MusicDucking>,
+    /// Volume automation, timed from `start`.
+    #[serde(default)]
+    pub volume_keyframes: Vec<VolumeKeyframe>,
 }
 
 impl AudioTrackSegment {
@@ -1018,6 +1050,31 @@ impl AudioTrackSegment {
     }
 }
 
+/// How far and how fast music is turned down under speech.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MusicDucking {
+    /// Voice level in dBFS at or above which the music ducks.
+    pub threshold_db: f32,
+    /// How many dB the music is lowered by under speech.
+    pub depth_db: f32,
+    /// Seconds the music takes to duck, finishing as the speech starts.
+    pub attack: f64,
+    /// Seconds the music takes to come back up once the speech stops.
+    pub release: f64,
+}
+
+impl Default for MusicDucking {
+    fn default() -> Self {
+        Self {
+            threshold_db: -40.0,
+            depth_db: 12.0,
+            attack: 0.15,
+            release: 0.6,
+        }
+    }
+}
+
 pub const MIN_CLIP_TRANSITION_DURATION: f64 = 0.05;
 
 #[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
@@ -1026,6 +1083,72 @@ pub enum ClipTransitionType {
     #[default]
     CrossFade,
     FadeThroughBlack,
+    /// A soft edge sweeps across the frame, revealing the incoming clip.
+    Wipe,
+    /// The incoming clip slides in and pushes the outgoing one out.
+    Slide,
+    /// The incoming clip opens from the centre in a growing circle.
+    Iris,
+    /// The outgoing clip zooms in and gives way to the incoming clip zooming
+    /// back out to rest.
+    ZoomThrough,
+    /// Both clips blur towards the midpoint while crossfading.
+    BlurDissolve,
+}
+
+/// How a transition's progress is paced over its duration. Video and audio
+/// follow the same curve.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipTransitionEasing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl ClipTransitionEasing {
+    /// Maps linear progress in `0..=1` onto this curve.
+    pub fn apply(self, progress: f64) -> f64 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t * t,
+            Self::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// The way a wipe's edge or a slide's clips move across the frame. Other
+/// transition types ignore it.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ClipTransitionDirection {
+    #[default]
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl ClipTransitionDirection {
+    /// Unit vector of the motion in frame coordinates, with y pointing down.
+    pub fn vector(self) -> XY<f64> {
+        match self {
+            Self::Left => XY::new(-1.0, 0.0),
+            Self::Right => XY::new(1.0, 0.0),
+            Self::Up => XY::new(0.0, -1.0),
+            Self::Down => XY::new(0.0, 1.0),
+        }
+    }
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Copy, Debug)]
@@ -1035,6 +1158,29 @@ pub struct ClipTransition {
     #[serde(rename = "type")]
     pub kind: ClipTransitionType,
     pub duration: f64,
+    #[serde(default)]
+    pub easing: ClipTransitionEasing,
+    #[serde(default)]
+    pub direction: ClipTransitionDirection,
+}
+
+impl ClipTransition {
+    pub fn style(&self) -> ClipTransitionStyle {
+        ClipTransitionStyle {
+            kind: self.kind,
+            easing: self.easing,
+            direction: self.direction,
+        }
+    }
+}
+
+/// Everything about a transition that affects how a frame or sample inside
+/// it is mixed, independent of where it sits on the timeline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClipTransitionStyle {
+    pub kind: ClipTransitionType,
+    pub easing: ClipTransitionEasing,
+    pub direction: ClipTransitionDirection,
 }
 
 fn deserialize_clip_transitions<'de, D>(deserializer: D) -> Result<Vec<ClipTransition>, D::Error>
@@ -1072,6 +1218,9 @@ pub struct TimelineConfiguration {
     pub keyboard_segments: Vec<crate::KeyboardTrackSegment>,
     #[serde(default)]
     pub audio_segments: Vec<AudioTrackSegment>,
+    /// Volume automation for the recording's mic and system audio.
+    #[serde(default, skip_serializing_if = "VolumeKeyframes::is_empty")]
+    pub volume_keyframes: VolumeKeyframes,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -1090,7 +1239,9 @@ pub enum TimelineFrameMapping<'a> {
     Transition {
         outgoing: TimelineSource<'a>,
         incoming: TimelineSource<'a>,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
+        /// Linear progress through the transition; apply `style.easing` to
+        /// get the mix amount.
         progress: f64,
         duration: f64,
         output_end: f64,
@@ -1200,7 +1351,7 @@ impl TimelineConfiguration {
                             segment_index,
                             segment,
                         },
-                        kind: transition.kind,
+                        style: transition.style(),
                         progress: (elapsed / transition.duration).clamp(0.0, 1.0),
                         duration: transition.duration,
                         output_end,
@@ -1285,6 +1436,41 @@ impl TimelineConfiguration {
                 .map(|transition| transition.duration)
                 .sum::<f64>()
     }
+
+    /// The timeline the editor creates for an un-edited recording: one clip per
+    /// recording segment spanning its full duration. `None` when no segment has
+    /// any media.
+    pub fn from_recording_durations(durations: &[f64]) -> Option<Self> {
+        let segments = durations
+            .iter()
+            .enumerate()
+            .filter(|(_, duration)| **duration > 0.0)
+            .map(|(i, duration)| TimelineSegment {
+                recording_clip: i as u32,
+                start: 0.0,
+                end: *duration,
+                timescale: 1.0,
+                name: None,
+                speed_audio_mode: None,
+            })
+            .collect::<Vec<_>>();
+        if segments.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            segments,
+            transitions: Vec::new(),
+            zoom_segments: Vec::new(),
+            scene_segments: Vec::new(),
+            mask_segments: Vec::new(),
+            text_segments: Vec::new(),
+            caption_segments: Vec::new(),
+            keyboard_segments: Vec::new(),
+            audio_segments: Vec::new(),
+            volume_keyframes: VolumeKeyframes::default(),
+        })
+    }
 }
 
 pub const WALLPAPERS_PATH: &str = "assets/backgrounds/macOS";
@@ -1487,7 +1673,7 @@ pub struct KeyboardData {
     pub settings: KeyboardSettings,
 }
 
-#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+#[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
 pub struct ClipOffsets {
     #[serde(default)]
     pub camera: f32,
@@ -1495,6 +1681,15 @@ pub struct ClipOffsets {
     pub mic: f32,
     #[serde(default)]
     pub system_audio: f32,
+    /// Offsets of the segment's `additional_mics`, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_mics: Vec<f32>,
+}
+
+impl ClipOffsets {
+    pub fn additional_mic(&self, index: usize) -> f32 {
+        self.additional_mics.get(index).copied().unwrap_or_default()
+    }
 }
 
 #[derive(Type, Serialize, Deserialize, Clone, Debug, Default)]
@@ -1542,6 +1737,9 @@ pub enum AnnotationValidationError {
         id: String,
         annotation_type: AnnotationType,
     },
+    TimingInvalid {
+        id: String,
+    },
 }
 
 impl fmt::Display for AnnotationValidationError {
@@ -1563,6 +1761,12 @@ impl fmt::Display for AnnotationValidationError {
                 f,
                 "annotation {id} with type {annotation_type:?} cannot include mask data"
             ),
+            Self::TimingInvalid { id } => {
+                write!(
+                    f,
+                    "annotation {id} has an invalid start, end or fadeDuration"
+                )
+            }
         }
     }
 }
@@ -1589,10 +1793,34 @@ pub struct Annotation {
     pub mask_type: Option<MaskType>,
     #[serde(default)]
     pub mask_level: Option<f64>,
+    /// Timeline time, in seconds, at which the annotation appears in studio
+    /// renders.
+    #[serde(default)]
+    pub start: f64,
+    /// Timeline time at which it disappears; `None` keeps it on screen until
+    /// the end.
+    #[serde(default)]
+    pub end: Option<f64>,
+    /// Seconds spent fading in after `start` and out before `end`.
+    #[serde(default)]
+    pub fade_duration: f64,
 }
 
 impl Annotation {
     pub fn validate(&self) -> Result<(), AnnotationValidationError> {
+        let timing_valid = self.start.is_finite()
+            && self.start >= 0.0
+            && self.fade_duration.is_finite()
+            && self.fade_duration >= 0.0
+            && self
+                .end
+                .is_none_or(|end| end.is_finite() && end >= self.start);
+        if !timing_valid {
+            return Err(AnnotationValidationError::TimingInvalid {
+                id: self.id.clone(),
+            });
+        }
+
         match self.annotation_type {
             AnnotationType::Mask => {
                 if self.mask_type.is_none() {
@@ -1847,6 +2075,7 @@ mod tests {
             caption_segments: Vec::new(),
             keyboard_segments: Vec::new(),
             audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
         }
     }
 
@@ -1877,6 +2106,8 @@ mod tests {
             segment_index: 1,
             kind: ClipTransitionType::CrossFade,
             duration: 1.0,
+            easing: Default::default(),
+            direction: Default::default(),
         }]);
 
         assert_eq!(timeline.duration(), 9.0);
@@ -1889,7 +2120,10 @@ mod tests {
             Some(TimelineFrameMapping::Transition {
                 outgoing,
                 incoming,
-                kind: ClipTransitionType::CrossFade,
+                style: ClipTransitionStyle {
+                    kind: ClipTransitionType::CrossFade,
+                    ..
+                },
                 progress,
                 duration: 1.0,
                 output_end: 4.0,
@@ -1935,6 +2169,8 @@ mod tests {
             segment_index: 1,
             kind: ClipTransitionType::FadeThroughBlack,
             duration: 9.0,
+            easing: Default::default(),
+            direction: Default::default(),
         }]);
 
         let transition = timeline.effective_transition(1).unwrap();
@@ -1982,6 +2218,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transition_easing_and_direction_default_for_legacy_json() {
+        let transition: ClipTransition = serde_json::from_value(serde_json::json!({
+            "segmentIndex": 1,
+            "type": "cross-fade",
+            "duration": 0.5
+        }))
+        .unwrap();
+        assert_eq!(transition.easing, ClipTransitionEasing::Linear);
+        assert_eq!(transition.direction, ClipTransitionDirection::Left);
+
+        let transition: ClipTransition = serde_json::from_value(serde_json::json!({
+            "segmentIndex": 1,
+            "type": "zoom-through",
+            "duration": 0.5,
+            "easing": "ease-in-out",
+            "direction": "up"
+        }))
+        .unwrap();
+        assert_eq!(
+            transition.style(),
+            ClipTransitionStyle {
+                kind: ClipTransitionType::ZoomThrough,
+                easing: ClipTransitionEasing::EaseInOut,
+                direction: ClipTransitionDirection::Up,
+            }
+        );
+    }
+
+    #[test]
+    fn transition_easing_curves_span_zero_to_one() {
+        for easing in [
+            ClipTransitionEasing::Linear,
+            ClipTransitionEasing::EaseIn,
+            ClipTransitionEasing::EaseOut,
+            ClipTransitionEasing::EaseInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+            assert_eq!(easing.apply(-1.0), 0.0);
+            assert_eq!(easing.apply(2.0), 1.0);
+            let samples = (0..=20)
+                .map(|step| easing.apply(step as f64 / 20.0))
+                .collect::<Vec<_>>();
+            assert!(samples.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+
+        assert!(ClipTransitionEasing::EaseIn.apply(0.25) < 0.25);
+        assert!(ClipTransitionEasing::EaseOut.apply(0.25) > 0.25);
+        assert_eq!(ClipTransitionEasing::EaseInOut.apply(0.5), 0.5);
+    }
+
     fn write_config_with_motion_blur_values(
         project_path: &std::path::Path,
         cursor_motion_blur: f64,
@@ -2149,6 +2437,7 @@ mod tests {
                 caption_segments: Vec::new(),
                 keyboard_segments: Vec::new(),
                 audio_segments: Vec::new(),
+                volume_keyframes: Default::default(),
             }),
             ..Default::default()
         };
@@ -2293,4 +2582,35 @@ mod tests {
         assert_eq!(spring.damping, default_spring.damping);
         assert_eq!(spring.mass, default_spring.mass);
     }
+
+    #[test]
+    fn untimed_annotations_span_the_whole_timeline() {
+        let mut annotation: Annotation = serde_json::from_value(serde_json::json!({
+            "id": "a",
+            "type": "arrow",
+            "x": 10.0,
+            "y": 20.0,
+            "width": 100.0,
+            "height": 50.0,
+            "strokeColor": "#F05656",
+            "strokeWidth": 4.0,
+            "fillColor": "transparent",
+            "opacity": 1.0,
+            "rotation": 0.0,
+            "text": null
+        }))
+        .unwrap();
+
+        assert_eq!(annotation.start, 0.0);
+        assert_eq!(annotation.end, None);
+        assert_eq!(annotation.fade_duration, 0.0);
+        assert_eq!(annotation.validate(), Ok(()));
+
+        annotation.start = 5.0;
+        annotation.end = Some(2.0);
+        assert_eq!(
+            annotation.validate(),
+            Err(AnnotationValidationError::TimingInvalid { id: "a".into() })
+        );
+    }
 }
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::ProjectConfiguration;
+
+/// A clip picked by its `TimelineSegment::name` or its position on the
+/// timeline.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Type)]
+#[serde(untagged)]
+pub enum SegmentSelector {
+    Index(u32),
+    Name(String),
+}
+
+/// Which part of the timeline an export covers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Type)]
+#[serde(tag = "type")]
+pub enum ExportRange {
+    /// Output-time seconds; a missing bound runs to that end of the timeline.
+    Time { from: Option<f64>, to: Option<f64> },
+    /// Whole clips, played back to back in timeline order.
+    Segments { segments: Vec<SegmentSelector> },
+}
+
+impl ExportRange {
+    /// Replace the project's timeline with just the selected part, moving its
+    /// annotations along with the footage.
+    pub fn apply(&self, project: &mut ProjectConfiguration) -> Result<(), String> {
+        let timeline = project
+            .timeline
+            .as_ref()
+            .ok_or("Project has no timeline to export a range of")?;
+
+        let clipped = match self {
+            Self::Time { from, to } => {
+                let duration = timeline.duration();
+                let from = from.unwrap_or(0.0);
+                let to = to.unwrap_or(duration);
+
+                if !from.is_finite() || !to.is_finite() || from < 0.0 {
+                    return Err(format!("Invalid export range {from}s to {to}s"));
+                }
+                if from >= to {
+                    return Err(format!(
+                        "Export range start {from}s must be before its end {to}s"
+                    ));
+                }
+
+                timeline.clip_to_range(from, to).ok_or_else(|| {
+                    format!("Export range starts after the end of the {duration:.2}s timeline")
+                })?
+            }
+            Self::Segments { segments } => {
+                let count = timeline.segments.len();
+                let indices = segments
+                    .iter()
+                    .map(|selector| match selector {
+                        SegmentSelector::Index(index) => Some(*index as usize)
+                            .filter(|index| *index < count)
+                            .ok_or_else(|| {
+                                format!("Timeline has no segment {index}; it has {count}")
+                            }),
+                        SegmentSelector::Name(name) => timeline
+                            .segment_index_by_name(name)
+                            .ok_or_else(|| format!("Timeline has no segment named '{name}'")),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                timeline
+                    .select_segments(&indices)
+                    .ok_or("No timeline segments selected for export")?
+            }
+        };
+
+        project.annotations = clipped.map_annotations(&project.annotations);
+        project.timeline = Some(clipped.timeline);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Annotation, AnnotationType, TimelineConfiguration, TimelineSegment};
+
+    use super::*;
+
+    fn project() -> ProjectConfiguration {
+        let mut project = ProjectConfiguration::default();
+        let mut timeline = TimelineConfiguration::from_recording_durations(&[10.0, 10.0]).unwrap();
+        timeline.segments[1].name = Some("demo".to_string());
+        project.timeline = Some(timeline);
+        project
+    }
+
+    fn segments(project: &ProjectConfiguration) -> Vec<TimelineSegment> {
+        project.timeline.as_ref().unwrap().segments.clone()
+    }
+
+    #[test]
+    fn time_range_defaults_to_the_timeline_edges() {
+        let mut project = project();
+        ExportRange::Time {
+            from: Some(15.0),
+            to: None,
+        }
+        .apply(&mut project)
+        .unwrap();
+
+        assert_eq!(project.timeline.as_ref().unwrap().duration(), 5.0);
+    }
+
+    #[test]
+    fn time_range_moves_annotations_with_the_footage() {
+        let mut project = project();
+        project.annotations.push(Annotation {
+            id: "a".to_string(),
+            annotation_type: AnnotationType::Arrow,
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 100.0,
+            stroke_color: "#F05656".to_string(),
+            stroke_width: 4.0,
+            fill_color: "transparent".to_string(),
+            opacity: 1.0,
+            rotation: 0.0,
+            text: None,
+            mask_type: None,
+            mask_level: None,
+            start: 12.0,
+            end: Some(18.0),
+            fade_duration: 0.5,
+        });
+
+        ExportRange::Time {
+            from: Some(15.0),
+            to: None,
+        }
+        .apply(&mut project)
+        .unwrap();
+
+        let annotation = &project.annotations[0];
+        assert_eq!((annotation.start, annotation.end), (0.0, Some(3.0)));
+        assert_eq!(annotation.fade_duration, 0.0);
+    }
+
+    #[test]
+    fn segments_resolve_by_name_or_index() {
+        let mut by_name = project();
+        let range: ExportRange =
+            serde_json::from_str(r#"{"type":"Segments","segments":["demo"]}"#).unwrap();
+        range.apply(&mut by_name).unwrap();
+        assert_eq!(segments(&by_name)[0].recording_clip, 1);
+
+        let mut by_index = project();
+        let range: ExportRange =
+            serde_json::from_str(r#"{"type":"Segments","segments":[0]}"#).unwrap();
+        range.apply(&mut by_index).unwrap();
+        assert_eq!(segments(&by_index)[0].recording_clip, 0);
+    }
+
+    #[test]
+    fn unknown_segments_and_empty_ranges_are_errors() {
+        let missing = ExportRange::Segments {
+            segments: vec![SegmentSelector::Name("intro".to_string())],
+        };
+        assert!(missing.apply(&mut project()).unwrap_err().contains("intro"));
+
+        let out_of_bounds = ExportRange::Segments {
+            segments: vec![SegmentSelector::Index(2)],
+        };
+        assert!(out_of_bounds.apply(&mut project()).is_err());
+
+        let backwards = ExportRange::Time {
+            from: Some(5.0),
+            to: Some(2.0),
+        };
+        assert!(backwards.apply(&mut project()).is_err());
+
+        let past_the_end = ExportRange::Time {
+            from: Some(30.0),
+            to: None,
+        };
+        assert!(past_the_end.apply(&mut project()).is_err());
+    }
+}
@@ -1,12 +1,24 @@
+pub mod captions;
 mod configuration;
 pub mod cursor;
+mod export_range;
 pub mod keyboard;
+mod markers;
 mod meta;
+mod silence;
+mod timeline_range;
+mod volume;
 
+pub use captions::*;
 pub use configuration::*;
 pub use cursor::*;
+pub use export_range::*;
 pub use keyboard::*;
+pub use markers::*;
 pub use meta::*;
+pub use silence::*;
+pub use timeline_range::*;
+pub use volume::*;
 
 use serde::{Deserialize, Serialize};
 use specta::Type;
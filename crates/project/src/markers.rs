@@ -0,0 +1,168 @@
+//! Named points dropped while recording, and the chapters they become once the
+//! timeline has been edited.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{TimelineConfiguration, captions::SubtitleCue};
+
+/// Chapters closer together than this are collapsed into the first one.
+const MIN_CHAPTER_GAP: f64 = 0.001;
+
+/// A named point in a recording, dropped live with the marker hotkey or
+/// `cap record marker`.
+///
+/// Markers are stored against the recording rather than the timeline, so
+/// trimming, splitting or reordering clips moves them along with the footage
+/// and a marker in a cut-out part simply disappears from exports.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingMarker {
+    /// Index of the recording segment the marker was dropped in.
+    pub segment: u32,
+    /// Seconds into that segment, on the same clock as `TimelineSegment::start`.
+    pub time: f64,
+    pub name: String,
+}
+
+/// A titled output-time span of an export.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub start: f64,
+    pub end: f64,
+    pub title: String,
+}
+
+impl TimelineConfiguration {
+    /// Where `markers` land in the output, each running until the next one or
+    /// the end of the timeline. A marker in footage that is used by more than
+    /// one clip becomes a chapter at each use.
+    pub fn chapters(&self, markers: &[RecordingMarker]) -> Vec<Chapter> {
+        let spans = self.segment_output_spans();
+
+        let mut starts = markers
+            .iter()
+            .flat_map(|marker| {
+                self.segments
+                    .iter()
+                    .zip(&spans)
+                    .filter(|(segment, _)| {
+                        segment.recording_clip == marker.segment
+                            && segment.start <= marker.time
+                            && marker.time < segment.end
+                    })
+                    .map(|(segment, span)| {
+                        (
+                            span.0 + (marker.time - segment.start) / segment.timescale,
+                            marker.name.as_str(),
+                        )
+                    })
+            })
+            .collect::<Vec<_>>();
+        starts.sort_by(|a, b| a.0.total_cmp(&b.0));
+        starts.dedup_by(|later, earlier| later.0 - earlier.0 < MIN_CHAPTER_GAP);
+
+        let duration = self.duration();
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, (start, title))| Chapter {
+                start: *start,
+                end: starts.get(i + 1).map_or(duration, |next| next.0),
+                title: title.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// A WebVTT chapters track, for players that read `kind="chapters"` sidecars.
+pub fn chapters_to_webvtt(chapters: &[Chapter]) -> String {
+    crate::captions::write_webvtt(
+        &chapters
+            .iter()
+            .map(|chapter| SubtitleCue {
+                start: chapter.start,
+                end: chapter.end,
+                text: chapter.title.clone(),
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClipTransition, ClipTransitionType, TimelineSegment};
+
+    use super::*;
+
+    fn segment(recording_clip: u32, start: f64, end: f64) -> TimelineSegment {
+        TimelineSegment {
+            recording_clip,
+            timescale: 1.0,
+            start,
+            end,
+            name: None,
+            speed_audio_mode: None,
+        }
+    }
+
+    fn marker(segment: u32, time: f64, name: &str) -> RecordingMarker {
+        RecordingMarker {
+            segment,
+            time,
+            name: name.to_string(),
+        }
+    }
+
+    fn starts(chapters: &[Chapter]) -> Vec<(f64, &str)> {
+        chapters
+            .iter()
+            .map(|chapter| (chapter.start, chapter.title.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn markers_follow_trimmed_and_reordered_clips() {
+        let mut timeline = TimelineConfiguration::from_recording_durations(&[10.0, 10.0]).unwrap();
+        timeline.segments = vec![segment(1, 2.0, 10.0), segment(0, 4.0, 10.0)];
+
+        let chapters = timeline.chapters(&[
+            marker(0, 1.0, "Cut"),
+            marker(0, 5.0, "Setup"),
+            marker(1, 3.0, "Intro"),
+        ]);
+
+        assert_eq!(starts(&chapters), vec![(1.0, "Intro"), (9.0, "Setup")]);
+        assert_eq!(chapters[0].end, 9.0);
+        assert_eq!(chapters[1].end, 14.0);
+    }
+
+    #[test]
+    fn markers_account_for_speed_and_transitions() {
+        let mut timeline = TimelineConfiguration::from_recording_durations(&[10.0, 10.0]).unwrap();
+        timeline.segments[0].timescale = 2.0;
+        timeline.transitions.push(ClipTransition {
+            segment_index: 1,
+            kind: ClipTransitionType::CrossFade,
+            duration: 1.0,
+            easing: Default::default(),
+            direction: Default::default(),
+        });
+
+        let chapters = timeline.chapters(&[marker(0, 4.0, "Fast"), marker(1, 2.0, "Next")]);
+
+        assert_eq!(starts(&chapters), vec![(2.0, "Fast"), (6.0, "Next")]);
+        assert_eq!(chapters[1].end, timeline.duration());
+    }
+
+    #[test]
+    fn chapters_write_as_a_webvtt_track() {
+        let vtt = chapters_to_webvtt(&[Chapter {
+            start: 0.0,
+            end: 61.5,
+            title: "Q&A".to_string(),
+        }]);
+
+        assert_eq!(vtt, "WEBVTT\n\n00:00:00.000 --> 00:01:01.500\nQ&amp;A\n\n");
+    }
+}
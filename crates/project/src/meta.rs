@@ -10,8 +10,8 @@ use std::{
 use tracing::{debug, info, warn};
 
 use crate::{
-    CaptionsData, CursorEvents, CursorImage, KeyboardEvents, ProjectConfiguration, XY,
-    cursor::SHORT_CURSOR_SHAPE_DEBOUNCE_MS,
+    CaptionsData, CursorEvents, CursorImage, KeyboardEvents, ProjectConfiguration, RecordingMarker,
+    XY, cursor::SHORT_CURSOR_SHAPE_DEBOUNCE_MS,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -358,6 +358,13 @@ impl StudioRecordingMeta {
         }
     }
 
+    pub fn markers(&self) -> &[RecordingMarker] {
+        match self {
+            Self::SingleSegment { .. } => &[],
+            Self::MultipleSegments { inner } => &inner.markers,
+        }
+    }
+
     pub fn min_fps(&self) -> u32 {
         match self {
             Self::SingleSegment { segment } => segment.display.fps,
@@ -398,6 +405,8 @@ pub struct MultipleSegments {
     pub cursors: Cursors,
     #[serde(default)]
     pub status: Option<StudioRecordingStatus>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markers: Vec<RecordingMarker>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -456,6 +465,9 @@ impl MultipleSegments {
                     ))
                     | Some(cap_cursor_info::CursorShape::Windows(
                         cap_cursor_info::CursorShapeWindows::Arrow,
+                    ))
+                    | Some(cap_cursor_info::CursorShape::Linux(
+                        cap_cursor_info::CursorShapeLinux::Default,
                     )) => Some(id.clone()),
                     _ => None,
                 })
@@ -485,6 +497,11 @@ pub struct MultipleSegment {
     pub camera: Option<VideoMeta>,
     #[serde(default, skip_serializing_if = "Option::is_none", alias = "audio")]
     pub mic: Option<AudioMeta>,
+    /// Microphones recorded alongside `mic`, each into its own file, in the
+    /// order they were asked for. A mic that produced no recording is `None`,
+    /// so the ones after it keep their position.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_mics: Vec<Option<AudioMeta>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub system_audio: Option<AudioMeta>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -567,6 +584,10 @@ impl MultipleSegment {
             value = value.max(mic.start_time?);
         }
 
+        for mic in self.additional_mics.iter().flatten() {
+            value = value.max(mic.start_time?);
+        }
+
         if let Some(system_audio) = &self.system_audio {
             value = value.max(system_audio.start_time?);
         }
@@ -578,9 +599,30 @@ impl MultipleSegment {
         self.calculate_audio_offsets_with_calibration(None)
     }
 
+    /// Offsets with `calibration_offset` applied to the first microphone and
+    /// system audio.
     pub fn calculate_audio_offsets_with_calibration(
         &self,
         calibration_offset: Option<f32>,
+    ) -> crate::ClipOffsets {
+        self.audio_offsets(calibration_offset, |_| None)
+    }
+
+    /// Offsets with each microphone's sync calibration applied, looked up by
+    /// its device id. System audio follows the first microphone's.
+    pub fn calculate_audio_offsets_with_device_calibration(
+        &self,
+        calibration_offset: impl Fn(&str) -> Option<f32>,
+    ) -> crate::ClipOffsets {
+        self.audio_offsets(self.mic_device_id().and_then(&calibration_offset), |mic| {
+            mic.device_id.as_deref().and_then(&calibration_offset)
+        })
+    }
+
+    fn audio_offsets(
+        &self,
+        calibration_offset: Option<f32>,
+        additional_mic_calibration: impl Fn(&AudioMeta) -> Option<f32>,
     ) -> crate::ClipOffsets {
         let latest = match self.latest_start_time() {
             Some(t) => t,
@@ -610,10 +652,25 @@ impl MultipleSegment {
             .map(|t| (latest - t) as f32 + cal_offset)
             .unwrap_or(0.0);
 
+        let additional_mics = self
+            .additional_mics
+            .iter()
+            .map(|mic| {
+                mic.as_ref()
+                    .and_then(|mic| {
+                        mic.start_time.map(|t| {
+                            (latest - t) as f32 + additional_mic_calibration(mic).unwrap_or(0.0)
+                        })
+                    })
+                    .unwrap_or(0.0)
+            })
+            .collect();
+
         crate::ClipOffsets {
             camera: camera_offset,
             mic: mic_offset,
             system_audio: system_audio_offset,
+            additional_mics,
         }
     }
 
@@ -765,6 +822,7 @@ mod test {
                 display: video(Some(display_start)),
                 camera: None,
                 mic: mic_start.map(|s| audio(Some(s))),
+                additional_mics: Vec::new(),
                 system_audio: system_start.map(|s| audio(Some(s))),
                 cursor: None,
                 keyboard: None,
@@ -803,5 +861,30 @@ mod test {
             assert!((offsets.mic - (0.6586015 - 0.5559852) as f32).abs() < 1e-6);
             assert_eq!(offsets.system_audio, 0.0);
         }
+
+        #[test]
+        fn additional_mics_get_their_own_offsets_and_calibration() {
+            let mut segment = segment(0.5, Some(0.48), None);
+            segment.mic.as_mut().unwrap().device_id = Some("first".to_string());
+            segment.additional_mics = vec![
+                Some(AudioMeta {
+                    device_id: Some("second".to_string()),
+                    ..audio(Some(0.45))
+                }),
+                None,
+                Some(audio(Some(0.6))),
+            ];
+
+            assert_eq!(segment.latest_start_time(), Some(0.6));
+
+            let offsets = segment.calculate_audio_offsets_with_device_calibration(|device| {
+                (device == "second").then_some(0.01)
+            });
+            assert!((offsets.mic - 0.12).abs() < 1e-6);
+            assert!((offsets.additional_mic(0) - 0.16).abs() < 1e-6);
+            assert_eq!(offsets.additional_mic(1), 0.0);
+            assert_eq!(offsets.additional_mic(2), 0.0);
+            assert_eq!(offsets.additional_mic(3), 0.0);
+        }
     }
 }
@@ -0,0 +1,352 @@
+//! Cutting the silent stretches out of a timeline, given where its recordings
+//! are audible.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::{ClipTransition, ClipTransitionType, TimelineConfiguration, TimelineExcerpt};
+
+const EPSILON: f64 = 1e-9;
+
+/// How aggressively [`TimelineConfiguration::without_silences`] cuts. All
+/// lengths are seconds of recording, before any clip speed is applied.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SilenceCutOptions {
+    /// Shortest quiet stretch that is cut; shorter pauses are kept.
+    pub min_silence: f64,
+    /// Quiet left either side of the audio at each cut, so words aren't
+    /// clipped.
+    pub padding: f64,
+    /// Shortest clip a cut may leave. A shorter clip is joined to its nearest
+    /// neighbour by keeping the pause between them.
+    pub min_clip: f64,
+    /// Length of the cross-fade added at each cut; `0` for hard cuts.
+    pub transition: f64,
+}
+
+impl Default for SilenceCutOptions {
+    fn default() -> Self {
+        Self {
+            min_silence: 0.75,
+            padding: 0.15,
+            min_clip: 0.5,
+            transition: 0.1,
+        }
+    }
+}
+
+/// The parts of `[start, end]` to keep, given its sorted audible ranges.
+fn kept_ranges(
+    start: f64,
+    end: f64,
+    audible: &[(f64, f64)],
+    options: &SilenceCutOptions,
+) -> Vec<(f64, f64)> {
+    let mut kept: Vec<(f64, f64)> = Vec::new();
+    for &(from, to) in audible {
+        let (from, to) = (from.max(start), to.min(end));
+        if to - from <= EPSILON {
+            continue;
+        }
+        match kept.last_mut() {
+            Some(last) if from - last.1 < options.min_silence => last.1 = last.1.max(to),
+            _ => kept.push((from, to)),
+        }
+    }
+
+    // Pauses at the edges of a clip are held to the same rule as the ones
+    // inside it.
+    if let Some(first) = kept.first_mut()
+        && first.0 - start < options.min_silence
+    {
+        first.0 = start;
+    }
+    if let Some(last) = kept.last_mut()
+        && end - last.1 < options.min_silence
+    {
+        last.1 = end;
+    }
+
+    let mut padded: Vec<(f64, f64)> = Vec::with_capacity(kept.len());
+    for (from, to) in kept {
+        let (from, to) = (
+            (from - options.padding).max(start),
+            (to + options.padding).min(end),
+        );
+        match padded.last_mut() {
+            Some(last) if from <= last.1 => last.1 = to,
+            _ => padded.push((from, to)),
+        }
+    }
+
+    let mut index = 0;
+    while padded.len() > 1 && index < padded.len() {
+        if padded[index].1 - padded[index].0 >= options.min_clip {
+            index += 1;
+            continue;
+        }
+
+        let gap_before = index
+            .checked_sub(1)
+            .map(|previous| padded[index].0 - padded[previous].1);
+        let gap_after = padded.get(index + 1).map(|next| next.0 - padded[index].1);
+        match (gap_before, gap_after) {
+            (Some(before), Some(after)) if before < after => {
+                padded[index - 1].1 = padded[index].1;
+                padded.remove(index);
+                index -= 1;
+            }
+            (_, Some(_)) => {
+                padded[index].1 = padded[index + 1].1;
+                padded.remove(index + 1);
+            }
+            _ => {
+                padded[index - 1].1 = padded[index].1;
+                padded.remove(index);
+                index -= 1;
+            }
+        }
+    }
+
+    padded
+}
+
+impl TimelineConfiguration {
+    /// This timeline with the long pauses cut out of its clips.
+    ///
+    /// `audible` holds, per recording segment, the sorted ranges where its
+    /// audio is audible, on the same clock as `TimelineSegment::start`. Clips
+    /// of a segment with `None` have nothing to judge by and are kept whole.
+    /// Every cut gets a `transition`-long cross-fade, transitions between
+    /// clips that stay neighbours are kept, and output-time tracks are shifted
+    /// to follow the footage. `None` when nothing audible is left.
+    pub fn without_silences(
+        &self,
+        audible: &[Option<Vec<(f64, f64)>>],
+        options: &SilenceCutOptions,
+    ) -> Option<TimelineExcerpt> {
+        let pieces = self
+            .segments
+            .iter()
+            .enumerate()
+            .flat_map(|(index, segment)| {
+                let kept = match audible.get(segment.recording_clip as usize) {
+                    Some(Some(ranges)) => kept_ranges(segment.start, segment.end, ranges, options),
+                    _ => vec![(segment.start, segment.end)],
+                };
+                kept.into_iter().map(move |(from, to)| (index, from, to))
+            })
+            .collect::<Vec<_>>();
+        if pieces.is_empty() {
+            return None;
+        }
+
+        let mut segments = Vec::with_capacity(pieces.len());
+        let mut transitions = Vec::new();
+        for (position, &(index, from, to)) in pieces.iter().enumerate() {
+            let source = &self.segments[index];
+            if let Some(&(previous, _, previous_to)) = position.checked_sub(1).map(|p| &pieces[p]) {
+                let untouched = previous + 1 == index
+                    && (previous_to - self.segments[previous].end).abs() <= EPSILON
+                    && (from - source.start).abs() <= EPSILON;
+                let transition = if untouched {
+                    self.transitions
+                        .iter()
+                        .find(|transition| transition.segment_index as usize == index)
+                        .copied()
+                } else {
+                    (options.transition > 0.0).then_some(ClipTransition {
+                        segment_index: 0,
+                        kind: ClipTransitionType::CrossFade,
+                        duration: options.transition,
+                        easing: Default::default(),
+                        direction: Default::default(),
+                    })
+                };
+                transitions.extend(transition.map(|transition| ClipTransition {
+                    segment_index: position as u32,
+                    ..transition
+                }));
+            }
+
+            let mut segment = source.clone();
+            segment.start = from;
+            segment.end = to;
+            segments.push(segment);
+        }
+
+        let mut cut = self.with_tracks_mapped(&[]);
+        cut.segments = segments;
+        cut.transitions = transitions;
+
+        // Each clip hands the tracks over halfway through the transition into
+        // the next, so the mapped ranges play back to back like the output.
+        let spans = self.segment_output_spans();
+        let mut ranges = pieces
+            .iter()
+            .map(|&(index, from, to)| {
+                let segment = &self.segments[index];
+                (
+                    spans[index].0 + (from - segment.start) / segment.timescale,
+                    spans[index].0 + (to - segment.start) / segment.timescale,
+                )
+            })
+            .collect::<Vec<_>>();
+        for position in 1..ranges.len() {
+            if let Some(transition) = cut.effective_transition(position) {
+                ranges[position - 1].1 -= transition.duration / 2.0;
+                ranges[position].0 += transition.duration / 2.0;
+            }
+        }
+
+        Some(TimelineExcerpt {
+            timeline: Self {
+                segments: cut.segments,
+                transitions: cut.transitions,
+                ..self.with_tracks_mapped(&ranges)
+            },
+            ranges,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CaptionTrackSegment, TimelineSegment};
+
+    use super::*;
+
+    fn segment(recording_clip: u32, start: f64, end: f64) -> TimelineSegment {
+        TimelineSegment {
+            recording_clip,
+            timescale: 1.0,
+            start,
+            end,
+            name: None,
+            speed_audio_mode: None,
+        }
+    }
+
+    fn bounds(timeline: &TimelineConfiguration) -> Vec<(u32, f64, f64)> {
+        timeline
+            .segments
+            .iter()
+            .map(|s| {
+                let round = |t: f64| (t * 1000.0).round() / 1000.0;
+                (s.recording_clip, round(s.start), round(s.end))
+            })
+            .collect()
+    }
+
+    fn hard_cuts() -> SilenceCutOptions {
+        SilenceCutOptions {
+            transition: 0.0,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn long_pauses_are_cut_with_padding_and_a_cross_fade() {
+        let timeline = TimelineConfiguration::from_recording_durations(&[10.0]).unwrap();
+
+        let cut = timeline
+            .without_silences(
+                &[Some(vec![(0.0, 3.0), (3.5, 4.0), (7.0, 10.0)])],
+                &SilenceCutOptions::default(),
+            )
+            .unwrap()
+            .timeline;
+
+        assert_eq!(bounds(&cut), vec![(0, 0.0, 4.15), (0, 6.85, 10.0)]);
+        assert_eq!(cut.transitions.len(), 1);
+        assert_eq!(cut.transitions[0].segment_index, 1);
+        assert!((cut.duration() - (4.15 + 3.15 - 0.1)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quiet_edges_are_trimmed_and_silent_clips_dropped() {
+        let timeline = TimelineConfiguration::from_recording_durations(&[10.0, 5.0]).unwrap();
+
+        let cut = timeline
+            .without_silences(&[Some(vec![(2.0, 8.0)]), Some(Vec::new())], &hard_cuts())
+            .unwrap()
+            .timeline;
+
+        assert_eq!(bounds(&cut), vec![(0, 1.85, 8.15)]);
+        assert!(cut.transitions.is_empty());
+
+        assert!(
+            timeline
+                .without_silences(&[Some(Vec::new()), Some(Vec::new())], &hard_cuts())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn short_clips_are_joined_to_their_nearest_neighbour() {
+        let timeline = TimelineConfiguration::from_recording_durations(&[20.0]).unwrap();
+
+        let cut = timeline
+            .without_silences(
+                &[Some(vec![(0.0, 5.0), (6.0, 6.1), (10.0, 20.0)])],
+                &hard_cuts(),
+            )
+            .unwrap()
+            .timeline;
+
+        assert_eq!(bounds(&cut), vec![(0, 0.0, 6.25), (0, 9.85, 20.0)]);
+    }
+
+    #[test]
+    fn clips_without_audio_and_untouched_transitions_are_kept() {
+        let mut timeline = TimelineConfiguration::from_recording_durations(&[10.0, 10.0]).unwrap();
+        timeline.segments = vec![segment(0, 0.0, 10.0), segment(1, 0.0, 10.0)];
+        timeline.transitions.push(ClipTransition {
+            segment_index: 1,
+            kind: ClipTransitionType::FadeThroughBlack,
+            duration: 1.0,
+            easing: Default::default(),
+            direction: Default::default(),
+        });
+
+        let cut = timeline
+            .without_silences(&[Some(vec![(0.0, 10.0)]), None], &hard_cuts())
+            .unwrap()
+            .timeline;
+
+        assert_eq!(bounds(&cut), vec![(0, 0.0, 10.0), (1, 0.0, 10.0)]);
+        assert_eq!(cut.transitions.len(), 1);
+        assert_eq!(
+            cut.transitions[0].kind,
+            ClipTransitionType::FadeThroughBlack
+        );
+    }
+
+    #[test]
+    fn tracks_follow_the_footage_across_a_cut() {
+        let mut timeline = TimelineConfiguration::from_recording_durations(&[10.0]).unwrap();
+        timeline.caption_segments.push(CaptionTrackSegment {
+            id: "c1".to_string(),
+            start: 7.0,
+            end: 9.0,
+            text: "after the pause".to_string(),
+            words: Vec::new(),
+            fade_duration_override: None,
+            linger_duration_override: None,
+            position_override: None,
+            color_override: None,
+            background_color_override: None,
+            font_size_override: None,
+        });
+
+        let cut = timeline
+            .without_silences(&[Some(vec![(0.0, 3.0), (7.0, 10.0)])], &hard_cuts())
+            .unwrap()
+            .timeline;
+
+        let caption = &cut.caption_segments[0];
+        assert!((caption.start - 3.3).abs() < 1e-9);
+        assert!((caption.end - 5.3).abs() < 1e-9);
+    }
+}
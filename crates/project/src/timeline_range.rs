@@ -0,0 +1,665 @@
+//! Cutting an excerpt out of a timeline, so a part of a project can be
+//! exported without hand-trimming a copy of it.
+
+use crate::{Annotation, ClipTransition, TimelineConfiguration};
+
+const EPSILON: f64 = 1e-9;
+
+/// One clip's contribution to an excerpt, as an output-time span of the
+/// source timeline.
+#[derive(Clone, Copy, Debug)]
+struct Piece {
+    index: usize,
+    from: f64,
+    to: f64,
+}
+
+impl Piece {
+    fn duration(&self) -> f64 {
+        self.to - self.from
+    }
+}
+
+/// Where part of an output-time item lands in the excerpt.
+struct MappedSpan {
+    start: f64,
+    end: f64,
+    /// Seconds cut off the front of the item.
+    head: f64,
+    /// Seconds cut off the back of the item.
+    tail: f64,
+    /// Offset from source output time to excerpt output time.
+    shift: f64,
+}
+
+/// A timeline cut out of another, along with the source output-time ranges it
+/// plays back to back, so anything else timed against the source can follow.
+#[derive(Debug, Clone)]
+pub struct TimelineExcerpt {
+    pub timeline: TimelineConfiguration,
+    pub ranges: Vec<(f64, f64)>,
+}
+
+impl TimelineExcerpt {
+    /// `annotations` moved to the excerpt's output time. An annotation that a
+    /// cut splits becomes one per piece, and loses its fade on any piece that
+    /// no longer starts and ends where it did; ones outside every range are
+    /// dropped.
+    pub fn map_annotations(&self, annotations: &[Annotation]) -> Vec<Annotation> {
+        let duration = self.ranges.iter().map(|(from, to)| to - from).sum::<f64>();
+        let mut mapped = Vec::new();
+
+        for annotation in annotations {
+            let end = annotation.end.unwrap_or(f64::INFINITY);
+            for (piece, span) in map_span(&self.ranges, annotation.start, end)
+                .into_iter()
+                .enumerate()
+            {
+                let mut annotation = annotation.clone();
+                if piece > 0 {
+                    annotation.id = format!("{}-{}", annotation.id, piece + 1);
+                }
+                annotation.start = span.start;
+                // Open-ended annotations stay that way if they still run to
+                // the end of the excerpt.
+                annotation.end = match annotation.end {
+                    None if span.end >= duration - EPSILON => None,
+                    _ => Some(span.end),
+                };
+                let tail_cut = annotation.end.is_some() && span.tail > EPSILON;
+                if span.head > EPSILON || tail_cut {
+                    annotation.fade_duration = 0.0;
+                }
+                mapped.push(annotation);
+            }
+        }
+
+        mapped
+    }
+}
+
+/// Maps `[start, end]` through the excerpt ranges, which play back to back.
+fn map_span(ranges: &[(f64, f64)], start: f64, end: f64) -> Vec<MappedSpan> {
+    let mut offset = 0.0;
+    let mut spans = Vec::new();
+
+    for &(from, to) in ranges {
+        let clipped_start = start.max(from);
+        let clipped_end = end.min(to);
+        if clipped_end - clipped_start > EPSILON {
+            let shift = offset - from;
+            spans.push(MappedSpan {
+                start: clipped_start + shift,
+                end: clipped_end + shift,
+                head: clipped_start - start,
+                tail: end - clipped_end,
+                shift,
+            });
+        }
+        offset += to - from;
+    }
+
+    spans
+}
+
+impl TimelineConfiguration {
+    /// Output-time `(start, end)` of every clip, including the parts that
+    /// overlap a neighbour during a transition.
+    pub fn segment_output_spans(&self) -> Vec<(f64, f64)> {
+        let mut start = 0.0;
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| {
+                start -= self
+                    .effective_transition(index)
+                    .map_or(0.0, |transition| transition.duration);
+                let span = (start, start + segment.duration());
+                start = span.1;
+                span
+            })
+            .collect()
+    }
+
+    pub fn segment_index_by_name(&self, name: &str) -> Option<usize> {
+        self.segments
+            .iter()
+            .position(|segment| segment.name.as_deref() == Some(name))
+    }
+
+    /// The part of the timeline between output times `from` and `to`, with
+    /// every output-time track shifted and clipped to match. A transition that
+    /// is cut by the range, or no longer fits in its shortened clips, becomes a
+    /// hard cut to the incoming clip so the excerpt keeps the source's timing.
+    /// `None` when the range is empty.
+    pub fn clip_to_range(&self, from: f64, to: f64) -> Option<TimelineExcerpt> {
+        let from = from.max(0.0);
+        let to = to.min(self.duration());
+        if !from.is_finite() || !to.is_finite() || to - from <= EPSILON {
+            return None;
+        }
+
+        let spans = self.segment_output_spans();
+        let mut pieces = spans
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &(start, end))| {
+                let piece = Piece {
+                    index,
+                    from: start.max(from),
+                    to: end.min(to),
+                };
+                (piece.duration() > EPSILON).then_some(piece)
+            })
+            .collect::<Vec<_>>();
+
+        // Right to left, since dropping a transition shortens the outgoing
+        // clip, which can in turn make the transition before it too long.
+        let mut transitions = vec![None; pieces.len()];
+        for k in (1..pieces.len()).rev() {
+            let Some(transition) = self.effective_transition(pieces[k].index) else {
+                continue;
+            };
+            let overlap_start = spans[pieces[k].index].0;
+
+            let fits = pieces[k - 1].from <= overlap_start + EPSILON
+                && pieces[k].to >= overlap_start + transition.duration - EPSILON
+                && pieces[k - 1].duration().min(pieces[k].duration()) / 2.0
+                    >= transition.duration - EPSILON;
+
+            if fits {
+                transitions[k] = Some(transition);
+            } else {
+                pieces[k - 1].to = pieces[k - 1].to.min(overlap_start);
+            }
+        }
+
+        let mut clipped = self.with_tracks_mapped(&[(from, to)]);
+        for (piece, transition) in pieces.iter().zip(transitions) {
+            if piece.duration() <= EPSILON {
+                continue;
+            }
+
+            if let Some(transition) = transition {
+                clipped.transitions.push(ClipTransition {
+                    segment_index: clipped.segments.len() as u32,
+                    ..transition
+                });
+            }
+
+            let mut segment = self.segments[piece.index].clone();
+            let span_start = spans[piece.index].0;
+            segment.start += (piece.from - span_start) * segment.timescale;
+            segment.end = segment.start + piece.duration() * segment.timescale;
+            clipped.segments.push(segment);
+        }
+
+        Some(TimelineExcerpt {
+            timeline: clipped,
+            ranges: vec![(from, to)],
+        })
+    }
+
+    /// Only the clips at `indices`, played back to back. Transitions between
+    /// clips that stay neighbours are kept; everything else becomes a hard
+    /// cut. `None` when `indices` is empty or out of bounds.
+    pub fn select_segments(&self, indices: &[usize]) -> Option<TimelineExcerpt> {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.is_empty() || indices.iter().any(|&i| i >= self.segments.len()) {
+            return None;
+        }
+
+        let spans = self.segment_output_spans();
+        let mut ranges: Vec<(f64, f64)> = Vec::new();
+        let mut previous = None;
+        for &index in &indices {
+            match ranges.last_mut() {
+                Some(range) if previous.map(|p| p + 1) == Some(index) => range.1 = spans[index].1,
+                _ => ranges.push(spans[index]),
+            }
+            previous = Some(index);
+        }
+
+        let mut selected = self.with_tracks_mapped(&ranges);
+        let mut previous = None;
+        for &index in &indices {
+            if previous.map(|p| p + 1) == Some(index)
+                && let Some(transition) = self.effective_transition(index)
+            {
+                selected.transitions.push(ClipTransition {
+                    segment_index: selected.segments.len() as u32,
+                    ..transition
+                });
+            }
+            selected.segments.push(self.segments[index].clone());
+            previous = Some(index);
+        }
+
+        Some(TimelineExcerpt {
+            timeline: selected,
+            ranges,
+        })
+    }
+
+    /// A timeline with no clips whose output-time tracks cover `ranges` of
+    /// this one, played back to back.
+    pub(crate) fn with_tracks_mapped(&self, ranges: &[(f64, f64)]) -> Self {
+        let mut mapped = Self {
+            segments: Vec::new(),
+            transitions: Vec::new(),
+            zoom_segments: Vec::new(),
+            scene_segments: Vec::new(),
+            mask_segments: Vec::new(),
+            text_segments: Vec::new(),
+            caption_segments: Vec::new(),
+            keyboard_segments: Vec::new(),
+            audio_segments: Vec::new(),
+            volume_keyframes: self.volume_keyframes.mapped(ranges),
+        };
+
+        for zoom in &self.zoom_segments {
+            for span in map_span(ranges, zoom.start, zoom.end) {
+                let mut zoom = zoom.clone();
+                zoom.start = span.start;
+                zoom.end = span.end;
+                mapped.zoom_segments.push(zoom);
+            }
+        }
+
+        for scene in &self.scene_segments {
+            for span in map_span(ranges, scene.start, scene.end) {
+                let mut scene = scene.clone();
+                scene.start = span.start;
+                scene.end = span.end;
+                // The excerpt starts or ends mid-scene, not on its edge.
+                if span.head > EPSILON {
+                    scene.transition_in = 0.0;
+                }
+                if span.tail > EPSILON {
+                    scene.transition_out = 0.0;
+                }
+                mapped.scene_segments.push(scene);
+            }
+        }
+
+        for mask in &self.mask_segments {
+            for span in map_span(ranges, mask.start, mask.end) {
+                let mut mask = mask.clone();
+                mask.start = span.start;
+                mask.end = span.end;
+                let keyframes = &mut mask.keyframes;
+                for keyframe in keyframes.position.iter_mut().chain(&mut keyframes.size) {
+                    keyframe.time -= span.head;
+                }
+                for keyframe in &mut keyframes.intensity {
+                    keyframe.time -= span.head;
+                }
+                mapped.mask_segments.push(mask);
+            }
+        }
+
+        for text in &self.text_segments {
+            for span in map_span(ranges, text.start, text.end) {
+                let mut text = text.clone();
+                text.start = span.start;
+                text.end = span.end;
+                mapped.text_segments.push(text);
+            }
+        }
+
+        for caption in &self.caption_segments {
+            for span in map_span(ranges, caption.start, caption.end) {
+                let mut caption = caption.clone();
+                caption.start = span.start;
+                caption.end = span.end;
+                caption.words = caption
+                    .words
+                    .into_iter()
+                    .filter_map(|mut word| {
+                        let start = (word.start as f64 + span.shift).max(span.start);
+                        let end = (word.end as f64 + span.shift).min(span.end);
+                        if end - start <= EPSILON {
+                            return None;
+                        }
+                        word.start = start as f32;
+                        word.end = end as f32;
+                        Some(word)
+                    })
+                    .collect();
+                mapped.caption_segments.push(caption);
+            }
+        }
+
+        for keyboard in &self.keyboard_segments {
+            for span in map_span(ranges, keyboard.start, keyboard.end) {
+                let mut keyboard = keyboard.clone();
+                keyboard.start = span.start;
+                keyboard.end = span.end;
+                for key in &mut keyboard.keys {
+                    key.time_offset -= span.head;
+                }
+                mapped.keyboard_segments.push(keyboard);
+            }
+        }
+
+        for audio in &self.audio_segments {
+            for span in map_span(ranges, audio.start, audio.end) {
+                let mut audio = audio.clone();
+                audio.start = span.start;
+                audio.end = span.end;
+                audio.trim_start += span.head;
+                audio.fade_in = (audio.fade_in - span.head).max(0.0);
+                audio.fade_out = (audio.fade_out - span.tail).max(0.0);
+                for keyframe in &mut audio.volume_keyframes {
+                    keyframe.time -= span.head;
+                }
+                mapped.audio_segments.push(audio);
+            }
+        }
+
+        mapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        AnnotationType, AudioTrackSegment, CaptionTrackSegment, CaptionWord, ClipTransitionType,
+        TimelineSegment,
+    };
+
+    use super::*;
+
+    fn segment(recording_clip: u32, start: f64, end: f64) -> TimelineSegment {
+        TimelineSegment {
+            recording_clip,
+            timescale: 1.0,
+            start,
+            end,
+            name: None,
+            speed_audio_mode: None,
+        }
+    }
+
+    fn timeline(
+        segments: Vec<TimelineSegment>,
+        transitions: &[(u32, f64)],
+    ) -> TimelineConfiguration {
+        TimelineConfiguration {
+            segments,
+            transitions: transitions
+                .iter()
+                .map(|&(segment_index, duration)| ClipTransition {
+                    segment_index,
+                    kind: ClipTransitionType::CrossFade,
+                    duration,
+                    easing: Default::default(),
+                    direction: Default::default(),
+                })
+                .collect(),
+            zoom_segments: Vec::new(),
+            scene_segments: Vec::new(),
+            mask_segments: Vec::new(),
+            text_segments: Vec::new(),
+            caption_segments: Vec::new(),
+            keyboard_segments: Vec::new(),
+            audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
+        }
+    }
+
+    fn annotation(id: &str, start: f64, end: Option<f64>) -> Annotation {
+        Annotation {
+            id: id.to_string(),
+            annotation_type: AnnotationType::Rectangle,
+            x: 0.0,
+            y: 0.0,
+            width: 100.0,
+            height: 100.0,
+            stroke_color: "#F05656".to_string(),
+            stroke_width: 4.0,
+            fill_color: "transparent".to_string(),
+            opacity: 1.0,
+            rotation: 0.0,
+            text: None,
+            mask_type: None,
+            mask_level: None,
+            start,
+            end,
+            fade_duration: 0.5,
+        }
+    }
+
+    fn timing(annotations: &[Annotation]) -> Vec<(&str, f64, Option<f64>, f64)> {
+        annotations
+            .iter()
+            .map(|a| (a.id.as_str(), a.start, a.end, a.fade_duration))
+            .collect()
+    }
+
+    fn bounds(timeline: &TimelineConfiguration) -> Vec<(u32, f64, f64)> {
+        timeline
+            .segments
+            .iter()
+            .map(|s| (s.recording_clip, s.start, s.end))
+            .collect()
+    }
+
+    #[test]
+    fn range_trims_clips_at_both_ends() {
+        let mut source = timeline(vec![segment(0, 0.0, 10.0), segment(1, 0.0, 10.0)], &[]);
+        source.segments[1].timescale = 2.0;
+        source.segments[1].end = 20.0;
+
+        let clipped = source.clip_to_range(5.0, 15.0).unwrap().timeline;
+
+        assert_eq!(bounds(&clipped), vec![(0, 5.0, 10.0), (1, 0.0, 10.0)]);
+        assert!((clipped.duration() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn range_keeps_a_transition_that_still_fits() {
+        let source = timeline(
+            vec![segment(0, 0.0, 10.0), segment(1, 0.0, 10.0)],
+            &[(1, 1.0)],
+        );
+
+        let clipped = source.clip_to_range(5.0, 15.0).unwrap().timeline;
+
+        assert_eq!(bounds(&clipped), vec![(0, 5.0, 10.0), (1, 0.0, 6.0)]);
+        assert_eq!(clipped.transitions.len(), 1);
+        assert!((clipped.duration() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn range_starting_inside_a_transition_cuts_to_the_incoming_clip() {
+        let source = timeline(
+            vec![segment(0, 0.0, 10.0), segment(1, 0.0, 10.0)],
+            &[(1, 1.0)],
+        );
+
+        let clipped = source.clip_to_range(9.5, 15.0).unwrap().timeline;
+
+        assert_eq!(bounds(&clipped), vec![(1, 0.5, 6.0)]);
+        assert!(clipped.transitions.is_empty());
+        assert!((clipped.duration() - 5.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn transition_too_long_for_the_excerpt_becomes_a_hard_cut() {
+        let source = timeline(
+            vec![segment(0, 0.0, 10.0), segment(1, 0.0, 10.0)],
+            &[(1, 1.0)],
+        );
+
+        let clipped = source.clip_to_range(8.5, 11.0).unwrap().timeline;
+
+        assert_eq!(bounds(&clipped), vec![(0, 8.5, 9.0), (1, 0.0, 2.0)]);
+        assert!(clipped.transitions.is_empty());
+        assert!((clipped.duration() - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn empty_range_is_rejected() {
+        let source = timeline(vec![segment(0, 0.0, 10.0)], &[]);
+
+        assert!(source.clip_to_range(4.0, 4.0).is_none());
+        assert!(source.clip_to_range(12.0, 20.0).is_none());
+    }
+
+    #[test]
+    fn range_shifts_and_clips_captions_and_music() {
+        let mut source = timeline(vec![segment(0, 0.0, 20.0)], &[]);
+        source.caption_segments.push(CaptionTrackSegment {
+            id: "c1".to_string(),
+            start: 4.0,
+            end: 8.0,
+            text: "one two".to_string(),
+            words: vec![
+                CaptionWord {
+                    text: "one".to_string(),
+                    start: 4.0,
+                    end: 5.5,
+                },
+                CaptionWord {
+                    text: "two".to_string(),
+                    start: 6.0,
+                    end: 8.0,
+                },
+            ],
+            fade_duration_override: None,
+            linger_duration_override: None,
+            position_override: None,
+            color_override: None,
+            background_color_override: None,
+            font_size_override: None,
+        });
+        source.audio_segments.push(AudioTrackSegment {
+            start: 2.0,
+            end: 18.0,
+            track: 0,
+            path: "music.mp3".to_string(),
+            name: None,
+            enabled: true,
+            trim_start: 1.0,
+            volume_db: 0.0,
+            fade_in: 1.0,
+            fade_out: 1.0,
+            duration: None,
+            ducking: None,
+            volume_keyframes: Vec::new(),
+        });
+
+        let clipped = source.clip_to_range(5.5, 15.0).unwrap().timeline;
+
+        let caption = &clipped.caption_segments[0];
+        assert_eq!((caption.start, caption.end), (0.0, 2.5));
+        assert_eq!(caption.words.len(), 1);
+        assert_eq!((caption.words[0].start, caption.words[0].end), (0.5, 2.5));
+
+        let audio = &clipped.audio_segments[0];
+        assert_eq!((audio.start, audio.end), (0.0, 9.5));
+        assert_eq!(audio.trim_start, 4.5);
+        assert_eq!((audio.fade_in, audio.fade_out), (0.0, 0.0));
+    }
+
+    #[test]
+    fn selected_segments_keep_transitions_only_between_neighbours() {
+        let source = timeline(
+            vec![
+                segment(0, 0.0, 10.0),
+                segment(1, 0.0, 10.0),
+                segment(2, 0.0, 10.0),
+            ],
+            &[(1, 1.0), (2, 1.0)],
+        );
+
+        let neighbours = source.select_segments(&[2, 1]).unwrap().timeline;
+        assert_eq!(bounds(&neighbours), vec![(1, 0.0, 10.0), (2, 0.0, 10.0)]);
+        assert_eq!(neighbours.transitions[0].segment_index, 1);
+        assert!((neighbours.duration() - 19.0).abs() < 1e-9);
+
+        let apart = source.select_segments(&[0, 2]).unwrap().timeline;
+        assert!(apart.transitions.is_empty());
+        assert!((apart.duration() - 20.0).abs() < 1e-9);
+
+        assert!(source.select_segments(&[]).is_none());
+        assert!(source.select_segments(&[3]).is_none());
+    }
+
+    #[test]
+    fn selected_segments_move_their_tracks_along() {
+        let mut source = timeline(vec![segment(0, 0.0, 10.0), segment(1, 0.0, 10.0)], &[]);
+        source.audio_segments.push(AudioTrackSegment {
+            start: 12.0,
+            end: 14.0,
+            track: 0,
+            path: "music.mp3".to_string(),
+            name: None,
+            enabled: true,
+            trim_start: 0.0,
+            volume_db: 0.0,
+            fade_in: 0.0,
+            fade_out: 0.0,
+            duration: None,
+            ducking: None,
+            volume_keyframes: Vec::new(),
+        });
+
+        let selected = source.select_segments(&[1]).unwrap().timeline;
+
+        let audio = &selected.audio_segments[0];
+        assert_eq!((audio.start, audio.end), (2.0, 4.0));
+    }
+
+    #[test]
+    fn range_shifts_and_clips_annotations() {
+        let source = timeline(vec![segment(0, 0.0, 20.0)], &[]);
+        let annotations = [
+            annotation("before", 1.0, Some(4.0)),
+            annotation("cut", 4.0, Some(8.0)),
+            annotation("inside", 8.0, Some(10.0)),
+            annotation("open", 12.0, None),
+            annotation("after", 16.0, Some(18.0)),
+        ];
+
+        let clipped = source.clip_to_range(5.0, 15.0).unwrap();
+
+        assert_eq!(
+            timing(&clipped.map_annotations(&annotations)),
+            vec![
+                ("cut", 0.0, Some(3.0), 0.0),
+                ("inside", 3.0, Some(5.0), 0.5),
+                ("open", 7.0, None, 0.5),
+            ]
+        );
+
+        let tail = source.clip_to_range(10.0, 30.0).unwrap();
+        assert_eq!(
+            timing(&tail.map_annotations(&annotations)),
+            vec![("open", 2.0, None, 0.5), ("after", 6.0, Some(8.0), 0.5)]
+        );
+    }
+
+    #[test]
+    fn annotations_spanning_skipped_segments_are_split() {
+        let source = timeline(
+            vec![
+                segment(0, 0.0, 10.0),
+                segment(1, 0.0, 10.0),
+                segment(2, 0.0, 10.0),
+            ],
+            &[],
+        );
+
+        let selected = source.select_segments(&[0, 2]).unwrap();
+        let annotations = selected.map_annotations(&[annotation("a", 8.0, Some(22.0))]);
+
+        assert_eq!(
+            timing(&annotations),
+            vec![("a", 8.0, Some(10.0), 0.0), ("a-2", 10.0, Some(12.0), 0.0)]
+        );
+    }
+}
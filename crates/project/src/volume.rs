@@ -0,0 +1,231 @@
+//! Volume automation: keyframes that raise or lower a track over time, so a
+//! cough can be muted or a quiet passage lifted without splitting clips.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// At or below this a keyframed volume is silence.
+pub const SILENT_VOLUME_DB: f32 = -60.0;
+
+const EPSILON: f64 = 1e-9;
+
+/// How the volume moves from one keyframe to the next.
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VolumeInterpolation {
+    /// Evenly in dB, which is heard as an even fade.
+    #[default]
+    Decibel,
+    /// Evenly in amplitude, which drops off quickly at the quiet end.
+    Linear,
+}
+
+#[derive(Type, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeKeyframe {
+    pub time: f64,
+    /// Gain in dB added to the track's volume.
+    pub volume_db: f32,
+    /// How the volume moves from this keyframe to the next.
+    #[serde(default)]
+    pub interpolation: VolumeInterpolation,
+}
+
+/// Volume keyframes for the recording's tracks, in output time.
+#[derive(Type, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct VolumeKeyframes {
+    /// Applies to the main microphone and every additional one.
+    pub mic: Vec<VolumeKeyframe>,
+    pub system_audio: Vec<VolumeKeyframe>,
+}
+
+impl VolumeKeyframes {
+    pub fn is_empty(&self) -> bool {
+        self.mic.is_empty() && self.system_audio.is_empty()
+    }
+
+    /// The keyframes that play `ranges` of the timeline back to back, as
+    /// `TimelineConfiguration::with_tracks_mapped` does.
+    pub(crate) fn mapped(&self, ranges: &[(f64, f64)]) -> Self {
+        let map = |keyframes: &[VolumeKeyframe]| {
+            VolumeEnvelope::new(keyframes).map_or_else(Vec::new, |envelope| envelope.mapped(ranges))
+        };
+        Self {
+            mic: map(&self.mic),
+            system_audio: map(&self.system_audio),
+        }
+    }
+}
+
+/// A track's keyframes, sorted for lookup. The volume holds at the first and
+/// last keyframes' values before and after them.
+#[derive(Clone, Debug)]
+pub struct VolumeEnvelope {
+    keyframes: Vec<VolumeKeyframe>,
+}
+
+impl VolumeEnvelope {
+    /// `None` when there are no usable keyframes, so the track plays at its
+    /// own volume throughout.
+    pub fn new(keyframes: &[VolumeKeyframe]) -> Option<Self> {
+        let mut keyframes = keyframes
+            .iter()
+            .filter(|keyframe| keyframe.time.is_finite() && !keyframe.volume_db.is_nan())
+            .copied()
+            .collect::<Vec<_>>();
+        if keyframes.is_empty() {
+            return None;
+        }
+        keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Some(Self { keyframes })
+    }
+
+    /// Gain in dB at `time`.
+    pub fn volume_db(&self, time: f64) -> f32 {
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        let (Some(previous), Some(next)) = (
+            next.checked_sub(1).map(|index| &self.keyframes[index]),
+            self.keyframes.get(next),
+        ) else {
+            let edge = if next == 0 {
+                self.keyframes.first()
+            } else {
+                self.keyframes.last()
+            };
+            return edge.map_or(0.0, |keyframe| keyframe.volume_db);
+        };
+
+        let t = ((time - previous.time) / (next.time - previous.time).max(EPSILON)) as f32;
+        match previous.interpolation {
+            VolumeInterpolation::Decibel => {
+                previous.volume_db + (next.volume_db - previous.volume_db) * t
+            }
+            VolumeInterpolation::Linear => {
+                let (from, to) = (amplitude(previous.volume_db), amplitude(next.volume_db));
+                let gain = from + (to - from) * t;
+                if gain > 0.0 {
+                    (20.0 * gain.log10()).max(SILENT_VOLUME_DB)
+                } else {
+                    SILENT_VOLUME_DB
+                }
+            }
+        }
+    }
+
+    /// Linear gain at `time`.
+    pub fn gain(&self, time: f64) -> f32 {
+        amplitude(self.volume_db(time))
+    }
+
+    /// How the volume moves away from `time`.
+    fn interpolation(&self, time: f64) -> VolumeInterpolation {
+        let next = self
+            .keyframes
+            .partition_point(|keyframe| keyframe.time <= time);
+        next.checked_sub(1)
+            .map_or(VolumeInterpolation::default(), |index| {
+                self.keyframes[index].interpolation
+            })
+    }
+
+    /// Keyframes for `ranges` of this envelope played back to back. Each range
+    /// starts and ends on a keyframe, so the level inside it is unchanged.
+    fn mapped(&self, ranges: &[(f64, f64)]) -> Vec<VolumeKeyframe> {
+        let mut offset = 0.0;
+        let mut mapped = Vec::new();
+        for &(from, to) in ranges {
+            if to - from <= EPSILON {
+                continue;
+            }
+            let shift = offset - from;
+            let edge = |time: f64| VolumeKeyframe {
+                time: time + shift,
+                volume_db: self.volume_db(time),
+                interpolation: self.interpolation(time),
+            };
+
+            mapped.push(edge(from));
+            mapped.extend(
+                self.keyframes
+                    .iter()
+                    .filter(|keyframe| keyframe.time > from && keyframe.time < to)
+                    .map(|keyframe| VolumeKeyframe {
+                        time: keyframe.time + shift,
+                        ..*keyframe
+                    }),
+            );
+            mapped.push(edge(to));
+            offset += to - from;
+        }
+        mapped
+    }
+}
+
+fn amplitude(volume_db: f32) -> f32 {
+    if volume_db <= SILENT_VOLUME_DB {
+        0.0
+    } else {
+        10f32.powf(volume_db / 20.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(time: f64, volume_db: f32, interpolation: VolumeInterpolation) -> VolumeKeyframe {
+        VolumeKeyframe {
+            time,
+            volume_db,
+            interpolation,
+        }
+    }
+
+    #[test]
+    fn volume_holds_outside_the_keyframes_and_moves_between_them() {
+        let envelope = VolumeEnvelope::new(&[
+            keyframe(2.0, -12.0, VolumeInterpolation::Decibel),
+            keyframe(1.0, 0.0, VolumeInterpolation::Decibel),
+        ])
+        .unwrap();
+
+        assert_eq!(envelope.volume_db(0.0), 0.0);
+        assert_eq!(envelope.volume_db(1.5), -6.0);
+        assert_eq!(envelope.volume_db(5.0), -12.0);
+        assert!(VolumeEnvelope::new(&[]).is_none());
+    }
+
+    #[test]
+    fn linear_interpolation_moves_evenly_in_amplitude() {
+        let envelope = VolumeEnvelope::new(&[
+            keyframe(0.0, 0.0, VolumeInterpolation::Linear),
+            keyframe(1.0, SILENT_VOLUME_DB, VolumeInterpolation::Linear),
+        ])
+        .unwrap();
+
+        assert!((envelope.gain(0.5) - 0.5).abs() < 1e-6);
+        assert_eq!(envelope.gain(1.0), 0.0);
+    }
+
+    #[test]
+    fn mapped_ranges_keep_their_levels() {
+        let keyframes = VolumeKeyframes {
+            mic: vec![
+                keyframe(0.0, 0.0, VolumeInterpolation::Decibel),
+                keyframe(10.0, -20.0, VolumeInterpolation::Decibel),
+            ],
+            system_audio: Vec::new(),
+        };
+
+        let mapped = keyframes.mapped(&[(2.0, 4.0), (6.0, 8.0)]);
+        let envelope = VolumeEnvelope::new(&mapped.mic).unwrap();
+
+        assert!(mapped.system_audio.is_empty());
+        assert!((envelope.volume_db(0.0) - -4.0).abs() < 1e-5);
+        assert!((envelope.volume_db(1.0) - -6.0).abs() < 1e-5);
+        assert!((envelope.volume_db(3.0) - -14.0).abs() < 1e-5);
+    }
+}
@@ -5,7 +5,9 @@
 //! 0.5.2 Linux (recording bails with "FFmpeg was built without x11grab", or the
 //! `.cap` is left InProgress with an incomplete manifest) do NOT happen.
 //!
-//! Exits 0 on success, non-zero with a diagnostic on failure.
+//! Exits 0 on success, non-zero with a diagnostic on failure. The capture logs
+//! whether it is using MIT-SHM and XDamage, and on stop how many frames were
+//! reused because the screen hadn't changed.
 //!
 //!   DISPLAY=:99 cargo run -p cap-recording --example linux-x11-e2e
 
@@ -66,6 +66,7 @@ pub trait MakeCapturePipeline: ScreenCaptureFormat + std::fmt::Debug + 'static {
         output_size: Option<(u32, u32)>,
         quality: StudioQuality,
         #[cfg(windows)] encoder_preferences: EncoderPreferences,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline>
     where
         Self: Sized;
@@ -76,6 +77,7 @@ pub trait MakeCapturePipeline: ScreenCaptureFormat + std::fmt::Debug + 'static {
         output_size: (u32, u32),
         start_time: Timestamps,
         segment_tx: Option<std::sync::mpsc::Sender<SegmentCompletedEvent>>,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline>
     where
         Self: Sized;
@@ -94,6 +96,7 @@ impl MakeCapturePipeline for screen_capture::CMSampleBufferCapture {
         shared_pause_state: Option<SharedPauseState>,
         output_size: Option<(u32, u32)>,
         quality: StudioQuality,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline> {
         let ultra = quality == StudioQuality::Ultra;
         let compatibility = quality == StudioQuality::Compatibility;
@@ -159,24 +162,30 @@ impl MakeCapturePipeline for screen_capture::CMSampleBufferCapture {
                 OutputPipeline::builder(fragments_dir)
                     .with_video::<screen_capture::VideoSource>(screen_capture)
                     .with_timestamps(start_time)
-                    .build::<OutOfProcessFragmentedM4SMuxer>(OutOfProcessFragmentedM4SMuxerConfig {
-                        preset,
-                        bpp,
-                        output_size,
-                        shared_pause_state,
-                        ..Default::default()
+                    .build::<StreamTeeMuxer<OutOfProcessFragmentedM4SMuxer>>(StreamTeeMuxerConfig {
+                        inner: OutOfProcessFragmentedM4SMuxerConfig {
+                            preset,
+                            bpp,
+                            output_size,
+                            shared_pause_state,
+                            ..Default::default()
+                        },
+                        stream: live_stream,
                     })
                     .await
             } else {
                 OutputPipeline::builder(fragments_dir)
                     .with_video::<screen_capture::VideoSource>(screen_capture)
                     .with_timestamps(start_time)
-                    .build::<MacOSFragmentedM4SMuxer>(MacOSFragmentedM4SMuxerConfig {
-                        preset,
-                        bpp,
-                        output_size,
-                        shared_pause_state,
-                        ..Default::default()
+                    .build::<StreamTeeMuxer<MacOSFragmentedM4SMuxer>>(StreamTeeMuxerConfig {
+                        inner: MacOSFragmentedM4SMuxerConfig {
+                            preset,
+                            bpp,
+                            output_size,
+                            shared_pause_state,
+                            ..Default::default()
+                        },
+                        stream: live_stream,
                     })
                     .await
             }
@@ -189,11 +198,14 @@ impl MakeCapturePipeline for screen_capture::CMSampleBufferCapture {
             OutputPipeline::builder(output_path.clone())
                 .with_video::<screen_capture::VideoSource>(screen_capture)
                 .with_timestamps(start_time)
-                .build::<AVFoundationMp4Muxer>(AVFoundationMp4MuxerConfig {
-                    output_height: output_size.map(|(_, h)| h),
-                    instant_mode: false,
-                    ultra_quality: ultra,
-                    compatibility_quality: compatibility,
+                .build::<StreamTeeMuxer<AVFoundationMp4Muxer>>(StreamTeeMuxerConfig {
+                    inner: AVFoundationMp4MuxerConfig {
+                        output_height: output_size.map(|(_, h)| h),
+                        instant_mode: false,
+                        ultra_quality: ultra,
+                        compatibility_quality: compatibility,
+                    },
+                    stream: live_stream,
                 })
                 .await
         }
@@ -205,15 +217,19 @@ impl MakeCapturePipeline for screen_capture::CMSampleBufferCapture {
         output_size: (u32, u32),
         start_time: Timestamps,
         segment_tx: Option<std::sync::mpsc::Sender<SegmentCompletedEvent>>,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline> {
         OutputPipeline::builder(segments_dir)
             .with_video::<screen_capture::VideoSource>(screen_capture)
             .with_timestamps(start_time)
-            .build::<MacOSFragmentedM4SMuxer>(MacOSFragmentedM4SMuxerConfig {
-                bpp: H264EncoderBuilder::INSTANT_MODE_BPP,
-                output_size: Some(output_size),
-                segment_tx,
-                ..Default::default()
+            .build::<StreamTeeMuxer<MacOSFragmentedM4SMuxer>>(StreamTeeMuxerConfig {
+                inner: MacOSFragmentedM4SMuxerConfig {
+                    bpp: H264EncoderBuilder::INSTANT_MODE_BPP,
+                    output_size: Some(output_size),
+                    segment_tx,
+                    ..Default::default()
+                },
+                stream: live_stream,
             })
             .await
     }
@@ -232,6 +248,7 @@ impl MakeCapturePipeline for screen_capture::Direct3DCapture {
         output_size: Option<(u32, u32)>,
         quality: StudioQuality,
         encoder_preferences: EncoderPreferences,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline> {
         let ultra = quality == StudioQuality::Ultra;
 
@@ -283,29 +300,35 @@ impl MakeCapturePipeline for screen_capture::Direct3DCapture {
                 OutputPipeline::builder(fragments_dir)
                     .with_video::<screen_capture::VideoSource>(screen_capture)
                     .with_timestamps(start_time)
-                    .build::<WindowsOOPFragmentedM4SMuxer>(WindowsOOPFragmentedM4SMuxerConfig {
-                        segment_duration: std::time::Duration::from_secs(2),
-                        preset,
-                        bpp,
-                        output_size,
-                        shared_pause_state,
-                        disk_space_callback: None,
-                        segment_tx: None,
-                        ..Default::default()
+                    .build::<StreamTeeMuxer<WindowsOOPFragmentedM4SMuxer>>(StreamTeeMuxerConfig {
+                        inner: WindowsOOPFragmentedM4SMuxerConfig {
+                            segment_duration: std::time::Duration::from_secs(2),
+                            preset,
+                            bpp,
+                            output_size,
+                            shared_pause_state,
+                            disk_space_callback: None,
+                            segment_tx: None,
+                            ..Default::default()
+                        },
+                        stream: live_stream,
                     })
                     .await
             } else {
                 OutputPipeline::builder(fragments_dir)
                     .with_video::<screen_capture::VideoSource>(screen_capture)
                     .with_timestamps(start_time)
-                    .build::<WindowsFragmentedM4SMuxer>(WindowsFragmentedM4SMuxerConfig {
-                        segment_duration: std::time::Duration::from_secs(2),
-                        preset,
-                        bpp,
-                        output_size,
-                        shared_pause_state,
-                        disk_space_callback: None,
-                        segment_tx: None,
+                    .build::<StreamTeeMuxer<WindowsFragmentedM4SMuxer>>(StreamTeeMuxerConfig {
+                        inner: WindowsFragmentedM4SMuxerConfig {
+                            segment_duration: std::time::Duration::from_secs(2),
+                            preset,
+                            bpp,
+                            output_size,
+                            shared_pause_state,
+                            disk_space_callback: None,
+                            segment_tx: None,
+                        },
+                        stream: live_stream,
                     })
                     .await
             }
@@ -316,18 +339,21 @@ impl MakeCapturePipeline for screen_capture::Direct3DCapture {
             OutputPipeline::builder(output_path.clone())
                 .with_video::<screen_capture::VideoSource>(screen_capture)
                 .with_timestamps(start_time)
-                .build::<WindowsMuxer>(WindowsMuxerConfig {
-                    pixel_format: screen_capture::Direct3DCapture::PIXEL_FORMAT.as_dxgi(),
-                    d3d_device,
-                    bitrate_multiplier,
-                    frame_rate: 30u32,
-                    output_size: output_size.map(|(w, h)| windows::Graphics::SizeInt32 {
-                        Width: w as i32,
-                        Height: h as i32,
-                    }),
-                    encoder_preferences,
-                    fragmented: false,
-                    frag_duration_us: 2_000_000,
+                .build::<StreamTeeMuxer<WindowsMuxer>>(StreamTeeMuxerConfig {
+                    inner: WindowsMuxerConfig {
+                        pixel_format: screen_capture::Direct3DCapture::PIXEL_FORMAT.as_dxgi(),
+                        d3d_device,
+                        bitrate_multiplier,
+                        frame_rate: 30u32,
+                        output_size: output_size.map(|(w, h)| windows::Graphics::SizeInt32 {
+                            Width: w as i32,
+                            Height: h as i32,
+                        }),
+                        encoder_preferences,
+                        fragmented: false,
+                        frag_duration_us: 2_000_000,
+                    },
+                    stream: live_stream,
                 })
                 .await
         }
@@ -339,18 +365,22 @@ impl MakeCapturePipeline for screen_capture::Direct3DCapture {
         output_size: (u32, u32),
         start_time: Timestamps,
         segment_tx: Option<std::sync::mpsc::Sender<SegmentCompletedEvent>>,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline> {
         OutputPipeline::builder(segments_dir)
             .with_video::<screen_capture::VideoSource>(screen_capture)
             .with_timestamps(start_time)
-            .build::<WindowsFragmentedM4SMuxer>(WindowsFragmentedM4SMuxerConfig {
-                segment_duration: std::time::Duration::from_secs(2),
-                preset: H264Preset::Ultrafast,
-                bpp: H264EncoderBuilder::INSTANT_MODE_BPP,
-                output_size: Some(output_size),
-                shared_pause_state: None,
-                disk_space_callback: None,
-                segment_tx,
+            .build::<StreamTeeMuxer<WindowsFragmentedM4SMuxer>>(StreamTeeMuxerConfig {
+                inner: WindowsFragmentedM4SMuxerConfig {
+                    segment_duration: std::time::Duration::from_secs(2),
+                    preset: H264Preset::Ultrafast,
+                    bpp: H264EncoderBuilder::INSTANT_MODE_BPP,
+                    output_size: Some(output_size),
+                    shared_pause_state: None,
+                    disk_space_callback: None,
+                    segment_tx,
+                },
+                stream: live_stream,
             })
             .await
     }
@@ -367,6 +397,7 @@ impl MakeCapturePipeline for screen_capture::X11Capture {
         shared_pause_state: Option<SharedPauseState>,
         output_size: Option<(u32, u32)>,
         quality: StudioQuality,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline> {
         let fragments_dir = output_path
             .parent()
@@ -377,15 +408,18 @@ impl MakeCapturePipeline for screen_capture::X11Capture {
         OutputPipeline::builder(fragments_dir)
             .with_video::<screen_capture::VideoSource>(screen_capture)
             .with_timestamps(start_time)
-            .build::<crate::ffmpeg::SegmentedVideoMuxer>(crate::ffmpeg::SegmentedVideoMuxerConfig {
-                segment_duration: std::time::Duration::from_secs(2),
-                preset: if ultra {
-                    H264Preset::Medium
-                } else {
-                    H264Preset::Ultrafast
+            .build::<StreamTeeMuxer<crate::ffmpeg::SegmentedVideoMuxer>>(StreamTeeMuxerConfig {
+                inner: crate::ffmpeg::SegmentedVideoMuxerConfig {
+                    segment_duration: std::time::Duration::from_secs(2),
+                    preset: if ultra {
+                        H264Preset::Medium
+                    } else {
+                        H264Preset::Ultrafast
+                    },
+                    output_size,
+                    shared_pause_state,
                 },
-                output_size,
-                shared_pause_state,
+                stream: live_stream,
             })
             .await
     }
@@ -396,15 +430,19 @@ impl MakeCapturePipeline for screen_capture::X11Capture {
         output_size: (u32, u32),
         start_time: Timestamps,
         _segment_tx: Option<std::sync::mpsc::Sender<SegmentCompletedEvent>>,
+        live_stream: Option<LiveStream>,
     ) -> anyhow::Result<OutputPipeline> {
         OutputPipeline::builder(segments_dir)
             .with_video::<screen_capture::VideoSource>(screen_capture)
             .with_timestamps(start_time)
-            .build::<crate::ffmpeg::SegmentedVideoMuxer>(crate::ffmpeg::SegmentedVideoMuxerConfig {
-                segment_duration: std::time::Duration::from_secs(2),
-                preset: H264Preset::Ultrafast,
-                output_size: Some(output_size),
-                shared_pause_state: None,
+            .build::<StreamTeeMuxer<crate::ffmpeg::SegmentedVideoMuxer>>(StreamTeeMuxerConfig {
+                inner: crate::ffmpeg::SegmentedVideoMuxerConfig {
+                    segment_duration: std::time::Duration::from_secs(2),
+                    preset: H264Preset::Ultrafast,
+                    output_size: Some(output_size),
+                    shared_pause_state: None,
+                },
+                stream: live_stream,
             })
             .await
     }
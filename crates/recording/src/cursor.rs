@@ -13,6 +13,9 @@ use std::{
 use tokio::sync::oneshot;
 use tokio_util::sync::{CancellationToken, DropGuard};
 
+#[cfg(target_os = "linux")]
+mod linux_keyboard;
+
 #[derive(Clone)]
 pub struct Cursor {
     pub file_name: String,
@@ -205,6 +208,80 @@ fn keycode_to_string(key: &device_query::Keycode) -> (String, String) {
     (display.to_string(), code.to_string())
 }
 
+/// Where key presses come from. `device_query` only sees the keys held at each
+/// poll, so on Linux they're read as events whenever that's possible.
+enum KeyboardCapture {
+    Polled(Vec<device_query::Keycode>),
+    #[cfg(target_os = "linux")]
+    Events(linux_keyboard::LinuxKeyboard),
+}
+
+impl KeyboardCapture {
+    fn new(device_state: &device_query::DeviceState) -> Self {
+        use device_query::DeviceQuery;
+
+        #[cfg(target_os = "linux")]
+        if let Some(keyboard) =
+            linux_keyboard::LinuxKeyboard::xinput().or_else(linux_keyboard::LinuxKeyboard::evdev)
+        {
+            return Self::Events(keyboard);
+        }
+
+        Self::Polled(device_state.get_keys())
+    }
+
+    fn capture(
+        &mut self,
+        device_state: &device_query::DeviceState,
+        start: Instant,
+        presses: &mut Vec<KeyPressEvent>,
+    ) {
+        match self {
+            Self::Polled(last_keys) => capture_polled_keys(device_state, last_keys, start, presses),
+            #[cfg(target_os = "linux")]
+            Self::Events(keyboard) => keyboard.drain(start, presses),
+        }
+    }
+}
+
+fn capture_polled_keys(
+    device_state: &device_query::DeviceState,
+    last_keys: &mut Vec<device_query::Keycode>,
+    start: Instant,
+    presses: &mut Vec<KeyPressEvent>,
+) {
+    use device_query::DeviceQuery;
+
+    let time_ms = start.elapsed().as_secs_f64() * 1000.0;
+    let current_keys = device_state.get_keys();
+
+    for key in &current_keys {
+        if !last_keys.contains(key) {
+            let (display, code) = keycode_to_string(key);
+            presses.push(KeyPressEvent {
+                key: display,
+                key_code: code,
+                time_ms,
+                down: true,
+            });
+        }
+    }
+
+    for key in last_keys.iter() {
+        if !current_keys.contains(key) {
+            let (display, code) = keycode_to_string(key);
+            presses.push(KeyPressEvent {
+                key: display,
+                key_code: code,
+                time_ms,
+                down: false,
+            });
+        }
+    }
+
+    *last_keys = current_keys;
+}
+
 #[tracing::instrument(name = "cursor", skip_all)]
 pub fn spawn_cursor_recorder(
     crop_bounds: CursorCropBounds,
@@ -217,6 +294,18 @@ pub fn spawn_cursor_recorder(
 ) -> CursorActor {
     #[cfg(target_os = "linux")]
     if prefers_wayland_portal_cursor() {
+        // The compositor keeps the cursor to itself, but keyboards can still
+        // be read through evdev when the user has access to them.
+        if let Some(keyboard) = linux_keyboard::LinuxKeyboard::evdev() {
+            return spawn_keyboard_recorder(
+                keyboard,
+                prev_cursors,
+                next_cursor_id,
+                start_time,
+                incremental_outputs,
+            );
+        }
+
         let (tx, rx) = oneshot::channel();
         let _ = tx.send(CursorActorResponse {
             cursors: prev_cursors,
@@ -246,7 +335,7 @@ pub fn spawn_cursor_recorder(
     let thread = std::thread::spawn(move || {
         let device_state = DeviceState::new();
         let mut last_mouse_state = device_state.get_mouse();
-        let mut last_keys: Vec<device_query::Keycode> = device_state.get_keys();
+        let mut keyboard = KeyboardCapture::new(&device_state);
 
         let mut last_position = cap_cursor_capture::RawCursorPosition::get();
 
@@ -372,33 +461,11 @@ pub fn spawn_cursor_recorder(
 
             last_mouse_state = mouse_state;
 
-            let current_keys = device_state.get_keys();
-
-            for key in &current_keys {
-                if !last_keys.contains(key) {
-                    let (display, code) = keycode_to_string(key);
-                    response.keyboard_presses.push(KeyPressEvent {
-                        key: display,
-                        key_code: code,
-                        time_ms: elapsed,
-                        down: true,
-                    });
-                }
-            }
-
-            for key in &last_keys {
-                if !current_keys.contains(key) {
-                    let (display, code) = keycode_to_string(key);
-                    response.keyboard_presses.push(KeyPressEvent {
-                        key: display,
-                        key_code: code,
-                        time_ms: elapsed,
-                        down: false,
-                    });
-                }
-            }
-
-            last_keys = current_keys;
+            keyboard.capture(
+                &device_state,
+                start_time.instant(),
+                &mut response.keyboard_presses,
+            );
 
             if last_flush.elapsed() >= flush_interval {
                 if let Some(ref path) = incremental_outputs.cursor {
@@ -432,6 +499,71 @@ pub fn spawn_cursor_recorder(
     }
 }
 
+/// Records only key presses, for sessions where the cursor can't be read.
+#[cfg(target_os = "linux")]
+fn spawn_keyboard_recorder(
+    mut keyboard: linux_keyboard::LinuxKeyboard,
+    prev_cursors: Cursors,
+    next_cursor_id: u32,
+    start_time: Timestamps,
+    incremental_outputs: IncrementalCaptureOutputs,
+) -> CursorActor {
+    use std::time::Duration;
+
+    let stop_token = CancellationToken::new();
+    let (tx, rx) = oneshot::channel();
+    let (stop_wakeup_tx, stop_wakeup_rx) = std::sync::mpsc::channel();
+
+    let stop_token_child = stop_token.child_token();
+    let thread = std::thread::spawn(move || {
+        let mut response = CursorActorResponse {
+            cursors: prev_cursors,
+            next_cursor_id,
+            moves: vec![],
+            clicks: vec![],
+            keyboard_presses: vec![],
+        };
+
+        let mut last_flush = Instant::now();
+        let flush_interval = Duration::from_secs(CURSOR_FLUSH_INTERVAL_SECS);
+
+        loop {
+            let stopping = stop_token_child.is_cancelled()
+                || stop_wakeup_rx
+                    .recv_timeout(Duration::from_millis(16))
+                    .is_ok();
+
+            keyboard.drain(start_time.instant(), &mut response.keyboard_presses);
+
+            if stopping {
+                break;
+            }
+
+            if last_flush.elapsed() >= flush_interval {
+                if let Some(ref kb_path) = incremental_outputs.keyboard {
+                    flush_keyboard_data(kb_path, &response.keyboard_presses);
+                }
+                last_flush = Instant::now();
+            }
+        }
+
+        tracing::info!("keyboard recorder done");
+
+        if let Some(ref kb_path) = incremental_outputs.keyboard {
+            flush_keyboard_data(kb_path, &response.keyboard_presses);
+        }
+
+        let _ = tx.send(response);
+    });
+
+    CursorActor {
+        stop: Some(stop_token.drop_guard()),
+        stop_wakeup: Some(stop_wakeup_tx),
+        thread: Some(thread),
+        rx: rx.shared(),
+    }
+}
+
 #[derive(Debug)]
 struct CursorData {
     image: Vec<u8>,
@@ -478,7 +610,7 @@ fn get_x11_cursor_data() -> Option<CursorData> {
 
     let (conn, _) = x11rb::connect(None).ok()?;
     conn.xfixes_query_version(5, 0).ok()?.reply().ok()?;
-    let cursor = conn.xfixes_get_cursor_image().ok()?.reply().ok()?;
+    let cursor = conn.xfixes_get_cursor_image_and_name().ok()?.reply().ok()?;
 
     let width = u32::from(cursor.width);
     let height = u32::from(cursor.height);
@@ -511,7 +643,12 @@ fn get_x11_cursor_data() -> Option<CursorData> {
             f64::from(cursor.xhot) / f64::from(width),
             f64::from(cursor.yhot) / f64::from(height),
         ),
-        shape: None,
+        // Themed cursors are named by the toolkit or libXcursor that loaded
+        // them; unnamed ones are drawn from their image.
+        shape: std::str::from_utf8(&cursor.name)
+            .ok()
+            .and_then(cap_cursor_info::CursorShapeLinux::from_name)
+            .map(Into::into),
     })
 }
 
@@ -529,7 +666,7 @@ fn fallback_cursor_data() -> Option<CursorData> {
     Some(CursorData {
         image,
         hotspot: XY::new(0.0, 0.0),
-        shape: None,
+        shape: Some(cap_cursor_info::CursorShapeLinux::Default.into()),
     })
 }
 
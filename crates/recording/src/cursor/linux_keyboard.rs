@@ -0,0 +1,549 @@
+//! Key presses on Linux, read as events rather than polled so that quick taps
+//! and modifiers held for a single frame still reach the keyboard overlay.
+//!
+//! X11 sessions use XInput2 raw key events, labelled from the server's keymap
+//! so that non-US layouts show the characters printed on their keys. Wayland
+//! hides other clients' input, so there the keyboards' evdev devices are read
+//! directly when the user may open them (usually via the `input` group).
+
+use cap_project::KeyPressEvent;
+use std::{
+    collections::HashSet,
+    fs::{File, OpenOptions},
+    io::{ErrorKind, Read},
+    os::{fd::AsRawFd, unix::fs::OpenOptionsExt},
+    path::Path,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+use x11rb::{
+    connection::{Connection, RequestConnection},
+    protocol::{
+        Event,
+        xinput::{self, ConnectionExt as _},
+        xproto::ConnectionExt as _,
+    },
+    rust_connection::RustConnection,
+};
+
+/// X11 keycodes are evdev keycodes offset by this much.
+const X11_KEYCODE_OFFSET: u32 = 8;
+
+const EV_KEY: u16 = 0x01;
+const KEY_ENTER: usize = 28;
+const KEY_A: usize = 30;
+const KEY_SPACE: usize = 57;
+const KEY_MAX: usize = 0x2ff;
+/// An evdev key event's value while the key auto-repeats.
+const KEY_REPEAT: i32 = 2;
+
+const fn eviocgbit(event_type: u16, len: usize) -> u32 {
+    (2 << 30) | ((len as u32) << 16) | ((b'E' as u32) << 8) | (0x20 + event_type as u32)
+}
+
+const EVIOCSCLOCKID: u32 = (1 << 30) | (4 << 16) | ((b'E' as u32) << 8) | 0xa0;
+
+pub(super) enum LinuxKeyboard {
+    XInput(XInputKeyboard),
+    Evdev(EvdevKeyboard),
+}
+
+impl LinuxKeyboard {
+    pub(super) fn xinput() -> Option<Self> {
+        match XInputKeyboard::open() {
+            Ok(keyboard) => {
+                info!("Capturing keys through XInput2 raw events");
+                Some(Self::XInput(keyboard))
+            }
+            Err(e) => {
+                debug!("XInput2 key capture unavailable: {e}");
+                None
+            }
+        }
+    }
+
+    pub(super) fn evdev() -> Option<Self> {
+        let keyboard = EvdevKeyboard::open()?;
+        info!(
+            devices = keyboard.devices.len(),
+            "Capturing keys from evdev keyboards"
+        );
+        Some(Self::Evdev(keyboard))
+    }
+
+    /// Appends the key presses since the last call, timed from `start`.
+    pub(super) fn drain(&mut self, start: Instant, presses: &mut Vec<KeyPressEvent>) {
+        match self {
+            Self::XInput(keyboard) => keyboard.drain(start, presses),
+            Self::Evdev(keyboard) => keyboard.drain(start, presses),
+        }
+    }
+}
+
+pub(super) struct XInputKeyboard {
+    conn: RustConnection,
+    keymap: Option<Keymap>,
+    clock: ServerClock,
+    keys: HeldKeys,
+}
+
+impl XInputKeyboard {
+    fn open() -> anyhow::Result<Self> {
+        let (conn, screen_num) = x11rb::connect(None)?;
+        if conn
+            .extension_information(xinput::X11_EXTENSION_NAME)?
+            .is_none()
+        {
+            anyhow::bail!("XInputExtension is not available");
+        }
+        let version = conn.xinput_xi_query_version(2, 2)?.reply()?;
+        if version.major_version < 2 {
+            anyhow::bail!(
+                "XInput {}.{} has no raw events",
+                version.major_version,
+                version.minor_version
+            );
+        }
+
+        let root = conn.setup().roots[screen_num].root;
+        conn.xinput_xi_select_events(
+            root,
+            &[xinput::EventMask {
+                deviceid: xinput::Device::ALL_MASTER.into(),
+                mask: vec![
+                    (xinput::XIEventMask::RAW_KEY_PRESS | xinput::XIEventMask::RAW_KEY_RELEASE)
+                        .into(),
+                ],
+            }],
+        )?
+        .check()?;
+
+        let keymap = Keymap::load(&conn);
+        Ok(Self {
+            conn,
+            keymap,
+            clock: ServerClock::default(),
+            keys: HeldKeys::default(),
+        })
+    }
+
+    fn drain(&mut self, start: Instant, presses: &mut Vec<KeyPressEvent>) {
+        loop {
+            let event = match self.conn.poll_for_event() {
+                Ok(Some(event)) => event,
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Lost the X11 connection for key capture: {e}");
+                    break;
+                }
+            };
+
+            let (time, keycode, down) = match event {
+                Event::XinputRawKeyPress(event) => (event.time, event.detail, true),
+                Event::XinputRawKeyRelease(event) => (event.time, event.detail, false),
+                // The layout changed, so the same keys now print something else.
+                Event::MappingNotify(_) => {
+                    self.keymap = Keymap::load(&self.conn);
+                    continue;
+                }
+                _ => continue,
+            };
+
+            let Some(code) = keycode
+                .checked_sub(X11_KEYCODE_OFFSET)
+                .and_then(|code| u16::try_from(code).ok())
+            else {
+                continue;
+            };
+            if !self.keys.update(code, down) {
+                continue;
+            }
+
+            let label = self
+                .keymap
+                .as_ref()
+                .and_then(|keymap| keymap.keysym(keycode))
+                .and_then(keysym_label);
+            let at = self.clock.instant(time);
+            presses.extend(key_press_event(code, label, down, at, start));
+        }
+    }
+}
+
+/// The server's unshifted keysym for each keycode.
+struct Keymap {
+    min_keycode: u8,
+    keysyms_per_keycode: u8,
+    keysyms: Vec<u32>,
+}
+
+impl Keymap {
+    fn load(conn: &RustConnection) -> Option<Self> {
+        let setup = conn.setup();
+        let count = setup.max_keycode.checked_sub(setup.min_keycode)? + 1;
+        let reply = conn
+            .get_keyboard_mapping(setup.min_keycode, count)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some(Self {
+            min_keycode: setup.min_keycode,
+            keysyms_per_keycode: reply.keysyms_per_keycode,
+            keysyms: reply.keysyms,
+        })
+    }
+
+    fn keysym(&self, keycode: u32) -> Option<u32> {
+        let index = keycode.checked_sub(u32::from(self.min_keycode))? as usize
+            * usize::from(self.keysyms_per_keycode);
+        self.keysyms
+            .get(index)
+            .copied()
+            .filter(|&keysym| keysym != 0)
+    }
+}
+
+/// Maps X server timestamps, in milliseconds, onto `Instant`s. Events are
+/// read some time after the server sent them, so the earliest mapping seen so
+/// far is the most accurate.
+#[derive(Default)]
+struct ServerClock {
+    origin: Option<Instant>,
+}
+
+impl ServerClock {
+    fn instant(&mut self, time: u32) -> Instant {
+        let now = Instant::now();
+        let since_origin = Duration::from_millis(u64::from(time));
+        let Some(origin) = now.checked_sub(since_origin) else {
+            return now;
+        };
+        let origin = self.origin.map_or(origin, |current| current.min(origin));
+        self.origin = Some(origin);
+        origin + since_origin
+    }
+}
+
+pub(super) struct EvdevKeyboard {
+    devices: Vec<EvdevDevice>,
+    keys: HeldKeys,
+}
+
+struct EvdevDevice {
+    file: File,
+    /// Whether the kernel stamps this device's events with `CLOCK_MONOTONIC`,
+    /// the clock `Instant` uses. Otherwise events are timed as they're read.
+    monotonic: bool,
+}
+
+impl EvdevKeyboard {
+    fn open() -> Option<Self> {
+        let entries = match std::fs::read_dir("/dev/input") {
+            Ok(entries) => entries,
+            Err(e) => {
+                debug!("Failed to list evdev devices: {e}");
+                return None;
+            }
+        };
+
+        let devices = entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("event"))
+            .filter_map(|entry| EvdevDevice::open_keyboard(&entry.path()))
+            .collect::<Vec<_>>();
+        if devices.is_empty() {
+            debug!("No readable evdev keyboards; the user may need to join the input group");
+            return None;
+        }
+
+        Some(Self {
+            devices,
+            keys: HeldKeys::default(),
+        })
+    }
+
+    fn drain(&mut self, start: Instant, presses: &mut Vec<KeyPressEvent>) {
+        let keys = &mut self.keys;
+        self.devices.retain_mut(|device| {
+            device.read(|code, down, at| {
+                if keys.update(code, down) {
+                    presses.extend(key_press_event(code, None, down, at, start));
+                }
+            })
+        });
+    }
+}
+
+impl EvdevDevice {
+    fn open_keyboard(path: &Path) -> Option<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK | libc::O_CLOEXEC)
+            .open(path)
+            .ok()?;
+
+        let mut key_bits = [0u8; KEY_MAX / 8 + 1];
+        // SAFETY: EVIOCGBIT writes at most `key_bits.len()` bytes into it.
+        let result = unsafe {
+            libc::ioctl(
+                file.as_raw_fd(),
+                eviocgbit(EV_KEY, key_bits.len()) as _,
+                key_bits.as_mut_ptr(),
+            )
+        };
+        let has_key = |key: usize| key_bits[key / 8] & (1 << (key % 8)) != 0;
+        if result < 0 || ![KEY_A, KEY_ENTER, KEY_SPACE].into_iter().all(has_key) {
+            return None;
+        }
+
+        let clock: libc::c_int = libc::CLOCK_MONOTONIC;
+        // SAFETY: EVIOCSCLOCKID only reads the clock id.
+        let monotonic = unsafe { libc::ioctl(file.as_raw_fd(), EVIOCSCLOCKID as _, &clock) } == 0;
+
+        debug!(path = %path.display(), monotonic, "Opened evdev keyboard");
+        Some(Self { file, monotonic })
+    }
+
+    /// Calls `on_key` for each key event waiting on the device. Returns
+    /// `false` once the device is gone.
+    fn read(&mut self, mut on_key: impl FnMut(u16, bool, Instant)) -> bool {
+        const EVENT_SIZE: usize = std::mem::size_of::<libc::input_event>();
+        let mut buffer = [0u8; EVENT_SIZE * 64];
+
+        loop {
+            let read = match self.file.read(&mut buffer) {
+                Ok(0) => return true,
+                Ok(read) => read,
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return true,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    debug!("Stopped reading an evdev keyboard: {e}");
+                    return false;
+                }
+            };
+
+            for chunk in buffer[..read].chunks_exact(EVENT_SIZE) {
+                // SAFETY: evdev reads whole `input_event`s, and the chunk is
+                // exactly one of them.
+                let event: libc::input_event =
+                    unsafe { std::ptr::read_unaligned(chunk.as_ptr().cast()) };
+                if event.type_ != EV_KEY || event.value == KEY_REPEAT {
+                    continue;
+                }
+
+                let at = if self.monotonic {
+                    monotonic_instant(event.time)
+                } else {
+                    Instant::now()
+                };
+                on_key(event.code, event.value != 0, at);
+            }
+        }
+    }
+}
+
+/// The `Instant` of a `CLOCK_MONOTONIC` timestamp.
+fn monotonic_instant(time: libc::timeval) -> Instant {
+    let now = Instant::now();
+    let mut clock = libc::timespec {
+        tv_sec: 0,
+        tv_nsec: 0,
+    };
+    // SAFETY: `clock` is a valid timespec for the call to fill in.
+    if unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut clock) } != 0 {
+        return now;
+    }
+
+    let clock = Duration::new(clock.tv_sec as u64, clock.tv_nsec as u32);
+    let event = Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1_000);
+    let age = clock.saturating_sub(event);
+    now.checked_sub(age).unwrap_or(now)
+}
+
+/// Keys currently held, so repeats and releases of keys pressed before
+/// capture started aren't recorded.
+#[derive(Default)]
+struct HeldKeys(HashSet<u16>);
+
+impl HeldKeys {
+    /// Whether the event changes the key's state.
+    fn update(&mut self, code: u16, down: bool) -> bool {
+        if down {
+            self.0.insert(code)
+        } else {
+            self.0.remove(&code)
+        }
+    }
+}
+
+fn key_press_event(
+    code: u16,
+    label: Option<String>,
+    down: bool,
+    at: Instant,
+    start: Instant,
+) -> Option<KeyPressEvent> {
+    let (display, key_code) = evdev_key(code)?;
+    // The layout only relabels keys that type something, so Enter stays Enter.
+    let key = match label {
+        Some(label) if display.chars().count() == 1 => label,
+        _ => display.to_string(),
+    };
+
+    Some(KeyPressEvent {
+        key,
+        key_code: key_code.to_string(),
+        time_ms: at.saturating_duration_since(start).as_secs_f64() * 1000.0,
+        down,
+    })
+}
+
+/// The text a printable keysym types, lowercased like the US labels.
+fn keysym_label(keysym: u32) -> Option<String> {
+    let c = match keysym {
+        0x21..=0x7e | 0xa1..=0xff => char::from_u32(keysym)?,
+        0x0100_00a1..=0x0110_ffff => char::from_u32(keysym - 0x0100_0000)?,
+        _ => return None,
+    };
+    Some(c.to_lowercase().collect())
+}
+
+/// A key's US label and name, named as `keycode_to_string` names them, from
+/// its evdev keycode.
+fn evdev_key(code: u16) -> Option<(&'static str, &'static str)> {
+    Some(match code {
+        1 => ("Escape", "Escape"),
+        2 => ("1", "Key1"),
+        3 => ("2", "Key2"),
+        4 => ("3", "Key3"),
+        5 => ("4", "Key4"),
+        6 => ("5", "Key5"),
+        7 => ("6", "Key6"),
+        8 => ("7", "Key7"),
+        9 => ("8", "Key8"),
+        10 => ("9", "Key9"),
+        11 => ("0", "Key0"),
+        12 => ("-", "Minus"),
+        13 => ("=", "Equal"),
+        14 => ("Backspace", "Backspace"),
+        15 => ("Tab", "Tab"),
+        16 => ("q", "Q"),
+        17 => ("w", "W"),
+        18 => ("e", "E"),
+        19 => ("r", "R"),
+        20 => ("t", "T"),
+        21 => ("y", "Y"),
+        22 => ("u", "U"),
+        23 => ("i", "I"),
+        24 => ("o", "O"),
+        25 => ("p", "P"),
+        26 => ("[", "LeftBracket"),
+        27 => ("]", "RightBracket"),
+        28 => ("Enter", "Enter"),
+        29 => ("LControl", "LControl"),
+        30 => ("a", "A"),
+        31 => ("s", "S"),
+        32 => ("d", "D"),
+        33 => ("f", "F"),
+        34 => ("g", "G"),
+        35 => ("h", "H"),
+        36 => ("j", "J"),
+        37 => ("k", "K"),
+        38 => ("l", "L"),
+        39 => (";", "Semicolon"),
+        40 => ("'", "Apostrophe"),
+        41 => ("`", "Grave"),
+        42 => ("LShift", "LShift"),
+        43 => ("\\", "BackSlash"),
+        44 => ("z", "Z"),
+        45 => ("x", "X"),
+        46 => ("c", "C"),
+        47 => ("v", "V"),
+        48 => ("b", "B"),
+        49 => ("n", "N"),
+        50 => ("m", "M"),
+        51 => (",", "Comma"),
+        52 => (".", "Dot"),
+        53 => ("/", "Slash"),
+        54 => ("RShift", "RShift"),
+        55 => ("*", "NumpadMultiply"),
+        56 => ("LAlt", "LAlt"),
+        57 => ("Space", "Space"),
+        58 => ("CapsLock", "CapsLock"),
+        59 => ("F1", "F1"),
+        60 => ("F2", "F2"),
+        61 => ("F3", "F3"),
+        62 => ("F4", "F4"),
+        63 => ("F5", "F5"),
+        64 => ("F6", "F6"),
+        65 => ("F7", "F7"),
+        66 => ("F8", "F8"),
+        67 => ("F9", "F9"),
+        68 => ("F10", "F10"),
+        71 => ("7", "Numpad7"),
+        72 => ("8", "Numpad8"),
+        73 => ("9", "Numpad9"),
+        74 => ("-", "NumpadSubtract"),
+        75 => ("4", "Numpad4"),
+        76 => ("5", "Numpad5"),
+        77 => ("6", "Numpad6"),
+        78 => ("+", "NumpadAdd"),
+        79 => ("1", "Numpad1"),
+        80 => ("2", "Numpad2"),
+        81 => ("3", "Numpad3"),
+        82 => ("0", "Numpad0"),
+        86 => ("\\", "IntlBackslash"),
+        87 => ("F11", "F11"),
+        88 => ("F12", "F12"),
+        96 => ("Enter", "Enter"),
+        97 => ("RControl", "RControl"),
+        98 => ("/", "NumpadDivide"),
+        100 => ("RAlt", "RAlt"),
+        102 => ("Home", "Home"),
+        103 => ("Up", "Up"),
+        104 => ("PageUp", "PageUp"),
+        105 => ("Left", "Left"),
+        106 => ("Right", "Right"),
+        107 => ("End", "End"),
+        108 => ("Down", "Down"),
+        109 => ("PageDown", "PageDown"),
+        110 => ("Insert", "Insert"),
+        111 => ("Delete", "Delete"),
+        125 => ("Meta", "Meta"),
+        126 => ("RMeta", "RMeta"),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_relabels_printable_keys_but_keeps_their_physical_name() {
+        let start = Instant::now();
+        // `q` on a US keyboard types `a` on AZERTY.
+        let azerty_a = key_press_event(16, keysym_label(0x41), true, start, start).unwrap();
+        assert_eq!(azerty_a.key, "a");
+        assert_eq!(azerty_a.key_code, "Q");
+
+        let cyrillic = key_press_event(33, keysym_label(0x0100_0430), true, start, start).unwrap();
+        assert_eq!(cyrillic.key, "а");
+
+        // Named keys keep their names whatever the keysym.
+        let enter = key_press_event(28, keysym_label(0xff0d), false, start, start).unwrap();
+        assert_eq!(enter.key, "Enter");
+        assert_eq!(keysym_label(0x20), None);
+        assert!(key_press_event(0x2ff, None, true, start, start).is_none());
+    }
+
+    #[test]
+    fn held_keys_drop_repeats_and_unmatched_releases() {
+        let mut keys = HeldKeys::default();
+
+        assert!(!keys.update(42, false));
+        assert!(keys.update(42, true));
+        assert!(!keys.update(42, true));
+        assert!(keys.update(42, false));
+    }
+}
@@ -1,12 +1,12 @@
 #[cfg(target_os = "macos")]
 use crate::SendableShareableContent;
 use crate::{
-    RecordingBaseInputs,
+    RecordingBaseInputs, RecordingStats,
     capture_pipeline::{
         MakeCapturePipeline, ScreenCaptureMethod, Stop, target_to_display_and_crop,
     },
     feeds::microphone::MicrophoneFeedLock,
-    output_pipeline::{self, OutputPipeline},
+    output_pipeline::{self, LiveStream, OutputPipeline},
     resolution_limits::ensure_even,
     sources::screen_capture::{ScreenCaptureConfig, ScreenCaptureTarget},
 };
@@ -89,6 +89,10 @@ impl ActorHandle {
         Ok(self.actor_ref.ask(IsPaused).await?)
     }
 
+    pub async fn stats(&self) -> anyhow::Result<RecordingStats> {
+        Ok(self.actor_ref.ask(GetStats).await?)
+    }
+
     pub fn take_segment_rx(
         &self,
     ) -> Option<std::sync::mpsc::Receiver<cap_enc_ffmpeg::segmented_stream::SegmentCompletedEvent>>
@@ -117,6 +121,7 @@ pub struct Actor {
     state: ActorState,
     total_pause_duration: std::time::Duration,
     pause_started_at: Option<f64>,
+    live_stream: Option<LiveStream>,
 }
 
 impl Actor {
@@ -144,6 +149,10 @@ impl Actor {
             }
         }
 
+        if let Some(live_stream) = self.live_stream.take() {
+            live_stream.finish().await;
+        }
+
         Ok(())
     }
 }
@@ -299,6 +308,33 @@ impl Message<IsPaused> for Actor {
     }
 }
 
+pub struct GetStats;
+
+impl Message<GetStats> for Actor {
+    type Reply = RecordingStats;
+
+    async fn handle(&mut self, _: GetStats, _: &mut Context<Self, Self::Reply>) -> Self::Reply {
+        let (ActorState::Recording {
+            pipeline,
+            segment_start_time,
+        }
+        | ActorState::Paused {
+            pipeline,
+            segment_start_time,
+        }) = &self.state
+        else {
+            return RecordingStats::default();
+        };
+
+        let until = self.pause_started_at.unwrap_or_else(current_time_f64);
+        RecordingStats {
+            elapsed: std::time::Duration::from_secs_f64((until - segment_start_time).max(0.0))
+                .saturating_sub(self.total_pause_duration),
+            video_frames: pipeline.video.video_frame_count(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CompletedRecording {
     pub project_path: PathBuf,
@@ -307,6 +343,7 @@ pub struct CompletedRecording {
     pub health: crate::RecordingHealth,
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn create_pipeline(
     content_dir: PathBuf,
     screen_capture: crate::sources::screen_capture::VideoSourceConfig,
@@ -315,6 +352,7 @@ async fn create_pipeline(
     system_audio_source: Option<crate::sources::screen_capture::SystemAudioSourceConfig>,
     max_output_size: Option<u32>,
     start_time: Timestamps,
+    live_stream: Option<LiveStream>,
 ) -> anyhow::Result<Pipeline> {
     let output_resolution = max_output_size
         .map(|max_output_size| {
@@ -349,6 +387,7 @@ async fn create_pipeline(
         output_resolution,
         start_time,
         segment_tx_for_video,
+        live_stream.clone(),
     )
     .await?;
 
@@ -370,11 +409,14 @@ async fn create_pipeline(
         let segment_tx_for_audio = segment_channel.as_ref().map(|(tx, _)| tx.clone());
 
         let audio_pipeline = builder
-            .build::<output_pipeline::DashSegmentedAudioMuxer>(
-                output_pipeline::DashSegmentedAudioMuxerConfig {
-                    shared_pause_state: None,
-                    segment_tx: segment_tx_for_audio,
-                    ..Default::default()
+            .build::<output_pipeline::StreamTeeMuxer<output_pipeline::DashSegmentedAudioMuxer>>(
+                output_pipeline::StreamTeeMuxerConfig {
+                    inner: output_pipeline::DashSegmentedAudioMuxerConfig {
+                        shared_pause_state: None,
+                        segment_tx: segment_tx_for_audio,
+                        ..Default::default()
+                    },
+                    stream: live_stream,
                 },
             )
             .await
@@ -415,6 +457,7 @@ pub struct ActorBuilder {
     camera_feed: Option<Arc<crate::feeds::camera::CameraFeedLock>>,
     max_output_size: Option<u32>,
     max_fps: u32,
+    live_stream: Option<LiveStream>,
     #[cfg(target_os = "macos")]
     excluded_windows: Vec<scap_targets::WindowId>,
 }
@@ -429,6 +472,7 @@ impl ActorBuilder {
             camera_feed: None,
             max_output_size: None,
             max_fps: crate::defaults::DEFAULT_INSTANT_MODE_FPS,
+            live_stream: None,
             #[cfg(target_os = "macos")]
             excluded_windows: Vec::new(),
         }
@@ -462,6 +506,12 @@ impl ActorBuilder {
         self
     }
 
+    /// Also sends the display and the mixed audio to `live_stream`.
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
+    }
+
     #[cfg(target_os = "macos")]
     pub fn with_excluded_windows(mut self, excluded_windows: Vec<scap_targets::WindowId>) -> Self {
         self.excluded_windows = excluded_windows;
@@ -472,6 +522,12 @@ impl ActorBuilder {
         self,
         #[cfg(target_os = "macos")] shareable_content: Option<SendableShareableContent>,
     ) -> anyhow::Result<ActorHandle> {
+        if self.live_stream.is_some()
+            && matches!(self.capture_target, ScreenCaptureTarget::CameraOnly)
+        {
+            anyhow::bail!("Live streaming needs a display, window or area to capture");
+        }
+
         spawn_instant_recording_actor(
             self.output_path,
             RecordingBaseInputs {
@@ -486,6 +542,7 @@ impl ActorBuilder {
             },
             self.max_output_size,
             self.max_fps,
+            self.live_stream,
         )
         .await
     }
@@ -497,6 +554,7 @@ pub async fn spawn_instant_recording_actor(
     inputs: RecordingBaseInputs,
     max_output_size: Option<u32>,
     max_fps: u32,
+    live_stream: Option<LiveStream>,
 ) -> anyhow::Result<ActorHandle> {
     ensure_dir(&recording_dir)?;
 
@@ -664,6 +722,7 @@ pub async fn spawn_instant_recording_actor(
                 system_audio_source,
                 max_output_size,
                 timestamps,
+                live_stream.clone(),
             )
             .await?;
 
@@ -692,6 +751,7 @@ pub async fn spawn_instant_recording_actor(
         },
         total_pause_duration: std::time::Duration::ZERO,
         pause_started_at: None,
+        live_stream,
     });
 
     let actor_handle = ActorHandle {
@@ -170,3 +170,12 @@ impl RecordingHealth {
         )
     }
 }
+
+/// Progress of a recording that is still running.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RecordingStats {
+    /// Time recorded so far, not counting pauses.
+    pub elapsed: std::time::Duration,
+    /// Screen frames sent to the encoder so far.
+    pub video_frames: u64,
+}
@@ -326,12 +326,23 @@ pub(crate) fn spawn_blocking_thread_timeout_cleanup(
     rx
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
 pub enum PipelineHealthEvent {
     FrameDropRateHigh {
         source: String,
         rate_pct: f64,
     },
+    /// Frames a muxer dropped since its last report, sent every few seconds
+    /// while it is dropping any.
+    FramesDropped {
+        source: String,
+        count: u64,
+    },
     AudioGapDetected {
         gap_ms: u64,
     },
@@ -372,12 +383,78 @@ pub enum PipelineHealthEvent {
     CaptureTargetLost {
         target: String,
     },
+    /// A live stream connected, or reconnected, to its ingest.
+    StreamConnected {
+        target: String,
+    },
+    /// A live stream lost, or couldn't open, its connection and will try
+    /// again after `retry_in_ms`.
+    StreamDisconnected {
+        target: String,
+        reason: String,
+        retry_in_ms: u64,
+    },
+}
+
+impl std::fmt::Display for PipelineHealthEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FrameDropRateHigh { source, rate_pct } => {
+                write!(f, "High frame drop rate on {source}: {rate_pct:.0}%")
+            }
+            Self::FramesDropped { source, count } => {
+                write!(f, "{count} frames dropped on {source}")
+            }
+            Self::AudioGapDetected { gap_ms } => write!(f, "Audio gap detected: {gap_ms}ms"),
+            Self::AudioDegradedToVideoOnly { reason } => write!(f, "Audio lost: {reason}"),
+            Self::SourceRestarting => f.write_str("Capture source restarting"),
+            Self::SourceRestarted => f.write_str("Capture source restarted"),
+            Self::Stalled { source, waited_ms } => {
+                write!(f, "Pipeline stalled on {source} ({waited_ms}ms)")
+            }
+            Self::MuxerCrashed { reason } => write!(f, "Muxer crashed: {reason}"),
+            Self::DiskSpaceLow {
+                bytes_remaining, ..
+            } => write!(
+                f,
+                "Low disk space: {:.2} GB remaining",
+                *bytes_remaining as f64 / 1_073_741_824.0
+            ),
+            Self::DiskSpaceExhausted { bytes_remaining } => write!(
+                f,
+                "Disk full: {:.2} GB remaining",
+                *bytes_remaining as f64 / 1_073_741_824.0
+            ),
+            Self::DeviceLost { subsystem } => write!(f, "Graphics device lost: {subsystem}"),
+            Self::EncoderRebuilt { backend, attempt } => {
+                write!(f, "Encoder rebuilt: {backend} (attempt {attempt})")
+            }
+            Self::SourceAudioReset {
+                source,
+                starvation_ms,
+            } => write!(f, "Audio source reset: {source} ({starvation_ms}ms)"),
+            Self::RecoveryFragmentCorrupt { path, reason } => {
+                write!(f, "Corrupt recovery fragment skipped: {path} ({reason})")
+            }
+            Self::CaptureTargetLost { target } => write!(f, "Capture target lost: {target}"),
+            Self::StreamConnected { target } => write!(f, "Live stream connected to {target}"),
+            Self::StreamDisconnected {
+                target,
+                reason,
+                retry_in_ms,
+            } => write!(
+                f,
+                "Live stream to {target} disconnected: {reason} (retrying in {:.0}s)",
+                *retry_in_ms as f64 / 1000.0
+            ),
+        }
+    }
 }
 
 pub type HealthSender = tokio::sync::mpsc::Sender<PipelineHealthEvent>;
 pub type HealthReceiver = tokio::sync::mpsc::Receiver<PipelineHealthEvent>;
 
-fn new_health_channel() -> (HealthSender, HealthReceiver) {
+pub(crate) fn new_health_channel() -> (HealthSender, HealthReceiver) {
     tokio::sync::mpsc::channel(HEALTH_CHANNEL_CAPACITY)
 }
 
@@ -2143,6 +2220,7 @@ fn spawn_video_encoder<TMutex: VideoMuxer<VideoFrame = TVideo::Frame>, TVideo: V
                     }
 
                     frame_count += 1;
+                    frame_counter.store(frame_count, Ordering::Release);
 
                     let timestamp = frame.timestamp();
 
@@ -3196,6 +3274,11 @@ impl OutputPipeline {
         self.video_info
     }
 
+    /// Video frames sent to the muxer so far.
+    pub fn video_frame_count(&self) -> u64 {
+        self.video_frame_count.load(Ordering::Acquire)
+    }
+
     pub fn done_fut(&self) -> DoneFut {
         self.done_fut.clone()
     }
@@ -94,6 +94,12 @@ impl FrameDropTracker {
                     );
                 }
             }
+            if self.drops_in_window > 0 {
+                self.health_tx.emit(PipelineHealthEvent::FramesDropped {
+                    source: self.source.to_string(),
+                    count: u64::from(self.drops_in_window),
+                });
+            }
             self.drops_in_window = 0;
             self.frames_in_window = 0;
             self.last_check = std::time::Instant::now();
@@ -95,6 +95,17 @@ impl FrameDropTracker {
                     );
                 }
             }
+            if self.drops_in_window > 0
+                && let Some(tx) = &self.health_tx
+            {
+                emit_health(
+                    tx,
+                    PipelineHealthEvent::FramesDropped {
+                        source: self.source.to_string(),
+                        count: u64::from(self.drops_in_window),
+                    },
+                );
+            }
             self.drops_in_window = 0;
             self.frames_in_window = 0;
             self.last_check = std::time::Instant::now();
@@ -10,9 +10,9 @@ use cap_enc_ffmpeg::remux::{
     probe_video_can_decode, probe_video_seek_points, remux_file,
 };
 use cap_project::{
-    AudioMeta, Cursors, MultipleSegment, MultipleSegments, ProjectConfiguration, RecordingMeta,
-    RecordingMetaInner, StudioRecordingMeta, StudioRecordingStatus, TimelineConfiguration,
-    TimelineSegment, VideoMeta,
+    AudioMeta, Cursors, MultipleSegment, MultipleSegments, ProjectConfiguration, RecordingMarker,
+    RecordingMeta, RecordingMetaInner, StudioRecordingMeta, StudioRecordingStatus,
+    TimelineConfiguration, TimelineSegment, VideoMeta,
 };
 use relative_path::RelativePathBuf;
 use tracing::{debug, warn};
@@ -41,6 +41,9 @@ pub struct RecoverableSegment {
     pub camera_fragments: Option<Vec<PathBuf>>,
     pub camera_init_segment: Option<PathBuf>,
     pub mic_fragments: Option<Vec<PathBuf>>,
+    /// Fragments of `audio-input-2`, `audio-input-3`, ... in order, with
+    /// `None` for a track that left nothing behind.
+    pub additional_mic_fragments: Vec<Option<Vec<PathBuf>>>,
     pub system_audio_fragments: Option<Vec<PathBuf>>,
     pub cursor_path: Option<PathBuf>,
 }
@@ -275,6 +278,13 @@ impl RecoveryManager {
             };
 
             let mic_fragments = Self::find_audio_fragments(&segment_path.join("audio-input"));
+            let additional_mic_fragments = (0..additional_mic_track_count(&segment_path))
+                .map(|index| {
+                    Self::find_audio_fragments(
+                        &segment_path.join(format!("audio-input-{}", index + 2)),
+                    )
+                })
+                .collect();
             let system_audio_fragments =
                 Self::find_audio_fragments(&segment_path.join("system_audio"));
 
@@ -291,6 +301,7 @@ impl RecoveryManager {
                 camera_fragments,
                 camera_init_segment,
                 mic_fragments,
+                additional_mic_fragments,
                 system_audio_fragments,
                 cursor_path,
             });
@@ -988,6 +999,17 @@ impl RecoveryManager {
                 }
             }
 
+            for (index, mic_frags) in segment.additional_mic_fragments.iter().enumerate() {
+                let Some(mic_frags) = mic_frags else {
+                    continue;
+                };
+                Self::finalize_audio_fragments_to_ogg(
+                    mic_frags,
+                    &segment_dir,
+                    &format!("audio-input-{}", index + 2),
+                )?;
+            }
+
             if let Some(system_frags) = &segment.system_audio_fragments {
                 let system_output = segment_dir.join("system_audio.ogg");
                 if system_frags.len() == 1 {
@@ -1263,6 +1285,59 @@ impl RecoveryManager {
         Ok(())
     }
 
+    /// Leaves a track's fragments as a single `{name}.ogg` in `segment_dir`,
+    /// as is done for the first microphone.
+    fn finalize_audio_fragments_to_ogg(
+        fragments: &[PathBuf],
+        segment_dir: &Path,
+        name: &str,
+    ) -> Result<(), RecoveryError> {
+        let output = segment_dir.join(format!("{name}.ogg"));
+        let [source] = fragments else {
+            if fragments.is_empty() {
+                return Ok(());
+            }
+            finalization_info!(
+                "Concatenating {} {name} fragments to {:?}",
+                fragments.len(),
+                output
+            );
+            concatenate_audio_to_ogg(fragments, &output).map_err(RecoveryError::AudioConcat)?;
+            for fragment in fragments {
+                if let Err(e) = std::fs::remove_file(fragment) {
+                    debug!("Failed to remove {name} fragment {:?}: {e}", fragment);
+                }
+            }
+            Self::remove_audio_fragments_dir(segment_dir, name);
+            return Ok(());
+        };
+
+        if source == &output {
+            return Ok(());
+        }
+        if source.extension().is_some_and(|e| e == "ogg") {
+            finalization_info!("Moving single {name} fragment to {:?}", output);
+            std::fs::rename(source, &output)?;
+        } else {
+            finalization_info!("Transcoding single {name} fragment to {:?}", output);
+            concatenate_audio_to_ogg(fragments, &output).map_err(RecoveryError::AudioConcat)?;
+            if let Err(e) = std::fs::remove_file(source) {
+                debug!("Failed to remove {name} source {:?}: {e}", source);
+            }
+        }
+        Self::remove_audio_fragments_dir(segment_dir, name);
+        Ok(())
+    }
+
+    fn remove_audio_fragments_dir(segment_dir: &Path, name: &str) {
+        let dir = segment_dir.join(name);
+        if dir.exists()
+            && let Err(e) = std::fs::remove_dir_all(&dir)
+        {
+            debug!("Failed to clean up {name} dir {:?}: {e}", dir);
+        }
+    }
+
     fn validate_required_video(path: &Path, label: &str) -> Result<(), RecoveryError> {
         finalization_info!("Validating finalized {} video: {:?}", label, path);
 
@@ -1404,6 +1479,29 @@ impl RecoveryManager {
                             None
                         }
                     },
+                    additional_mics: (0..seg.additional_mic_fragments.len())
+                        .map(|index| {
+                            let name = format!("audio-input-{}.ogg", index + 2);
+                            let size = std::fs::metadata(segment_dir.join(&name))
+                                .map(|m| m.len())
+                                .unwrap_or(0);
+                            const MIN_VALID_AUDIO_SIZE: u64 = 500;
+                            if size <= MIN_VALID_AUDIO_SIZE {
+                                return None;
+                            }
+                            let original = original_segment
+                                .and_then(|s| s.additional_mics.get(index))
+                                .and_then(Option::as_ref);
+                            Some(AudioMeta {
+                                path: RelativePathBuf::from(format!("{segment_base}/{name}")),
+                                start_time: get_start_time_or_fallback(
+                                    original.and_then(|m| m.start_time),
+                                ),
+                                device_id: original.and_then(|m| m.device_id.clone()),
+                                gap_summary: None,
+                            })
+                        })
+                        .collect(),
                     system_audio: {
                         let file_size = std::fs::metadata(&system_audio_path)
                             .map(|m| m.len())
@@ -1449,11 +1547,31 @@ impl RecoveryManager {
 
         let existing_cursors = Self::load_existing_cursors(&recording.project_path);
 
+        // Keep markers on the segments that survived, renumbered to match.
+        let markers = recording
+            .meta
+            .studio_meta()
+            .map(|meta| meta.markers())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|marker| {
+                let position = recording
+                    .recoverable_segments
+                    .iter()
+                    .position(|seg| seg.index == marker.segment)?;
+                Some(RecordingMarker {
+                    segment: position as u32,
+                    ..marker.clone()
+                })
+            })
+            .collect();
+
         Ok(StudioRecordingMeta::MultipleSegments {
             inner: MultipleSegments {
                 segments,
                 cursors: existing_cursors,
                 status: Some(StudioRecordingStatus::Complete),
+                markers,
             },
         })
     }
@@ -1518,6 +1636,7 @@ impl RecoveryManager {
             caption_segments: Vec::new(),
             keyboard_segments: Vec::new(),
             audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
         });
 
         config
@@ -1649,6 +1768,26 @@ impl RecoveryManager {
     }
 }
 
+/// How many additional mic slots a segment directory has room for: the
+/// highest `audio-input-N` entry, file or fragment directory, less the main
+/// mic. Slots below it may be missing when a mic failed to record.
+fn additional_mic_track_count(segment_path: &Path) -> usize {
+    let Ok(entries) = std::fs::read_dir(segment_path) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let name = path.file_stem()?.to_str()?;
+            name.strip_prefix("audio-input-")?.parse::<usize>().ok()
+        })
+        .filter(|track| *track >= 2)
+        .max()
+        .map_or(0, |track| track - 1)
+}
+
 fn start_time_or_display_fallback(
     original_time: Option<f64>,
     display_start_time: Option<f64>,
@@ -1666,7 +1805,7 @@ fn replace_file(src: &Path, dst: &Path) -> Result<(), RecoveryError> {
 
 #[cfg(test)]
 mod tests {
-    use super::{replace_file, start_time_or_display_fallback};
+    use super::{additional_mic_track_count, replace_file, start_time_or_display_fallback};
     use std::fs;
     use tempfile::tempdir;
 
@@ -1685,6 +1824,19 @@ mod tests {
         assert!(!src.exists());
     }
 
+    #[test]
+    fn additional_mic_slots_extend_past_a_missing_track() {
+        let dir = tempdir().unwrap();
+        assert_eq!(additional_mic_track_count(dir.path()), 0);
+
+        fs::write(dir.path().join("audio-input.ogg"), b"").unwrap();
+        fs::write(dir.path().join("audio-input-3.ogg"), b"").unwrap();
+        assert_eq!(additional_mic_track_count(dir.path()), 2);
+
+        fs::create_dir(dir.path().join("audio-input-5")).unwrap();
+        assert_eq!(additional_mic_track_count(dir.path()), 4);
+    }
+
     #[test]
     fn start_time_fallback_prefers_original_value() {
         let original = Some(0.8);
@@ -1229,6 +1229,10 @@ fn capture_screenshot_x11_blocking(target: &ScreenCaptureTarget) -> anyhow::Resu
         height,
         fps: 1,
         show_cursor: false,
+        window: target
+            .window()
+            .and_then(|id| scap_targets::Window::from_id(&id))
+            .and_then(|window| window.raw_handle().x11_window()),
     };
     let mut grabber = X11Grabber::new(&config)?;
     let frame = grabber.grab()?;
@@ -26,7 +26,7 @@ use std::{
 };
 use tokio_util::sync::CancellationToken;
 use x11rb::connection::Connection as _;
-use x11rb::protocol::xproto::{ConnectionExt as _, ImageFormat, ImageOrder};
+use x11rb::protocol::xproto::{ConnectionExt as _, ImageFormat, ImageOrder, Rectangle};
 use x11rb::rust_connection::RustConnection;
 
 #[derive(Debug)]
@@ -67,6 +67,9 @@ pub(crate) struct X11InputConfig {
     pub height: u32,
     pub fps: u32,
     pub show_cursor: bool,
+    /// Captured through XComposite and scaled into the region's size, falling
+    /// back to the region when that isn't possible.
+    pub window: Option<u32>,
 }
 
 struct WaylandInputConfig {
@@ -160,6 +163,10 @@ impl ScreenCaptureConfig<X11Capture> {
                     height,
                     fps: self.config.fps,
                     show_cursor: self.config.show_cursor,
+                    window: match self.config.linux_source {
+                        LinuxCaptureSource::Window { x11_window } => x11_window,
+                        LinuxCaptureSource::Display | LinuxCaptureSource::Area => None,
+                    },
                 }),
             },
             system_audio,
@@ -303,7 +310,7 @@ async fn create_wayland_source_config(
     let portal = open_wayland_portal(config.config.linux_source, config.config.show_cursor).await?;
     let crop_bounds = match config.config.linux_source {
         LinuxCaptureSource::Area => config.config.crop_bounds,
-        LinuxCaptureSource::Display | LinuxCaptureSource::Window => None,
+        LinuxCaptureSource::Display | LinuxCaptureSource::Window { .. } => None,
     };
     let video_info = wayland_video_info(&portal.stream, config.video_info, crop_bounds);
 
@@ -386,7 +393,7 @@ fn prefers_wayland_portal() -> bool {
 
 fn wayland_source_type(source: LinuxCaptureSource) -> ashpd::enumflags2::BitFlags<SourceType> {
     match source {
-        LinuxCaptureSource::Window => SourceType::Window.into(),
+        LinuxCaptureSource::Window { .. } => SourceType::Window.into(),
         LinuxCaptureSource::Display | LinuxCaptureSource::Area => SourceType::Monitor.into(),
     }
 }
@@ -1181,6 +1188,7 @@ fn capture_x11(
     tracing::info!(
         sent,
         dropped,
+        reused = grabber.reused_frames(),
         elapsed_ms = started.elapsed().as_millis() as u64,
         "Linux X11 capture stopped"
     );
@@ -1205,8 +1213,19 @@ pub(crate) struct X11Grabber {
     output: VideoInfo,
     scaler: Option<FrameScaler>,
     show_cursor: bool,
+    window: Option<X11CompositeWindow>,
+    shm: Option<X11ShmSegment>,
+    damage: Option<X11Damage>,
+    /// The last captured image without the cursor, and when it was captured,
+    /// reused while XDamage reports nothing redrawn in the region.
+    last_frame: Option<(ffmpeg::frame::Video, Instant)>,
+    reused_frames: u64,
 }
 
+/// Longest an image is reused for while XDamage reports no changes, in case
+/// the server misses some (e.g. drawing done straight to the framebuffer).
+const X11_DAMAGE_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
 impl X11Grabber {
     pub(crate) fn new(config: &X11InputConfig) -> anyhow::Result<Self> {
         ffmpeg::init().context("initialize FFmpeg")?;
@@ -1220,30 +1239,7 @@ impl X11Grabber {
             .get(screen_num)
             .ok_or_else(|| anyhow!("X11 screen {screen_num} not found"))?;
         let root = screen.root;
-        let root_depth = screen.root_depth;
-        let root_visual_id = screen.root_visual;
-
-        let visual = screen
-            .allowed_depths
-            .iter()
-            .flat_map(|depth| depth.visuals.iter())
-            .find(|visual| visual.visual_id == root_visual_id)
-            .ok_or_else(|| anyhow!("X11 root visual {root_visual_id} not found"))?;
-
-        let bits_per_pixel = setup
-            .pixmap_formats
-            .iter()
-            .find(|format| format.depth == root_depth)
-            .map(|format| format.bits_per_pixel)
-            .ok_or_else(|| anyhow!("X11 pixmap format for depth {root_depth} not found"))?;
-
-        let source_pixel = x11_source_pixel(
-            setup.image_byte_order == ImageOrder::MSB_FIRST,
-            bits_per_pixel,
-            visual.red_mask,
-            visual.green_mask,
-            visual.blue_mask,
-        )?;
+        let source_pixel = x11_visual_pixel(setup, screen, screen.root_depth, screen.root_visual)?;
 
         let x = i16::try_from(config.x)
             .map_err(|_| anyhow!("X11 capture x offset {} out of range", config.x))?;
@@ -1274,6 +1270,32 @@ impl X11Grabber {
             config.fps.max(1),
         );
 
+        // Without XComposite a window is recorded as the area it covers on
+        // screen, which is what it looked like when the recording started.
+        let window = config.window.and_then(|window| {
+            X11CompositeWindow::redirect(&conn, screen_num, window)
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        window,
+                        error = %error,
+                        "X11 window capture unavailable; recording its screen area"
+                    );
+                })
+                .ok()
+        });
+
+        // Both extensions are optimisations: without MIT-SHM (e.g. on a remote
+        // display) every image comes back over the socket, and without XDamage
+        // every frame is captured.
+        let shm = X11ShmSegment::attach(&conn, usize::from(width) * usize::from(height) * 4);
+        let damage = X11Damage::create(&conn, window.as_ref().map_or(root, |window| window.id));
+        tracing::info!(
+            window = window.is_some(),
+            shm = shm.is_some(),
+            damage = damage.is_some(),
+            "X11 capture initialized"
+        );
+
         Ok(Self {
             conn,
             root,
@@ -1285,62 +1307,99 @@ impl X11Grabber {
             output,
             scaler: None,
             show_cursor,
+            window,
+            shm,
+            damage,
+            last_frame: None,
+            reused_frames: 0,
         })
     }
 
-    /// Capture one frame of the configured region as a BGRZ video frame.
+    /// Frames that repeated the previous image because nothing in the region
+    /// had been redrawn.
+    pub(crate) fn reused_frames(&self) -> u64 {
+        self.reused_frames
+    }
+
+    /// Capture one frame of the configured region, or of the window scaled to
+    /// fit it, as a BGRZ video frame.
     pub(crate) fn grab(&mut self) -> anyhow::Result<ffmpeg::frame::Video> {
-        let reply = self
-            .conn
-            .get_image(
-                ImageFormat::Z_PIXMAP,
-                self.root,
-                self.x,
-                self.y,
-                self.width,
-                self.height,
-                u32::MAX,
-            )
-            .context("request X11 image")?
-            .reply()
-            .context("read X11 image")?;
+        let mut resized = false;
+        let region = match &mut self.window {
+            Some(window) => {
+                let geometry = self
+                    .conn
+                    .get_geometry(window.id)
+                    .context("request X11 window geometry")?
+                    .reply()
+                    .context("read X11 window geometry")?;
+                resized = (geometry.width, geometry.height) != window.size;
+                window.size = (geometry.width, geometry.height);
+                Rectangle {
+                    x: 0,
+                    y: 0,
+                    width: geometry.width,
+                    height: geometry.height,
+                }
+            }
+            None => Rectangle {
+                x: self.x,
+                y: self.y,
+                width: self.width,
+                height: self.height,
+            },
+        };
+        let changed = match &self.damage {
+            Some(damage) => damage.take_changed(&self.conn, region)? || resized,
+            None => true,
+        };
 
-        let width = usize::from(self.width);
-        let height = usize::from(self.height);
-        let row_bytes = width * 4;
-        let source_stride = reply
-            .data
-            .len()
-            .checked_div(height)
-            .filter(|stride| *stride >= row_bytes)
-            .ok_or_else(|| {
-                anyhow!(
-                    "X11 image too small: {} bytes for {}x{}",
-                    reply.data.len(),
-                    width,
-                    height
-                )
-            })?;
-
-        let mut source = ffmpeg::frame::Video::new(
-            self.source_pixel,
-            u32::from(self.width),
-            u32::from(self.height),
-        );
-        let dst_stride = source.stride(0);
-        let copy = row_bytes.min(dst_stride);
-        for row in 0..height {
-            let src_start = row * source_stride;
-            let dst_start = row * dst_stride;
-            source.data_mut(0)[dst_start..dst_start + copy]
-                .copy_from_slice(&reply.data[src_start..src_start + copy]);
+        let mut frame = match &self.last_frame {
+            Some((frame, captured_at))
+                if !changed && captured_at.elapsed() < X11_DAMAGE_REFRESH_INTERVAL =>
+            {
+                self.reused_frames += 1;
+                frame.clone()
+            }
+            _ => match self.capture_image(region) {
+                Ok(frame) => {
+                    if self.damage.is_some() || self.window.is_some() {
+                        self.last_frame = Some((frame.clone(), Instant::now()));
+                    }
+                    frame
+                }
+                // An unmapped (e.g. minimised) window has no contents to read,
+                // so the recording holds its last image until it comes back.
+                Err(error) if self.window.is_some() && self.last_frame.is_some() => {
+                    tracing::trace!(error = %error, "X11 window capture skipped");
+                    self.reused_frames += 1;
+                    self.last_frame.as_ref().expect("last frame").0.clone()
+                }
+                Err(error) => return Err(error),
+            },
+        };
+
+        if self.show_cursor {
+            if let Err(error) = self.composite_cursor(&mut frame) {
+                tracing::trace!(error = %error, "X11 cursor composite skipped");
+            }
         }
 
-        // Convert to BGRZ only when the server's visual differs; the common
-        // case (32-bit little-endian BGRX) is already BGRZ and short-circuits.
-        let mut frame = if self.source_pixel == self.output.pixel_format {
-            source
-        } else {
+        Ok(frame)
+    }
+
+    /// Read `region`'s pixels from the server and convert them to the output.
+    fn capture_image(&mut self, region: Rectangle) -> anyhow::Result<ffmpeg::frame::Video> {
+        use x11rb::protocol::composite::ConnectionExt as _;
+
+        let Some(window) = &self.window else {
+            let source = self.read_image(self.root, region, self.source_pixel)?;
+
+            // Convert to BGRZ only when the server's visual differs; the common
+            // case (32-bit little-endian BGRX) is already BGRZ and short-circuits.
+            if self.source_pixel == self.output.pixel_format {
+                return Ok(source);
+            }
             if self.scaler.is_none() {
                 self.scaler = Some(FrameScaler::new(
                     self.source_pixel,
@@ -1349,19 +1408,104 @@ impl X11Grabber {
                     self.output,
                 )?);
             }
-            self.scaler
+            return self
+                .scaler
                 .as_mut()
                 .expect("scaler initialized")
-                .scale(&source, self.output)?
+                .scale(&source, self.output);
         };
 
-        if self.show_cursor {
-            if let Err(error) = self.composite_cursor(&mut frame) {
-                tracing::trace!(error = %error, "X11 cursor composite skipped");
+        // The named pixmap holds the window's contents wherever it is and
+        // whatever covers it. The server replaces it whenever the window is
+        // resized or remapped, so a fresh one is named for every capture.
+        let (id, pixel) = (window.id, window.source_pixel);
+        let pixmap = self.conn.generate_id().context("allocate X11 pixmap id")?;
+        self.conn
+            .composite_name_window_pixmap(id, pixmap)
+            .context("request X11 window pixmap")?
+            .check()
+            .context("name X11 window pixmap")?;
+        let source = self.read_image(pixmap, region, pixel);
+        self.conn
+            .free_pixmap(pixmap)
+            .context("free X11 window pixmap")?;
+
+        let output = self.output;
+        self.window
+            .as_mut()
+            .expect("window capture")
+            .fit(&source?, output)
+    }
+
+    /// Read a Z-pixmap image of `region` of `drawable`, through shared memory
+    /// when it is attached.
+    fn read_image(
+        &mut self,
+        drawable: x11rb::protocol::xproto::Drawable,
+        region: Rectangle,
+        pixel: ffmpeg::format::Pixel,
+    ) -> anyhow::Result<ffmpeg::frame::Video> {
+        // A window can grow past the segment that was big enough so far.
+        let size = usize::from(region.width) * usize::from(region.height) * 4;
+        if let Some(shm) = self.shm.take_if(|shm| shm.size < size) {
+            shm.detach(&self.conn);
+            self.shm = X11ShmSegment::attach(&self.conn, size);
+        }
+
+        if let Some(shm) = &self.shm {
+            match shm.get_image(&self.conn, drawable, region) {
+                Ok(data) => return x11_source_frame(data, pixel, region.width, region.height),
+                Err(error) => {
+                    tracing::trace!(error = %error, "X11 shared-memory capture failed");
+                }
             }
         }
 
-        Ok(frame)
+        let reply = self
+            .conn
+            .get_image(
+                ImageFormat::Z_PIXMAP,
+                drawable,
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                u32::MAX,
+            )
+            .context("request X11 image")?
+            .reply()
+            .context("read X11 image")?;
+        x11_source_frame(&reply.data, pixel, region.width, region.height)
+    }
+
+    /// Where the captured area's top-left corner is on the root window, and
+    /// how the output is placed over it: `(origin_x, origin_y, scale,
+    /// offset_x, offset_y)`.
+    fn placement(&self) -> anyhow::Result<(i32, i32, f64, i32, i32)> {
+        let Some(window) = &self.window else {
+            return Ok((i32::from(self.x), i32::from(self.y), 1.0, 0, 0));
+        };
+
+        let origin = self
+            .conn
+            .translate_coordinates(window.id, self.root, 0, 0)
+            .context("request X11 window position")?
+            .reply()
+            .context("read X11 window position")?;
+        let (source_width, source_height) = window.size;
+        let (x, y, width, _) = letterbox_rect(
+            u32::from(source_width),
+            u32::from(source_height),
+            self.output.width,
+            self.output.height,
+        );
+        Ok((
+            i32::from(origin.dst_x),
+            i32::from(origin.dst_y),
+            f64::from(width) / f64::from(source_width.max(1)),
+            x as i32,
+            y as i32,
+        ))
     }
 
     /// Alpha-blend the X11 cursor onto a BGRZ frame (mirrors x11grab's
@@ -1386,9 +1530,14 @@ impl X11Grabber {
             return Ok(());
         }
 
-        // Top-left of the cursor image in capture-region coordinates.
-        let origin_x = i32::from(cursor.x) - i32::from(cursor.xhot) - i32::from(self.x);
-        let origin_y = i32::from(cursor.y) - i32::from(cursor.yhot) - i32::from(self.y);
+        // Top-left of the cursor image in frame coordinates. Only its position
+        // follows a scaled window; the cursor is drawn at its own size.
+        let (region_x, region_y, scale, offset_x, offset_y) = self.placement()?;
+        let to_frame = |root: i16, region: i32, offset: i32| {
+            offset + (f64::from(i32::from(root) - region) * scale).round() as i32
+        };
+        let origin_x = to_frame(cursor.x, region_x, offset_x) - i32::from(cursor.xhot);
+        let origin_y = to_frame(cursor.y, region_y, offset_y) - i32::from(cursor.yhot);
 
         let frame_width = i32::from(self.width);
         let frame_height = i32::from(self.height);
@@ -1425,6 +1574,363 @@ impl X11Grabber {
     }
 }
 
+/// A window redirected with XComposite, so its contents are kept off screen and
+/// can be read whether or not it is covered, moved or partly off the display.
+struct X11CompositeWindow {
+    id: x11rb::protocol::xproto::Window,
+    source_pixel: ffmpeg::format::Pixel,
+    /// The window's size when it was last captured.
+    size: (u16, u16),
+    scaler: Option<FrameScaler>,
+}
+
+impl X11CompositeWindow {
+    fn redirect(
+        conn: &RustConnection,
+        screen_num: usize,
+        id: x11rb::protocol::xproto::Window,
+    ) -> anyhow::Result<Self> {
+        use x11rb::protocol::composite::{ConnectionExt as _, Redirect};
+
+        // NameWindowPixmap needs Composite 0.2.
+        let version = conn
+            .composite_query_version(0, 4)
+            .context("request XComposite version")?
+            .reply()
+            .context("XComposite unavailable")?;
+        if (version.major_version, version.minor_version) < (0, 2) {
+            bail!(
+                "XComposite {}.{} is too old",
+                version.major_version,
+                version.minor_version
+            );
+        }
+
+        let attributes = conn
+            .get_window_attributes(id)
+            .context("request X11 window attributes")?
+            .reply()
+            .context("read X11 window attributes")?;
+        let geometry = conn
+            .get_geometry(id)
+            .context("request X11 window geometry")?
+            .reply()
+            .context("read X11 window geometry")?;
+        let setup = conn.setup();
+        let screen = setup
+            .roots
+            .get(screen_num)
+            .ok_or_else(|| anyhow!("X11 screen {screen_num} not found"))?;
+        let source_pixel = x11_visual_pixel(setup, screen, geometry.depth, attributes.visual)?;
+
+        // Automatic redirection leaves the window drawn on screen as before;
+        // it is undone when the connection closes.
+        conn.composite_redirect_window(id, Redirect::AUTOMATIC)
+            .context("request XComposite redirect")?
+            .check()
+            .context("redirect X11 window")?;
+
+        Ok(Self {
+            id,
+            source_pixel,
+            size: (0, 0),
+            scaler: None,
+        })
+    }
+
+    /// Scale the window's image to fit `output`, letterboxed in black when
+    /// the window's shape no longer matches it.
+    fn fit(
+        &mut self,
+        source: &ffmpeg::frame::Video,
+        output: VideoInfo,
+    ) -> anyhow::Result<ffmpeg::frame::Video> {
+        let (x, y, width, height) =
+            letterbox_rect(source.width(), source.height(), output.width, output.height);
+        let fitted = VideoInfo {
+            width,
+            height,
+            ..output
+        };
+        if self.scaler.is_none() {
+            self.scaler = Some(FrameScaler::new(
+                source.format(),
+                source.width(),
+                source.height(),
+                fitted,
+            )?);
+        }
+        let scaled = self
+            .scaler
+            .as_mut()
+            .expect("scaler initialized")
+            .scale(source, fitted)?;
+        if (width, height) == (output.width, output.height) {
+            return Ok(scaled);
+        }
+
+        let mut frame = ffmpeg::frame::Video::new(output.pixel_format, output.width, output.height);
+        frame.data_mut(0).fill(0);
+        let (src_stride, dst_stride) = (scaled.stride(0), frame.stride(0));
+        let row_bytes = width as usize * 4;
+        for row in 0..height as usize {
+            let src_start = row * src_stride;
+            let dst_start = (y as usize + row) * dst_stride + x as usize * 4;
+            frame.data_mut(0)[dst_start..dst_start + row_bytes]
+                .copy_from_slice(&scaled.data(0)[src_start..src_start + row_bytes]);
+        }
+        Ok(frame)
+    }
+}
+
+/// The largest even-sized `(x, y, width, height)` inside a `output_width` x
+/// `output_height` frame with a `source_width` x `source_height` image's
+/// aspect ratio, centred.
+fn letterbox_rect(
+    source_width: u32,
+    source_height: u32,
+    output_width: u32,
+    output_height: u32,
+) -> (u32, u32, u32, u32) {
+    let scale = (f64::from(output_width) / f64::from(source_width.max(1)))
+        .min(f64::from(output_height) / f64::from(source_height.max(1)));
+    let fit = |source: u32, output: u32| {
+        ensure_even((f64::from(source) * scale).round() as u32).clamp(2.min(output), output)
+    };
+    let (width, height) = (
+        fit(source_width, output_width),
+        fit(source_height, output_height),
+    );
+    (
+        (output_width - width) / 2,
+        (output_height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// The FFmpeg pixel format of images drawn with `visual_id` at `depth`.
+fn x11_visual_pixel(
+    setup: &x11rb::protocol::xproto::Setup,
+    screen: &x11rb::protocol::xproto::Screen,
+    depth: u8,
+    visual_id: x11rb::protocol::xproto::Visualid,
+) -> anyhow::Result<ffmpeg::format::Pixel> {
+    let visual = screen
+        .allowed_depths
+        .iter()
+        .flat_map(|depth| depth.visuals.iter())
+        .find(|visual| visual.visual_id == visual_id)
+        .ok_or_else(|| anyhow!("X11 visual {visual_id} not found"))?;
+
+    let bits_per_pixel = setup
+        .pixmap_formats
+        .iter()
+        .find(|format| format.depth == depth)
+        .map(|format| format.bits_per_pixel)
+        .ok_or_else(|| anyhow!("X11 pixmap format for depth {depth} not found"))?;
+
+    x11_source_pixel(
+        setup.image_byte_order == ImageOrder::MSB_FIRST,
+        bits_per_pixel,
+        visual.red_mask,
+        visual.green_mask,
+        visual.blue_mask,
+    )
+}
+
+/// Copy a Z-pixmap image of `width`x`height` into a frame of `pixel`, dropping
+/// any row padding the server added.
+fn x11_source_frame(
+    data: &[u8],
+    pixel: ffmpeg::format::Pixel,
+    width: u16,
+    height: u16,
+) -> anyhow::Result<ffmpeg::frame::Video> {
+    let rows = usize::from(height);
+    let row_bytes = usize::from(width) * 4;
+    let source_stride = data
+        .len()
+        .checked_div(rows)
+        .filter(|stride| *stride >= row_bytes)
+        .ok_or_else(|| {
+            anyhow!(
+                "X11 image too small: {} bytes for {}x{}",
+                data.len(),
+                width,
+                height
+            )
+        })?;
+
+    let mut frame = ffmpeg::frame::Video::new(pixel, u32::from(width), u32::from(height));
+    let dst_stride = frame.stride(0);
+    let copy = row_bytes.min(dst_stride);
+    for row in 0..rows {
+        let src_start = row * source_stride;
+        let dst_start = row * dst_stride;
+        frame.data_mut(0)[dst_start..dst_start + copy]
+            .copy_from_slice(&data[src_start..src_start + copy]);
+    }
+    Ok(frame)
+}
+
+/// A System V shared-memory segment attached to the X server with MIT-SHM, so
+/// captured images are written straight into our memory instead of being sent
+/// over the X11 socket.
+struct X11ShmSegment {
+    seg: x11rb::protocol::shm::Seg,
+    addr: *mut libc::c_void,
+    size: usize,
+}
+
+// SAFETY: the mapping is owned by the segment and only read through `&self`
+// after the server has finished writing a reply.
+unsafe impl Send for X11ShmSegment {}
+
+impl X11ShmSegment {
+    /// `None` when the server can't share memory with us: the extension is
+    /// missing, or the display is on another machine.
+    fn attach(conn: &RustConnection, size: usize) -> Option<Self> {
+        use x11rb::protocol::shm::ConnectionExt as _;
+
+        conn.shm_query_version().ok()?.reply().ok()?;
+
+        let id = unsafe { libc::shmget(libc::IPC_PRIVATE, size, libc::IPC_CREAT | 0o600) };
+        if id < 0 {
+            return None;
+        }
+        let addr = unsafe { libc::shmat(id, std::ptr::null(), 0) };
+        if addr as isize == -1 {
+            unsafe { libc::shmctl(id, libc::IPC_RMID, std::ptr::null_mut()) };
+            return None;
+        }
+
+        let seg = conn.generate_id().ok().and_then(|seg| {
+            conn.shm_attach(seg, id as u32, false).ok()?.check().ok()?;
+            Some(seg)
+        });
+        // Marked for removal now, so the segment is freed once both we and the
+        // server have detached, even if we exit without cleaning up.
+        unsafe { libc::shmctl(id, libc::IPC_RMID, std::ptr::null_mut()) };
+
+        match seg {
+            Some(seg) => Some(Self { seg, addr, size }),
+            None => {
+                unsafe { libc::shmdt(addr) };
+                None
+            }
+        }
+    }
+
+    /// Capture a Z-pixmap image of `region` into the segment and return it.
+    fn get_image(
+        &self,
+        conn: &RustConnection,
+        drawable: x11rb::protocol::xproto::Drawable,
+        region: Rectangle,
+    ) -> anyhow::Result<&[u8]> {
+        use x11rb::protocol::shm::ConnectionExt as _;
+
+        let reply = conn
+            .shm_get_image(
+                drawable,
+                region.x,
+                region.y,
+                region.width,
+                region.height,
+                u32::MAX,
+                ImageFormat::Z_PIXMAP.into(),
+                self.seg,
+                0,
+            )
+            .context("request X11 shared-memory image")?
+            .reply()
+            .context("read X11 shared-memory image")?;
+
+        let len = (reply.size as usize).min(self.size);
+        Ok(unsafe { std::slice::from_raw_parts(self.addr as *const u8, len) })
+    }
+    /// Detach the server's side before dropping a segment the connection
+    /// outlives.
+    fn detach(self, conn: &RustConnection) {
+        use x11rb::protocol::shm::ConnectionExt as _;
+
+        let _ = conn.shm_detach(self.seg);
+    }
+}
+
+impl Drop for X11ShmSegment {
+    fn drop(&mut self) {
+        // Otherwise the server detaches its side when the connection closes.
+        unsafe { libc::shmdt(self.addr) };
+    }
+}
+
+/// XDamage tracking of the captured drawable, used to tell whether anything in
+/// the capture region has been redrawn since the last frame.
+struct X11Damage {
+    damage: x11rb::protocol::damage::Damage,
+}
+
+impl X11Damage {
+    fn create(conn: &RustConnection, drawable: x11rb::protocol::xproto::Drawable) -> Option<Self> {
+        use x11rb::protocol::damage::{ConnectionExt as _, ReportLevel};
+        use x11rb::protocol::xfixes::ConnectionExt as _;
+
+        // Damage is built on XFixes, which has to be initialised first.
+        conn.xfixes_query_version(5, 0).ok()?.reply().ok()?;
+        conn.damage_query_version(1, 1).ok()?.reply().ok()?;
+
+        let damage = conn.generate_id().ok()?;
+        conn.damage_create(damage, drawable, ReportLevel::BOUNDING_BOX)
+            .ok()?
+            .check()
+            .ok()?;
+        Some(Self { damage })
+    }
+
+    /// Whether `region` has been redrawn since the last call. Call it before
+    /// capturing, so anything drawn during the capture is reported next time.
+    fn take_changed(&self, conn: &RustConnection, region: Rectangle) -> anyhow::Result<bool> {
+        use x11rb::protocol::Event;
+        use x11rb::protocol::damage::ConnectionExt as _;
+
+        let mut notified = false;
+        let mut changed = false;
+        while let Some(event) = conn.poll_for_event().context("read X11 events")? {
+            if let Event::DamageNotify(notify) = event
+                && notify.damage == self.damage
+            {
+                notified = true;
+                changed |= rectangles_intersect(notify.area, region);
+            }
+        }
+
+        // A bounding-box report only comes when the damaged area grows, so the
+        // damage is cleared on every report, even outside the region.
+        if notified {
+            conn.damage_subtract(self.damage, x11rb::NONE, x11rb::NONE)
+                .context("clear X11 damage")?;
+            conn.flush().context("flush X11 connection")?;
+        }
+
+        Ok(changed)
+    }
+}
+
+fn rectangles_intersect(a: Rectangle, b: Rectangle) -> bool {
+    let (a_x, a_y, b_x, b_y) = (
+        i32::from(a.x),
+        i32::from(a.y),
+        i32::from(b.x),
+        i32::from(b.y),
+    );
+    a_x < b_x + i32::from(b.width)
+        && b_x < a_x + i32::from(a.width)
+        && a_y < b_y + i32::from(b.height)
+        && b_y < a_y + i32::from(a.height)
+}
+
 /// Map an X11 32-bit TrueColor visual (byte order + RGB masks) to the matching
 /// packed FFmpeg pixel format. The overwhelmingly common desktop case
 /// (depth 24/32, little-endian, BGRX) resolves to BGRZ.
@@ -1469,3 +1975,16 @@ fn x11_source_pixel(
         _ => bail!("Unsupported X11 channel order: b={blue} g={green} r={red}"),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn letterbox_rect_fits_and_centres_the_window() {
+        assert_eq!(letterbox_rect(1280, 720, 1280, 720), (0, 0, 1280, 720));
+        assert_eq!(letterbox_rect(640, 360, 1280, 720), (0, 0, 1280, 720));
+        assert_eq!(letterbox_rect(1280, 1440, 1280, 720), (320, 0, 640, 720));
+        assert_eq!(letterbox_rect(1600, 600, 800, 600), (0, 150, 800, 300));
+    }
+}
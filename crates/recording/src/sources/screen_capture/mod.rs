@@ -76,7 +76,11 @@ pub enum ScreenCaptureTarget {
 #[derive(Clone, Copy, Debug)]
 pub enum LinuxCaptureSource {
     Display,
-    Window,
+    Window {
+        /// Captured through XComposite when set, so the recording follows the
+        /// window rather than the area it started in.
+        x11_window: Option<u32>,
+    },
     Area,
 }
 
@@ -84,7 +88,9 @@ pub enum LinuxCaptureSource {
 impl LinuxCaptureSource {
     pub fn from_target(target: &ScreenCaptureTarget) -> Self {
         match target {
-            ScreenCaptureTarget::Window { .. } => Self::Window,
+            ScreenCaptureTarget::Window { id } => Self::Window {
+                x11_window: Window::from_id(id).and_then(|window| window.raw_handle().x11_window()),
+            },
             ScreenCaptureTarget::Area { .. } => Self::Area,
             ScreenCaptureTarget::Display { .. } | ScreenCaptureTarget::CameraOnly => Self::Display,
         }
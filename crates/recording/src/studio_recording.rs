@@ -7,7 +7,7 @@ use crate::output_pipeline::{
 };
 use crate::{
     ActorError, H264_MAX_DIMENSION, MediaError, RecordingBaseInputs, RecordingError,
-    SharedPauseState, calculate_gpu_compatible_size,
+    RecordingStats, SharedPauseState, calculate_gpu_compatible_size,
     capture_pipeline::{
         MakeCapturePipeline, ScreenCaptureMethod, Stop, target_to_display_and_crop,
     },
@@ -15,8 +15,9 @@ use crate::{
     feeds::{camera::CameraFeedLock, microphone::MicrophoneFeedLock},
     ffmpeg::{FragmentedAudioMuxer, FragmentedAudioMuxerConfig, OggMuxer},
     output_pipeline::{
-        AudioAnchor, AudioGapSummary, DoneFut, FinishedOutputPipeline, OutputPipeline,
-        PipelineDoneError,
+        AudioAnchor, AudioGapSummary, DoneFut, FinishedOutputPipeline, HealthReceiver,
+        HealthSender, LiveStream, OutputPipeline, PipelineDoneError, StreamTeeMuxer,
+        StreamTeeMuxerConfig, emit_health, new_health_channel,
     },
     screen_capture::ScreenCaptureConfig,
     sources::{self, screen_capture},
@@ -88,6 +89,7 @@ pub struct ActorHandle {
     actor_ref: kameo::actor::ActorRef<Actor>,
     pub capture_target: screen_capture::ScreenCaptureTarget,
     done_fut: DoneFut,
+    health_rx: Arc<std::sync::Mutex<Option<HealthReceiver>>>,
     // pub bounds: Bounds,
 }
 
@@ -97,9 +99,19 @@ pub struct Actor {
     state: Option<ActorState>,
     segment_factory: SegmentPipelineFactory,
     segments: Vec<RecordingSegment>,
+    markers: Vec<PendingMarker>,
     completion_tx: watch::Sender<Option<Result<(), PipelineDoneError>>>,
 }
 
+/// A marker whose segment timing isn't known until the segment's tracks have
+/// reported their first frames.
+struct PendingMarker {
+    segment: u32,
+    /// Seconds since the segment pipeline's start time.
+    elapsed: f64,
+    name: String,
+}
+
 impl Actor {
     async fn stop_pipeline(
         &mut self,
@@ -144,6 +156,7 @@ impl Actor {
 
         let camera_device_id = self.segment_factory.camera_device_id();
         let mic_device_id = self.segment_factory.mic_device_id();
+        let additional_mic_device_ids = self.segment_factory.additional_mic_device_ids();
 
         self.segments.push(RecordingSegment {
             start: segment_start_time,
@@ -151,6 +164,7 @@ impl Actor {
             pipeline,
             camera_device_id,
             mic_device_id,
+            additional_mic_device_ids,
         });
 
         Ok(cursors)
@@ -186,12 +200,15 @@ impl Message<Stop> for Actor {
             _ => return Err(anyhow!("Not recording")),
         };
 
+        self.segment_factory.finish_live_stream().await;
+
         ctx.actor_ref().stop_gracefully().await?;
 
         let recording = stop_recording(
             self.recording_dir.clone(),
             std::mem::take(&mut self.segments),
             cursors,
+            std::mem::take(&mut self.markers),
             self.segment_factory.fragmented,
         )
         .await?;
@@ -280,6 +297,8 @@ impl Message<Cancel> for Actor {
             self.notify_completion_ok();
         }
 
+        self.segment_factory.finish_live_stream().await;
+
         Ok(())
     }
 }
@@ -330,6 +349,45 @@ impl Message<SetCameraFeed> for Actor {
     }
 }
 
+struct AddMarker {
+    name: Option<String>,
+    /// When the marker was asked for; it lands at this point of the segment.
+    requested_at: Instant,
+}
+
+impl Message<AddMarker> for Actor {
+    type Reply = anyhow::Result<String>;
+
+    async fn handle(&mut self, msg: AddMarker, _: &mut Context<Self, Self::Reply>) -> Self::Reply {
+        match self.state.as_ref() {
+            Some(ActorState::Recording {
+                pipeline, index, ..
+            }) => {
+                let name = msg
+                    .name
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| format!("Chapter {}", self.markers.len() + 1));
+
+                self.markers.push(PendingMarker {
+                    segment: *index,
+                    elapsed: msg
+                        .requested_at
+                        .saturating_duration_since(pipeline.start_time.instant())
+                        .as_secs_f64(),
+                    name: name.clone(),
+                });
+
+                Ok(name)
+            }
+            Some(ActorState::Paused { .. }) => {
+                bail!("Resume the recording before adding a marker")
+            }
+            None => Err(anyhow!("Recording no longer active")),
+        }
+    }
+}
+
 pub struct IsPaused;
 
 impl Message<IsPaused> for Actor {
@@ -340,12 +398,42 @@ impl Message<IsPaused> for Actor {
     }
 }
 
+struct GetStats;
+
+impl Message<GetStats> for Actor {
+    type Reply = RecordingStats;
+
+    async fn handle(&mut self, _: GetStats, _: &mut Context<Self, Self::Reply>) -> Self::Reply {
+        let finished = self
+            .segments
+            .iter()
+            .fold(RecordingStats::default(), |stats, segment| RecordingStats {
+                elapsed: stats.elapsed
+                    + Duration::from_secs_f64((segment.end - segment.start).max(0.0)),
+                video_frames: stats.video_frames + segment.pipeline.screen.video_frame_count,
+            });
+
+        match &self.state {
+            Some(ActorState::Recording {
+                pipeline,
+                segment_start_instant,
+                ..
+            }) => RecordingStats {
+                elapsed: finished.elapsed + segment_start_instant.elapsed(),
+                video_frames: finished.video_frames + pipeline.screen.video_frame_count(),
+            },
+            _ => finished,
+        }
+    }
+}
+
 pub struct RecordingSegment {
     pub start: f64,
     pub end: f64,
     pipeline: FinishedPipeline,
     pub camera_device_id: Option<String>,
     pub mic_device_id: Option<String>,
+    pub additional_mic_device_ids: Vec<String>,
 }
 
 pub struct ScreenPipelineOutput {
@@ -358,6 +446,7 @@ struct Pipeline {
     // sources
     pub screen: OutputPipeline,
     pub microphone: Option<OutputPipeline>,
+    pub additional_microphones: Vec<OutputPipeline>,
     pub camera: Option<OutputPipeline>,
     pub system_audio: Option<OutputPipeline>,
     pub cursor: Option<CursorPipeline>,
@@ -370,6 +459,8 @@ struct FinishedPipeline {
     // sources
     pub screen: FinishedOutputPipeline,
     pub microphone: Option<FinishedOutputPipeline>,
+    /// One entry per additional microphone, `None` where its track failed.
+    pub additional_microphones: Vec<Option<FinishedOutputPipeline>>,
     pub camera: Option<FinishedOutputPipeline>,
     pub system_audio: Option<FinishedOutputPipeline>,
     pub cursor: Option<CursorPipeline>,
@@ -381,6 +472,8 @@ struct FinishedPipeline {
 enum RecordingTrackKind {
     Display,
     Microphone,
+    /// An additional microphone, by its position in the segment.
+    AdditionalMicrophone(u32),
     Camera,
     SystemAudio,
 }
@@ -519,8 +612,9 @@ fn write_recording_failure_diagnostics(
 
 impl Pipeline {
     pub async fn stop(mut self) -> anyhow::Result<FinishedPipeline> {
-        let (microphone, camera, system_audio) = futures::join!(
+        let (microphone, additional_microphones, camera, system_audio) = futures::join!(
             OptionFuture::from(self.microphone.map(|s| s.stop())),
+            futures::future::join_all(self.additional_microphones.into_iter().map(|s| s.stop())),
             OptionFuture::from(self.camera.map(|s| s.stop())),
             OptionFuture::from(self.system_audio.map(|s| s.stop()))
         );
@@ -545,6 +639,17 @@ impl Pipeline {
                 microphone.transpose(),
                 &self.track_failures,
             ),
+            additional_microphones: additional_microphones
+                .into_iter()
+                .enumerate()
+                .map(|(index, microphone)| {
+                    finalize_optional_track(
+                        RecordingTrackKind::AdditionalMicrophone(index as u32),
+                        microphone.map(Some),
+                        &self.track_failures,
+                    )
+                })
+                .collect(),
             camera: finalize_optional_track(
                 RecordingTrackKind::Camera,
                 camera.transpose(),
@@ -560,6 +665,32 @@ impl Pipeline {
         })
     }
 
+    /// Passes the health events of each of the segment's tracks on to
+    /// `health_tx`, which outlives the segment.
+    fn forward_health(&mut self, health_tx: &HealthSender) {
+        let outputs = [
+            Some(&mut self.screen),
+            self.microphone.as_mut(),
+            self.camera.as_mut(),
+            self.system_audio.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.additional_microphones.iter_mut());
+
+        for output in outputs {
+            let Some(mut rx) = output.take_health_rx() else {
+                continue;
+            };
+            let health_tx = health_tx.clone();
+            tokio::spawn(async move {
+                while let Some(event) = rx.recv().await {
+                    emit_health(&health_tx, event);
+                }
+            });
+        }
+    }
+
     fn spawn_watcher(
         &mut self,
         completion_tx: watch::Sender<Option<Result<(), PipelineDoneError>>>,
@@ -585,6 +716,14 @@ impl Pipeline {
             }));
         }
 
+        for (index, microphone) in self.additional_microphones.iter().enumerate() {
+            futures.push(Box::pin({
+                let done_fut = microphone.done_fut();
+                let track = RecordingTrackKind::AdditionalMicrophone(index as u32);
+                async move { (track, false, done_fut.await) }
+            }));
+        }
+
         if let Some(ref camera) = self.camera {
             futures.push(Box::pin({
                 let done_fut = camera.done_fut();
@@ -602,6 +741,11 @@ impl Pipeline {
         // Ensure non-video pipelines stop promptly when the video pipeline completes
         {
             let mic_cancel = self.microphone.as_ref().map(|p| p.cancel_token());
+            let additional_mic_cancels = self
+                .additional_microphones
+                .iter()
+                .map(|p| p.cancel_token())
+                .collect::<Vec<_>>();
             let cam_cancel = self.camera.as_ref().map(|p| p.cancel_token());
             let sys_cancel = self.system_audio.as_ref().map(|p| p.cancel_token());
 
@@ -612,6 +756,9 @@ impl Pipeline {
                 if let Some(token) = mic_cancel.as_ref() {
                     token.cancel();
                 }
+                for token in &additional_mic_cancels {
+                    token.cancel();
+                }
                 if let Some(token) = cam_cancel.as_ref() {
                     token.cancel();
                 }
@@ -685,9 +832,36 @@ impl ActorHandle {
         Ok(self.actor_ref.ask(SetCameraFeed { camera_feed }).await?)
     }
 
+    /// Drop a named marker at the current point of the recording, which
+    /// exports turn into a chapter. Returns the marker's name, which defaults
+    /// to "Chapter N".
+    pub async fn add_marker(&self, name: Option<String>) -> anyhow::Result<String> {
+        self.add_marker_at(name, Instant::now()).await
+    }
+
+    /// Like [`Self::add_marker`], but placed at `requested_at` rather than when the actor gets
+    /// to it, for requests that were queued before being delivered.
+    pub async fn add_marker_at(
+        &self,
+        name: Option<String>,
+        requested_at: Instant,
+    ) -> anyhow::Result<String> {
+        Ok(self.actor_ref.ask(AddMarker { name, requested_at }).await?)
+    }
+
     pub async fn is_paused(&self) -> anyhow::Result<bool> {
         Ok(self.actor_ref.ask(IsPaused).await?)
     }
+
+    pub async fn stats(&self) -> anyhow::Result<RecordingStats> {
+        Ok(self.actor_ref.ask(GetStats).await?)
+    }
+
+    /// Health events from every track of every segment. Only one caller can
+    /// take them.
+    pub fn take_health_rx(&self) -> Option<HealthReceiver> {
+        self.health_rx.lock().ok().and_then(|mut rx| rx.take())
+    }
 }
 
 impl Actor {
@@ -704,6 +878,7 @@ pub struct ActorBuilder {
     capture_target: screen_capture::ScreenCaptureTarget,
     system_audio: bool,
     mic_feed: Option<Arc<MicrophoneFeedLock>>,
+    additional_mic_feeds: Vec<Arc<MicrophoneFeedLock>>,
     camera_feed: Option<Arc<CameraFeedLock>>,
     custom_cursor: bool,
     keyboard_capture: bool,
@@ -711,6 +886,7 @@ pub struct ActorBuilder {
     use_oop_muxer: bool,
     max_fps: u32,
     quality: crate::StudioQuality,
+    live_stream: Option<LiveStream>,
     #[cfg(target_os = "macos")]
     excluded_windows: Vec<scap_targets::WindowId>,
 }
@@ -722,6 +898,7 @@ impl ActorBuilder {
             capture_target,
             system_audio: false,
             mic_feed: None,
+            additional_mic_feeds: Vec::new(),
             camera_feed: None,
             custom_cursor: false,
             keyboard_capture: true,
@@ -729,6 +906,7 @@ impl ActorBuilder {
             use_oop_muxer: false,
             max_fps: 60,
             quality: crate::StudioQuality::Balanced,
+            live_stream: None,
             #[cfg(target_os = "macos")]
             excluded_windows: Vec::new(),
         }
@@ -744,6 +922,13 @@ impl ActorBuilder {
         self
     }
 
+    /// Records another microphone into its own track, alongside the one from
+    /// `with_mic_feed`.
+    pub fn with_additional_mic_feed(mut self, mic_feed: Arc<MicrophoneFeedLock>) -> Self {
+        self.additional_mic_feeds.push(mic_feed);
+        self
+    }
+
     pub fn with_camera_feed(mut self, camera_feed: Arc<CameraFeedLock>) -> Self {
         self.camera_feed = Some(camera_feed);
         self
@@ -779,6 +964,13 @@ impl ActorBuilder {
         self
     }
 
+    /// Also sends the display, and the microphone or else system audio, to
+    /// `live_stream`. Pausing pauses the stream too.
+    pub fn with_live_stream(mut self, live_stream: LiveStream) -> Self {
+        self.live_stream = Some(live_stream);
+        self
+    }
+
     #[cfg(target_os = "macos")]
     pub fn with_excluded_windows(mut self, excluded_windows: Vec<scap_targets::WindowId>) -> Self {
         self.excluded_windows = excluded_windows;
@@ -789,6 +981,15 @@ impl ActorBuilder {
         self,
         #[cfg(target_os = "macos")] shareable_content: Option<SendableShareableContent>,
     ) -> anyhow::Result<ActorHandle> {
+        if self.live_stream.is_some()
+            && matches!(
+                self.capture_target,
+                screen_capture::ScreenCaptureTarget::CameraOnly
+            )
+        {
+            bail!("Live streaming needs a display, window or area to capture");
+        }
+
         spawn_studio_recording_actor(
             self.output_path,
             RecordingBaseInputs {
@@ -801,12 +1002,14 @@ impl ActorBuilder {
                 #[cfg(target_os = "macos")]
                 excluded_windows: self.excluded_windows,
             },
+            self.additional_mic_feeds,
             self.custom_cursor,
             self.keyboard_capture,
             self.fragmented,
             self.use_oop_muxer,
             self.max_fps,
             self.quality,
+            self.live_stream,
         )
         .await
     }
@@ -817,12 +1020,14 @@ impl ActorBuilder {
 async fn spawn_studio_recording_actor(
     recording_dir: PathBuf,
     base_inputs: RecordingBaseInputs,
+    additional_mic_feeds: Vec<Arc<MicrophoneFeedLock>>,
     custom_cursor_capture: bool,
     keyboard_capture: bool,
     fragmented: bool,
     use_oop_muxer: bool,
     max_fps: u32,
     quality: crate::StudioQuality,
+    live_stream: Option<LiveStream>,
 ) -> anyhow::Result<ActorHandle> {
     ensure_dir(&recording_dir)?;
 
@@ -835,6 +1040,7 @@ async fn spawn_studio_recording_actor(
 
     let (completion_tx, completion_rx) =
         watch::channel::<Option<Result<(), PipelineDoneError>>>(None);
+    let (health_tx, health_rx) = new_health_channel();
 
     if let Some(camera_feed) = &base_inputs.camera_feed {
         debug!("camera device info: {:#?}", camera_feed.camera_info());
@@ -845,17 +1051,28 @@ async fn spawn_studio_recording_actor(
         debug!("mic audio info: {:#?}", mic_feed.audio_info());
     };
 
+    for mic_feed in &additional_mic_feeds {
+        debug!(
+            "additional mic {} audio info: {:#?}",
+            mic_feed.device_name(),
+            mic_feed.audio_info()
+        );
+    }
+
     let mut segment_pipeline_factory = SegmentPipelineFactory::new(
         segments_dir,
         cursors_dir,
         base_inputs.clone(),
+        additional_mic_feeds,
         custom_cursor_capture,
         keyboard_capture,
         fragmented,
         use_oop_muxer,
         max_fps,
         quality,
+        live_stream,
         completion_tx.clone(),
+        health_tx,
     );
 
     if fragmented {
@@ -886,6 +1103,7 @@ async fn spawn_studio_recording_actor(
         }),
         segment_factory: segment_pipeline_factory,
         segments: Vec::new(),
+        markers: Vec::new(),
         completion_tx: completion_tx.clone(),
     });
 
@@ -893,6 +1111,7 @@ async fn spawn_studio_recording_actor(
         actor_ref,
         capture_target: base_inputs.capture_target,
         done_fut,
+        health_rx: Arc::new(std::sync::Mutex::new(Some(health_rx))),
     })
 }
 
@@ -919,6 +1138,7 @@ async fn stop_recording(
     recording_dir: PathBuf,
     segments: Vec<RecordingSegment>,
     cursors: Cursors,
+    markers: Vec<PendingMarker>,
     fragmented: bool,
 ) -> Result<CompletedRecording, RecordingError> {
     use cap_project::*;
@@ -1062,6 +1282,20 @@ async fn stop_recording(
                         device_id: s.mic_device_id.clone(),
                         gap_summary: to_project_gap_summary(mic.audio_gap_summary),
                     }),
+                    additional_mics: s
+                        .pipeline
+                        .additional_microphones
+                        .into_iter()
+                        .enumerate()
+                        .map(|(index, mic)| {
+                            mic.map(|mic| AudioMeta {
+                                path: make_relative(&mic.path),
+                                start_time: Some(to_start_time(mic.first_timestamp)),
+                                device_id: s.additional_mic_device_ids.get(index).cloned(),
+                                gap_summary: to_project_gap_summary(mic.audio_gap_summary),
+                            })
+                        })
+                        .collect(),
                     system_audio: s.pipeline.system_audio.map(|audio| {
                         let raw_sys_start = to_start_time(audio.first_timestamp);
                         let sys_start_time = if let Some(mic_start) = mic_start_time {
@@ -1124,6 +1358,19 @@ async fn stop_recording(
         .into_iter()
         .map(|segment| segment.meta)
         .collect();
+    // Timeline time zero is the segment's latest track start, so that's what
+    // a marker is measured from.
+    let markers = markers
+        .into_iter()
+        .filter_map(|marker| {
+            let segment = segment_metas.get(marker.segment as usize)?;
+            Some(RecordingMarker {
+                segment: marker.segment,
+                time: (marker.elapsed - segment.latest_start_time().unwrap_or(0.0)).max(0.0),
+                name: marker.name,
+            })
+        })
+        .collect();
     let clip_configs = segment_metas
         .iter()
         .all(|segment| segment.camera.is_none())
@@ -1174,6 +1421,7 @@ async fn stop_recording(
                     .collect(),
             ),
             status,
+            markers,
         },
     };
 
@@ -1191,6 +1439,7 @@ async fn stop_recording(
             caption_segments: Vec::new(),
             keyboard_segments: Vec::new(),
             audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
         });
     }
     if let Some(clips) = clip_configs {
@@ -1223,14 +1472,17 @@ struct SegmentPipelineFactory {
     segments_dir: PathBuf,
     cursors_dir: PathBuf,
     base_inputs: RecordingBaseInputs,
+    additional_mic_feeds: Vec<Arc<MicrophoneFeedLock>>,
     custom_cursor_capture: bool,
     keyboard_capture: bool,
     fragmented: bool,
     use_oop_muxer: bool,
     max_fps: u32,
     quality: crate::StudioQuality,
+    live_stream: Option<LiveStream>,
     index: u32,
     completion_tx: watch::Sender<Option<Result<(), PipelineDoneError>>>,
+    health_tx: HealthSender,
     #[cfg(windows)]
     encoder_preferences: crate::capture_pipeline::EncoderPreferences,
 }
@@ -1241,26 +1493,32 @@ impl SegmentPipelineFactory {
         segments_dir: PathBuf,
         cursors_dir: PathBuf,
         base_inputs: RecordingBaseInputs,
+        additional_mic_feeds: Vec<Arc<MicrophoneFeedLock>>,
         custom_cursor_capture: bool,
         keyboard_capture: bool,
         fragmented: bool,
         use_oop_muxer: bool,
         max_fps: u32,
         quality: crate::StudioQuality,
+        live_stream: Option<LiveStream>,
         completion_tx: watch::Sender<Option<Result<(), PipelineDoneError>>>,
+        health_tx: HealthSender,
     ) -> Self {
         Self {
             segments_dir,
             cursors_dir,
             base_inputs,
+            additional_mic_feeds,
             custom_cursor_capture,
             keyboard_capture,
             fragmented,
             use_oop_muxer,
             max_fps,
             quality,
+            live_stream,
             index: 0,
             completion_tx,
+            health_tx,
             #[cfg(windows)]
             encoder_preferences: crate::capture_pipeline::EncoderPreferences::new(),
         }
@@ -1271,12 +1529,19 @@ impl SegmentPipelineFactory {
         cursors: Cursors,
         next_cursors_id: u32,
     ) -> anyhow::Result<Pipeline> {
+        if self.index > 0
+            && let Some(live_stream) = &self.live_stream
+        {
+            live_stream.start_segment();
+        }
+
         let segment_start_time = Timestamps::now();
         let mut pipeline = create_segment_pipeline(
             &self.segments_dir,
             &self.cursors_dir,
             self.index,
             self.base_inputs.clone(),
+            self.additional_mic_feeds.clone(),
             cursors,
             next_cursors_id,
             self.custom_cursor_capture,
@@ -1285,6 +1550,7 @@ impl SegmentPipelineFactory {
             self.use_oop_muxer,
             self.max_fps,
             self.quality,
+            self.live_stream.clone(),
             segment_start_time,
             #[cfg(windows)]
             self.encoder_preferences.clone(),
@@ -1294,10 +1560,17 @@ impl SegmentPipelineFactory {
         self.index += 1;
 
         pipeline.spawn_watcher(self.completion_tx.clone());
+        pipeline.forward_health(&self.health_tx);
 
         Ok(pipeline)
     }
 
+    async fn finish_live_stream(&self) {
+        if let Some(live_stream) = &self.live_stream {
+            live_stream.finish().await;
+        }
+    }
+
     pub fn set_mic_feed(&mut self, mic_feed: Option<Arc<MicrophoneFeedLock>>) {
         self.base_inputs.mic_feed = mic_feed;
     }
@@ -1319,6 +1592,13 @@ impl SegmentPipelineFactory {
             .as_ref()
             .map(|f| f.device_name().to_string())
     }
+
+    pub fn additional_mic_device_ids(&self) -> Vec<String> {
+        self.additional_mic_feeds
+            .iter()
+            .map(|f| f.device_name().to_string())
+            .collect()
+    }
 }
 
 fn completion_rx_to_done_fut(
@@ -1364,6 +1644,7 @@ async fn create_segment_pipeline(
     cursors_dir: &Path,
     index: u32,
     base_inputs: RecordingBaseInputs,
+    additional_mic_feeds: Vec<Arc<MicrophoneFeedLock>>,
     prev_cursors: Cursors,
     next_cursors_id: u32,
     custom_cursor_capture: bool,
@@ -1372,6 +1653,7 @@ async fn create_segment_pipeline(
     use_oop_muxer: bool,
     max_fps: u32,
     quality: crate::StudioQuality,
+    live_stream: Option<LiveStream>,
     start_time: Timestamps,
     #[cfg(windows)] encoder_preferences: crate::capture_pipeline::EncoderPreferences,
 ) -> anyhow::Result<Pipeline> {
@@ -1539,6 +1821,7 @@ async fn create_segment_pipeline(
             quality,
             #[cfg(windows)]
             encoder_preferences.clone(),
+            live_stream.clone(),
         )
         .instrument(error_span!("screen-out"))
         .await
@@ -1639,14 +1922,25 @@ async fn create_segment_pipeline(
         None
     };
 
+    // A live stream carries a single audio track: the microphone, or system
+    // audio when there's no microphone.
+    let (mic_stream, system_audio_stream) = if base_inputs.mic_feed.is_some() {
+        (live_stream, None)
+    } else {
+        (None, live_stream)
+    };
+
     let microphone = if let Some(mic_feed) = base_inputs.mic_feed {
         let pipeline = if segment_fragmented {
             let output_path = dir.join("audio-input.m4a");
             OutputPipeline::builder(output_path)
                 .with_audio_source::<sources::Microphone>(mic_feed)
                 .with_timestamps(start_time)
-                .build::<FragmentedAudioMuxer>(FragmentedAudioMuxerConfig {
-                    shared_pause_state: shared_pause_state.clone(),
+                .build::<StreamTeeMuxer<FragmentedAudioMuxer>>(StreamTeeMuxerConfig {
+                    inner: FragmentedAudioMuxerConfig {
+                        shared_pause_state: shared_pause_state.clone(),
+                    },
+                    stream: mic_stream,
                 })
                 .instrument(error_span!("mic-out"))
                 .await
@@ -1654,7 +1948,10 @@ async fn create_segment_pipeline(
             OutputPipeline::builder(dir.join("audio-input.ogg"))
                 .with_audio_source::<sources::Microphone>(mic_feed)
                 .with_timestamps(start_time)
-                .build::<OggMuxer>(())
+                .build::<StreamTeeMuxer<OggMuxer>>(StreamTeeMuxerConfig {
+                    inner: (),
+                    stream: mic_stream,
+                })
                 .instrument(error_span!("mic-out"))
                 .await
         };
@@ -1663,6 +1960,30 @@ async fn create_segment_pipeline(
         None
     };
 
+    let mut additional_microphones = Vec::with_capacity(additional_mic_feeds.len());
+    for (mic_index, mic_feed) in additional_mic_feeds.into_iter().enumerate() {
+        let track = mic_index + 2;
+        let pipeline = if segment_fragmented {
+            OutputPipeline::builder(dir.join(format!("audio-input-{track}.m4a")))
+                .with_audio_source::<sources::Microphone>(mic_feed)
+                .with_timestamps(start_time)
+                .build::<FragmentedAudioMuxer>(FragmentedAudioMuxerConfig {
+                    shared_pause_state: shared_pause_state.clone(),
+                })
+                .instrument(error_span!("additional-mic-out", track))
+                .await
+        } else {
+            OutputPipeline::builder(dir.join(format!("audio-input-{track}.ogg")))
+                .with_audio_source::<sources::Microphone>(mic_feed)
+                .with_timestamps(start_time)
+                .build::<OggMuxer>(())
+                .instrument(error_span!("additional-mic-out", track))
+                .await
+        };
+        additional_microphones
+            .push(pipeline.with_context(|| format!("microphone {track} pipeline setup"))?);
+    }
+
     let system_audio = if let Some(system_audio_source) = system_audio {
         // System audio is intermittent (WASAPI loopback only delivers while
         // sound plays), so its first packet is not a "source ready" marker:
@@ -1675,8 +1996,11 @@ async fn create_segment_pipeline(
                 .with_audio_source::<screen_capture::SystemAudioSource>(system_audio_source)
                 .with_timestamps(start_time)
                 .with_audio_anchor(AudioAnchor::PipelineEpoch)
-                .build::<FragmentedAudioMuxer>(FragmentedAudioMuxerConfig {
-                    shared_pause_state: shared_pause_state.clone(),
+                .build::<StreamTeeMuxer<FragmentedAudioMuxer>>(StreamTeeMuxerConfig {
+                    inner: FragmentedAudioMuxerConfig {
+                        shared_pause_state: shared_pause_state.clone(),
+                    },
+                    stream: system_audio_stream,
                 })
                 .instrument(error_span!("system-audio-out"))
                 .await
@@ -1685,7 +2009,10 @@ async fn create_segment_pipeline(
                 .with_audio_source::<screen_capture::SystemAudioSource>(system_audio_source)
                 .with_timestamps(start_time)
                 .with_audio_anchor(AudioAnchor::PipelineEpoch)
-                .build::<OggMuxer>(())
+                .build::<StreamTeeMuxer<OggMuxer>>(StreamTeeMuxerConfig {
+                    inner: (),
+                    stream: system_audio_stream,
+                })
                 .instrument(error_span!("system-audio-out"))
                 .await
         };
@@ -1747,6 +2074,7 @@ async fn create_segment_pipeline(
         start_time,
         screen,
         microphone,
+        additional_microphones,
         camera,
         cursor,
         system_audio,
@@ -1804,6 +2132,7 @@ fn write_in_progress_meta(recording_dir: &Path) -> anyhow::Result<()> {
                 segments: Vec::new(),
                 cursors: cap_project::Cursors::default(),
                 status: Some(StudioRecordingStatus::InProgress),
+                markers: Vec::new(),
             },
         })),
         upload: None,
@@ -2000,6 +2329,7 @@ mod tests {
                     None,
                     0,
                 )),
+                additional_microphones: Vec::new(),
                 camera: None,
                 system_audio: None,
                 cursor: None,
@@ -2007,12 +2337,14 @@ mod tests {
             },
             camera_device_id: None,
             mic_device_id: Some("mic".to_string()),
+            additional_mic_device_ids: Vec::new(),
         };
 
         let completed = stop_recording(
             recording_dir.clone(),
             vec![segment],
             Default::default(),
+            Vec::new(),
             false,
         )
         .await
@@ -2030,6 +2362,139 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn stop_recording_writes_each_additional_microphone_with_its_offset() {
+        let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+        let recording_dir = temp_dir.path().join("recording");
+        let start_time = Timestamps::now();
+        std::fs::create_dir_all(recording_dir.join("content"))
+            .expect("recording content dir should be created");
+
+        let audio_at = |name: &str, millis: u64| {
+            test_finished_output_pipeline_at(
+                recording_dir.join(format!("content/{name}")),
+                Timestamp::Instant(start_time.instant() + Duration::from_millis(millis)),
+                None,
+                0,
+            )
+        };
+        let segment = RecordingSegment {
+            start: 0.0,
+            end: 1.0,
+            pipeline: FinishedPipeline {
+                start_time,
+                screen: test_finished_output_pipeline_at(
+                    recording_dir.join("content/display.mp4"),
+                    Timestamp::Instant(start_time.instant() + Duration::from_millis(300)),
+                    Some(test_video_info()),
+                    60,
+                ),
+                microphone: Some(audio_at("audio-input.ogg", 100)),
+                additional_microphones: vec![None, Some(audio_at("audio-input-3.ogg", 200))],
+                camera: None,
+                system_audio: None,
+                cursor: None,
+                track_failures: Vec::new(),
+            },
+            camera_device_id: None,
+            mic_device_id: Some("first".to_string()),
+            additional_mic_device_ids: vec!["second".to_string(), "third".to_string()],
+        };
+
+        let completed = stop_recording(
+            recording_dir.clone(),
+            vec![segment],
+            Default::default(),
+            Vec::new(),
+            false,
+        )
+        .await
+        .expect("recording should stop");
+
+        let StudioRecordingMeta::MultipleSegments { inner } = completed.meta else {
+            panic!("expected multiple segments meta");
+        };
+        let segment = inner.segments.first().expect("segment should be present");
+
+        let [None, Some(mic)] = segment.additional_mics.as_slice() else {
+            panic!("expected a gap for the failed microphone before the surviving one");
+        };
+        assert_eq!(mic.path.as_str(), "content/audio-input-3.ogg");
+        assert_eq!(mic.device_id.as_deref(), Some("third"));
+        assert_eq!(mic.start_time, Some(0.2));
+
+        let offsets = segment.calculate_audio_offsets();
+        assert!((offsets.mic - 0.2).abs() < 1e-6);
+        assert_eq!(offsets.additional_mic(0), 0.0);
+        assert!((offsets.additional_mic(1) - 0.1).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn stop_recording_measures_markers_from_the_timeline_start() {
+        let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+        let recording_dir = temp_dir.path().join("recording");
+        let start_time = Timestamps::now();
+        std::fs::create_dir_all(recording_dir.join("content"))
+            .expect("recording content dir should be created");
+
+        let segment = RecordingSegment {
+            start: 0.0,
+            end: 3.0,
+            pipeline: FinishedPipeline {
+                start_time,
+                screen: test_finished_output_pipeline_at(
+                    recording_dir.join("content/display.mp4"),
+                    Timestamp::Instant(start_time.instant() + Duration::from_millis(500)),
+                    Some(test_video_info()),
+                    60,
+                ),
+                microphone: None,
+                additional_microphones: Vec::new(),
+                camera: None,
+                system_audio: None,
+                cursor: None,
+                track_failures: Vec::new(),
+            },
+            camera_device_id: None,
+            mic_device_id: None,
+            additional_mic_device_ids: Vec::new(),
+        };
+
+        let completed = stop_recording(
+            recording_dir.clone(),
+            vec![segment],
+            Default::default(),
+            vec![
+                PendingMarker {
+                    segment: 0,
+                    elapsed: 0.2,
+                    name: "Before video".to_string(),
+                },
+                PendingMarker {
+                    segment: 0,
+                    elapsed: 2.0,
+                    name: "Demo".to_string(),
+                },
+                PendingMarker {
+                    segment: 1,
+                    elapsed: 1.0,
+                    name: "Missing segment".to_string(),
+                },
+            ],
+            false,
+        )
+        .await
+        .expect("recording should stop");
+
+        let markers = completed
+            .meta
+            .markers()
+            .iter()
+            .map(|marker| (marker.name.as_str(), marker.time))
+            .collect::<Vec<_>>();
+        assert_eq!(markers, vec![("Before video", 0.0), ("Demo", 1.5)]);
+    }
+
     #[test]
     fn camera_active_capture_size_leaves_non_compatibility_native() {
         for quality in [crate::StudioQuality::Balanced, crate::StudioQuality::Ultra] {
@@ -2193,6 +2658,7 @@ mod tests {
                     1,
                 ),
                 microphone: None,
+                additional_microphones: Vec::new(),
                 camera: None,
                 system_audio: None,
                 cursor: None,
@@ -2204,12 +2670,14 @@ mod tests {
             },
             camera_device_id: None,
             mic_device_id: None,
+            additional_mic_device_ids: Vec::new(),
         };
 
         let completed = stop_recording(
             recording_dir.clone(),
             vec![segment],
             Default::default(),
+            Vec::new(),
             false,
         )
         .await
@@ -2264,6 +2732,7 @@ mod tests {
             start_time: timestamps,
             screen,
             microphone: Some(microphone),
+            additional_microphones: Vec::new(),
             camera: None,
             system_audio: None,
             cursor: None,
@@ -1059,6 +1059,7 @@ mod tests {
             caption_segments: vec![],
             keyboard_segments: vec![],
             audio_segments: vec![],
+            volume_keyframes: Default::default(),
         };
         let config = ProjectConfiguration {
             timeline: Some(timeline),
@@ -1121,6 +1122,7 @@ mod tests {
             caption_segments: vec![],
             keyboard_segments: vec![],
             audio_segments: vec![],
+            volume_keyframes: Default::default(),
         };
         let config = ProjectConfiguration {
             timeline: Some(timeline),
@@ -133,12 +133,14 @@ impl TestRecording {
                         },
                         camera: None,
                         mic: None,
+                        additional_mics: Vec::new(),
                         system_audio: None,
                         cursor: None,
                         keyboard: None,
                     }],
                     cursors: Cursors::default(),
                     status: Some(status),
+                    markers: Vec::new(),
                 },
             })),
         };
@@ -0,0 +1,351 @@
+use cap_project::{Annotation, AnnotationType, MaskType, ProjectConfiguration, XY};
+
+use crate::{
+    Coord, MaskRenderMode, PreparedMask, ProjectUniforms, RawDisplaySpace, RenderOptions,
+    text::PreparedText, zoom::InterpolatedZoom,
+};
+
+/// Arrow heads use the screenshot editor's proportions (see `arrow.ts`),
+/// measured in display pixels before scaling to the output.
+const ARROW_HEAD_MIN_LENGTH: f64 = 20.0;
+const ARROW_HEAD_MIN_WIDTH: f64 = 14.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationShape {
+    Arrow,
+    Circle,
+    Rectangle,
+}
+
+#[derive(Debug, Clone)]
+pub struct PreparedAnnotation {
+    pub shape: AnnotationShape,
+    /// Centre of the unrotated shape, in output pixels.
+    pub center: XY<f32>,
+    /// Extent of the unrotated shape, in output pixels. Arrows keep the sign
+    /// and run from `center - size / 2` to `center + size / 2`.
+    pub size: XY<f32>,
+    /// Clockwise rotation around `center`, in radians.
+    pub rotation: f32,
+    pub stroke_width: f32,
+    pub stroke_color: [f32; 4],
+    pub fill_color: [f32; 4],
+    /// Arrow head length and width, in output pixels.
+    pub arrow_head: XY<f32>,
+    pub opacity: f32,
+    /// Visible part of the display, in output pixels. Shapes are clipped to
+    /// it so they never spill onto the background padding.
+    pub clip_bounds: [f32; 4],
+}
+
+/// Annotations split by the layer that draws them: shapes get their own
+/// layer, while masks and text reuse the mask and text layers.
+#[derive(Debug, Default)]
+pub struct PreparedAnnotations {
+    pub shapes: Vec<PreparedAnnotation>,
+    pub masks: Vec<PreparedMask>,
+    pub texts: Vec<PreparedText>,
+}
+
+/// Parses the editor's `#rgb`, `#rrggbb` and `#rrggbbaa` colors. Anything
+/// else, including `transparent`, is fully transparent.
+fn parse_color(value: &str) -> [f32; 4] {
+    let Some(hex) = value.trim().strip_prefix('#') else {
+        return [0.0; 4];
+    };
+
+    let channel = |range: std::ops::Range<usize>| {
+        hex.get(range)
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            .map(|value| value as f32 / 255.0)
+    };
+    let short_channel = |index: usize| {
+        hex.get(index..index + 1)
+            .and_then(|digit| u8::from_str_radix(digit, 16).ok())
+            .map(|value| (value * 17) as f32 / 255.0)
+    };
+
+    let channels = match hex.len() {
+        3 => [
+            short_channel(0),
+            short_channel(1),
+            short_channel(2),
+            Some(1.0),
+        ],
+        6 => [channel(0..2), channel(2..4), channel(4..6), Some(1.0)],
+        8 => [channel(0..2), channel(2..4), channel(4..6), channel(6..8)],
+        _ => return [0.0; 4],
+    };
+
+    match channels {
+        [Some(r), Some(g), Some(b), Some(a)] => [r, g, b, a],
+        _ => [0.0; 4],
+    }
+}
+
+/// How visible an annotation is at `frame_time`, fading in after `start` and
+/// out before `end` like text segments. `None` outside its time range.
+fn visibility(annotation: &Annotation, frame_time: f64) -> Option<f64> {
+    let end = annotation.end.unwrap_or(f64::INFINITY);
+    if frame_time < annotation.start || frame_time > end {
+        return None;
+    }
+
+    let fade_duration = annotation.fade_duration.max(0.0);
+    if fade_duration <= 0.0 {
+        return Some(1.0);
+    }
+
+    let fade_in = ((frame_time - annotation.start) / fade_duration).min(1.0);
+    let fade_out = ((end - frame_time) / fade_duration).min(1.0);
+    Some(fade_in * fade_out)
+}
+
+/// Places the project's annotations for `frame_time`. Their geometry is in
+/// display pixels, so it goes through the same crop, padding and zoom
+/// transforms as the cursor and stays pinned to the content it marks.
+pub fn prepare_annotations(
+    options: &RenderOptions,
+    project: &ProjectConfiguration,
+    resolution_base: XY<u32>,
+    zoom: &InterpolatedZoom,
+    frame_time: f64,
+    screen_opacity: f64,
+) -> PreparedAnnotations {
+    let mut prepared = PreparedAnnotations::default();
+    if project.annotations.is_empty() {
+        return prepared;
+    }
+
+    let output_size = ProjectUniforms::get_output_size(options, project, resolution_base);
+    let output_size = XY::new(output_size.0, output_size.1);
+    let to_output = |point: XY<f64>| {
+        Coord::<RawDisplaySpace>::new(point)
+            .to_cropped_display_space(options, project)
+            .to_frame_space(options, project, resolution_base)
+            .to_zoomed_frame_space(options, project, resolution_base, zoom)
+            .coord
+    };
+
+    let unit = to_output(XY::new(1.0, 1.0)) - to_output(XY::new(0.0, 0.0));
+    let scale = (unit.x + unit.y) / 2.0;
+
+    let crop = ProjectUniforms::get_crop(options, project);
+    let visible_start = to_output(crop.position.map(|v| v as f64));
+    let visible_end = to_output((crop.position + crop.size).map(|v| v as f64));
+    let clip_bounds = [
+        visible_start.x.max(0.0) as f32,
+        visible_start.y.max(0.0) as f32,
+        visible_end.x.min(output_size.x as f64) as f32,
+        visible_end.y.min(output_size.y as f64) as f32,
+    ];
+
+    for annotation in &project.annotations {
+        let Some(visibility) = visibility(annotation, frame_time) else {
+            continue;
+        };
+
+        let start = to_output(XY::new(annotation.x, annotation.y));
+        let end = to_output(XY::new(
+            annotation.x + annotation.width,
+            annotation.y + annotation.height,
+        ));
+        let top_left = XY::new(start.x.min(end.x), start.y.min(end.y));
+        let bottom_right = XY::new(start.x.max(end.x), start.y.max(end.y));
+        let opacity = annotation.opacity.clamp(0.0, 1.0) * visibility * screen_opacity;
+
+        match annotation.annotation_type {
+            AnnotationType::Mask => {
+                // Masks hide sensitive content, so they ignore opacity and
+                // fades and stay fully applied for their whole time range.
+                let size = bottom_right - top_left;
+                if size.x <= 0.0 || size.y <= 0.0 {
+                    continue;
+                }
+
+                let output = output_size.map(|v| v as f64);
+                let center = (top_left + bottom_right) / 2.0 / output;
+                let mode = match annotation.mask_type {
+                    Some(MaskType::Pixelate) => MaskRenderMode::Pixelate,
+                    Some(MaskType::Blur) | None => MaskRenderMode::Blur,
+                };
+
+                prepared.masks.push(PreparedMask {
+                    center: center.map(|v| v as f32),
+                    size: (size / output).map(|v| v as f32),
+                    feather: 0.0,
+                    opacity: 1.0,
+                    effect_size: (annotation.mask_level.unwrap_or(16.0).max(1.0) * scale) as f32,
+                    darkness: 0.0,
+                    mode,
+                    output_size,
+                });
+            }
+            AnnotationType::Text => {
+                let Some(content) = annotation.text.as_ref().filter(|text| !text.is_empty()) else {
+                    continue;
+                };
+
+                prepared.texts.push(PreparedText {
+                    content: content.clone(),
+                    bounds: [
+                        top_left.x as f32,
+                        top_left.y as f32,
+                        bottom_right.x as f32,
+                        bottom_right.y as f32,
+                    ],
+                    color: parse_color(&annotation.stroke_color),
+                    font_family: "sans-serif".to_string(),
+                    font_size: (bottom_right.y - top_left.y).max(1.0) as f32,
+                    font_weight: 400.0,
+                    italic: false,
+                    opacity: opacity as f32,
+                });
+            }
+            AnnotationType::Arrow | AnnotationType::Circle | AnnotationType::Rectangle => {
+                let shape = match annotation.annotation_type {
+                    AnnotationType::Arrow => AnnotationShape::Arrow,
+                    AnnotationType::Circle => AnnotationShape::Circle,
+                    _ => AnnotationShape::Rectangle,
+                };
+                let stroke_width = annotation.stroke_width.max(0.0);
+                let head_stroke = stroke_width.max(1.0);
+
+                prepared.shapes.push(PreparedAnnotation {
+                    shape,
+                    center: ((start + end) / 2.0).map(|v| v as f32),
+                    size: (end - start).map(|v| v as f32),
+                    rotation: annotation.rotation.to_radians() as f32,
+                    stroke_width: (stroke_width * scale) as f32,
+                    stroke_color: parse_color(&annotation.stroke_color),
+                    fill_color: parse_color(&annotation.fill_color),
+                    arrow_head: XY::new(
+                        (ARROW_HEAD_MIN_LENGTH.max(head_stroke * 6.0) * scale) as f32,
+                        (ARROW_HEAD_MIN_WIDTH.max(head_stroke * 5.0) * scale) as f32,
+                    ),
+                    opacity: opacity as f32,
+                    clip_bounds,
+                });
+            }
+        }
+    }
+
+    prepared
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zoom::SegmentBounds;
+
+    fn annotation(annotation_type: AnnotationType) -> Annotation {
+        Annotation {
+            id: "a".to_string(),
+            annotation_type,
+            x: 100.0,
+            y: 100.0,
+            width: 200.0,
+            height: 100.0,
+            stroke_color: "#F05656".to_string(),
+            stroke_width: 4.0,
+            fill_color: "transparent".to_string(),
+            opacity: 1.0,
+            rotation: 0.0,
+            text: None,
+            mask_type: None,
+            mask_level: None,
+            start: 2.0,
+            end: Some(6.0),
+            fade_duration: 1.0,
+        }
+    }
+
+    fn prepare(annotations: Vec<Annotation>, zoom_amount: f64) -> PreparedAnnotations {
+        let options = RenderOptions {
+            camera_size: None,
+            screen_size: XY::new(1920, 1080),
+            preserve_screen_alpha: false,
+        };
+        let mut project = ProjectConfiguration::default();
+        project.background.padding = 0.0;
+        project.annotations = annotations;
+        let zoom = InterpolatedZoom {
+            t: 1.0,
+            bounds: SegmentBounds::from_amount_center(zoom_amount, XY::new(0.0, 0.0)),
+        };
+
+        prepare_annotations(&options, &project, XY::new(1920, 1080), &zoom, 3.0, 1.0)
+    }
+
+    #[test]
+    fn annotations_fade_in_and_out_around_their_range() {
+        let annotation = annotation(AnnotationType::Rectangle);
+
+        assert_eq!(visibility(&annotation, 1.9), None);
+        assert_eq!(visibility(&annotation, 2.5), Some(0.5));
+        assert_eq!(visibility(&annotation, 4.0), Some(1.0));
+        assert_eq!(visibility(&annotation, 5.75), Some(0.25));
+        assert_eq!(visibility(&annotation, 6.1), None);
+
+        let untimed = Annotation {
+            start: 0.0,
+            end: None,
+            ..annotation
+        };
+        assert_eq!(visibility(&untimed, 3600.0), Some(1.0));
+    }
+
+    #[test]
+    fn colors_parse_editor_formats() {
+        assert_eq!(parse_color("#ff0000"), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(parse_color("#0f0"), [0.0, 1.0, 0.0, 1.0]);
+        assert_eq!(parse_color("#0000ff00"), [0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(parse_color("transparent"), [0.0; 4]);
+        assert_eq!(parse_color("#zzzzzz"), [0.0; 4]);
+    }
+
+    #[test]
+    fn shapes_follow_zoom() {
+        let unzoomed = prepare(vec![annotation(AnnotationType::Arrow)], 1.0);
+        let zoomed = prepare(vec![annotation(AnnotationType::Arrow)], 2.0);
+
+        let [unzoomed] = unzoomed.shapes.as_slice() else {
+            panic!("expected one shape");
+        };
+        let [zoomed] = zoomed.shapes.as_slice() else {
+            panic!("expected one shape");
+        };
+
+        assert_eq!(unzoomed.center, XY::new(200.0, 150.0));
+        assert_eq!(unzoomed.size, XY::new(200.0, 100.0));
+        assert_eq!(unzoomed.stroke_width, 4.0);
+        assert_eq!(zoomed.center, XY::new(400.0, 300.0));
+        assert_eq!(zoomed.size, XY::new(400.0, 200.0));
+        assert_eq!(zoomed.stroke_width, 8.0);
+        assert_eq!(zoomed.clip_bounds, [0.0, 0.0, 1920.0, 1080.0]);
+    }
+
+    #[test]
+    fn masks_and_text_go_to_their_own_layers() {
+        let mask = Annotation {
+            mask_type: Some(MaskType::Pixelate),
+            mask_level: Some(12.0),
+            ..annotation(AnnotationType::Mask)
+        };
+        let text = Annotation {
+            text: Some("Click here".to_string()),
+            ..annotation(AnnotationType::Text)
+        };
+
+        let prepared = prepare(vec![mask, text], 1.0);
+
+        assert!(prepared.shapes.is_empty());
+        assert_eq!(prepared.masks.len(), 1);
+        assert_eq!(prepared.masks[0].mode, MaskRenderMode::Pixelate);
+        assert_eq!(prepared.masks[0].opacity, 1.0);
+        assert_eq!(prepared.masks[0].effect_size, 12.0);
+        assert_eq!(prepared.texts.len(), 1);
+        assert_eq!(prepared.texts[0].bounds, [100.0, 100.0, 300.0, 200.0]);
+        assert_eq!(prepared.texts[0].font_size, 100.0);
+    }
+}
@@ -0,0 +1,232 @@
+use bytemuck::{Pod, Zeroable};
+use wgpu::include_wgsl;
+
+use crate::annotation::{AnnotationShape, PreparedAnnotation};
+
+/// Draws arrow, circle and rectangle annotations as signed distance fields,
+/// one scissored quad per shape. Mask and text annotations are handled by
+/// the mask and text layers.
+pub struct AnnotationLayer {
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline: wgpu::RenderPipeline,
+    /// Uniforms for each visible shape, reused across frames and grown to
+    /// the most shapes seen on screen at once.
+    slots: Vec<ShapeSlot>,
+    /// Scissor rect of each shape this frame, drawn with the slot at the
+    /// same index.
+    scissors: Vec<[u32; 4]>,
+    output_size: (u32, u32),
+}
+
+struct ShapeSlot {
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct AnnotationUniforms {
+    stroke_color: [f32; 4],
+    fill_color: [f32; 4],
+    center: [f32; 2],
+    size: [f32; 2],
+    arrow_head: [f32; 2],
+    rotation: f32,
+    stroke_width: f32,
+    opacity: f32,
+    shape: u32,
+    _padding: [f32; 2],
+}
+
+impl AnnotationUniforms {
+    fn from_annotation(annotation: &PreparedAnnotation) -> Self {
+        Self {
+            stroke_color: annotation.stroke_color,
+            fill_color: annotation.fill_color,
+            center: [annotation.center.x, annotation.center.y],
+            size: [annotation.size.x, annotation.size.y],
+            arrow_head: [annotation.arrow_head.x, annotation.arrow_head.y],
+            rotation: annotation.rotation,
+            stroke_width: annotation.stroke_width,
+            opacity: annotation.opacity,
+            shape: match annotation.shape {
+                AnnotationShape::Arrow => 0,
+                AnnotationShape::Circle => 1,
+                AnnotationShape::Rectangle => 2,
+            },
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+/// Pixel rect that can contain the shape at any rotation, intersected with
+/// its clip bounds. `None` when nothing of it is visible.
+fn scissor_rect(annotation: &PreparedAnnotation, output_size: (u32, u32)) -> Option<[u32; 4]> {
+    let half_size = annotation.size.map(|v| v.abs() / 2.0);
+    let radius = (half_size.x * half_size.x + half_size.y * half_size.y).sqrt()
+        + annotation.stroke_width
+        + annotation.arrow_head.y
+        + 2.0;
+
+    let [clip_left, clip_top, clip_right, clip_bottom] = annotation.clip_bounds;
+    let left = (annotation.center.x - radius)
+        .max(clip_left)
+        .max(0.0)
+        .floor();
+    let top = (annotation.center.y - radius)
+        .max(clip_top)
+        .max(0.0)
+        .floor();
+    let right = (annotation.center.x + radius)
+        .min(clip_right)
+        .min(output_size.0 as f32)
+        .ceil();
+    let bottom = (annotation.center.y + radius)
+        .min(clip_bottom)
+        .min(output_size.1 as f32)
+        .ceil();
+
+    if right <= left || bottom <= top {
+        return None;
+    }
+
+    Some([
+        left as u32,
+        top as u32,
+        (right - left) as u32,
+        (bottom - top) as u32,
+    ])
+}
+
+impl AnnotationLayer {
+    pub fn new(device: &wgpu::Device) -> Self {
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("Annotation Bind Group Layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let shader = device.create_shader_module(include_wgsl!("../shaders/annotation.wgsl"));
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Annotation Pipeline Layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Annotation Pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Rgba8Unorm,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            bind_group_layout,
+            pipeline,
+            slots: Vec::new(),
+            scissors: Vec::new(),
+            output_size: (0, 0),
+        }
+    }
+
+    fn create_slot(&self, device: &wgpu::Device) -> ShapeSlot {
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Annotation Uniform Buffer"),
+            size: std::mem::size_of::<AnnotationUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Annotation Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        ShapeSlot {
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    pub fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        output_size: (u32, u32),
+        annotations: &[PreparedAnnotation],
+    ) {
+        self.output_size = output_size;
+        self.scissors.clear();
+
+        for annotation in annotations {
+            if annotation.opacity <= 0.0 {
+                continue;
+            }
+            let Some(scissor) = scissor_rect(annotation, output_size) else {
+                continue;
+            };
+
+            if self.slots.len() == self.scissors.len() {
+                let slot = self.create_slot(device);
+                self.slots.push(slot);
+            }
+            queue.write_buffer(
+                &self.slots[self.scissors.len()].uniform_buffer,
+                0,
+                bytemuck::bytes_of(&AnnotationUniforms::from_annotation(annotation)),
+            );
+            self.scissors.push(scissor);
+        }
+    }
+
+    pub fn has_content(&self) -> bool {
+        !self.scissors.is_empty()
+    }
+
+    pub fn render<'a>(&'a self, pass: &mut wgpu::RenderPass<'a>) {
+        pass.set_pipeline(&self.pipeline);
+
+        for (slot, &[x, y, width, height]) in self.slots.iter().zip(&self.scissors) {
+            pass.set_scissor_rect(x, y, width, height);
+            pass.set_bind_group(0, &slot.bind_group, &[]);
+            pass.draw(0..6, 0..1);
+        }
+
+        pass.set_scissor_rect(0, 0, self.output_size.0, self.output_size.1);
+    }
+}
@@ -1,3 +1,4 @@
+mod annotation;
 mod background;
 mod blur;
 mod camera;
@@ -60,6 +61,7 @@ pub(crate) fn new_font_system() -> glyphon::FontSystem {
     glyphon::FontSystem::new_with_locale_and_db(locale.clone(), db.clone())
 }
 
+pub use annotation::*;
 pub use background::*;
 pub use blur::*;
 pub use camera::*;
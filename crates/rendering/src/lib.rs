@@ -1,7 +1,7 @@
 use anyhow::Result;
 use cap_project::{
     AspectRatio, Camera, CameraShape, CameraXPosition, CameraYPosition, ClipOffsets,
-    ClipTransitionType, CornerStyle, Crop, CursorEvents, CursorType, FrameConfiguration,
+    ClipTransitionStyle, CornerStyle, Crop, CursorEvents, CursorType, FrameConfiguration,
     FrameStyle, ProjectConfiguration, RecordingMeta, SceneMode, StudioRecordingMeta,
     TimelineFrameMapping, TimelineSource, XY,
 };
@@ -17,8 +17,8 @@ use frame_pipeline::{
 };
 use futures::future::OptionFuture;
 use layers::{
-    Background, BackgroundLayer, BlurLayer, CameraLayer, CaptionsLayer, CursorLayer, DisplayLayer,
-    FrameLayer, KeyboardLayer, MaskLayer, TextLayer,
+    AnnotationLayer, Background, BackgroundLayer, BlurLayer, CameraLayer, CaptionsLayer,
+    CursorLayer, DisplayLayer, FrameLayer, KeyboardLayer, MaskLayer, TextLayer,
 };
 use specta::Type;
 use spring_mass_damper::SpringMassDamperSimulationConfig;
@@ -29,6 +29,7 @@ use std::sync::{
 use std::{path::PathBuf, time::Instant};
 use tokio::sync::mpsc;
 
+mod annotation;
 pub mod composite_frame;
 mod coord;
 pub mod cpu_yuv;
@@ -67,6 +68,7 @@ pub fn prewarm_fonts() {
     drop(layers::new_font_system());
 }
 
+use annotation::{PreparedAnnotation, prepare_annotations};
 pub use cursor_interpolation::PrecomputedCursorTimeline;
 use mask::interpolate_masks;
 use scene::*;
@@ -643,10 +645,10 @@ pub async fn render_video_to_channel(
             match timeline.get_frame_mapping(frame_time) {
                 Some(TimelineFrameMapping::Transition {
                     outgoing,
-                    kind,
+                    style,
                     progress,
                     ..
-                }) => Some((outgoing, kind, progress)),
+                }) => Some((outgoing, style, progress)),
                 _ => None,
             }
         });
@@ -685,7 +687,7 @@ pub async fn render_video_to_channel(
                         segment_time,
                         needs_camera,
                         render_segment.render_display,
-                        clip_config.map(|v| v.offsets).unwrap_or_default(),
+                        clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
                         current_frame_number,
                         is_initial_frame,
                         fps,
@@ -698,7 +700,7 @@ pub async fn render_video_to_channel(
                     segment_time,
                     needs_camera,
                     render_segment.render_display,
-                    clip_config.map(|v| v.offsets).unwrap_or_default(),
+                    clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
                     current_frame_number,
                     is_initial_frame,
                     fps,
@@ -762,7 +764,9 @@ pub async fn render_video_to_channel(
                         next_seg_time,
                         needs_camera,
                         next_render_segment.render_display,
-                        next_clip_config.map(|v| v.offsets).unwrap_or_default(),
+                        next_clip_config
+                            .map(|v| v.offsets.clone())
+                            .unwrap_or_default(),
                         next_frame_number,
                         next_is_initial,
                         fps,
@@ -774,7 +778,7 @@ pub async fn render_video_to_channel(
                 None
             };
 
-            let render_result = if let Some((outgoing, kind, progress)) = transition_mapping {
+            let render_result = if let Some((outgoing, style, progress)) = transition_mapping {
                 let render_future = render_transition_rgba(
                     TransitionExportContext {
                         constants,
@@ -792,7 +796,7 @@ pub async fn render_video_to_channel(
                     &mut frame_renderer,
                     &mut layers,
                     (outgoing, outgoing_frames),
-                    kind,
+                    style,
                     progress,
                     TransitionRenderInput {
                         segment_frames,
@@ -1096,10 +1100,10 @@ pub async fn render_video_to_channel_nv12(
             match timeline.get_frame_mapping(frame_time) {
                 Some(TimelineFrameMapping::Transition {
                     outgoing,
-                    kind,
+                    style,
                     progress,
                     ..
-                }) => Some((outgoing, kind, progress)),
+                }) => Some((outgoing, style, progress)),
                 _ => None,
             }
         });
@@ -1141,7 +1145,7 @@ pub async fn render_video_to_channel_nv12(
                         segment_time,
                         needs_camera,
                         render_segment.render_display,
-                        clip_config.map(|v| v.offsets).unwrap_or_default(),
+                        clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
                         current_frame_number,
                         is_initial_frame,
                         fps,
@@ -1154,7 +1158,7 @@ pub async fn render_video_to_channel_nv12(
                     segment_time,
                     needs_camera,
                     render_segment.render_display,
-                    clip_config.map(|v| v.offsets).unwrap_or_default(),
+                    clip_config.map(|v| v.offsets.clone()).unwrap_or_default(),
                     current_frame_number,
                     is_initial_frame,
                     fps,
@@ -1219,7 +1223,9 @@ pub async fn render_video_to_channel_nv12(
                         next_seg_time,
                         needs_camera,
                         next_render_segment.render_display,
-                        next_clip_config.map(|v| v.offsets).unwrap_or_default(),
+                        next_clip_config
+                            .map(|v| v.offsets.clone())
+                            .unwrap_or_default(),
                         next_frame_number,
                         next_is_initial,
                         fps,
@@ -1236,7 +1242,7 @@ pub async fn render_video_to_channel_nv12(
                 first_phase_render_ms,
                 first_phase_prefetch_ms,
                 first_phase_join_wall_ms,
-            ) = if let Some((outgoing, kind, progress)) = transition_mapping {
+            ) = if let Some((outgoing, style, progress)) = transition_mapping {
                 let render_future = render_transition_nv12_export(
                     TransitionExportContext {
                         constants,
@@ -1254,7 +1260,7 @@ pub async fn render_video_to_channel_nv12(
                     &mut frame_renderer,
                     &mut layers,
                     (outgoing, outgoing_frames),
-                    kind,
+                    style,
                     progress,
                     TransitionRenderInput {
                         segment_frames,
@@ -1591,7 +1597,9 @@ async fn decode_timeline_source_frames(
         source.source_time,
         needs_camera,
         render_segment.render_display,
-        clip_config.map(|clip| clip.offsets).unwrap_or_default(),
+        clip_config
+            .map(|clip| clip.offsets.clone())
+            .unwrap_or_default(),
         current_frame_number,
         is_initial_frame,
         fps,
@@ -1604,7 +1612,7 @@ async fn render_transition_rgba(
     frame_renderer: &mut FrameRenderer<'_>,
     layers: &mut RendererLayers,
     outgoing: (TimelineSource<'_>, Option<DecodedSegmentFrames>),
-    kind: ClipTransitionType,
+    style: ClipTransitionStyle,
     progress: f64,
     incoming: TransitionRenderInput<'_>,
 ) -> Result<Option<RenderedFrame>, RenderingError> {
@@ -1651,7 +1659,7 @@ async fn render_transition_rgba(
                 render_display: outgoing_render_segment.render_display,
             },
             incoming,
-            kind,
+            style,
             progress as f32,
             layers,
         )
@@ -1663,7 +1671,7 @@ async fn render_transition_nv12_export(
     frame_renderer: &mut FrameRenderer<'_>,
     layers: &mut RendererLayers,
     outgoing: (TimelineSource<'_>, Option<DecodedSegmentFrames>),
-    kind: ClipTransitionType,
+    style: ClipTransitionStyle,
     progress: f64,
     incoming: TransitionRenderInput<'_>,
 ) -> Result<Option<Nv12RenderedFrame>, RenderingError> {
@@ -1710,7 +1718,7 @@ async fn render_transition_nv12_export(
                 render_display: outgoing_render_segment.render_display,
             },
             incoming,
-            kind,
+            style,
             progress as f32,
             layers,
         )
@@ -1747,7 +1755,7 @@ async fn recover_initial_frames_with_backtrack(
 ) -> Option<DecodedSegmentFrames> {
     for recovery_time in initial_decode_recovery_times(segment_time as f32, fps) {
         let Some(_) = decoders
-            .get_frames(recovery_time, needs_camera, needs_display, offsets)
+            .get_frames(recovery_time, needs_camera, needs_display, offsets.clone())
             .await
         else {
             continue;
@@ -1762,7 +1770,12 @@ async fn recover_initial_frames_with_backtrack(
         );
 
         if let Some(recovered) = decoders
-            .get_frames(segment_time as f32, needs_camera, needs_display, offsets)
+            .get_frames(
+                segment_time as f32,
+                needs_camera,
+                needs_display,
+                offsets.clone(),
+            )
             .await
         {
             return Some(recovered);
@@ -1803,11 +1816,21 @@ async fn decode_segment_frames_with_retry(
 
         result = if is_initial_frame {
             decoders
-                .get_frames_initial(segment_time as f32, needs_camera, needs_display, offsets)
+                .get_frames_initial(
+                    segment_time as f32,
+                    needs_camera,
+                    needs_display,
+                    offsets.clone(),
+                )
                 .await
         } else {
             decoders
-                .get_frames(segment_time as f32, needs_camera, needs_display, offsets)
+                .get_frames(
+                    segment_time as f32,
+                    needs_camera,
+                    needs_display,
+                    offsets.clone(),
+                )
                 .await
         };
 
@@ -1882,6 +1905,7 @@ pub struct RenderVideoConstants {
     adapter_name: String,
 }
 
+#[derive(Clone)]
 pub struct SharedWgpuDevice {
     pub instance: wgpu::Instance,
     pub adapter: wgpu::Adapter,
@@ -1964,17 +1988,19 @@ impl RenderVideoConstants {
         recording_meta: RecordingMeta,
         meta: StudioRecordingMeta,
     ) -> Result<Self, RenderingError> {
-        let first_segment = segments.first().ok_or(RenderingError::NoSegments)?;
+        if segments.is_empty() {
+            return Err(RenderingError::NoSegments);
+        }
 
-        let options = RenderOptions {
-            screen_size: XY::new(first_segment.display.width, first_segment.display.height),
-            camera_size: first_segment
-                .camera
-                .as_ref()
-                .map(|c| XY::new(c.width, c.height)),
-            preserve_screen_alpha: false,
-        };
+        let shared = SharedWgpuDevice::create().await?;
+        Self::new_with_device(shared, segments, recording_meta, meta)
+    }
+}
 
+impl SharedWgpuDevice {
+    /// Picks the best available adapter, falling back to a software one when
+    /// no GPU is usable, and opens a device on it.
+    pub async fn create() -> Result<Self, RenderingError> {
         let instance = create_wgpu_instance().await;
 
         let force_software_adapter = force_software_wgpu_adapter();
@@ -1995,7 +2021,7 @@ impl RenderVideoConstants {
                 .ok()
         };
 
-        let (adapter, is_software_adapter, adapter_name) = if let Some(adapter) = hardware_adapter {
+        let (adapter, is_software_adapter) = if let Some(adapter) = hardware_adapter {
             let adapter_info = adapter.get_info();
             let is_software = is_software_wgpu_adapter(&adapter_info);
 
@@ -2015,7 +2041,7 @@ impl RenderVideoConstants {
                 );
             }
 
-            (adapter, is_software, adapter_info.name)
+            (adapter, is_software)
         } else {
             tracing::warn!("No hardware GPU adapter found, attempting software fallback");
             let software_adapter = instance
@@ -2034,7 +2060,7 @@ impl RenderVideoConstants {
                 adapter_device_type = ?adapter_info.device_type,
                 "Using software adapter (CPU rendering - performance may be reduced)"
             );
-            (software_adapter, true, adapter_info.name)
+            (software_adapter, true)
         };
 
         let mut required_features = wgpu::Features::empty();
@@ -2050,23 +2076,12 @@ impl RenderVideoConstants {
 
         let (device, queue) = adapter.request_device(&device_descriptor).await?;
 
-        let background_textures = Arc::new(BackgroundTextureCache::default());
-
-        if is_software_adapter {
-            frame_pipeline::note_software_adapter_in_use();
-        }
-
         Ok(Self {
-            _instance: instance,
-            _adapter: adapter,
+            instance,
+            adapter,
             device,
             queue,
-            options,
-            background_textures,
-            meta,
-            recording_meta,
             is_software_adapter,
-            adapter_name,
         })
     }
 }
@@ -2258,6 +2273,7 @@ pub struct ProjectUniforms {
     pub motion_blur_amount: f32,
     pub masks: Vec<PreparedMask>,
     pub texts: Vec<PreparedText>,
+    pub annotations: Vec<PreparedAnnotation>,
 }
 
 #[derive(Debug, Clone)]
@@ -3939,7 +3955,7 @@ impl ProjectUniforms {
                 }
             });
 
-        let masks = project
+        let mut masks = project
             .timeline
             .as_ref()
             .map(|timeline| {
@@ -3951,7 +3967,7 @@ impl ProjectUniforms {
             })
             .unwrap_or_default();
 
-        let texts = project
+        let mut texts = project
             .timeline
             .as_ref()
             .map(|timeline| {
@@ -3964,6 +3980,24 @@ impl ProjectUniforms {
             })
             .unwrap_or_default();
 
+        // Only studio projects have a timeline; the screenshot editor draws
+        // its annotations itself over the rendered frame.
+        let annotations = if project.timeline.is_some() && scene.should_render_screen() {
+            let prepared = prepare_annotations(
+                options,
+                project,
+                resolution_base,
+                &zoom,
+                frame_time as f64,
+                scene.screen_opacity,
+            );
+            masks.extend(prepared.masks);
+            texts.extend(prepared.texts);
+            prepared.shapes
+        } else {
+            Vec::new()
+        };
+
         Self {
             output_size,
             cursor_size: project.cursor.size as f32,
@@ -3987,6 +4021,7 @@ impl ProjectUniforms {
             motion_blur_amount: cursor_motion_blur,
             masks,
             texts,
+            annotations,
         }
     }
 }
@@ -4431,6 +4466,7 @@ pub struct FrameRenderStageTimings {
     pub camera_prepare_duration: std::time::Duration,
     pub camera_only_prepare_duration: std::time::Duration,
     pub camera_blur_prepare_duration: std::time::Duration,
+    pub annotation_prepare_duration: std::time::Duration,
     pub text_prepare_duration: std::time::Duration,
     pub captions_prepare_duration: std::time::Duration,
     pub keyboard_prepare_duration: std::time::Duration,
@@ -4563,7 +4599,7 @@ impl<'a> FrameRenderer<'a> {
         &mut self,
         outgoing: TransitionRenderInput<'_>,
         incoming: TransitionRenderInput<'_>,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
         layers: &mut RendererLayers,
     ) -> Result<Option<RenderedFrame>, RenderingError> {
@@ -4606,7 +4642,7 @@ impl<'a> FrameRenderer<'a> {
                 self.constants,
                 &outgoing,
                 &incoming,
-                (kind, progress),
+                (style, progress),
                 layers,
                 session,
                 compositor,
@@ -4644,12 +4680,12 @@ impl<'a> FrameRenderer<'a> {
         &mut self,
         outgoing: TransitionRenderInput<'_>,
         incoming: TransitionRenderInput<'_>,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
         layers: &mut RendererLayers,
     ) -> Result<RenderedFrame, RenderingError> {
         if let Some(frame) = self
-            .render_transition(outgoing, incoming, kind, progress, layers)
+            .render_transition(outgoing, incoming, style, progress, layers)
             .await?
         {
             return Ok(frame);
@@ -4759,13 +4795,13 @@ impl<'a> FrameRenderer<'a> {
         &mut self,
         outgoing: TransitionRenderInput<'_>,
         incoming: TransitionRenderInput<'_>,
-        kind: ClipTransitionType,
+        style: ClipTransitionStyle,
         progress: f32,
         layers: &mut RendererLayers,
     ) -> Result<Option<frame_pipeline::Nv12RenderedFrame>, RenderingError> {
         if self.constants.is_software_adapter {
             let frame = self
-                .render_transition(outgoing, incoming, kind, progress, layers)
+                .render_transition(outgoing, incoming, style, progress, layers)
                 .await?;
             return Ok(frame.map(|frame| self.convert_rgba_to_nv12(frame)));
         }
@@ -4808,7 +4844,7 @@ impl<'a> FrameRenderer<'a> {
                 self.constants,
                 &outgoing,
                 &incoming,
-                (kind, progress),
+                (style, progress),
                 layers,
                 session,
                 compositor,
@@ -5052,6 +5088,7 @@ pub struct RendererLayers {
     camera: CameraLayer,
     camera_only: CameraLayer,
     mask: MaskLayer,
+    annotation: AnnotationLayer,
     text: TextLayer,
     captions: CaptionsLayer,
     keyboard: KeyboardLayer,
@@ -5095,6 +5132,7 @@ impl RendererLayers {
                 shared_composite_pipeline,
             ),
             mask: MaskLayer::new(device),
+            annotation: AnnotationLayer::new(device),
             text: TextLayer::new(device, queue),
             captions: CaptionsLayer::new(device, queue),
             keyboard: KeyboardLayer::new(device, queue),
@@ -5303,6 +5341,13 @@ impl RendererLayers {
             self.run_shared_camera_blur(&constants.device, &constants.queue, mode);
         }
 
+        self.annotation.prepare(
+            &constants.device,
+            &constants.queue,
+            uniforms.output_size,
+            &uniforms.annotations,
+        );
+
         self.text.prepare(
             &constants.device,
             &constants.queue,
@@ -5468,6 +5513,15 @@ impl RendererLayers {
         }
         timings.camera_blur_prepare_duration = start.elapsed();
 
+        let start = Instant::now();
+        self.annotation.prepare(
+            &constants.device,
+            &constants.queue,
+            uniforms.output_size,
+            &uniforms.annotations,
+        );
+        timings.annotation_prepare_duration = start.elapsed();
+
         let start = Instant::now();
         self.text.prepare(
             &constants.device,
@@ -5593,6 +5647,11 @@ impl RendererLayers {
             }
         }
 
+        if self.annotation.has_content() {
+            let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
+            self.annotation.render(&mut pass);
+        }
+
         if !uniforms.texts.is_empty() {
             let mut pass = render_pass!(session.current_texture_view(), wgpu::LoadOp::Load);
             self.text.render(&mut pass);
@@ -5670,7 +5729,7 @@ async fn produce_transition_texture(
     constants: &RenderVideoConstants,
     outgoing: &TransitionRenderInput<'_>,
     incoming: &TransitionRenderInput<'_>,
-    transition: (ClipTransitionType, f32),
+    transition: (ClipTransitionStyle, f32),
     layers: &mut RendererLayers,
     session: &mut RenderSession,
     compositor: &TransitionCompositor,
@@ -5734,7 +5793,7 @@ async fn produce_transition_texture(
         session.current_texture(),
         session.current_texture_view(),
         TransitionParameters {
-            kind: transition.0,
+            style: transition.0,
             progress: transition.1,
             opaque: outgoing.render_display || incoming.render_display,
         },
@@ -142,6 +142,7 @@ impl ProjectRecordingsMeta {
                     display,
                     camera,
                     mic,
+                    additional_mics: Vec::new(),
                     system_audio: None,
                 }]
             }
@@ -202,6 +203,16 @@ impl ProjectRecordingsMeta {
                         mic: Option::map(s.mic.as_ref(), load_audio)
                             .transpose()
                             .map_err(|e| format!("mic / {e}"))?,
+                        additional_mics: s
+                            .additional_mics
+                            .iter()
+                            .enumerate()
+                            .map(|(i, mic)| {
+                                Option::map(mic.as_ref(), load_audio)
+                                    .transpose()
+                                    .map_err(|e| format!("mic {} / {e}", i + 2))
+                            })
+                            .collect::<Result<_, _>>()?,
                         system_audio,
                     })
                 })
@@ -227,6 +238,8 @@ pub struct SegmentRecordings {
     pub display: Video,
     pub camera: Option<Video>,
     pub mic: Option<Audio>,
+    /// In the recording's order, with `None` where one produced no recording.
+    pub additional_mics: Vec<Option<Audio>>,
     pub system_audio: Option<Audio>,
 }
 
@@ -239,6 +252,7 @@ impl SegmentRecordings {
         ]
         .into_iter()
         .flatten()
+        .chain(self.additional_mics.iter().flatten().map(|s| s.duration))
         .collect::<Vec<_>>();
         duration_ns.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
         duration_ns[0]
@@ -0,0 +1,162 @@
+struct Uniforms {
+    stroke_color: vec4<f32>,
+    fill_color: vec4<f32>,
+    center: vec2<f32>,
+    size: vec2<f32>,
+    arrow_head: vec2<f32>,
+    rotation: f32,
+    stroke_width: f32,
+    opacity: f32,
+    shape: u32,
+    _padding: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+
+const SHAPE_ARROW: u32 = 0u;
+const SHAPE_CIRCLE: u32 = 1u;
+const SHAPE_RECTANGLE: u32 = 2u;
+
+const EDGE_SOFTNESS: f32 = 0.75;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var positions = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(-1.0, 1.0),
+        vec2<f32>(1.0, -1.0),
+        vec2<f32>(1.0, 1.0),
+    );
+
+    var output: VertexOutput;
+    output.position = vec4<f32>(positions[vertex_index], 0.0, 1.0);
+    return output;
+}
+
+fn rotate(p: vec2<f32>, angle: f32) -> vec2<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec2<f32>(c * p.x - s * p.y, s * p.x + c * p.y);
+}
+
+fn box_sdf(p: vec2<f32>, half_size: vec2<f32>) -> f32 {
+    let d = abs(p) - half_size;
+    return length(max(d, vec2<f32>(0.0))) + min(max(d.x, d.y), 0.0);
+}
+
+// Approximate distance to an ellipse; exact for circles.
+fn ellipse_sdf(p: vec2<f32>, radii: vec2<f32>) -> f32 {
+    let r = max(radii, vec2<f32>(1e-3));
+    let k0 = length(p / r);
+    let k1 = length(p / (r * r));
+    if k1 <= 0.0 {
+        return -min(r.x, r.y);
+    }
+    return k0 * (k0 - 1.0) / k1;
+}
+
+fn segment_sdf(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+    let pa = p - a;
+    let ba = b - a;
+    let h = clamp(dot(pa, ba) / max(dot(ba, ba), 1e-6), 0.0, 1.0);
+    return length(pa - ba * h);
+}
+
+fn triangle_sdf(p: vec2<f32>, p0: vec2<f32>, p1: vec2<f32>, p2: vec2<f32>) -> f32 {
+    let e0 = p1 - p0;
+    let e1 = p2 - p1;
+    let e2 = p0 - p2;
+    let v0 = p - p0;
+    let v1 = p - p1;
+    let v2 = p - p2;
+    let pq0 = v0 - e0 * clamp(dot(v0, e0) / dot(e0, e0), 0.0, 1.0);
+    let pq1 = v1 - e1 * clamp(dot(v1, e1) / dot(e1, e1), 0.0, 1.0);
+    let pq2 = v2 - e2 * clamp(dot(v2, e2) / dot(e2, e2), 0.0, 1.0);
+    let s = sign(e0.x * e2.y - e0.y * e2.x);
+    let d = min(
+        min(
+            vec2<f32>(dot(pq0, pq0), s * (v0.x * e0.y - v0.y * e0.x)),
+            vec2<f32>(dot(pq1, pq1), s * (v1.x * e1.y - v1.y * e1.x)),
+        ),
+        vec2<f32>(dot(pq2, pq2), s * (v2.x * e2.y - v2.y * e2.x)),
+    );
+    return -sqrt(d.x) * sign(d.y);
+}
+
+fn coverage(distance: f32) -> f32 {
+    return 1.0 - smoothstep(-EDGE_SOFTNESS, EDGE_SOFTNESS, distance);
+}
+
+// Straight-alpha "over": the stroke sits on top of the fill.
+fn over(top: vec4<f32>, bottom: vec4<f32>) -> vec4<f32> {
+    let alpha = top.a + bottom.a * (1.0 - top.a);
+    if alpha <= 0.0 {
+        return vec4<f32>(0.0);
+    }
+    let rgb = (top.rgb * top.a + bottom.rgb * bottom.a * (1.0 - top.a)) / alpha;
+    return vec4<f32>(rgb, alpha);
+}
+
+fn arrow_color(p: vec2<f32>) -> vec4<f32> {
+    let start = -uniforms.size * 0.5;
+    let end = uniforms.size * 0.5;
+    let length_px = length(end - start);
+    if length_px < 1e-3 {
+        return vec4<f32>(0.0);
+    }
+
+    let direction = (end - start) / length_px;
+    let normal = vec2<f32>(-direction.y, direction.x);
+    let base = end - direction * min(uniforms.arrow_head.x, length_px);
+    let half_head = normal * uniforms.arrow_head.y * 0.5;
+
+    let line = segment_sdf(p, start, base) - uniforms.stroke_width * 0.5;
+    let head = triangle_sdf(p, end, base + half_head, base - half_head);
+    let color = uniforms.stroke_color;
+    return vec4<f32>(color.rgb, color.a * coverage(min(line, head)));
+}
+
+fn outline_color(p: vec2<f32>) -> vec4<f32> {
+    let half_size = abs(uniforms.size) * 0.5;
+    var distance: f32;
+    if uniforms.shape == SHAPE_CIRCLE {
+        distance = ellipse_sdf(p, half_size);
+    } else {
+        distance = box_sdf(p, half_size);
+    }
+
+    // Canvas-style strokes straddle the outline, half inside and half out.
+    let stroke_distance = abs(distance) - uniforms.stroke_width * 0.5;
+    let fill = vec4<f32>(uniforms.fill_color.rgb, uniforms.fill_color.a * coverage(distance));
+    let stroke = vec4<f32>(
+        uniforms.stroke_color.rgb,
+        uniforms.stroke_color.a * coverage(stroke_distance),
+    );
+    return over(stroke, fill);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    // Sampling with the inverse rotation turns the shape clockwise on screen
+    // (y points down).
+    let p = rotate(position.xy - uniforms.center, -uniforms.rotation);
+
+    var color: vec4<f32>;
+    if uniforms.shape == SHAPE_ARROW {
+        color = arrow_color(p);
+    } else {
+        color = outline_color(p);
+    }
+
+    let alpha = color.a * uniforms.opacity;
+    if alpha <= 0.0 {
+        discard;
+    }
+    return vec4<f32>(color.rgb, alpha);
+}
@@ -3,8 +3,27 @@ struct TransitionUniforms {
     kind: u32,
     opaque: u32,
     padding: u32,
+    direction: vec2<f32>,
+    direction_padding: vec2<f32>,
 };
 
+const KIND_CROSS_FADE: u32 = 0u;
+const KIND_FADE_THROUGH_BLACK: u32 = 1u;
+const KIND_WIPE: u32 = 2u;
+const KIND_SLIDE: u32 = 3u;
+const KIND_IRIS: u32 = 4u;
+const KIND_ZOOM_THROUGH: u32 = 5u;
+const KIND_BLUR_DISSOLVE: u32 = 6u;
+
+// Width of the soft edge on wipes and irises, as a fraction of the sweep.
+const EDGE_SOFTNESS: f32 = 0.02;
+// How far past full size the clips are scaled at the peak of a zoom-through.
+const ZOOM_AMOUNT: f32 = 0.6;
+// Blur radius in pixels at the midpoint of a blur dissolve.
+const MAX_BLUR_RADIUS: f32 = 24.0;
+const BLUR_TAPS: u32 = 12u;
+const TAU: f32 = 6.28318530718;
+
 @group(0) @binding(0) var<uniform> uniforms: TransitionUniforms;
 @group(0) @binding(1) var outgoing_texture: texture_2d<f32>;
 @group(0) @binding(2) var incoming_texture: texture_2d<f32>;
@@ -29,19 +48,102 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
     return output;
 }
 
-@fragment
-fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-    let outgoing = textureSample(outgoing_texture, frame_sampler, input.uv);
-    let incoming = textureSample(incoming_texture, frame_sampler, input.uv);
-    if uniforms.kind == 0u {
-        return mix(outgoing, incoming, uniforms.progress);
+fn sample_outgoing(uv: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(outgoing_texture, frame_sampler, uv, 0.0);
+}
+
+fn sample_incoming(uv: vec2<f32>) -> vec4<f32> {
+    return textureSampleLevel(incoming_texture, frame_sampler, uv, 0.0);
+}
+
+// How far along the motion direction `uv` lies, from 0 at the side the
+// motion starts from to 1 at the side it heads towards.
+fn position_along_direction(uv: vec2<f32>) -> f32 {
+    return dot(uv - 0.5, uniforms.direction) + 0.5;
+}
+
+// Coverage of the incoming clip behind a soft edge that travels from just
+// before `position` 0 at progress 0 to just past 1 at progress 1.
+fn sweep(position: f32, progress: f32) -> f32 {
+    let edge = mix(-EDGE_SOFTNESS, 1.0 + EDGE_SOFTNESS, progress);
+    return 1.0 - smoothstep(edge - EDGE_SOFTNESS, edge + EDGE_SOFTNESS, position);
+}
+
+fn zoomed_uv(uv: vec2<f32>, scale: f32) -> vec2<f32> {
+    return (uv - 0.5) / scale + 0.5;
+}
+
+fn blurred(source: texture_2d<f32>, uv: vec2<f32>, radius: f32) -> vec4<f32> {
+    var sum = textureSampleLevel(source, frame_sampler, uv, 0.0);
+    if radius <= 0.0 {
+        return sum;
+    }
+
+    let texel = 1.0 / vec2<f32>(textureDimensions(source));
+    for (var tap = 0u; tap < BLUR_TAPS; tap++) {
+        let angle = f32(tap) * TAU / f32(BLUR_TAPS);
+        // Alternate taps between half and full radius to fill the disc.
+        let ring = select(0.5, 1.0, tap % 2u == 1u);
+        let offset = vec2<f32>(cos(angle), sin(angle)) * radius * ring * texel;
+        sum += textureSampleLevel(source, frame_sampler, uv + offset, 0.0);
     }
+    return sum / f32(BLUR_TAPS + 1u);
+}
 
-    let outgoing_weight = max(1.0 - uniforms.progress * 2.0, 0.0);
-    let incoming_weight = max(uniforms.progress * 2.0 - 1.0, 0.0);
+fn fade_through_black(outgoing: vec4<f32>, incoming: vec4<f32>, progress: f32) -> vec4<f32> {
+    let outgoing_weight = max(1.0 - progress * 2.0, 0.0);
+    let incoming_weight = max(progress * 2.0 - 1.0, 0.0);
     let black_weight = 1.0 - outgoing_weight - incoming_weight;
     let black_alpha = select(0.0, 1.0, uniforms.opaque != 0u);
     return outgoing * outgoing_weight
         + incoming * incoming_weight
         + vec4<f32>(0.0, 0.0, 0.0, black_alpha) * black_weight;
 }
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    let uv = input.uv;
+    let progress = uniforms.progress;
+
+    var color: vec4<f32>;
+    switch uniforms.kind {
+        case KIND_FADE_THROUGH_BLACK: {
+            color = fade_through_black(sample_outgoing(uv), sample_incoming(uv), progress);
+        }
+        case KIND_WIPE: {
+            let coverage = sweep(position_along_direction(uv), progress);
+            color = mix(sample_outgoing(uv), sample_incoming(uv), coverage);
+        }
+        case KIND_SLIDE: {
+            // The incoming clip enters from the side the motion starts from
+            // and the outgoing clip leaves ahead of it, edge to edge.
+            let outgoing = sample_outgoing(uv - uniforms.direction * progress);
+            let incoming = sample_incoming(uv + uniforms.direction * (1.0 - progress));
+            color = select(outgoing, incoming, position_along_direction(uv) < progress);
+        }
+        case KIND_IRIS: {
+            let size = vec2<f32>(textureDimensions(incoming_texture));
+            let aspect = vec2<f32>(size.x / size.y, 1.0);
+            let radius = length((uv - 0.5) * aspect) / length(aspect * 0.5);
+            let coverage = sweep(radius, progress);
+            color = mix(sample_outgoing(uv), sample_incoming(uv), coverage);
+        }
+        case KIND_ZOOM_THROUGH: {
+            let outgoing = sample_outgoing(zoomed_uv(uv, 1.0 + ZOOM_AMOUNT * progress));
+            let incoming =
+                sample_incoming(zoomed_uv(uv, 1.0 + ZOOM_AMOUNT * (1.0 - progress)));
+            color = mix(outgoing, incoming, smoothstep(0.3, 0.7, progress));
+        }
+        case KIND_BLUR_DISSOLVE: {
+            let radius = MAX_BLUR_RADIUS * sin(progress * TAU * 0.5);
+            let outgoing = blurred(outgoing_texture, uv, radius);
+            let incoming = blurred(incoming_texture, uv, radius);
+            color = mix(outgoing, incoming, progress);
+        }
+        case KIND_CROSS_FADE, default: {
+            color = mix(sample_outgoing(uv), sample_incoming(uv), progress);
+        }
+    }
+
+    return color;
+}
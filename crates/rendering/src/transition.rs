@@ -1,4 +1,4 @@
-use cap_project::ClipTransitionType;
+use cap_project::{ClipTransitionStyle, ClipTransitionType};
 use wgpu::util::DeviceExt;
 
 #[repr(C)]
@@ -8,6 +8,8 @@ struct TransitionUniforms {
     kind: u32,
     opaque: u32,
     padding: u32,
+    direction: [f32; 2],
+    direction_padding: [f32; 2],
 }
 
 struct TransitionTextures {
@@ -27,7 +29,8 @@ pub struct TransitionCompositor {
 }
 
 pub struct TransitionParameters {
-    pub kind: ClipTransitionType,
+    pub style: ClipTransitionStyle,
+    /// Linear progress; the compositor applies the style's easing.
     pub progress: f32,
     pub opaque: bool,
 }
@@ -122,6 +125,8 @@ impl TransitionCompositor {
                 kind: 0,
                 opaque: 1,
                 padding: 0,
+                direction: [-1.0, 0.0],
+                direction_padding: [0.0; 2],
             }),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
@@ -226,17 +231,26 @@ impl TransitionCompositor {
             textures.width,
             textures.height,
         );
+        let style = parameters.style;
+        let direction = style.direction.vector();
         queue.write_buffer(
             &self.uniforms_buffer,
             0,
             bytemuck::bytes_of(&TransitionUniforms {
-                progress: parameters.progress.clamp(0.0, 1.0),
-                kind: match parameters.kind {
+                progress: style.easing.apply(parameters.progress as f64) as f32,
+                kind: match style.kind {
                     ClipTransitionType::CrossFade => 0,
                     ClipTransitionType::FadeThroughBlack => 1,
+                    ClipTransitionType::Wipe => 2,
+                    ClipTransitionType::Slide => 3,
+                    ClipTransitionType::Iris => 4,
+                    ClipTransitionType::ZoomThrough => 5,
+                    ClipTransitionType::BlurDissolve => 6,
                 },
                 opaque: u32::from(parameters.opaque),
                 padding: 0,
+                direction: [direction.x as f32, direction.y as f32],
+                direction_padding: [0.0; 2],
             }),
         );
 
@@ -1501,6 +1501,7 @@ mod tests {
             caption_segments: vec![],
             keyboard_segments: vec![],
             audio_segments: vec![],
+            volume_keyframes: Default::default(),
         };
         let cursor = CursorEvents {
             moves: vec![
@@ -1562,6 +1563,8 @@ mod tests {
                 segment_index: 1,
                 kind: ClipTransitionType::CrossFade,
                 duration: 0.5,
+                easing: Default::default(),
+                direction: Default::default(),
             }],
             zoom_segments: Vec::new(),
             scene_segments: Vec::new(),
@@ -1570,6 +1573,7 @@ mod tests {
             caption_segments: Vec::new(),
             keyboard_segments: Vec::new(),
             audio_segments: Vec::new(),
+            volume_keyframes: Default::default(),
         };
         let map = build_time_map(Some(&timeline));
 
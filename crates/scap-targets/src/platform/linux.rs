@@ -274,6 +274,13 @@ impl WindowImpl {
     pub fn level(&self) -> Option<i32> {
         Some(0)
     }
+
+    /// The X11 window to capture on its own with XComposite, or `None` for
+    /// the placeholder that stands in for a window picked through the
+    /// Wayland portal.
+    pub fn x11_window(&self) -> Option<u32> {
+        (!is_wayland_portal_window(self.0)).then_some(self.0)
+    }
 }
 
 fn filter_window_candidates(windows: Vec<Window>) -> Vec<WindowImpl> {
@@ -0,0 +1,597 @@
+use std::path::{Path, PathBuf};
+
+use cap_audio::AudioData;
+use ffmpeg::{
+    ChannelLayout, codec as avcodec,
+    format::{self as avformat},
+    software::resampling,
+};
+
+/// Sample rate of the audio handed to transcription engines.
+pub const TRANSCRIPTION_SAMPLE_RATE: u32 = 16000;
+
+enum AudioExtractionSource {
+    ProjectDirectory {
+        base_path: PathBuf,
+        meta_path: PathBuf,
+    },
+    MediaFile(PathBuf),
+}
+
+fn resolve_audio_extraction_source(source: &Path) -> Result<AudioExtractionSource, String> {
+    let path = source.to_path_buf();
+    let metadata =
+        std::fs::metadata(&path).map_err(|e| format!("Failed to read video path metadata: {e}"))?;
+
+    if metadata.is_dir() {
+        let meta_path = path.join("recording-meta.json");
+        if !meta_path.is_file() {
+            return Err("Recording directory is missing recording-meta.json".to_string());
+        }
+
+        return Ok(AudioExtractionSource::ProjectDirectory {
+            base_path: path,
+            meta_path,
+        });
+    }
+
+    if metadata.is_file() {
+        return Ok(AudioExtractionSource::MediaFile(path));
+    }
+
+    Err("Video path is neither a file nor a recording directory".to_string())
+}
+
+/// Writes the audio of a media file, or the mixed mic and system audio of every
+/// segment of a recording project, to `output_path` as 16 kHz mono PCM WAV.
+pub async fn extract_audio(source: &Path, output_path: &Path) -> Result<(), String> {
+    tracing::info!("=== EXTRACT AUDIO START ===");
+    tracing::info!("Attempting to extract audio from: {source:?}");
+    tracing::info!("Output path: {output_path:?}");
+
+    match resolve_audio_extraction_source(source)? {
+        AudioExtractionSource::ProjectDirectory {
+            base_path,
+            meta_path,
+        } => {
+            tracing::info!("Detected recording project directory");
+
+            let meta_content = std::fs::read_to_string(&meta_path)
+                .map_err(|e| format!("Failed to read recording metadata: {e}"))?;
+
+            let meta: serde_json::Value = serde_json::from_str(&meta_content)
+                .map_err(|e| format!("Failed to parse recording metadata: {e}"))?;
+
+            struct SegmentAudio {
+                sources: Vec<PathBuf>,
+            }
+
+            let mut segment_audios: Vec<SegmentAudio> = Vec::new();
+
+            if let Some(segments) = meta["segments"].as_array() {
+                for segment in segments {
+                    let mut sources = Vec::new();
+                    let mut push_source = |path: Option<&str>| {
+                        if let Some(path) = path {
+                            let full_path = base_path.join(path);
+                            if full_path.exists() && !sources.contains(&full_path) {
+                                sources.push(full_path);
+                            }
+                        }
+                    };
+
+                    push_source(segment["system_audio"]["path"].as_str());
+                    push_source(segment["mic"]["path"].as_str());
+                    if let Some(mics) = segment["additional_mics"].as_array() {
+                        for mic in mics {
+                            push_source(mic["path"].as_str());
+                        }
+                    }
+                    push_source(segment["audio"]["path"].as_str());
+
+                    if !sources.is_empty() {
+                        segment_audios.push(SegmentAudio { sources });
+                    }
+                }
+            }
+
+            if segment_audios.is_empty() {
+                return Err("No audio sources found in the recording metadata".to_string());
+            }
+
+            tracing::info!("Found {} segments with audio sources", segment_audios.len());
+
+            let mut final_samples: Vec<f32> = Vec::new();
+
+            for (segment_idx, segment_audio) in segment_audios.iter().enumerate() {
+                tracing::info!(
+                    "Processing segment {} with {} audio sources",
+                    segment_idx,
+                    segment_audio.sources.len()
+                );
+
+                let mut segment_samples: Vec<f32> = Vec::new();
+
+                for source in &segment_audio.sources {
+                    match AudioData::from_file(source) {
+                        Ok(audio) => {
+                            tracing::info!(
+                                "Processing audio source {:?}: {} channels, {} samples",
+                                source,
+                                audio.channels(),
+                                audio.sample_count()
+                            );
+
+                            let mono_samples = if audio.channels() > 1 {
+                                convert_to_mono(audio.samples(), audio.channels() as usize)
+                            } else {
+                                audio.samples().to_vec()
+                            };
+
+                            if segment_samples.is_empty() {
+                                segment_samples = mono_samples;
+                            } else {
+                                mix_samples(&mut segment_samples, &mono_samples);
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to process audio source {source:?}: {e}");
+                            continue;
+                        }
+                    }
+                }
+
+                if !segment_samples.is_empty() {
+                    tracing::info!(
+                        "Segment {} produced {} samples, appending to final audio",
+                        segment_idx,
+                        segment_samples.len()
+                    );
+                    final_samples.extend(segment_samples);
+                }
+            }
+
+            let mut mixed_samples = final_samples;
+            let channel_count = 1_usize;
+
+            if mixed_samples.is_empty() {
+                tracing::error!("No audio samples after processing all sources");
+                return Err("Failed to process any audio sources".to_string());
+            }
+
+            let gain = normalize_audio_for_transcription(&mut mixed_samples);
+            if (gain - 1.0).abs() > 0.01 {
+                tracing::info!("Applied transcription audio gain: {gain:.2}x");
+            }
+
+            tracing::info!("Final mixed audio: {} samples", mixed_samples.len());
+            let mix_rms = (mixed_samples.iter().map(|&s| s * s).sum::<f32>()
+                / mixed_samples.len() as f32)
+                .sqrt();
+            tracing::info!("Mixed audio RMS: {mix_rms:.4}");
+
+            if mix_rms < 0.001 {
+                tracing::warn!(
+                    "WARNING: Mixed audio RMS is very low ({mix_rms:.6}) - audio may be nearly silent!"
+                );
+            }
+
+            let mut output = avformat::output(&output_path)
+                .map_err(|e| format!("Failed to create output file: {e}"))?;
+
+            let codec = avcodec::encoder::find_by_name("pcm_s16le")
+                .ok_or_else(|| "PCM encoder not found".to_string())?;
+
+            let mut encoder = avcodec::Context::new()
+                .encoder()
+                .audio()
+                .map_err(|e| format!("Failed to create encoder: {e}"))?;
+
+            encoder.set_rate(TRANSCRIPTION_SAMPLE_RATE as i32);
+            let channel_layout = ChannelLayout::MONO;
+            encoder.set_channel_layout(channel_layout);
+            encoder.set_format(avformat::Sample::I16(avformat::sample::Type::Packed));
+
+            let mut encoder = encoder
+                .open_as(codec)
+                .map_err(|e| format!("Failed to open encoder: {e}"))?;
+
+            let mut stream = output
+                .add_stream(codec)
+                .map_err(|e| format!("Failed to add stream: {e}"))?;
+            stream.set_parameters(&encoder);
+
+            output
+                .write_header()
+                .map_err(|e| format!("Failed to write header: {e}"))?;
+
+            let mut resampler = resampling::Context::get(
+                avformat::Sample::F32(avformat::sample::Type::Packed),
+                channel_layout,
+                AudioData::SAMPLE_RATE,
+                avformat::Sample::I16(avformat::sample::Type::Packed),
+                channel_layout,
+                TRANSCRIPTION_SAMPLE_RATE,
+            )
+            .map_err(|e| format!("Failed to create resampler: {e}"))?;
+
+            let frame_size = encoder.frame_size() as usize;
+            let frame_size = if frame_size == 0 { 1024 } else { frame_size };
+
+            tracing::info!(
+                "Using frame size: {}, total samples: {}, channel count: {}",
+                frame_size,
+                mixed_samples.len(),
+                channel_count
+            );
+
+            let mut frame = ffmpeg::frame::Audio::new(
+                avformat::Sample::I16(avformat::sample::Type::Packed),
+                frame_size,
+                ChannelLayout::MONO,
+            );
+            frame.set_rate(TRANSCRIPTION_SAMPLE_RATE);
+
+            if !mixed_samples.is_empty() && frame_size * channel_count > 0 {
+                for (chunk_idx, chunk) in
+                    mixed_samples.chunks(frame_size * channel_count).enumerate()
+                {
+                    if chunk_idx % 100 == 0 {
+                        tracing::info!("Processing chunk {}, size: {}", chunk_idx, chunk.len());
+                    }
+
+                    let mut input_frame = ffmpeg::frame::Audio::new(
+                        avformat::Sample::F32(avformat::sample::Type::Packed),
+                        chunk.len() / channel_count,
+                        channel_layout,
+                    );
+                    input_frame.set_rate(AudioData::SAMPLE_RATE);
+
+                    let bytes = unsafe {
+                        std::slice::from_raw_parts(
+                            chunk.as_ptr() as *const u8,
+                            std::mem::size_of_val(chunk),
+                        )
+                    };
+                    input_frame.data_mut(0)[0..bytes.len()].copy_from_slice(bytes);
+
+                    let mut output_frame = ffmpeg::frame::Audio::new(
+                        avformat::Sample::I16(avformat::sample::Type::Packed),
+                        frame_size,
+                        ChannelLayout::MONO,
+                    );
+                    output_frame.set_rate(TRANSCRIPTION_SAMPLE_RATE);
+
+                    match resampler.run(&input_frame, &mut output_frame) {
+                        Ok(_) => {
+                            if chunk_idx % 100 == 0 {
+                                tracing::info!(
+                                    "Successfully resampled chunk {}, output samples: {}",
+                                    chunk_idx,
+                                    output_frame.samples()
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to resample chunk {chunk_idx}: {e}");
+                            continue;
+                        }
+                    }
+
+                    if let Err(e) = encoder.send_frame(&output_frame) {
+                        tracing::error!("Failed to send frame to encoder: {e}");
+                        continue;
+                    }
+
+                    loop {
+                        let mut packet = ffmpeg::Packet::empty();
+                        match encoder.receive_packet(&mut packet) {
+                            Ok(_) => {
+                                if let Err(e) = packet.write_interleaved(&mut output) {
+                                    tracing::error!("Failed to write packet: {e}");
+                                }
+                            }
+                            Err(_) => break,
+                        }
+                    }
+                }
+            }
+
+            encoder
+                .send_eof()
+                .map_err(|e| format!("Failed to send EOF: {e}"))?;
+
+            loop {
+                let mut packet = ffmpeg::Packet::empty();
+                let received = encoder.receive_packet(&mut packet);
+
+                if received.is_err() {
+                    break;
+                }
+
+                {
+                    if let Err(e) = packet.write_interleaved(&mut output) {
+                        return Err(format!("Failed to write final packet: {e}"));
+                    }
+                }
+            }
+
+            output
+                .write_trailer()
+                .map_err(|e| format!("Failed to write trailer: {e}"))?;
+
+            tracing::info!("=== EXTRACT AUDIO END (from recording project) ===");
+            Ok(())
+        }
+        AudioExtractionSource::MediaFile(video_path) => {
+            let mut input = avformat::input(&video_path)
+                .map_err(|e| format!("Failed to open video file: {e}"))?;
+
+            let stream = input
+                .streams()
+                .best(ffmpeg::media::Type::Audio)
+                .ok_or_else(|| "No audio stream found".to_string())?;
+
+            let codec_params = stream.parameters();
+
+            let decoder_ctx = avcodec::Context::from_parameters(codec_params.clone())
+                .map_err(|e| format!("Failed to create decoder context: {e}"))?;
+
+            let mut decoder = decoder_ctx
+                .decoder()
+                .audio()
+                .map_err(|e| format!("Failed to create decoder: {e}"))?;
+
+            let decoder_format = decoder.format();
+            let decoder_channel_layout = decoder.channel_layout();
+            let decoder_rate = decoder.rate();
+
+            let channel_layout = ChannelLayout::MONO;
+
+            let mut encoder_ctx = avcodec::Context::new()
+                .encoder()
+                .audio()
+                .map_err(|e| format!("Failed to create encoder: {e}"))?;
+
+            encoder_ctx.set_rate(TRANSCRIPTION_SAMPLE_RATE as i32);
+            encoder_ctx.set_channel_layout(channel_layout);
+            encoder_ctx.set_format(avformat::Sample::I16(avformat::sample::Type::Packed));
+
+            let codec = avcodec::encoder::find_by_name("pcm_s16le")
+                .ok_or_else(|| "PCM encoder not found".to_string())?;
+
+            let mut encoder = encoder_ctx
+                .open_as(codec)
+                .map_err(|e| format!("Failed to open encoder: {e}"))?;
+
+            let mut output = avformat::output(&output_path)
+                .map_err(|e| format!("Failed to create output file: {e}"))?;
+
+            let stream_params = {
+                let mut output_stream = output
+                    .add_stream(codec)
+                    .map_err(|e| format!("Failed to add stream: {e}"))?;
+
+                output_stream.set_parameters(&encoder);
+
+                (output_stream.index(), output_stream.id())
+            };
+
+            output
+                .write_header()
+                .map_err(|e| format!("Failed to write header: {e}"))?;
+
+            let mut resampler = resampling::Context::get(
+                decoder_format,
+                decoder_channel_layout,
+                decoder_rate,
+                avformat::Sample::I16(avformat::sample::Type::Packed),
+                channel_layout,
+                TRANSCRIPTION_SAMPLE_RATE,
+            )
+            .map_err(|e| format!("Failed to create resampler: {e}"))?;
+
+            let mut decoded_frame = ffmpeg::frame::Audio::empty();
+            let mut resampled_frame = ffmpeg::frame::Audio::new(
+                avformat::Sample::I16(avformat::sample::Type::Packed),
+                encoder.frame_size() as usize,
+                channel_layout,
+            );
+
+            let input_stream_index = stream.index();
+
+            let mut packet_queue = Vec::new();
+
+            {
+                for (stream_idx, packet) in input.packets() {
+                    if stream_idx.index() == input_stream_index
+                        && let Some(data) = packet.data()
+                    {
+                        let mut cloned_packet = ffmpeg::Packet::copy(data);
+                        if let Some(pts) = packet.pts() {
+                            cloned_packet.set_pts(Some(pts));
+                        }
+                        if let Some(dts) = packet.dts() {
+                            cloned_packet.set_dts(Some(dts));
+                        }
+                        packet_queue.push(cloned_packet);
+                    }
+                }
+            }
+
+            for packet_res in packet_queue {
+                if let Err(e) = decoder.send_packet(&packet_res) {
+                    tracing::warn!("Failed to send packet to decoder: {e}");
+                    continue;
+                }
+
+                while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                    if let Err(e) = resampler.run(&decoded_frame, &mut resampled_frame) {
+                        tracing::warn!("Failed to resample audio: {e}");
+                        continue;
+                    }
+
+                    if let Err(e) = encoder.send_frame(&resampled_frame) {
+                        tracing::warn!("Failed to send frame to encoder: {e}");
+                        continue;
+                    }
+
+                    loop {
+                        let mut packet = ffmpeg::Packet::empty();
+                        match encoder.receive_packet(&mut packet) {
+                            Ok(_) => {
+                                packet.set_stream(stream_params.0);
+
+                                if let Err(e) = packet.write_interleaved(&mut output) {
+                                    tracing::error!("Failed to write packet: {e}");
+                                }
+                            }
+                            Err(_) => break,
+                        }
+                    }
+                }
+            }
+
+            decoder
+                .send_eof()
+                .map_err(|e| format!("Failed to send EOF to decoder: {e}"))?;
+
+            while decoder.receive_frame(&mut decoded_frame).is_ok() {
+                resampler
+                    .run(&decoded_frame, &mut resampled_frame)
+                    .map_err(|e| format!("Failed to resample final audio: {e}"))?;
+
+                encoder
+                    .send_frame(&resampled_frame)
+                    .map_err(|e| format!("Failed to send final frame: {e}"))?;
+
+                loop {
+                    let mut packet = ffmpeg::Packet::empty();
+                    let received = encoder.receive_packet(&mut packet);
+
+                    if received.is_err() {
+                        break;
+                    }
+
+                    packet
+                        .write_interleaved(&mut output)
+                        .map_err(|e| format!("Failed to write final packet: {e}"))?;
+                }
+            }
+
+            output
+                .write_trailer()
+                .map_err(|e| format!("Failed to write trailer: {e}"))?;
+
+            tracing::info!("=== EXTRACT AUDIO END (from video) ===");
+            Ok(())
+        }
+    }
+}
+
+pub(crate) fn normalize_audio_for_transcription(samples: &mut [f32]) -> f32 {
+    if samples.is_empty() {
+        return 1.0;
+    }
+
+    let peak = samples
+        .iter()
+        .fold(0.0_f32, |max, sample| max.max(sample.abs()));
+    if peak <= f32::EPSILON {
+        return 1.0;
+    }
+
+    let rms =
+        (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt();
+    if rms <= f32::EPSILON {
+        return 1.0;
+    }
+
+    let target_rms = 0.08_f32;
+    let desired_gain = (target_rms / rms).clamp(1.0, 8.0);
+    let peak_limited_gain = 0.98 / peak;
+    let gain = desired_gain.min(peak_limited_gain);
+
+    if (gain - 1.0).abs() > 0.01 {
+        for sample in samples {
+            *sample = (*sample * gain).clamp(-0.98, 0.98);
+        }
+    }
+
+    gain
+}
+
+fn convert_to_mono(samples: &[f32], channels: usize) -> Vec<f32> {
+    if channels == 1 {
+        return samples.to_vec();
+    }
+
+    let sample_count = samples.len() / channels;
+    let mut mono_samples = Vec::with_capacity(sample_count);
+
+    for i in 0..sample_count {
+        let mut sample_sum = 0.0;
+        for c in 0..channels {
+            sample_sum += samples[i * channels + c];
+        }
+        mono_samples.push(sample_sum / channels as f32);
+    }
+
+    mono_samples
+}
+
+fn mix_samples(dest: &mut [f32], source: &[f32]) -> usize {
+    let length = dest.len().min(source.len());
+    for i in 0..length {
+        dest[i] = (dest[i] + source[i]) * 0.5;
+    }
+    length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AudioExtractionSource, resolve_audio_extraction_source};
+    use tempfile::tempdir;
+
+    #[test]
+    fn audio_extraction_source_accepts_project_directory_without_cap_extension() {
+        let dir = tempdir().unwrap();
+        let project_dir = dir.path().join("recording");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(project_dir.join("recording-meta.json"), "{}").unwrap();
+
+        match resolve_audio_extraction_source(&project_dir).unwrap() {
+            AudioExtractionSource::ProjectDirectory {
+                base_path,
+                meta_path,
+            } => {
+                assert_eq!(base_path, project_dir);
+                assert_eq!(meta_path, base_path.join("recording-meta.json"));
+            }
+            AudioExtractionSource::MediaFile(_) => panic!("expected project directory"),
+        }
+    }
+
+    #[test]
+    fn audio_extraction_source_rejects_directory_without_recording_metadata() {
+        let dir = tempdir().unwrap();
+        let result = resolve_audio_extraction_source(dir.path());
+
+        match result {
+            Ok(_) => panic!("expected missing metadata error"),
+            Err(error) => assert_eq!(error, "Recording directory is missing recording-meta.json"),
+        }
+    }
+
+    #[test]
+    fn audio_extraction_source_accepts_media_file() {
+        let dir = tempdir().unwrap();
+        let media_file = dir.path().join("recording.mp4");
+        std::fs::write(&media_file, []).unwrap();
+
+        match resolve_audio_extraction_source(&media_file).unwrap() {
+            AudioExtractionSource::MediaFile(path) => assert_eq!(path, media_file),
+            AudioExtractionSource::ProjectDirectory { .. } => panic!("expected media file"),
+        }
+    }
+}
@@ -0,0 +1,142 @@
+mod audio;
+mod parakeet;
+mod whisper;
+mod words;
+
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+};
+
+use cap_project::CaptionSegment;
+
+pub use audio::{TRANSCRIPTION_SAMPLE_RATE, extract_audio};
+#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+pub use parakeet::PARAKEET_UNSUPPORTED_MESSAGE;
+pub use parakeet::ParakeetEngine;
+pub use whisper::WhisperEngine;
+
+/// Turns mono 16kHz PCM into timed caption segments.
+///
+/// Implementations run on a blocking thread and may cache their model between
+/// calls; [`release_models`] drops any such cache.
+pub trait TranscriptionEngine: Send + Sync {
+    /// Short lowercase identifier used in logs and errors.
+    fn name(&self) -> &'static str;
+
+    /// Transcribes a 16-bit little-endian mono WAV at
+    /// [`TRANSCRIPTION_SAMPLE_RATE`], returning segments timed in seconds from
+    /// the start of the audio.
+    fn transcribe(
+        &self,
+        audio_path: &Path,
+        options: &TranscriptionOptions,
+    ) -> Result<Vec<CaptionSegment>, String>;
+}
+
+#[derive(Debug, Clone)]
+pub struct TranscriptionOptions {
+    /// ISO 639-1 language code, or "auto" to detect it.
+    pub language: String,
+    /// Names and terms the engine should prefer when the audio is ambiguous.
+    pub hints: Vec<String>,
+}
+
+impl Default for TranscriptionOptions {
+    fn default() -> Self {
+        Self {
+            language: "auto".to_string(),
+            hints: Vec::new(),
+        }
+    }
+}
+
+/// Picks the engine a model on disk belongs to: Parakeet models are
+/// directories of ONNX files, Whisper models are single GGML files.
+pub fn engine_for_model(model_path: impl Into<PathBuf>) -> Arc<dyn TranscriptionEngine> {
+    let model_path = model_path.into();
+    if model_path.is_dir() {
+        Arc::new(ParakeetEngine::new(model_path))
+    } else {
+        Arc::new(WhisperEngine::new(model_path))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptionError {
+    #[error("Failed to create temporary directory: {0}")]
+    TempDir(std::io::Error),
+    #[error("Failed to extract audio from video: {0}")]
+    AudioExtraction(String),
+    #[error("Failed to create audio file for transcription")]
+    MissingAudio,
+    #[error("{0} task panicked: {1}")]
+    TaskPanicked(&'static str, String),
+    #[error("Failed to transcribe audio: {0}")]
+    Engine(String),
+    #[error("No speech detected in the audio")]
+    NoSpeech,
+}
+
+// Engines hold large models and saturate the CPU, so run one transcription at
+// a time no matter how many callers ask.
+static TRANSCRIPTION_LOCK: LazyLock<std::sync::Mutex<()>> =
+    LazyLock::new(|| std::sync::Mutex::new(()));
+
+fn lock_transcription_worker_slot() -> std::sync::MutexGuard<'static, ()> {
+    TRANSCRIPTION_LOCK
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Transcribes the audio of a media file or a '.cap' recording directory
+/// (mixing its microphone and system audio), with segments timed against the
+/// recording.
+pub async fn transcribe(
+    source: &Path,
+    engine: Arc<dyn TranscriptionEngine>,
+    options: TranscriptionOptions,
+) -> Result<Vec<CaptionSegment>, TranscriptionError> {
+    let temp_dir = tempfile::tempdir().map_err(TranscriptionError::TempDir)?;
+    let audio_path = temp_dir.path().join("audio.wav");
+    tracing::info!("Temp audio path: {audio_path:?}");
+
+    extract_audio(source, &audio_path)
+        .await
+        .map_err(TranscriptionError::AudioExtraction)?;
+
+    if !audio_path.exists() {
+        tracing::error!("Audio file was not created at {audio_path:?}");
+        return Err(TranscriptionError::MissingAudio);
+    }
+
+    let engine_name = engine.name();
+    tracing::info!("Starting {engine_name} transcription in blocking task...");
+    let segments = tokio::task::spawn_blocking(move || {
+        let _guard = lock_transcription_worker_slot();
+        engine.transcribe(&audio_path, &options)
+    })
+    .await
+    .map_err(|e| TranscriptionError::TaskPanicked(engine_name, e.to_string()))?
+    .map_err(TranscriptionError::Engine)?;
+
+    if segments.is_empty() {
+        tracing::warn!("No caption segments were generated");
+        return Err(TranscriptionError::NoSpeech);
+    }
+
+    tracing::info!("Transcription produced {} segments", segments.len());
+    Ok(segments)
+}
+
+/// Drops every cached model to free memory.
+pub async fn release_models() {
+    whisper::release_whisper_context().await;
+    parakeet::release_parakeet_context().await;
+}
+
+/// Forgets a cached Parakeet model loaded from `model_dir`, for when its files
+/// are replaced or deleted.
+pub async fn invalidate_parakeet_model(model_dir: &Path) {
+    parakeet::invalidate_parakeet_cache_for_dir(model_dir).await;
+}
@@ -0,0 +1,231 @@
+//! Parakeet TDT transcription. parakeet-rs's ONNX runtime has no Intel macOS
+//! build, so there the engine reports itself unavailable.
+
+use std::path::{Path, PathBuf};
+
+use cap_project::CaptionSegment;
+
+use crate::{TranscriptionEngine, TranscriptionOptions};
+
+#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+pub const PARAKEET_UNSUPPORTED_MESSAGE: &str =
+    "Parakeet transcription is not available on Intel macOS";
+
+/// Transcribes with a Parakeet TDT model directory (`vocab.txt` plus the
+/// encoder and decoder ONNX files).
+pub struct ParakeetEngine {
+    model_dir: PathBuf,
+}
+
+impl ParakeetEngine {
+    pub fn new(model_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            model_dir: model_dir.into(),
+        }
+    }
+}
+
+impl TranscriptionEngine for ParakeetEngine {
+    fn name(&self) -> &'static str {
+        "parakeet"
+    }
+
+    fn transcribe(
+        &self,
+        audio_path: &Path,
+        _options: &TranscriptionOptions,
+    ) -> Result<Vec<CaptionSegment>, String> {
+        imp::process_with_parakeet(audio_path, &self.model_dir.to_string_lossy())
+    }
+}
+
+pub(crate) use imp::{invalidate_parakeet_cache_for_dir, release_parakeet_context};
+
+#[cfg(not(all(target_os = "macos", target_arch = "x86_64")))]
+mod imp {
+    use std::{path::Path, sync::Arc, sync::LazyLock};
+
+    use cap_project::{CaptionSegment, CaptionWord};
+    use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
+    use tokio::sync::Mutex;
+
+    use crate::words::{caption_text_from_words, caption_word_chunks, normalize_caption_words};
+
+    static PARAKEET_CONTEXT: LazyLock<Mutex<Option<CachedParakeetContext>>> =
+        LazyLock::new(|| Mutex::new(None));
+
+    struct CachedParakeetContext {
+        model_dir: String,
+        model: Arc<std::sync::Mutex<ParakeetTDT>>,
+    }
+
+    fn parakeet_model_dir_matches(cached_model_dir: &str, model_dir: &Path) -> bool {
+        cached_model_dir == model_dir.to_string_lossy()
+    }
+
+    pub async fn invalidate_parakeet_cache_for_dir(model_dir: &Path) {
+        let mut ctx = PARAKEET_CONTEXT.lock().await;
+        if ctx
+            .as_ref()
+            .is_some_and(|cached| parakeet_model_dir_matches(&cached.model_dir, model_dir))
+        {
+            tracing::info!(
+                "Invalidating cached Parakeet context for {}",
+                model_dir.display()
+            );
+            *ctx = None;
+        }
+    }
+
+    pub async fn release_parakeet_context() {
+        let mut ctx = PARAKEET_CONTEXT.lock().await;
+        if ctx.is_some() {
+            tracing::info!("Releasing Parakeet context to free memory");
+            *ctx = None;
+        }
+    }
+
+    pub fn process_with_parakeet(
+        audio_path: &Path,
+        model_dir: &str,
+    ) -> Result<Vec<CaptionSegment>, String> {
+        tracing::info!("Processing audio file: {audio_path:?}");
+        tracing::info!("Model directory: {model_dir}");
+
+        let cached_model = {
+            let guard = PARAKEET_CONTEXT.blocking_lock();
+            guard.as_ref().and_then(|cached| {
+                if cached.model_dir == model_dir {
+                    Some(Arc::clone(&cached.model))
+                } else {
+                    None
+                }
+            })
+        };
+
+        let model_arc = if let Some(model) = cached_model {
+            tracing::info!("Reusing cached Parakeet TDT model");
+            model
+        } else {
+            tracing::info!("Loading Parakeet TDT model from: {model_dir}");
+            let model =
+                ParakeetTDT::from_pretrained(model_dir, None).map_err(|e| format!("{e}"))?;
+            let loaded_model = Arc::new(std::sync::Mutex::new(model));
+
+            let mut guard = PARAKEET_CONTEXT.blocking_lock();
+            if let Some(cached) = guard
+                .as_ref()
+                .filter(|cached| cached.model_dir == model_dir)
+            {
+                tracing::info!("Reusing cached Parakeet TDT model");
+                Arc::clone(&cached.model)
+            } else {
+                *guard = Some(CachedParakeetContext {
+                    model_dir: model_dir.to_string(),
+                    model: Arc::clone(&loaded_model),
+                });
+                tracing::info!("Parakeet TDT model loaded successfully");
+                loaded_model
+            }
+        };
+
+        let result = {
+            let mut parakeet = model_arc
+                .lock()
+                .map_err(|e| format!("Failed to lock Parakeet model: {e}"))?;
+            parakeet
+                .transcribe_file(audio_path, Some(TimestampMode::Words))
+                .map_err(|e| format!("Parakeet transcription failed: {e}"))?
+        };
+
+        tracing::info!("Transcription text: {}", result.text);
+        tracing::info!("Got {} timed tokens", result.tokens.len());
+
+        let words = normalize_caption_words(
+            result
+                .tokens
+                .iter()
+                .filter(|t| !t.text.trim().is_empty())
+                .map(|t| CaptionWord {
+                    text: t.text.trim().to_string(),
+                    start: t.start,
+                    end: t.end,
+                })
+                .collect(),
+        );
+
+        if words.is_empty() {
+            tracing::warn!("Parakeet produced no words");
+            return Err("No speech detected in the audio".to_string());
+        }
+
+        let mut segments = Vec::new();
+
+        for (chunk_idx, chunk) in caption_word_chunks(&words).into_iter().enumerate() {
+            let segment_text = caption_text_from_words(chunk);
+            let segment_start = chunk.first().map(|w| w.start).unwrap_or(0.0);
+            let segment_end = chunk.last().map(|w| w.end).unwrap_or(0.0);
+
+            segments.push(CaptionSegment {
+                id: format!("segment-{chunk_idx}"),
+                start: segment_start,
+                end: segment_end,
+                text: segment_text,
+                words: chunk.to_vec(),
+            });
+        }
+
+        tracing::info!("Total segments: {}", segments.len());
+        tracing::info!(
+            "Total words: {}",
+            segments.iter().map(|s| s.words.len()).sum::<usize>()
+        );
+
+        Ok(segments)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::parakeet_model_dir_matches;
+        use tempfile::tempdir;
+
+        #[test]
+        fn parakeet_model_dir_match_uses_full_directory_path() {
+            let dir = tempdir().unwrap();
+            let model_dir = dir.path().join("models").join("parakeet-best");
+
+            assert!(parakeet_model_dir_matches(
+                model_dir.to_string_lossy().as_ref(),
+                &model_dir
+            ));
+            assert!(!parakeet_model_dir_matches(
+                dir.path()
+                    .join("models")
+                    .join("parakeet-best-max")
+                    .to_string_lossy()
+                    .as_ref(),
+                &model_dir
+            ));
+        }
+    }
+}
+
+#[cfg(all(target_os = "macos", target_arch = "x86_64"))]
+mod imp {
+    use std::path::Path;
+
+    use cap_project::CaptionSegment;
+
+    use super::PARAKEET_UNSUPPORTED_MESSAGE;
+
+    pub async fn invalidate_parakeet_cache_for_dir(_model_dir: &Path) {}
+
+    pub async fn release_parakeet_context() {}
+
+    pub fn process_with_parakeet(
+        _audio_path: &Path,
+        _model_dir: &str,
+    ) -> Result<Vec<CaptionSegment>, String> {
+        Err(PARAKEET_UNSUPPORTED_MESSAGE.to_string())
+    }
+}
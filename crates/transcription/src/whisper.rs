@@ -0,0 +1,414 @@
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock},
+};
+
+use cap_project::{CaptionSegment, CaptionWord};
+use tokio::sync::Mutex;
+use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
+
+use crate::{
+    TranscriptionEngine, TranscriptionOptions,
+    audio::{TRANSCRIPTION_SAMPLE_RATE, normalize_audio_for_transcription},
+    words::{caption_text_from_words, caption_word_chunks, normalize_caption_words},
+};
+
+struct CachedWhisperContext {
+    model_path: PathBuf,
+    context: Arc<WhisperContext>,
+}
+
+static WHISPER_CONTEXT: LazyLock<Mutex<Option<CachedWhisperContext>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+/// Transcribes with a whisper.cpp GGML model file.
+pub struct WhisperEngine {
+    model_path: PathBuf,
+}
+
+impl WhisperEngine {
+    pub fn new(model_path: impl Into<PathBuf>) -> Self {
+        Self {
+            model_path: model_path.into(),
+        }
+    }
+}
+
+impl TranscriptionEngine for WhisperEngine {
+    fn name(&self) -> &'static str {
+        "whisper"
+    }
+
+    fn transcribe(
+        &self,
+        audio_path: &Path,
+        options: &TranscriptionOptions,
+    ) -> Result<Vec<CaptionSegment>, String> {
+        let context = match get_whisper_context_blocking(&self.model_path) {
+            Ok(ctx) => {
+                tracing::info!("Whisper context ready");
+                ctx
+            }
+            Err(e) => {
+                tracing::error!("Failed to initialize Whisper context: {e}");
+                return Err(format!("Failed to initialize transcription model: {e}"));
+            }
+        };
+        let result = process_with_whisper(audio_path, context, &options.language, &options.hints);
+        release_whisper_context_after_transcription();
+        result
+    }
+}
+
+pub(crate) async fn release_whisper_context() {
+    let mut ctx = WHISPER_CONTEXT.lock().await;
+    if ctx.is_some() {
+        tracing::info!("Releasing Whisper context to free memory");
+        *ctx = None;
+    }
+}
+
+fn get_whisper_context_blocking(model_path: &Path) -> Result<Arc<WhisperContext>, String> {
+    let mut context_guard = WHISPER_CONTEXT.blocking_lock();
+
+    if let Some(existing) = context_guard
+        .as_ref()
+        .filter(|cached| cached.model_path == model_path)
+    {
+        tracing::info!("Reusing cached Whisper context");
+        return Ok(existing.context.clone());
+    }
+
+    tracing::info!("Initializing Whisper context with model: {model_path:?}");
+    let ctx = WhisperContext::new_with_params(
+        &model_path.to_string_lossy(),
+        WhisperContextParameters::default(),
+    )
+    .map_err(|e| format!("Failed to load Whisper model: {e}"))?;
+
+    let ctx_arc = Arc::new(ctx);
+    *context_guard = Some(CachedWhisperContext {
+        model_path: model_path.to_path_buf(),
+        context: ctx_arc.clone(),
+    });
+
+    Ok(ctx_arc)
+}
+
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+fn release_whisper_context_after_transcription() {
+    let mut ctx = WHISPER_CONTEXT.blocking_lock();
+    *ctx = None;
+}
+
+#[cfg(not(all(target_os = "macos", target_arch = "aarch64")))]
+fn release_whisper_context_after_transcription() {}
+
+fn is_special_token(token_text: &str) -> bool {
+    let trimmed = token_text.trim();
+    if trimmed.is_empty() {
+        return true;
+    }
+
+    let is_special = trimmed.contains('[')
+        || trimmed.contains(']')
+        || trimmed.contains("_TT_")
+        || trimmed.contains("_BEG_")
+        || trimmed.contains("<|");
+
+    if is_special {
+        tracing::debug!("Filtering special token: {token_text:?}");
+    }
+
+    is_special
+}
+
+fn process_with_whisper(
+    audio_path: &Path,
+    context: Arc<WhisperContext>,
+    language: &str,
+    transcription_hints: &[String],
+) -> Result<Vec<CaptionSegment>, String> {
+    tracing::info!("=== WHISPER TRANSCRIPTION START ===");
+    tracing::info!("Processing audio file: {audio_path:?}");
+    tracing::info!("Language setting: {language}");
+
+    let mut params = FullParams::new(SamplingStrategy::BeamSearch {
+        beam_size: 5,
+        patience: 1.0,
+    });
+
+    params.set_translate(false);
+    params.set_print_special(false);
+    params.set_print_progress(false);
+    params.set_print_realtime(false);
+    params.set_token_timestamps(true);
+    params.set_language(Some(if language == "auto" { "auto" } else { language }));
+    params.set_max_len(i32::MAX);
+
+    if let Some(initial_prompt) = build_initial_prompt(transcription_hints) {
+        params.set_initial_prompt(&initial_prompt);
+    }
+
+    tracing::info!(
+        "Whisper params - translate: false, token_timestamps: true, beam_size: 5, max_len: MAX"
+    );
+
+    let mut audio_file = File::open(audio_path)
+        .map_err(|e| format!("Failed to open audio file: {e} at path: {audio_path:?}"))?;
+    let mut audio_data = Vec::new();
+    audio_file
+        .read_to_end(&mut audio_data)
+        .map_err(|e| format!("Failed to read audio file: {e}"))?;
+
+    tracing::info!("Processing audio file of size: {} bytes", audio_data.len());
+
+    let mut audio_data_f32 = Vec::new();
+    for i in (0..audio_data.len()).step_by(2) {
+        if i + 1 < audio_data.len() {
+            let sample = i16::from_le_bytes([audio_data[i], audio_data[i + 1]]) as f32 / 32768.0;
+            audio_data_f32.push(sample);
+        }
+    }
+
+    let gain = normalize_audio_for_transcription(&mut audio_data_f32);
+    if (gain - 1.0).abs() > 0.01 {
+        tracing::info!("Applied Whisper input gain: {gain:.2}x");
+    }
+
+    let duration_seconds = audio_data_f32.len() as f32 / TRANSCRIPTION_SAMPLE_RATE as f32;
+    tracing::info!(
+        "Converted {} samples to f32 format (duration: {:.2}s at {}Hz)",
+        audio_data_f32.len(),
+        duration_seconds,
+        TRANSCRIPTION_SAMPLE_RATE
+    );
+
+    if !audio_data_f32.is_empty() {
+        let min_sample = audio_data_f32.iter().fold(f32::MAX, |a, &b| a.min(b));
+        let max_sample = audio_data_f32.iter().fold(f32::MIN, |a, &b| a.max(b));
+        let avg_sample = audio_data_f32.iter().sum::<f32>() / audio_data_f32.len() as f32;
+        let rms = (audio_data_f32.iter().map(|&s| s * s).sum::<f32>()
+            / audio_data_f32.len() as f32)
+            .sqrt();
+        tracing::info!(
+            "Audio samples - min: {min_sample:.4}, max: {max_sample:.4}, avg: {avg_sample:.6}, RMS: {rms:.4}"
+        );
+
+        if rms < 0.001 {
+            tracing::warn!(
+                "WARNING: Audio RMS is very low ({rms:.6}) - audio may be nearly silent!"
+            );
+        }
+
+        tracing::info!("First 20 audio samples:");
+        for (i, sample) in audio_data_f32.iter().take(20).enumerate() {
+            tracing::info!("  Sample[{i}] = {sample:.6}");
+        }
+    }
+
+    let mut state = context
+        .create_state()
+        .map_err(|e| format!("Failed to create Whisper state: {e}"))?;
+
+    state
+        .full(params, &audio_data_f32[..])
+        .map_err(|e| format!("Failed to run Whisper transcription: {e}"))?;
+
+    let num_segments = state
+        .full_n_segments()
+        .map_err(|e| format!("Failed to get number of segments: {e}"))?;
+
+    tracing::info!("Found {num_segments} segments");
+
+    let mut segments = Vec::new();
+
+    for i in 0..num_segments {
+        let raw_text = state
+            .full_get_segment_text(i)
+            .map_err(|e| format!("Failed to get segment text: {e}"))?;
+
+        let start_i64 = state
+            .full_get_segment_t0(i)
+            .map_err(|e| format!("Failed to get segment start time: {e}"))?;
+        let end_i64 = state
+            .full_get_segment_t1(i)
+            .map_err(|e| format!("Failed to get segment end time: {e}"))?;
+
+        let start_time = (start_i64 as f32) / 100.0;
+        let end_time = (end_i64 as f32) / 100.0;
+
+        tracing::info!(
+            "=== Segment {}: start={:.2}s, end={:.2}s, raw_text='{}'",
+            i,
+            start_time,
+            end_time,
+            raw_text.trim()
+        );
+
+        let mut words = Vec::new();
+        let num_tokens = state
+            .full_n_tokens(i)
+            .map_err(|e| format!("Failed to get token count: {e}"))?;
+
+        tracing::info!("  Segment {i} has {num_tokens} tokens");
+
+        let mut current_word = String::new();
+        let mut word_start: Option<f32> = None;
+        let mut word_end: f32 = start_time;
+
+        for t in 0..num_tokens {
+            let token_text = state.full_get_token_text(i, t).unwrap_or_default();
+            let token_id = state.full_get_token_id(i, t).unwrap_or(0);
+            let token_prob = state.full_get_token_prob(i, t).unwrap_or(0.0);
+
+            if is_special_token(&token_text) {
+                tracing::debug!(
+                    "  Token[{t}]: id={token_id}, text={token_text:?} -> SKIPPED (special)"
+                );
+                continue;
+            }
+
+            let token_data = state.full_get_token_data(i, t).ok();
+
+            if let Some(data) = token_data {
+                let token_start = (data.t0 as f32) / 100.0;
+                let token_end = (data.t1 as f32) / 100.0;
+
+                tracing::info!(
+                    "  Token[{t}]: id={token_id}, text={token_text:?}, t0={token_start:.2}s, t1={token_end:.2}s, prob={token_prob:.4}"
+                );
+
+                if token_text.starts_with(' ') || token_text.starts_with('\n') {
+                    if !current_word.is_empty()
+                        && let Some(ws) = word_start
+                    {
+                        tracing::info!(
+                            "    -> Completing word: '{}' ({:.2}s - {:.2}s)",
+                            current_word.trim(),
+                            ws,
+                            word_end
+                        );
+                        words.push(CaptionWord {
+                            text: current_word.trim().to_string(),
+                            start: ws,
+                            end: word_end,
+                        });
+                    }
+                    current_word = token_text.trim().to_string();
+                    word_start = Some(token_start);
+                    tracing::debug!(
+                        "    -> Starting new word: '{current_word}' at {token_start:.2}s"
+                    );
+                } else {
+                    if word_start.is_none() {
+                        word_start = Some(token_start);
+                        tracing::debug!("    -> Word start set to {token_start:.2}s");
+                    }
+                    current_word.push_str(&token_text);
+                    tracing::debug!("    -> Appending to word: '{current_word}'");
+                }
+                word_end = token_end;
+            } else {
+                tracing::warn!(
+                    "  Token[{t}]: id={token_id}, text={token_text:?} -> NO TIMING DATA"
+                );
+            }
+        }
+
+        if !current_word.trim().is_empty()
+            && let Some(ws) = word_start
+        {
+            tracing::info!(
+                "    -> Final word: '{}' ({:.2}s - {:.2}s)",
+                current_word.trim(),
+                ws,
+                word_end
+            );
+            words.push(CaptionWord {
+                text: current_word.trim().to_string(),
+                start: ws,
+                end: word_end,
+            });
+        }
+
+        let words = normalize_caption_words(words);
+
+        tracing::info!("  Segment {} produced {} words", i, words.len());
+        for (w_idx, word) in words.iter().enumerate() {
+            tracing::info!(
+                "    Word[{}]: '{}' ({:.2}s - {:.2}s)",
+                w_idx,
+                word.text,
+                word.start,
+                word.end
+            );
+        }
+
+        if words.is_empty() {
+            tracing::warn!("  Segment {i} has no words, skipping");
+            continue;
+        }
+
+        for (chunk_idx, chunk_words) in caption_word_chunks(&words).into_iter().enumerate() {
+            let segment_text = caption_text_from_words(chunk_words);
+
+            let segment_start = chunk_words
+                .first()
+                .map(|word| word.start)
+                .unwrap_or(start_time);
+            let segment_end = chunk_words.last().map(|word| word.end).unwrap_or(end_time);
+
+            segments.push(CaptionSegment {
+                id: format!("segment-{i}-{chunk_idx}"),
+                start: segment_start,
+                end: segment_end,
+                text: segment_text,
+                words: chunk_words.to_vec(),
+            });
+        }
+    }
+
+    tracing::info!("=== WHISPER TRANSCRIPTION COMPLETE ===");
+    tracing::info!("Total segments: {}", segments.len());
+
+    let total_words: usize = segments.iter().map(|s| s.words.len()).sum();
+    tracing::info!("Total words: {total_words}");
+
+    tracing::info!("=== FINAL TRANSCRIPTION SUMMARY ===");
+    for segment in &segments {
+        tracing::info!(
+            "Segment '{}' ({:.2}s - {:.2}s): {}",
+            segment.id,
+            segment.start,
+            segment.end,
+            segment.text
+        );
+    }
+    tracing::info!("=== END SUMMARY ===");
+
+    Ok(segments)
+}
+
+fn build_initial_prompt(transcription_hints: &[String]) -> Option<String> {
+    let mut normalized = Vec::new();
+
+    for hint in transcription_hints {
+        let value = hint.replace('\0', "").trim().to_string();
+        if value.is_empty() || normalized.contains(&value) {
+            continue;
+        }
+        normalized.push(value);
+    }
+
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "Preferred spellings, names, and capitalization for this transcript: {}",
+            normalized.join("; ")
+        ))
+    }
+}